@@ -18,6 +18,30 @@ fn setup() -> (PhysicsConfig, SensitivityWeights, PressureVector) {
     (config, weights, pressure)
 }
 
+// ============================================================================
+// CALIBRATION (machine speed reference for `cargo xtask bench-check`)
+// ============================================================================
+
+/// Fixed integer/float workload that touches no crate code, so only the
+/// machine changes its timing
+fn bench_calibration(c: &mut Criterion) {
+    let mut group = c.benchmark_group("calibration");
+    group.bench_function("spin", |b| {
+        b.iter(|| {
+            let mut x = black_box(1u64);
+            let mut acc = 0.0f64;
+            for _ in 0..1_000 {
+                x = x
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                acc += (x >> 11) as f64 * 1e-16;
+            }
+            acc
+        })
+    });
+    group.finish();
+}
+
 // ============================================================================
 // VECTOR MATH BENCHMARKS
 // ============================================================================
@@ -60,6 +84,42 @@ fn bench_calculate_resistance(c: &mut Criterion) {
     });
 }
 
+// ============================================================================
+// FAST-MATH BENCHMARKS (libm vs approximation)
+// ============================================================================
+
+/// Map `f` over a batch of lanes, the shape `fast-math` targets
+fn map_batch(inputs: &[f64], out: &mut [f64], f: impl Fn(f64) -> f64) {
+    for (o, &x) in out.iter_mut().zip(inputs) {
+        *o = f(x);
+    }
+}
+
+fn bench_fast_math(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fast_math");
+    let inputs: Vec<f64> = (0..1_000).map(|i| -(i as f64) * 0.01).collect();
+    let mut out = vec![0.0; inputs.len()];
+    group.throughput(criterion::Throughput::Elements(inputs.len() as u64));
+
+    group.bench_function("exp_libm", |b| {
+        b.iter(|| map_batch(black_box(&inputs), &mut out, f64::exp))
+    });
+    group.bench_function("exp_approx", |b| {
+        b.iter(|| map_batch(black_box(&inputs), &mut out, fastmath::exp_approx))
+    });
+    group.bench_function("max_std", |b| {
+        b.iter(|| map_batch(black_box(&inputs), &mut out, |x| x.max(-1.0)))
+    });
+    group.bench_function("max_branchless", |b| {
+        b.iter(|| {
+            map_batch(black_box(&inputs), &mut out, |x| {
+                fastmath::max_branchless(x, -1.0)
+            })
+        })
+    });
+    group.finish();
+}
+
 // ============================================================================
 // SCAR BENCHMARKS
 // ============================================================================
@@ -146,6 +206,132 @@ fn bench_throughput(c: &mut Criterion) {
             });
         });
     }
+
+    // String ids vs interned handles through a pool
+    const TARGETS: usize = 1_000;
+    let (config, weights, pressure) = setup();
+    let ids: Vec<String> = (0..TARGETS).map(|i| format!("target-{i}")).collect();
+    let mut pool = pool::EnginePool::new(config, weights);
+    let handles: Vec<_> = ids.iter().map(|id| pool.resolve(id)).collect();
+    let mut now = 0.0;
+    group.throughput(criterion::Throughput::Elements(TARGETS as u64));
+    group.bench_function("pool_tick_by_id", |b| {
+        b.iter(|| {
+            now += 100.0;
+            for id in &ids {
+                black_box(pool.tick(id, now, pressure));
+            }
+        })
+    });
+    group.bench_function("pool_tick_resolved", |b| {
+        b.iter(|| {
+            now += 100.0;
+            for &handle in &handles {
+                black_box(pool.tick_resolved(handle, now, pressure));
+            }
+        })
+    });
+    group.finish();
+}
+
+// ============================================================================
+// BATCH KERNEL BENCHMARKS (dynamic vs const-generic)
+// ============================================================================
+
+fn bench_batch_kernels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resistance_batch");
+    let (config, weights, _) = setup();
+    let lanes: Vec<_> = (0..1024)
+        .map(|i| {
+            let f = i as f64 / 1024.0;
+            resistance::ResistanceLane::new(
+                &PressureVector::new(f * 0.5, f * 0.3, f * 0.2),
+                Momentum(f),
+                Scar(f * 10.0),
+                &weights,
+                &config,
+                0.0,
+            )
+        })
+        .collect();
+    let mut out = vec![Ohms(0.0); lanes.len()];
+    group.throughput(criterion::Throughput::Elements(lanes.len() as u64));
+
+    group.bench_function("dynamic", |b| {
+        b.iter(|| resistance::calculate_resistance_batch(black_box(&lanes), &mut out))
+    });
+    group.bench_function("const_generic_8", |b| {
+        b.iter(|| resistance::calculate_resistance_chunked(black_box(&lanes), &mut out))
+    });
+    group.finish();
+}
+
+// ============================================================================
+// POOL ITERATION BENCHMARKS (100k targets)
+// ============================================================================
+
+fn bench_pool_tick_all(c: &mut Criterion) {
+    const TARGETS: usize = 100_000;
+    let mut group = c.benchmark_group("pool_100k");
+    group.sample_size(10);
+    group.throughput(criterion::Throughput::Elements(TARGETS as u64));
+
+    let (config, weights, pressure) = setup();
+    let ids: Vec<String> = (0..TARGETS).map(|i| format!("target-{i}")).collect();
+    let mut pool = pool::EnginePool::new(config, weights);
+    let handles: Vec<_> = ids.iter().map(|id| pool.register(id)).collect();
+
+    let mut now = 0.0;
+    group.bench_function("tick_by_id", |b| {
+        b.iter(|| {
+            now += 100.0;
+            for id in &ids {
+                pool.tick(id, now, pressure);
+            }
+        })
+    });
+    group.bench_function("tick_by_handle", |b| {
+        b.iter(|| {
+            now += 100.0;
+            for &handle in &handles {
+                pool.tick_handle(handle, now, pressure);
+            }
+        })
+    });
+    group.bench_function("tick_all", |b| {
+        b.iter(|| {
+            now += 100.0;
+            pool.tick_all(now, |_| Some(pressure))
+        })
+    });
+    group.finish();
+}
+
+// ============================================================================
+// WORKLOAD REPLAY BENCHMARKS
+// ============================================================================
+
+fn bench_workloads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("workload_replay");
+    let (config, weights, _) = setup();
+    let timeline = workloads::Timeline::default();
+    let cases = [
+        ("diurnal", workloads::Workload::diurnal()),
+        ("flash_crowd", workloads::Workload::flash_crowd()),
+        ("slow_burn", workloads::Workload::slow_burn()),
+        (
+            "correlated_failure",
+            workloads::Workload::correlated_failure(),
+        ),
+    ];
+
+    for (name, workload) in cases {
+        let trace = workload.generate(name, &timeline);
+        group.throughput(criterion::Throughput::Elements(trace.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| counterfactual::replay(0, black_box(&trace), &weights, &config))
+        });
+    }
     group.finish();
 }
 
@@ -153,6 +339,8 @@ fn bench_throughput(c: &mut Criterion) {
 // CRITERION GROUPS
 // ============================================================================
 
+criterion_group!(calibration_benches, bench_calibration);
+
 criterion_group!(
     vector_benches,
     bench_vector_magnitude,
@@ -163,9 +351,22 @@ criterion_group!(
     physics_benches,
     bench_calculate_resistance,
     bench_update_scar,
-    bench_update_momentum
+    bench_update_momentum,
+    bench_fast_math
 );
 
-criterion_group!(engine_benches, bench_physics_engine, bench_throughput);
+criterion_group!(
+    engine_benches,
+    bench_physics_engine,
+    bench_throughput,
+    bench_batch_kernels,
+    bench_pool_tick_all,
+    bench_workloads
+);
 
-criterion_main!(vector_benches, physics_benches, engine_benches);
+criterion_main!(
+    calibration_benches,
+    vector_benches,
+    physics_benches,
+    engine_benches
+);
@@ -0,0 +1,22 @@
+//! Print the JSON Schema for the config and snapshot formats.
+//!
+//! cargo run --example json_schema --features schema -- [config|weights|snapshot]
+
+use atrion_physics::schema::{config_schema, snapshot_schema, weights_schema};
+
+fn main() {
+    let which = std::env::args().nth(1).unwrap_or_else(|| "config".into());
+    let schema = match which.as_str() {
+        "config" => config_schema(),
+        "weights" => weights_schema(),
+        "snapshot" => snapshot_schema(),
+        other => {
+            eprintln!("unknown schema '{other}' (expected config, weights or snapshot)");
+            std::process::exit(2);
+        }
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&schema).expect("schema serializes")
+    );
+}
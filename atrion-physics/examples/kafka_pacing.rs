@@ -0,0 +1,100 @@
+//! Pace an rdkafka consumer from a downstream target's resistance.
+//!
+//! cargo run --example kafka_pacing --features kafka -- <brokers> <group> <topic>
+//!
+//! rdkafka has no max.poll.records, so the limit is applied per loop
+//! iteration; pausing uses the consumer's current assignment.
+
+use std::time::{Duration, Instant};
+
+use atrion_physics::engine::TargetEngine;
+use atrion_physics::pacing::{PacedConsumer, Pacer, PacingConfig, PacingSignal};
+use atrion_physics::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::error::KafkaError;
+
+struct Paced {
+    consumer: BaseConsumer,
+    max_records: u32,
+}
+
+impl PacedConsumer for Paced {
+    type Error = KafkaError;
+
+    fn pause(&mut self) -> Result<(), KafkaError> {
+        self.consumer.pause(&self.consumer.assignment()?)
+    }
+
+    fn resume(&mut self) -> Result<(), KafkaError> {
+        self.consumer.resume(&self.consumer.assignment()?)
+    }
+
+    fn set_max_poll_records(&mut self, max_records: u32) {
+        self.max_records = max_records;
+    }
+}
+
+/// Stand-in for the real downstream call; returns latency pressure
+fn process(_payload: &[u8]) -> f64 {
+    0.1
+}
+
+fn main() -> Result<(), KafkaError> {
+    let args: Vec<String> = std::env::args().collect();
+    let (brokers, group, topic) = match args.as_slice() {
+        [_, brokers, group, topic] => (brokers, group, topic),
+        _ => {
+            eprintln!("usage: kafka_pacing <brokers> <group> <topic>");
+            std::process::exit(2);
+        }
+    };
+
+    let consumer: BaseConsumer = ClientConfig::new()
+        .set("bootstrap.servers", brokers)
+        .set("group.id", group)
+        .create()?;
+    consumer.subscribe(&[topic.as_str()])?;
+
+    let mut paced = Paced {
+        consumer,
+        max_records: 0,
+    };
+    let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+    let mut pacer = Pacer::new(PacingConfig::default());
+    let started = Instant::now();
+
+    loop {
+        let signal = pacer.apply(engine.admission_ratio(), &mut paced)?;
+        let mut latency = 0.0;
+        let mut processed = 0u32;
+
+        if let PacingSignal::Poll { max_records } = signal {
+            while processed < max_records {
+                match paced.consumer.poll(Duration::from_millis(100)) {
+                    Some(Ok(message)) => {
+                        latency += process(rdkafka::Message::payload(&message).unwrap_or_default());
+                        processed += 1;
+                    }
+                    Some(Err(e)) => eprintln!("poll error: {e}"),
+                    None => break,
+                }
+            }
+        } else {
+            // Keep the group session alive while paused
+            paced.consumer.poll(Duration::from_millis(100));
+        }
+
+        let mean_latency = if processed > 0 {
+            latency / processed as f64
+        } else {
+            0.0
+        };
+        let now_ms = started.elapsed().as_secs_f64() * 1000.0;
+        engine.tick(now_ms, PressureVector::new(mean_latency, 0.0, 0.0));
+        println!(
+            "{signal:?} processed={processed} R={:.1}",
+            engine.state().resistance.0
+        );
+    }
+}
@@ -0,0 +1,155 @@
+/**
+ * HTTP/JSON admin endpoint (feature "admin", native only).
+ *
+ * Tiny axum surface for operators to inspect and tweak a live pool:
+ *
+ * - GET        /targets
+ * - GET        /targets/{id}/state
+ * - GET        /targets/{id}/explain
+ * - GET | PUT  /config
+ * - GET        /metrics  (Prometheus text)
+ */
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+
+use crate::metrics;
+use crate::pool::EnginePool;
+use crate::types::PhysicsConfig;
+
+type SharedPool = Arc<Mutex<EnginePool>>;
+
+/// Build the admin router over a shared pool
+pub fn router(pool: SharedPool) -> Router {
+    Router::new()
+        .route("/targets", get(list_targets))
+        .route("/targets/{id}/state", get(target_state))
+        .route("/targets/{id}/explain", get(target_explain))
+        .route("/config", get(get_config).put(put_config))
+        .route("/metrics", get(render_metrics))
+        .with_state(pool)
+}
+
+/// Serve the admin router on `addr`
+pub async fn serve(pool: SharedPool, addr: SocketAddr) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(pool)).await
+}
+
+fn lock(pool: &SharedPool) -> MutexGuard<'_, EnginePool> {
+    pool.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn not_found(id: &str) -> Response {
+    (StatusCode::NOT_FOUND, format!("unknown target: {id}")).into_response()
+}
+
+async fn list_targets(State(pool): State<SharedPool>) -> Json<Vec<String>> {
+    let mut ids: Vec<String> = lock(&pool).iter().map(|(id, _)| id.clone()).collect();
+    ids.sort();
+    Json(ids)
+}
+
+async fn target_state(State(pool): State<SharedPool>, Path(id): Path<String>) -> Response {
+    match lock(&pool).get(&id) {
+        Some(engine) => Json(*engine.state()).into_response(),
+        None => not_found(&id),
+    }
+}
+
+async fn target_explain(State(pool): State<SharedPool>, Path(id): Path<String>) -> Response {
+    match lock(&pool).get(&id) {
+        Some(engine) => Json(engine.explain()).into_response(),
+        None => not_found(&id),
+    }
+}
+
+async fn get_config(State(pool): State<SharedPool>) -> Json<PhysicsConfig> {
+    Json(lock(&pool).config().clone())
+}
+
+async fn put_config(
+    State(pool): State<SharedPool>,
+    Json(config): Json<PhysicsConfig>,
+) -> Json<PhysicsConfig> {
+    lock(&pool).set_config(config.clone());
+    Json(config)
+}
+
+async fn render_metrics(State(pool): State<SharedPool>) -> Response {
+    let body = metrics::render_prometheus(&lock(&pool));
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PressureVector, SensitivityWeights};
+    use std::io::{Read, Write};
+
+    /// Minimal blocking HTTP/1.1 client (no extra dev-dependencies)
+    fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> String {
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: admin\r\nConnection: close\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_admin_routes() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        pool.tick("api", 0.0, PressureVector::new(0.2, 0.1, 0.1));
+        let pool = Arc::new(Mutex::new(pool));
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = router(Arc::clone(&pool));
+        std::thread::spawn(move || {
+            runtime.block_on(async { axum::serve(listener, app).await.unwrap() });
+        });
+
+        assert!(request(addr, "GET", "/targets/api/state", "").contains("\"Bootstrap\""));
+        assert!(request(addr, "GET", "/targets/api/explain", "").contains("\"total\""));
+        assert!(request(addr, "GET", "/targets/nope/state", "").starts_with("HTTP/1.1 404"));
+        assert!(request(addr, "GET", "/metrics", "").contains("atrion_scar{target=\"api\"}"));
+
+        let config = PhysicsConfig {
+            base_resistance: 42.0,
+            ..PhysicsConfig::default()
+        };
+        let body = serde_json::to_string(&config).unwrap();
+        assert!(request(addr, "PUT", "/config", &body).starts_with("HTTP/1.1 200"));
+        assert!(request(addr, "GET", "/config", "").contains("\"baseResistance\":42.0"));
+        assert_eq!(
+            pool.lock()
+                .unwrap()
+                .get("api")
+                .unwrap()
+                .config()
+                .base_resistance,
+            42.0
+        );
+    }
+}
@@ -0,0 +1,128 @@
+/**
+ * Admission decisions (flow gate).
+ *
+ * MUST match src/core/flow.ts decideFlow():
+ * Flow = PASS if V > R AND mode ≠ CIRCUIT_BREAKER
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::engine::TargetState;
+use crate::types::{Ohms, OperationalMode, PhysicsConfig};
+
+/// Why a request was not admitted
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RejectReason {
+    /// Circuit breaker is open
+    CircuitOpen,
+    /// Request voltage does not overcome resistance
+    InsufficientVoltage,
+    /// Tenant already consumed its fair share of the admission budget
+    FairShareExceeded,
+    /// Request priority is below the current minimum admitted priority
+    PriorityShed,
+    /// Request cost does not fit the remaining cost budget
+    BudgetExhausted,
+    /// Target is on the pool's quarantine list
+    Quarantined,
+    /// Predicted queueing plus service time exceeds the request deadline
+    DeadlineUnmeetable,
+}
+
+impl RejectReason {
+    /// snake_case label for headers and metric tags
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectReason::CircuitOpen => "circuit_open",
+            RejectReason::InsufficientVoltage => "insufficient_voltage",
+            RejectReason::FairShareExceeded => "fair_share_exceeded",
+            RejectReason::PriorityShed => "priority_shed",
+            RejectReason::BudgetExhausted => "budget_exhausted",
+            RejectReason::Quarantined => "quarantined",
+            RejectReason::DeadlineUnmeetable => "deadline_unmeetable",
+        }
+    }
+}
+
+/// Outcome of an admission check
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdmissionDecision {
+    Admit,
+    Reject(RejectReason),
+}
+
+impl AdmissionDecision {
+    #[inline]
+    pub fn is_admitted(&self) -> bool {
+        matches!(self, AdmissionDecision::Admit)
+    }
+}
+
+/// Voltage gate (TS parity)
+#[inline]
+pub fn decide_flow(voltage: f64, state: &TargetState) -> AdmissionDecision {
+    if state.mode == OperationalMode::CircuitBreaker {
+        return AdmissionDecision::Reject(RejectReason::CircuitOpen);
+    }
+    if voltage > state.resistance.0 {
+        AdmissionDecision::Admit
+    } else {
+        AdmissionDecision::Reject(RejectReason::InsufficientVoltage)
+    }
+}
+
+/// Fraction of offered load that fits at the given resistance
+///
+/// 1.0 up to `recovery_threshold`, falling linearly to 0.0 at
+/// `break_threshold`. This is the admission budget shared by the
+/// fairness, priority and cost layers.
+#[inline]
+pub fn admission_ratio(resistance: Ohms, config: &PhysicsConfig) -> f64 {
+    let span = config.break_threshold - config.recovery_threshold;
+    if span <= 0.0 {
+        return if resistance.0 < config.break_threshold {
+            1.0
+        } else {
+            0.0
+        };
+    }
+    ((config.break_threshold - resistance.0) / span).clamp(0.0, 1.0)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide_flow() {
+        let config = PhysicsConfig::default();
+        let mut state = TargetState::bootstrap(&config);
+        state.mode = OperationalMode::Operational;
+        state.resistance = Ohms(20.0);
+
+        assert_eq!(decide_flow(25.0, &state), AdmissionDecision::Admit);
+        assert_eq!(
+            decide_flow(20.0, &state),
+            AdmissionDecision::Reject(RejectReason::InsufficientVoltage)
+        );
+
+        state.mode = OperationalMode::CircuitBreaker;
+        assert_eq!(
+            decide_flow(1000.0, &state),
+            AdmissionDecision::Reject(RejectReason::CircuitOpen)
+        );
+    }
+
+    #[test]
+    fn test_admission_ratio_band() {
+        let config = PhysicsConfig::default();
+        assert_eq!(admission_ratio(Ohms(10.0), &config), 1.0);
+        assert_eq!(admission_ratio(Ohms(50.0), &config), 1.0);
+        assert!((admission_ratio(Ohms(75.0), &config) - 0.5).abs() < 1e-10);
+        assert_eq!(admission_ratio(Ohms(100.0), &config), 0.0);
+        assert_eq!(admission_ratio(Ohms(500.0), &config), 0.0);
+    }
+}
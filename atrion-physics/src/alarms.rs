@@ -0,0 +1,275 @@
+/**
+ * Physics-native alarms.
+ *
+ * Threshold alarms over trailing windows of the engine's own signals:
+ *
+ * - trauma rate: ticks that added scar in the last minute
+ * - scar growth: net scar gained over the last minute
+ * - breaker time: milliseconds spent in CircuitBreaker over the last hour
+ *
+ * Crossing a threshold pushes `AlarmRaised` into the engine event log;
+ * dropping back below it pushes `AlarmCleared`. Paging rules can key off
+ * those events instead of scraping gauges.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::TargetState;
+use crate::events::EngineEvent;
+use crate::types::OperationalMode;
+
+const MINUTE_MS: f64 = 60_000.0;
+const HOUR_MS: f64 = 3_600_000.0;
+
+/// Alarm thresholds (`None` = alarm disabled)
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlarmConfig {
+    /// Trauma events per minute
+    #[serde(alias = "trauma_per_minute")]
+    pub trauma_per_minute: Option<f64>,
+    /// Net scar growth per minute
+    #[serde(alias = "scar_growth_per_minute")]
+    pub scar_growth_per_minute: Option<f64>,
+    /// Milliseconds in CircuitBreaker per hour
+    #[serde(alias = "breaker_ms_per_hour")]
+    pub breaker_ms_per_hour: Option<f64>,
+}
+
+/// Which signal an alarm watches
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AlarmKind {
+    TraumaRate,
+    ScarGrowth,
+    BreakerTime,
+}
+
+impl AlarmKind {
+    pub const ALL: [AlarmKind; 3] = [
+        AlarmKind::TraumaRate,
+        AlarmKind::ScarGrowth,
+        AlarmKind::BreakerTime,
+    ];
+}
+
+/// Current value of each signal
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlarmSignals {
+    #[serde(alias = "trauma_per_minute")]
+    pub trauma_per_minute: f64,
+    #[serde(alias = "scar_growth_per_minute")]
+    pub scar_growth_per_minute: f64,
+    #[serde(alias = "breaker_ms_per_hour")]
+    pub breaker_ms_per_hour: f64,
+}
+
+impl AlarmSignals {
+    pub fn get(&self, kind: AlarmKind) -> f64 {
+        match kind {
+            AlarmKind::TraumaRate => self.trauma_per_minute,
+            AlarmKind::ScarGrowth => self.scar_growth_per_minute,
+            AlarmKind::BreakerTime => self.breaker_ms_per_hour,
+        }
+    }
+}
+
+/// Windowed signal tracker with raised/cleared state per alarm
+#[derive(Debug, Clone, Default)]
+pub struct Alarms {
+    config: AlarmConfig,
+    /// Timestamps of ticks that added scar
+    traumas: VecDeque<f64>,
+    /// (timestamp, scar) samples
+    scars: VecDeque<(f64, f64)>,
+    /// (end timestamp, duration) of ticks spent in CircuitBreaker
+    breaker: VecDeque<(f64, f64)>,
+    raised: [bool; 3],
+}
+
+impl Alarms {
+    pub fn new(config: AlarmConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self) -> &AlarmConfig {
+        &self.config
+    }
+
+    fn threshold(&self, kind: AlarmKind) -> Option<f64> {
+        match kind {
+            AlarmKind::TraumaRate => self.config.trauma_per_minute,
+            AlarmKind::ScarGrowth => self.config.scar_growth_per_minute,
+            AlarmKind::BreakerTime => self.config.breaker_ms_per_hour,
+        }
+    }
+
+    /// Whether an alarm is currently raised
+    pub fn is_raised(&self, kind: AlarmKind) -> bool {
+        self.raised[kind as usize]
+    }
+
+    /// Signal values over the windows ending at the last observed tick
+    pub fn signals(&self) -> AlarmSignals {
+        let scar_growth = match (self.scars.front(), self.scars.back()) {
+            (Some(first), Some(last)) => last.1 - first.1,
+            _ => 0.0,
+        };
+        AlarmSignals {
+            trauma_per_minute: self.traumas.len() as f64,
+            scar_growth_per_minute: scar_growth,
+            breaker_ms_per_hour: self.breaker.iter().map(|(_, ms)| ms).sum(),
+        }
+    }
+
+    /// Feed one tick's transition; returns alarm events to publish
+    pub fn observe(
+        &mut self,
+        now_ms: f64,
+        prev: &TargetState,
+        next: &TargetState,
+    ) -> Vec<EngineEvent> {
+        if next.scar.0 > prev.scar.0 {
+            self.traumas.push_back(now_ms);
+        }
+        self.scars.push_back((now_ms, next.scar.0));
+        if prev.mode == OperationalMode::CircuitBreaker && prev.tick_count > 0 {
+            let spent = (now_ms - prev.last_updated_ms).max(0.0);
+            self.breaker.push_back((now_ms, spent));
+        }
+        self.trim(now_ms);
+
+        let signals = self.signals();
+        let mut events = Vec::new();
+        for kind in AlarmKind::ALL {
+            let Some(threshold) = self.threshold(kind) else {
+                continue;
+            };
+            let value = signals.get(kind);
+            let raised = &mut self.raised[kind as usize];
+            if !*raised && value >= threshold {
+                *raised = true;
+                events.push(EngineEvent::AlarmRaised {
+                    at_ms: now_ms,
+                    alarm: kind,
+                    value,
+                    threshold,
+                });
+            } else if *raised && value < threshold {
+                *raised = false;
+                events.push(EngineEvent::AlarmCleared {
+                    at_ms: now_ms,
+                    alarm: kind,
+                    value,
+                });
+            }
+        }
+        events
+    }
+
+    fn trim(&mut self, now_ms: f64) {
+        while self
+            .traumas
+            .front()
+            .is_some_and(|&t| now_ms - t > MINUTE_MS)
+        {
+            self.traumas.pop_front();
+        }
+        while self
+            .scars
+            .front()
+            .is_some_and(|&(t, _)| now_ms - t > MINUTE_MS)
+        {
+            self.scars.pop_front();
+        }
+        while self
+            .breaker
+            .front()
+            .is_some_and(|&(t, _)| now_ms - t > HOUR_MS)
+        {
+            self.breaker.pop_front();
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_trauma_rate_raises_then_clears() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_alarms(AlarmConfig {
+            trauma_per_minute: Some(5.0),
+            ..AlarmConfig::default()
+        });
+        let mut now = 0.0;
+        for _ in 0..20 {
+            engine.tick(now, PressureVector::new(0.1, 0.0, 0.0));
+            now += 1_000.0;
+        }
+        for _ in 0..5 {
+            engine.tick(now, PressureVector::new(0.9, 0.0, 0.0));
+            now += 1_000.0;
+        }
+        assert!(engine.alarms().unwrap().is_raised(AlarmKind::TraumaRate));
+        for _ in 0..70 {
+            engine.tick(now, PressureVector::new(0.1, 0.0, 0.0));
+            now += 1_000.0;
+        }
+        assert!(!engine.alarms().unwrap().is_raised(AlarmKind::TraumaRate));
+
+        let alarms: Vec<_> = engine
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                EngineEvent::AlarmRaised { alarm, value, .. } => Some((alarm, true, value)),
+                EngineEvent::AlarmCleared { alarm, value, .. } => Some((alarm, false, value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            alarms,
+            [
+                (AlarmKind::TraumaRate, true, 5.0),
+                (AlarmKind::TraumaRate, false, 4.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_breaker_time_accumulates() {
+        let mut alarms = Alarms::new(AlarmConfig {
+            breaker_ms_per_hour: Some(30_000.0),
+            ..AlarmConfig::default()
+        });
+        let config = PhysicsConfig::default();
+        let mut state = TargetState::bootstrap(&config);
+        state.mode = OperationalMode::CircuitBreaker;
+        state.tick_count = 20;
+        let mut raised_at = None;
+        for i in 1..=60 {
+            let mut next = state;
+            next.last_updated_ms = i as f64 * 1_000.0;
+            if !alarms
+                .observe(next.last_updated_ms, &state, &next)
+                .is_empty()
+            {
+                raised_at.get_or_insert(next.last_updated_ms);
+            }
+            state = next;
+        }
+        assert_eq!(raised_at, Some(30_000.0));
+        assert_eq!(alarms.signals().breaker_ms_per_hour, 60_000.0);
+    }
+}
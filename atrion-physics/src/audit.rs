@@ -0,0 +1,238 @@
+/**
+ * Sampled admission audit log.
+ *
+ * For post-incident review ("what did we shed, and why?") the pool can
+ * keep a sample of its admission decisions. Time is cut into
+ * `interval_ms` windows; each window keeps exact admit/reject counts plus
+ * a uniform reservoir (Algorithm R, seeded SplitMix64) of at most
+ * `samples_per_interval` decisions, and only the newest `max_intervals`
+ * windows are retained, so memory stays bounded however hot the pool
+ * runs.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::admission::{AdmissionDecision, RejectReason};
+
+/// Audit sampling configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct AuditConfig {
+    #[serde(alias = "interval_ms")]
+    pub interval_ms: f64,
+    /// Reservoir size per interval
+    #[serde(alias = "samples_per_interval")]
+    pub samples_per_interval: usize,
+    /// Closed intervals retained (oldest dropped first)
+    #[serde(alias = "max_intervals")]
+    pub max_intervals: usize,
+    pub seed: u64,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 60_000.0,
+            samples_per_interval: 100,
+            max_intervals: 60,
+            seed: 0x5EED,
+        }
+    }
+}
+
+/// One sampled admission decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    #[serde(alias = "timestamp_ms")]
+    pub timestamp_ms: f64,
+    pub target: String,
+    pub admitted: bool,
+    pub reason: Option<RejectReason>,
+    /// Target resistance when the decision was made
+    pub resistance: f64,
+}
+
+/// Counts and sampled decisions for one interval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditInterval {
+    #[serde(alias = "start_ms")]
+    pub start_ms: f64,
+    pub admitted: u64,
+    pub rejected: u64,
+    pub samples: Vec<AuditRecord>,
+}
+
+impl AuditInterval {
+    fn new(start_ms: f64) -> Self {
+        Self {
+            start_ms,
+            admitted: 0,
+            rejected: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Decisions seen in the interval (sampled or not)
+    pub fn seen(&self) -> u64 {
+        self.admitted + self.rejected
+    }
+}
+
+/// Bounded, sampled stream of admission decisions
+#[derive(Debug, Clone)]
+pub struct AdmissionAudit {
+    config: AuditConfig,
+    intervals: VecDeque<AuditInterval>,
+    rng: u64,
+}
+
+impl AdmissionAudit {
+    pub fn new(config: AuditConfig) -> Self {
+        Self {
+            config,
+            intervals: VecDeque::new(),
+            rng: config.seed,
+        }
+    }
+
+    pub fn config(&self) -> &AuditConfig {
+        &self.config
+    }
+
+    /// Retained intervals, oldest first (the last one may still be open)
+    pub fn intervals(&self) -> impl Iterator<Item = &AuditInterval> {
+        self.intervals.iter()
+    }
+
+    /// Take every retained interval, leaving the audit empty
+    pub fn drain(&mut self) -> Vec<AuditInterval> {
+        self.intervals.drain(..).collect()
+    }
+
+    /// Count `decision` and maybe sample it
+    pub fn record(
+        &mut self,
+        timestamp_ms: f64,
+        target: &str,
+        decision: AdmissionDecision,
+        resistance: f64,
+    ) {
+        let interval_ms = self.config.interval_ms.max(1.0);
+        let start_ms = (timestamp_ms / interval_ms).floor() * interval_ms;
+        // Late decisions count toward the open interval
+        if self
+            .intervals
+            .back()
+            .is_none_or(|open| open.start_ms < start_ms)
+        {
+            self.intervals.push_back(AuditInterval::new(start_ms));
+            while self.intervals.len() > self.config.max_intervals.max(1) {
+                self.intervals.pop_front();
+            }
+        }
+        let slot = self.next_slot();
+        let capacity = self.config.samples_per_interval;
+        let interval = self.intervals.back_mut().expect("interval opened above");
+        match decision {
+            AdmissionDecision::Admit => interval.admitted += 1,
+            AdmissionDecision::Reject(_) => interval.rejected += 1,
+        }
+        let record = || AuditRecord {
+            timestamp_ms,
+            target: target.to_string(),
+            admitted: decision.is_admitted(),
+            reason: match decision {
+                AdmissionDecision::Admit => None,
+                AdmissionDecision::Reject(reason) => Some(reason),
+            },
+            resistance,
+        };
+        // Algorithm R: the n-th decision replaces a random sample with
+        // probability capacity / n
+        let seen = interval.seen();
+        if interval.samples.len() < capacity {
+            interval.samples.push(record());
+        } else {
+            let index = (slot * seen as f64) as usize;
+            if index < capacity {
+                interval.samples[index] = record();
+            }
+        }
+    }
+
+    /// Uniform in `[0, 1)` (SplitMix64)
+    fn next_slot(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::EnginePool;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_reservoir_bounds_memory_and_keeps_counts() {
+        let mut audit = AdmissionAudit::new(AuditConfig {
+            samples_per_interval: 10,
+            max_intervals: 2,
+            ..AuditConfig::default()
+        });
+        for i in 0..3_000 {
+            let decision = if i % 3 == 0 {
+                AdmissionDecision::Reject(RejectReason::InsufficientVoltage)
+            } else {
+                AdmissionDecision::Admit
+            };
+            audit.record(i as f64 * 60.0, "api", decision, 42.0);
+        }
+        let intervals: Vec<_> = audit.intervals().collect();
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0].start_ms, 60_000.0);
+        assert_eq!(intervals[0].seen(), 1_000);
+        assert_eq!(intervals[0].rejected, 333);
+        assert_eq!(intervals[0].samples.len(), 10);
+        // Samples come from across the interval, not just its start
+        assert!(intervals[0]
+            .samples
+            .iter()
+            .any(|record| record.timestamp_ms > 90_000.0));
+    }
+
+    #[test]
+    fn test_pool_audits_decisions() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        pool.enable_admission_audit(AuditConfig::default());
+        for i in 0..10 {
+            pool.tick("api", i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        assert!(pool.try_admit("api", 1e9).is_admitted());
+        assert!(!pool.try_admit("api", 0.0).is_admitted());
+
+        let intervals = pool.drain_admission_audit();
+        assert_eq!(intervals.len(), 1);
+        let rejected = &intervals[0].samples[1];
+        assert_eq!(rejected.target, "api");
+        assert_eq!(rejected.reason, Some(RejectReason::InsufficientVoltage));
+        assert_eq!(
+            rejected.resistance,
+            pool.get("api").unwrap().state().resistance.0
+        );
+        assert!(pool.drain_admission_audit().is_empty());
+    }
+}
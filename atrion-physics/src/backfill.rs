@@ -0,0 +1,258 @@
+/**
+ * Bounded rewind window for late-arriving samples.
+ *
+ * Metrics pipelines deliver samples 5–30 s late. A plain tick drops such
+ * a sample as a backwards clock. With backfill enabled the engine keeps
+ * the state from before each recent tick; `TargetEngine::tick_at` rewinds
+ * to just before the late sample's timestamp, applies it, and re-applies
+ * the newer samples on top, so scar and momentum come out as if the
+ * sample had arrived on time. Each rewind point also keeps the engine's
+ * per-tick layers (soft-start ramp, breaker backoff, flap / metastable /
+ * change-point detectors, brownout level, learned baselines), so replayed
+ * ticks are observed once, not on top of what they already taught them.
+ *
+ * A rewind reaches back at most `window_ms` behind the newest tick and
+ * `max_ticks` ticks, and never past an operator action (reset, override,
+ * config change, shared-state absorb): replaying across one would undo it.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{TargetState, TickLayers};
+use crate::reorder::SequencedSample;
+
+/// Rewind window configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BackfillConfig {
+    /// How far behind the newest tick a late sample may land
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Ticks kept for rewinding (memory bound)
+    #[serde(alias = "max_ticks")]
+    pub max_ticks: usize,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 30_000.0,
+            max_ticks: 256,
+        }
+    }
+}
+
+/// Recent ticks with the state each one started from
+#[derive(Debug, Clone)]
+pub struct Backfill {
+    config: BackfillConfig,
+    /// (state and layers before the tick, sample applied), oldest first
+    ticks: VecDeque<(TargetState, TickLayers, SequencedSample)>,
+}
+
+impl Backfill {
+    pub fn new(config: BackfillConfig) -> Self {
+        Self {
+            config,
+            ticks: VecDeque::new(),
+        }
+    }
+
+    pub fn config(&self) -> &BackfillConfig {
+        &self.config
+    }
+
+    /// Ticks currently available for rewinding
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    /// Remember an applied tick and the state it started from
+    pub(crate) fn record(
+        &mut self,
+        before: TargetState,
+        layers: TickLayers,
+        sample: SequencedSample,
+    ) {
+        self.ticks.push_back((before, layers, sample));
+        let horizon = sample.timestamp_ms - self.config.window_ms;
+        while self.ticks.len() > self.config.max_ticks
+            || self
+                .ticks
+                .front()
+                .is_some_and(|(_, _, oldest)| oldest.timestamp_ms < horizon)
+        {
+            self.ticks.pop_front();
+        }
+    }
+
+    /// Nothing before this point may be rewound
+    pub(crate) fn clear(&mut self) {
+        self.ticks.clear();
+    }
+
+    /// State and layers to restore and samples to re-apply for a sample at
+    /// `timestamp_ms`, removing those ticks from the window
+    ///
+    /// `None` when the sample is not late (it is at or after the newest
+    /// tick) or is older than the window can reach.
+    pub(crate) fn rewind(
+        &mut self,
+        timestamp_ms: f64,
+    ) -> Option<(TargetState, TickLayers, Vec<SequencedSample>)> {
+        let (_, _, newest) = self.ticks.back()?;
+        let late = newest.timestamp_ms - self.config.window_ms..newest.timestamp_ms;
+        if !late.contains(&timestamp_ms) {
+            return None;
+        }
+        let index = self
+            .ticks
+            .partition_point(|(_, _, sample)| sample.timestamp_ms <= timestamp_ms);
+        let before = self.ticks[index].0;
+        // The tick before the retained ones may itself be newer
+        if before.tick_count > 0 && before.last_updated_ms > timestamp_ms {
+            return None;
+        }
+        let mut replay = self.ticks.drain(index..);
+        let (_, layers, first) = replay.next()?;
+        let replay = std::iter::once(first)
+            .chain(replay.map(|(_, _, sample)| sample))
+            .collect();
+        Some((before, layers, replay))
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backoff::BreakerBackoffConfig;
+    use crate::engine::TargetEngine;
+    use crate::flap::FlapConfig;
+    use crate::types::{OperationalMode, PhysicsConfig, PressureVector, SensitivityWeights};
+
+    const SPIKE: PressureVector = PressureVector {
+        latency: 0.95,
+        error: 0.8,
+        saturation: 0.9,
+    };
+
+    fn engine() -> TargetEngine {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_backfill(BackfillConfig::default());
+        engine
+    }
+
+    fn calm(i: usize) -> PressureVector {
+        PressureVector::new(0.1 + 0.01 * (i % 3) as f64, 0.0, 0.2)
+    }
+
+    #[test]
+    fn test_late_sample_recomputes_as_if_on_time() {
+        let mut on_time = engine();
+        let mut late = engine();
+        for i in 0..40 {
+            let now = i as f64 * 1_000.0;
+            on_time.tick(now, calm(i));
+            late.tick(now, calm(i));
+            if i == 30 {
+                on_time.tick(now + 500.0, SPIKE);
+            }
+        }
+        // 9.5 s late: lands between the ticks at 30 s and 31 s
+        let state = late.tick_at(30_500.0, SPIKE);
+        let expected = on_time.state();
+        assert_eq!(state.tick_count, expected.tick_count);
+        assert_eq!(state.last_updated_ms, 39_000.0);
+        assert_eq!(state.scar, expected.scar);
+        assert_eq!(state.momentum, expected.momentum);
+        assert_eq!(state.resistance, expected.resistance);
+
+        let stamps = |engine: &TargetEngine| -> Vec<f64> {
+            engine.history().iter().map(|s| s.timestamp_ms).collect()
+        };
+        assert_eq!(stamps(&late), stamps(&on_time));
+        assert_eq!(
+            late.backfill().unwrap().len(),
+            on_time.backfill().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_replay_observes_each_tick_once() {
+        let config = PhysicsConfig {
+            break_threshold: 25.0,
+            recovery_threshold: 20.0,
+            breaker_backoff: Some(BreakerBackoffConfig {
+                min_open_ms: 2_000.0,
+                ..BreakerBackoffConfig::default()
+            }),
+            ..PhysicsConfig::default()
+        };
+        let engine = || {
+            let mut engine = TargetEngine::new(config.clone(), SensitivityWeights::default());
+            engine.enable_backfill(BackfillConfig::default());
+            engine.enable_flap_detection(FlapConfig {
+                flaps: 2,
+                ..FlapConfig::default()
+            });
+            engine
+        };
+        // Two trip/close cycles inside the rewind window
+        let pressure = |i: usize| {
+            if (35..38).contains(&i) || (45..48).contains(&i) {
+                SPIKE
+            } else {
+                calm(i)
+            }
+        };
+        let mut on_time = engine();
+        let mut late = engine();
+        for i in 0..60 {
+            let now = i as f64 * 1_000.0;
+            on_time.tick(now, pressure(i));
+            late.tick(now, pressure(i));
+            if i == 31 {
+                on_time.tick(now + 500.0, calm(0));
+            }
+        }
+        late.tick_at(31_500.0, calm(0));
+        assert_eq!(on_time.breaker_backoff().trips(), 2);
+        assert_eq!(on_time.flap_detector().unwrap().level(), 1);
+        assert_eq!(late.breaker_backoff(), on_time.breaker_backoff());
+        assert_eq!(late.flap_detector(), on_time.flap_detector());
+        assert_eq!(late.state().scar, on_time.state().scar);
+        assert_eq!(late.transitions().count(), 5);
+        assert!(late.transitions().eq(on_time.transitions()));
+    }
+
+    #[test]
+    fn test_rewind_is_bounded() {
+        let mut engine = engine();
+        for i in 0..40 {
+            engine.tick(i as f64 * 1_000.0, calm(i));
+        }
+        let before = *engine.state();
+        // Beyond the 30 s window: dropped like any backwards sample
+        engine.tick_at(5_000.0, SPIKE);
+        assert_eq!(engine.state().tick_count, before.tick_count);
+
+        // Operator actions cannot be rewound across
+        engine.force_mode_until(OperationalMode::Operational, 60_000.0);
+        assert!(engine.backfill().unwrap().is_empty());
+        engine.tick_at(38_500.0, SPIKE);
+        assert_eq!(engine.state().tick_count, before.tick_count);
+
+        engine.tick(40_000.0, calm(0));
+        engine.tick_at(40_000.0, SPIKE);
+        assert_eq!(engine.state().tick_count, before.tick_count + 2);
+    }
+}
@@ -0,0 +1,194 @@
+/**
+ * Minimum open time with exponential backoff on repeated trips.
+ *
+ * The threshold-only breaker closes as soon as resistance falls under the
+ * recovery threshold, which can be a single quiet tick after opening. With
+ * `PhysicsConfig::breaker_backoff` set the breaker also stays open for at
+ * least `min_open_ms`, and every further trip multiplies that by
+ * `multiplier` (up to `max_open_ms`), the way standard breakers back off a
+ * flapping dependency. After `reset_after_ms` of uninterrupted operation
+ * the trip count starts over.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::types::OperationalMode;
+
+/// Breaker open-time backoff configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BreakerBackoffConfig {
+    /// Open time after the first trip
+    #[serde(alias = "min_open_ms")]
+    pub min_open_ms: f64,
+    /// Growth of the open time per repeated trip (≥ 1)
+    pub multiplier: f64,
+    #[serde(alias = "max_open_ms")]
+    pub max_open_ms: f64,
+    /// Healthy time after which the trip count resets
+    #[serde(alias = "reset_after_ms")]
+    pub reset_after_ms: f64,
+}
+
+impl Default for BreakerBackoffConfig {
+    fn default() -> Self {
+        Self {
+            min_open_ms: 5_000.0,
+            multiplier: 2.0,
+            max_open_ms: 300_000.0,
+            reset_after_ms: 60_000.0,
+        }
+    }
+}
+
+impl BreakerBackoffConfig {
+    /// Open time after the `trips`-th consecutive trip
+    pub fn open_ms(&self, trips: u32) -> f64 {
+        let exponent = trips.saturating_sub(1).min(i32::MAX as u32) as i32;
+        (self.min_open_ms * self.multiplier.max(1.0).powi(exponent)).min(self.max_open_ms)
+    }
+}
+
+/// Trip count and open/close times of one breaker
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct BreakerBackoff {
+    trips: u32,
+    opened_ms: Option<f64>,
+    closed_ms: Option<f64>,
+}
+
+impl BreakerBackoff {
+    /// Consecutive trips counted toward the backoff
+    pub fn trips(&self) -> u32 {
+        self.trips
+    }
+
+    /// Earliest time an open breaker may close (`None` while closed)
+    pub fn open_until(&self, config: &BreakerBackoffConfig) -> Option<f64> {
+        self.opened_ms
+            .map(|opened| opened + config.open_ms(self.trips))
+    }
+
+    pub fn may_close(&self, config: &BreakerBackoffConfig, now_ms: f64) -> bool {
+        self.open_until(config).is_none_or(|until| now_ms >= until)
+    }
+
+    /// Record a tick's mode transition
+    pub fn observe(
+        &mut self,
+        config: &BreakerBackoffConfig,
+        now_ms: f64,
+        from: OperationalMode,
+        to: OperationalMode,
+    ) {
+        let open = to == OperationalMode::CircuitBreaker;
+        if open == (from == OperationalMode::CircuitBreaker) {
+            return;
+        }
+        if open {
+            let healthy = self
+                .closed_ms
+                .is_some_and(|closed| now_ms - closed >= config.reset_after_ms);
+            if healthy {
+                self.trips = 0;
+            }
+            self.trips = self.trips.saturating_add(1);
+            self.opened_ms = Some(now_ms);
+        } else {
+            self.opened_ms = None;
+            self.closed_ms = Some(now_ms);
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backfill::BackfillConfig;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    use OperationalMode::{CircuitBreaker, Operational};
+
+    #[test]
+    fn test_open_time_doubles_and_resets() {
+        let config = BreakerBackoffConfig::default();
+        let mut backoff = BreakerBackoff::default();
+        backoff.observe(&config, 0.0, Operational, CircuitBreaker);
+        assert_eq!(backoff.open_until(&config), Some(5_000.0));
+        assert!(!backoff.may_close(&config, 4_999.0));
+        backoff.observe(&config, 5_000.0, CircuitBreaker, Operational);
+        backoff.observe(&config, 6_000.0, Operational, CircuitBreaker);
+        assert_eq!(backoff.open_until(&config), Some(16_000.0));
+        assert_eq!(config.open_ms(10), 300_000.0);
+
+        // A minute of health forgives the earlier trips
+        backoff.observe(&config, 16_000.0, CircuitBreaker, Operational);
+        backoff.observe(&config, 76_000.0, Operational, CircuitBreaker);
+        assert_eq!(backoff.trips(), 1);
+    }
+
+    #[test]
+    fn test_engine_holds_breaker_open() {
+        let config = PhysicsConfig {
+            breaker_backoff: Some(BreakerBackoffConfig {
+                min_open_ms: 20_000.0,
+                ..BreakerBackoffConfig::default()
+            }),
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        let mut now = 0.0;
+        for _ in 0..10 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        let mut open_spans = Vec::new();
+        for _ in 0..2 {
+            while engine.state().mode != CircuitBreaker {
+                now += 100.0;
+                engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+            }
+            let opened = now;
+            while engine.state().mode == CircuitBreaker {
+                now += 1_000.0;
+                engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+            }
+            open_spans.push(now - opened);
+        }
+        assert_eq!(open_spans, vec![20_000.0, 40_000.0]);
+        assert_eq!(engine.breaker_backoff().trips(), 2);
+    }
+
+    #[test]
+    fn test_late_sample_does_not_count_a_trip_twice() {
+        let config = PhysicsConfig {
+            breaker_backoff: Some(BreakerBackoffConfig::default()),
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        engine.enable_backfill(BackfillConfig::default());
+        let mut now = 0.0;
+        for _ in 0..10 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        let calm_until = now;
+        while engine.state().mode != CircuitBreaker {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+        }
+        let tripped = *engine.breaker_backoff();
+        assert_eq!(tripped.trips(), 1);
+
+        // The rewind starts before the trip, so the replay trips again
+        engine.tick_at(calm_until + 50.0, PressureVector::new(0.0, 0.0, 0.0));
+        assert_eq!(engine.state().mode, CircuitBreaker);
+        assert_eq!(*engine.breaker_backoff(), tripped);
+    }
+}
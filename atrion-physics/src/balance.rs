@@ -0,0 +1,185 @@
+/**
+ * Load balancing from resistance (routing, not just admission).
+ *
+ * Two pickers over per-target costs (resistance, infinite when the target
+ * must not receive traffic):
+ *
+ * - weighted: selection probability ∝ 1 / R^power
+ * - power of two choices: sample two candidates, take the cheaper
+ *
+ * Randomness is supplied by the caller as uniform draws in [0, 1), so the
+ * same helpers serve native code and JS (`Math.random()`).
+ */
+use wasm_bindgen::prelude::*;
+
+use crate::engine::TargetState;
+use crate::pool::EnginePool;
+use crate::types::OperationalMode;
+
+/// Normalized selection weights ∝ `1 / cost^power`
+///
+/// Non-finite or non-positive costs get weight 0. All zeros when no
+/// candidate is routable.
+pub fn selection_weights(costs: &[f64], power: f64) -> Vec<f64> {
+    let raw: Vec<f64> = costs
+        .iter()
+        .map(|&cost| {
+            if cost.is_finite() && cost > 0.0 {
+                cost.powf(-power)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let total: f64 = raw.iter().sum();
+    if total > 0.0 && total.is_finite() {
+        raw.into_iter().map(|w| w / total).collect()
+    } else {
+        vec![0.0; costs.len()]
+    }
+}
+
+/// Index chosen by a weighted draw `r` in [0, 1)
+pub fn pick_weighted(weights: &[f64], r: f64) -> Option<usize> {
+    let mut remaining = r.clamp(0.0, 1.0);
+    let mut last = None;
+    for (i, &w) in weights.iter().enumerate() {
+        if w <= 0.0 {
+            continue;
+        }
+        if remaining < w {
+            return Some(i);
+        }
+        remaining -= w;
+        last = Some(i);
+    }
+    // Rounding left `remaining` just above the final weight
+    last
+}
+
+/// Power of two choices: the cheaper of two distinct candidates
+///
+/// `r1` and `r2` are uniform draws in [0, 1). Returns `None` when neither
+/// candidate is routable.
+pub fn pick_two(costs: &[f64], r1: f64, r2: f64) -> Option<usize> {
+    let n = costs.len();
+    if n == 0 {
+        return None;
+    }
+    let a = index(r1, n);
+    let b = if n > 1 {
+        (a + 1 + index(r2, n - 1)) % n
+    } else {
+        a
+    };
+    let cheaper = if costs[b] < costs[a] { b } else { a };
+    costs[cheaper].is_finite().then_some(cheaper)
+}
+
+fn index(r: f64, n: usize) -> usize {
+    ((r.clamp(0.0, 1.0) * n as f64) as usize).min(n - 1)
+}
+
+// ============================================================================
+// POOL ROUTING
+// ============================================================================
+
+impl EnginePool {
+    /// Cost of routing to a target: its resistance, infinite when
+    /// quarantined or tripped, the bootstrap resistance when unknown
+    pub fn routing_cost(&self, id: &str) -> f64 {
+        if self.quarantined(id).is_some() {
+            return f64::INFINITY;
+        }
+        match self.get(id).map(|engine| engine.state()) {
+            Some(state) if state.mode == OperationalMode::CircuitBreaker => f64::INFINITY,
+            Some(state) => state.resistance.0,
+            None => TargetState::bootstrap(&self.config_for(id)).resistance.0,
+        }
+    }
+
+    /// Selection weights ∝ 1 / resistance^power over `ids` (sum to 1)
+    pub fn selection_weights(&self, ids: &[&str], power: f64) -> Vec<f64> {
+        let costs: Vec<f64> = ids.iter().map(|id| self.routing_cost(id)).collect();
+        selection_weights(&costs, power)
+    }
+
+    /// Power-of-two-choices pick among `ids` with uniform draws `r1`, `r2`
+    pub fn pick_two<'a>(&self, ids: &[&'a str], r1: f64, r2: f64) -> Option<&'a str> {
+        let costs: Vec<f64> = ids.iter().map(|id| self.routing_cost(id)).collect();
+        pick_two(&costs, r1, r2).map(|i| ids[i])
+    }
+}
+
+#[wasm_bindgen]
+impl EnginePool {
+    /// Selection weights for `ids`, in order
+    #[wasm_bindgen(js_name = selectionWeights)]
+    pub fn selection_weights_js(&self, ids: Vec<String>, power: f64) -> Vec<f64> {
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        self.selection_weights(&ids, power)
+    }
+
+    /// Power-of-two-choices pick (pass two `Math.random()` draws)
+    #[wasm_bindgen(js_name = pickTwo)]
+    pub fn pick_two_js(&self, ids: Vec<String>, r1: f64, r2: f64) -> Option<String> {
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        self.pick_two(&ids, r1, r2).map(str::to_string)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_weights_inverse_to_resistance() {
+        let weights = selection_weights(&[10.0, 20.0, f64::INFINITY], 1.0);
+        assert!((weights[0] - 2.0 / 3.0).abs() < 1e-12);
+        assert!((weights[1] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(weights[2], 0.0);
+
+        let sharper = selection_weights(&[10.0, 20.0], 2.0);
+        assert!((sharper[0] - 0.8).abs() < 1e-12);
+
+        assert_eq!(pick_weighted(&weights, 0.5), Some(0));
+        assert_eq!(pick_weighted(&weights, 0.9), Some(1));
+        assert_eq!(pick_weighted(&weights, 1.0), Some(1));
+        assert_eq!(pick_weighted(&[0.0, 0.0], 0.3), None);
+    }
+
+    #[test]
+    fn test_pick_two_prefers_cheaper_distinct_candidate() {
+        let costs = [50.0, 10.0, f64::INFINITY];
+        assert_eq!(pick_two(&costs, 0.0, 0.0), Some(1));
+        assert_eq!(pick_two(&costs, 0.9, 0.0), Some(0));
+        assert_eq!(pick_two(&[f64::INFINITY; 2], 0.1, 0.1), None);
+        assert_eq!(pick_two(&[7.0], 0.5, 0.5), Some(0));
+    }
+
+    #[test]
+    fn test_pool_routing_avoids_tripped_and_quarantined() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut now = 0.0;
+        while pool.mode("down") != Some(OperationalMode::CircuitBreaker) {
+            pool.tick("down", now, PressureVector::new(1.0, 1.0, 1.0));
+            pool.tick("busy", now, PressureVector::new(0.5, 0.3, 0.0));
+            pool.tick("idle", now, PressureVector::new(0.0, 0.0, 0.0));
+            now += 100.0;
+        }
+        pool.quarantine("idle", "draining", now, None);
+
+        let ids = ["down", "busy", "idle", "new"];
+        let weights = pool.selection_weights(&ids, 1.0);
+        assert_eq!(weights[0], 0.0);
+        assert_eq!(weights[2], 0.0);
+        assert!((weights[1] + weights[3] - 1.0).abs() < 1e-12);
+        assert_eq!(pool.pick_two(&ids[..3], 0.0, 0.0), Some("busy"));
+        assert_eq!(pool.pick_two(&["down", "idle"], 0.0, 0.0), None);
+    }
+}
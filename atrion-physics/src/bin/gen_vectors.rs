@@ -0,0 +1,23 @@
+//! Emit cross-language parity vectors as JSON.
+//!
+//! cargo run --bin gen-vectors --features vectors -- [out.json]
+//!
+//! Writes to stdout when no path is given. The TS suite replays the file;
+//! `ATRION_PARITY_VECTORS=<file> cargo test parity` replays one here.
+
+use std::io::Write;
+
+use atrion_physics::parity::generate;
+use atrion_physics::types::{PhysicsConfig, SensitivityWeights};
+
+fn main() -> std::io::Result<()> {
+    let vectors = generate(PhysicsConfig::default(), SensitivityWeights::default());
+    let json = serde_json::to_string_pretty(&vectors)?;
+
+    match std::env::args().nth(1) {
+        Some(path) => std::fs::write(&path, json)?,
+        None => writeln!(std::io::stdout().lock(), "{json}")?,
+    }
+    eprintln!("{} vectors", vectors.cases.len());
+    Ok(())
+}
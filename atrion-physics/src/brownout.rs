@@ -0,0 +1,135 @@
+/**
+ * Brownout: multi-stage degradation driven by resistance bands.
+ *
+ * The breaker is binary, but many services can shed optional work
+ * (recommendations, rich previews, background sync) well before they
+ * have to fail closed. With `PhysicsConfig::brownout` set the engine also
+ * tracks a degradation level: level 0 admits everything, level `i` is
+ * entered once resistance reaches `levels[i-1].enter` and left again only
+ * when it falls below that level's `exit`, so each band has its own
+ * hysteresis. Consumers read `degradation_level()` (or the matching
+ * admission fraction) and switch features off accordingly.
+ */
+use serde::{Deserialize, Serialize};
+
+/// One rung of the degradation ladder
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BrownoutLevel {
+    /// Resistance at which this level is entered
+    pub enter: f64,
+    /// Resistance below which it is left again (≤ `enter`)
+    pub exit: f64,
+    /// Share of traffic (or features) kept at this level (0..=1)
+    pub admission: f64,
+}
+
+/// Degradation ladder, mildest level first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct BrownoutConfig {
+    pub levels: Vec<BrownoutLevel>,
+}
+
+impl Default for BrownoutConfig {
+    /// 75/50/25/0% between the default recovery and break thresholds
+    fn default() -> Self {
+        let level = |enter, exit, admission| BrownoutLevel {
+            enter,
+            exit,
+            admission,
+        };
+        Self {
+            levels: vec![
+                level(40.0, 30.0, 0.75),
+                level(60.0, 50.0, 0.5),
+                level(80.0, 70.0, 0.25),
+                level(100.0, 90.0, 0.0),
+            ],
+        }
+    }
+}
+
+impl BrownoutConfig {
+    /// Level after observing `resistance` at `level`
+    pub fn step(&self, level: u32, resistance: f64) -> u32 {
+        let mut level = (level as usize).min(self.levels.len());
+        while level < self.levels.len() && resistance >= self.levels[level].enter {
+            level += 1;
+        }
+        while level > 0 && resistance < self.levels[level - 1].exit {
+            level -= 1;
+        }
+        level as u32
+    }
+
+    /// Admission fraction at `level` (1.0 at level 0)
+    pub fn admission(&self, level: u32) -> f64 {
+        match level {
+            0 => 1.0,
+            n => self
+                .levels
+                .get(n as usize - 1)
+                .or(self.levels.last())
+                .map_or(1.0, |level| level.admission),
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::events::EngineEvent;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_bands_have_hysteresis() {
+        let config = BrownoutConfig::default();
+        assert_eq!(config.step(0, 39.0), 0);
+        assert_eq!(config.step(0, 65.0), 2);
+        // Inside the 50..60 band the level holds in both directions
+        assert_eq!(config.step(2, 55.0), 2);
+        assert_eq!(config.step(1, 55.0), 1);
+        assert_eq!(config.step(2, 45.0), 1);
+        assert_eq!(config.step(4, 10.0), 0);
+        assert_eq!(config.admission(2), 0.5);
+        assert_eq!(config.admission(4), 0.0);
+    }
+
+    #[test]
+    fn test_engine_degrades_progressively() {
+        let config = PhysicsConfig {
+            brownout: Some(BrownoutConfig::default()),
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        let mut now = 0.0;
+        for _ in 0..10 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        assert_eq!(engine.degradation_level(), 0);
+        let mut levels = vec![0];
+        while engine.degradation_level() < 4 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+            if levels.last() != Some(&engine.degradation_level()) {
+                levels.push(engine.degradation_level());
+            }
+        }
+        assert!(levels.len() > 2, "stepped straight to {levels:?}");
+        assert!(engine.drain_events().iter().any(|event| matches!(
+            event,
+            EngineEvent::DegradationChanged { to: 4, admission, .. } if *admission == 0.0
+        )));
+    }
+}
@@ -0,0 +1,476 @@
+/**
+ * Fluent builders for PhysicsConfig and SensitivityWeights.
+ *
+ * Builders start from the defaults, so callers only name what they change
+ * and keep compiling as fields are added. `build()` validates the result;
+ * the same setters are exported to JS (`new PhysicsConfigBuilder()
+ * .baseResistance(12).build()`).
+ */
+use wasm_bindgen::prelude::*;
+
+use crate::backoff::BreakerBackoffConfig;
+use crate::brownout::{BrownoutConfig, BrownoutLevel};
+use crate::critical::AdaptiveCriticalConfig;
+use crate::error::AtrionError;
+use crate::jitter::RecoveryJitterConfig;
+use crate::markers::DeployGraceConfig;
+use crate::pid::PidConfig;
+use crate::resistance::TermCaps;
+use crate::scar::DualScarConfig;
+use crate::seasonal::SeasonalConfig;
+use crate::softstart::SoftStartConfig;
+use crate::types::{
+    ControlMode, FormulaRevision, PhysicsConfig, ScarPrecision, SensitivityWeights,
+    ThresholdComparison,
+};
+use crate::warmup::WarmupConfig;
+
+fn invalid(field: &'static str, reason: &str) -> AtrionError {
+    AtrionError::InvalidConfig {
+        field,
+        reason: reason.to_string(),
+    }
+}
+
+fn non_negative(field: &'static str, value: f64) -> Result<(), AtrionError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(field, "must be a finite number >= 0"))
+    }
+}
+
+fn positive(field: &'static str, value: f64) -> Result<(), AtrionError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(field, "must be a finite number > 0"))
+    }
+}
+
+// ============================================================================
+// PHYSICS CONFIG
+// ============================================================================
+
+impl PhysicsConfig {
+    /// Check ranges and cross-field invariants
+    pub fn validate(&self) -> Result<(), AtrionError> {
+        positive("base_resistance", self.base_resistance)?;
+        non_negative("damping_factor", self.damping_factor)?;
+        non_negative("scar_factor", self.scar_factor)?;
+        positive("momentum_halflife", self.momentum_halflife)?;
+        non_negative("max_delta_t_ms", self.max_delta_t_ms)?;
+        positive("break_threshold", self.break_threshold)?;
+        non_negative("recovery_threshold", self.recovery_threshold)?;
+        if self.break_threshold <= self.base_resistance {
+            return Err(invalid("break_threshold", "must exceed base_resistance"));
+        }
+        if self.recovery_threshold >= self.break_threshold {
+            return Err(invalid(
+                "recovery_threshold",
+                "must be below break_threshold",
+            ));
+        }
+        if let Some(ceiling) = self.resistance_ceiling {
+            positive("resistance_ceiling", ceiling)?;
+        }
+        let pid = &self.pid;
+        non_negative("pid.kp", pid.kp)?;
+        non_negative("pid.ki", pid.ki)?;
+        non_negative("pid.kd", pid.kd)?;
+        non_negative("pid.integral_max", pid.integral_max)?;
+        if let Some(dual) = &self.dual_scar {
+            non_negative("dual_scar.fast_weight", dual.fast_weight)?;
+            non_negative("dual_scar.slow_weight", dual.slow_weight)?;
+            non_negative("dual_scar.slow_factor", dual.slow_factor)?;
+            non_negative("dual_scar.slow_decay_rate", dual.slow_decay_rate)?;
+        }
+        if let Some(soft_start) = &self.soft_start {
+            positive("soft_start.window_ms", soft_start.window_ms)?;
+        }
+        if let Some(backoff) = &self.breaker_backoff {
+            non_negative("breaker_backoff.min_open_ms", backoff.min_open_ms)?;
+            non_negative("breaker_backoff.max_open_ms", backoff.max_open_ms)?;
+            non_negative("breaker_backoff.reset_after_ms", backoff.reset_after_ms)?;
+            if !backoff.multiplier.is_finite() || backoff.multiplier < 1.0 {
+                return Err(invalid(
+                    "breaker_backoff.multiplier",
+                    "must be a finite number >= 1",
+                ));
+            }
+        }
+        if let Some(jitter) = &self.recovery_jitter {
+            non_negative("recovery_jitter.max_delay_ms", jitter.max_delay_ms)?;
+            if !(0.0..1.0).contains(&jitter.slope_spread) {
+                return Err(invalid("recovery_jitter.slope_spread", "must be in [0, 1)"));
+            }
+        }
+        if let Some(brownout) = &self.brownout {
+            let mut previous: Option<&BrownoutLevel> = None;
+            for level in &brownout.levels {
+                non_negative("brownout.levels.exit", level.exit)?;
+                if level.exit > level.enter {
+                    return Err(invalid("brownout.levels.exit", "must not exceed enter"));
+                }
+                if !(0.0..=1.0).contains(&level.admission) {
+                    return Err(invalid("brownout.levels.admission", "must be in [0, 1]"));
+                }
+                if let Some(previous) = previous {
+                    if level.enter <= previous.enter || level.admission > previous.admission {
+                        return Err(invalid(
+                            "brownout.levels",
+                            "must be ordered by rising enter and falling admission",
+                        ));
+                    }
+                }
+                previous = Some(level);
+            }
+        }
+        if let Some(seasonal) = &self.seasonal {
+            if !(0.0..=1.0).contains(&seasonal.alpha) {
+                return Err(invalid("seasonal.alpha", "must be in [0, 1]"));
+            }
+            if !seasonal.utc_offset_ms.is_finite() {
+                return Err(invalid("seasonal.utc_offset_ms", "must be finite"));
+            }
+        }
+        if let Some(grace) = &self.deploy_grace {
+            non_negative("deploy_grace.window_ms", grace.window_ms)?;
+            if !(0.0..=1.0).contains(&grace.trauma_scale) {
+                return Err(invalid("deploy_grace.trauma_scale", "must be in [0, 1]"));
+            }
+        }
+        if let Some(warmup) = &self.warmup {
+            non_negative("warmup.window_ms", warmup.window_ms)?;
+            if !(0.0..=1.0).contains(&warmup.trauma_scale) {
+                return Err(invalid("warmup.trauma_scale", "must be in [0, 1]"));
+            }
+        }
+        if let Some(adaptive) = &self.adaptive_critical {
+            if !(0.0..=1.0).contains(&adaptive.quantile) {
+                return Err(invalid("adaptive_critical.quantile", "must be in [0, 1]"));
+            }
+            non_negative("adaptive_critical.window_ms", adaptive.window_ms)?;
+            positive("adaptive_critical.min", adaptive.min)?;
+            if adaptive.max < adaptive.min {
+                return Err(invalid("adaptive_critical.max", "must be >= min"));
+            }
+        }
+        if let Some(caps) = &self.term_caps {
+            let limits = [
+                ("term_caps.latency", caps.latency),
+                ("term_caps.error", caps.error),
+                ("term_caps.saturation", caps.saturation),
+                ("term_caps.momentum", caps.momentum),
+                ("term_caps.scar", caps.scar),
+                ("term_caps.staleness", caps.staleness),
+            ];
+            for (field, limit) in limits {
+                if let Some(limit) = limit {
+                    non_negative(field, limit)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl PhysicsConfig {
+    /// Builder starting from the defaults
+    pub fn builder() -> PhysicsConfigBuilder {
+        PhysicsConfigBuilder::new()
+    }
+}
+
+/// Fluent, validating PhysicsConfig builder
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct PhysicsConfigBuilder {
+    config: PhysicsConfig,
+}
+
+impl PhysicsConfigBuilder {
+    pub fn pid(mut self, pid: PidConfig) -> Self {
+        self.config.pid = pid;
+        self
+    }
+
+    pub fn dual_scar(mut self, dual: DualScarConfig) -> Self {
+        self.config.dual_scar = Some(dual);
+        self
+    }
+
+    pub fn term_caps(mut self, caps: TermCaps) -> Self {
+        self.config.term_caps = Some(caps);
+        self
+    }
+
+    pub fn soft_start(mut self, soft_start: SoftStartConfig) -> Self {
+        self.config.soft_start = Some(soft_start);
+        self
+    }
+
+    pub fn breaker_backoff(mut self, backoff: BreakerBackoffConfig) -> Self {
+        self.config.breaker_backoff = Some(backoff);
+        self
+    }
+
+    pub fn recovery_jitter(mut self, jitter: RecoveryJitterConfig) -> Self {
+        self.config.recovery_jitter = Some(jitter);
+        self
+    }
+
+    pub fn brownout(mut self, brownout: BrownoutConfig) -> Self {
+        self.config.brownout = Some(brownout);
+        self
+    }
+
+    pub fn seasonal(mut self, seasonal: SeasonalConfig) -> Self {
+        self.config.seasonal = Some(seasonal);
+        self
+    }
+
+    pub fn deploy_grace(mut self, grace: DeployGraceConfig) -> Self {
+        self.config.deploy_grace = Some(grace);
+        self
+    }
+
+    pub fn warmup(mut self, warmup: WarmupConfig) -> Self {
+        self.config.warmup = Some(warmup);
+        self
+    }
+
+    pub fn adaptive_critical(mut self, adaptive: AdaptiveCriticalConfig) -> Self {
+        self.config.adaptive_critical = Some(adaptive);
+        self
+    }
+
+    /// Validate and return the config
+    pub fn build(self) -> Result<PhysicsConfig, AtrionError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+#[wasm_bindgen]
+impl PhysicsConfigBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[wasm_bindgen(js_name = baseResistance)]
+    pub fn base_resistance(mut self, value: f64) -> Self {
+        self.config.base_resistance = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = dampingFactor)]
+    pub fn damping_factor(mut self, value: f64) -> Self {
+        self.config.damping_factor = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = scarFactor)]
+    pub fn scar_factor(mut self, value: f64) -> Self {
+        self.config.scar_factor = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = momentumHalflife)]
+    pub fn momentum_halflife(mut self, value: f64) -> Self {
+        self.config.momentum_halflife = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = bootstrapTicks)]
+    pub fn bootstrap_ticks(mut self, value: u32) -> Self {
+        self.config.bootstrap_ticks = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = breakThreshold)]
+    pub fn break_threshold(mut self, value: f64) -> Self {
+        self.config.break_threshold = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = recoveryThreshold)]
+    pub fn recovery_threshold(mut self, value: f64) -> Self {
+        self.config.recovery_threshold = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = historyCapacity)]
+    pub fn history_capacity(mut self, value: u32) -> Self {
+        self.config.history_capacity = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = maxDeltaTMs)]
+    pub fn max_delta_t_ms(mut self, value: f64) -> Self {
+        self.config.max_delta_t_ms = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = controlMode)]
+    pub fn control_mode(mut self, mode: ControlMode) -> Self {
+        self.config.control_mode = mode;
+        self
+    }
+
+    #[wasm_bindgen(js_name = formula)]
+    pub fn formula(mut self, revision: FormulaRevision) -> Self {
+        self.config.formula = revision;
+        self
+    }
+
+    #[wasm_bindgen(js_name = criticalComparison)]
+    pub fn critical_comparison(mut self, comparison: ThresholdComparison) -> Self {
+        self.config.critical_comparison = comparison;
+        self
+    }
+
+    #[wasm_bindgen(js_name = scarPrecision)]
+    pub fn scar_precision(mut self, precision: ScarPrecision) -> Self {
+        self.config.scar_precision = precision;
+        self
+    }
+
+    #[wasm_bindgen(js_name = observeOnly)]
+    pub fn observe_only(mut self, enabled: bool) -> Self {
+        self.config.observe_only = enabled;
+        self
+    }
+
+    #[wasm_bindgen(js_name = resistanceCeiling)]
+    pub fn resistance_ceiling(mut self, value: f64) -> Self {
+        self.config.resistance_ceiling = Some(value);
+        self
+    }
+
+    /// Validate and return the config, throwing on invalid values
+    #[wasm_bindgen(js_name = build)]
+    pub fn build_js(self) -> Result<PhysicsConfig, JsValue> {
+        Ok(self.build()?)
+    }
+}
+
+// ============================================================================
+// SENSITIVITY WEIGHTS
+// ============================================================================
+
+impl SensitivityWeights {
+    /// Weights must be finite and non-negative
+    pub fn validate(&self) -> Result<(), AtrionError> {
+        non_negative("w_latency", self.w_latency)?;
+        non_negative("w_error", self.w_error)?;
+        non_negative("w_saturation", self.w_saturation)
+    }
+}
+
+#[wasm_bindgen]
+impl SensitivityWeights {
+    /// Builder starting from the default (TS-derived) weights
+    pub fn builder() -> SensitivityWeightsBuilder {
+        SensitivityWeightsBuilder::new()
+    }
+}
+
+/// Fluent, validating SensitivityWeights builder
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct SensitivityWeightsBuilder {
+    weights: SensitivityWeights,
+}
+
+impl SensitivityWeightsBuilder {
+    /// Validate and return the weights
+    pub fn build(self) -> Result<SensitivityWeights, AtrionError> {
+        self.weights.validate()?;
+        Ok(self.weights)
+    }
+}
+
+#[wasm_bindgen]
+impl SensitivityWeightsBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[wasm_bindgen(js_name = latency)]
+    pub fn w_latency(mut self, value: f64) -> Self {
+        self.weights.w_latency = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = error)]
+    pub fn w_error(mut self, value: f64) -> Self {
+        self.weights.w_error = value;
+        self
+    }
+
+    #[wasm_bindgen(js_name = saturation)]
+    pub fn w_saturation(mut self, value: f64) -> Self {
+        self.weights.w_saturation = value;
+        self
+    }
+
+    /// Validate and return the weights, throwing on invalid values
+    #[wasm_bindgen(js_name = build)]
+    pub fn build_js(self) -> Result<SensitivityWeights, JsValue> {
+        Ok(self.build()?)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_overrides_only_named_fields() {
+        let config = PhysicsConfig::builder()
+            .base_resistance(12.0)
+            .scar_factor(3.0)
+            .build()
+            .unwrap();
+        assert_eq!(config.base_resistance, 12.0);
+        assert_eq!(config.scar_factor, 3.0);
+        assert_eq!(
+            config.damping_factor,
+            PhysicsConfig::default().damping_factor
+        );
+
+        let weights = SensitivityWeights::builder().w_error(4.0).build().unwrap();
+        assert_eq!(weights.w_error, 4.0);
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_values() {
+        assert_eq!(
+            PhysicsConfig::builder()
+                .base_resistance(-1.0)
+                .build()
+                .unwrap_err(),
+            invalid("base_resistance", "must be a finite number > 0")
+        );
+        assert!(matches!(
+            PhysicsConfig::builder().recovery_threshold(150.0).build(),
+            Err(AtrionError::InvalidConfig {
+                field: "recovery_threshold",
+                ..
+            })
+        ));
+        assert!(SensitivityWeights::builder()
+            .w_latency(f64::NAN)
+            .build()
+            .is_err());
+        assert!(PhysicsConfig::default().validate().is_ok());
+    }
+}
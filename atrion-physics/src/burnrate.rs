@@ -0,0 +1,276 @@
+/**
+ * SLO error-budget burn rate (multi-window).
+ *
+ * burn = observed_error_rate / (1 - slo_target)
+ *
+ * A burn rate of 1.0 spends the error budget exactly over the SLO period.
+ * Following SRE multi-window alerting, the effective burn is the MINIMUM of
+ * the short and long windows: both must agree before the physics reacts.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::confidence;
+use crate::types::PressureVector;
+
+/// How the burn rate feeds back into the physics
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+pub enum BurnRateMapping {
+    /// Raise the error axis to at least the burn-rate pressure
+    ErrorAxis,
+    /// Add `ohms_per_burn × max(0, burn - 1)` to resistance
+    Surcharge {
+        #[serde(alias = "ohms_per_burn")]
+        ohms_per_burn: f64,
+    },
+}
+
+/// Burn rate tracker configuration
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BurnRateConfig {
+    /// SLO success target, e.g. 0.999
+    #[serde(alias = "slo_target")]
+    pub slo_target: f64,
+    #[serde(alias = "short_window_ms")]
+    pub short_window_ms: f64,
+    #[serde(alias = "long_window_ms")]
+    pub long_window_ms: f64,
+    /// Outcome aggregation granularity (at least 1ms)
+    #[serde(alias = "bucket_ms")]
+    pub bucket_ms: f64,
+    /// Burn rate mapped to full pressure (1.0) on the error axis
+    #[serde(alias = "alert_burn_rate")]
+    pub alert_burn_rate: f64,
+    pub mapping: BurnRateMapping,
+}
+
+impl Default for BurnRateConfig {
+    fn default() -> Self {
+        // Google SRE workbook "page" policy: 14.4x over 5m and 1h
+        Self {
+            slo_target: 0.999,
+            short_window_ms: 300_000.0,
+            long_window_ms: 3_600_000.0,
+            bucket_ms: 10_000.0,
+            alert_burn_rate: 14.4,
+            mapping: BurnRateMapping::ErrorAxis,
+        }
+    }
+}
+
+/// Burn rates for both windows
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BurnRates {
+    pub short: f64,
+    pub long: f64,
+}
+
+impl BurnRates {
+    /// Multi-window effective burn (both windows must agree)
+    #[inline]
+    pub fn effective(&self) -> f64 {
+        self.short.min(self.long)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OutcomeBucket {
+    #[serde(alias = "start_ms")]
+    start_ms: f64,
+    good: u64,
+    bad: u64,
+}
+
+/// Sliding-window outcome counter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurnRateTracker {
+    config: BurnRateConfig,
+    buckets: VecDeque<OutcomeBucket>,
+}
+
+impl BurnRateTracker {
+    pub fn new(config: BurnRateConfig) -> Self {
+        Self {
+            config,
+            buckets: VecDeque::new(),
+        }
+    }
+
+    pub fn config(&self) -> &BurnRateConfig {
+        &self.config
+    }
+
+    #[inline]
+    fn bucket_ms(&self) -> f64 {
+        self.config.bucket_ms.max(1.0)
+    }
+
+    /// Ingest a batch of request outcomes observed at `now_ms`
+    pub fn record(&mut self, now_ms: f64, good: u64, bad: u64) {
+        let bucket_ms = self.bucket_ms();
+        let start_ms = (now_ms / bucket_ms).floor() * bucket_ms;
+        match self.buckets.back_mut() {
+            Some(last) if last.start_ms == start_ms => {
+                last.good += good;
+                last.bad += bad;
+            }
+            _ => self.buckets.push_back(OutcomeBucket {
+                start_ms,
+                good,
+                bad,
+            }),
+        }
+
+        // Drop buckets that fell out of the longest window
+        let horizon = now_ms - self.config.short_window_ms.max(self.config.long_window_ms);
+        while self
+            .buckets
+            .front()
+            .is_some_and(|b| b.start_ms + bucket_ms <= horizon)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Burn rate over `window_ms` ending at `now_ms`
+    pub fn burn_rate(&self, now_ms: f64, window_ms: f64) -> f64 {
+        let (good, bad) = self.outcomes(now_ms, window_ms);
+        let total = good + bad;
+        let budget = 1.0 - self.config.slo_target;
+        if total == 0 || budget <= 0.0 {
+            return 0.0;
+        }
+        (bad as f64 / total as f64) / budget
+    }
+
+    /// Confidence in the short-window error rate, from its outcome count
+    pub fn confidence(&self, now_ms: f64) -> f64 {
+        let (good, bad) = self.outcomes(now_ms, self.config.short_window_ms);
+        confidence::from_sample_count((good + bad) as f64)
+    }
+
+    /// `(good, bad)` totals over `window_ms` ending at `now_ms`
+    fn outcomes(&self, now_ms: f64, window_ms: f64) -> (u64, u64) {
+        let start_ms = now_ms - window_ms;
+        let bucket_ms = self.bucket_ms();
+        self.buckets
+            .iter()
+            .filter(|b| b.start_ms + bucket_ms > start_ms && b.start_ms <= now_ms)
+            .fold((0u64, 0u64), |(g, e), b| (g + b.good, e + b.bad))
+    }
+
+    /// Short and long window burn rates
+    pub fn burn_rates(&self, now_ms: f64) -> BurnRates {
+        BurnRates {
+            short: self.burn_rate(now_ms, self.config.short_window_ms),
+            long: self.burn_rate(now_ms, self.config.long_window_ms),
+        }
+    }
+
+    /// Effective burn normalized to [0, 1] (1.0 at `alert_burn_rate`)
+    pub fn pressure(&self, now_ms: f64) -> f64 {
+        if self.config.alert_burn_rate <= 0.0 {
+            return 0.0;
+        }
+        (self.burn_rates(now_ms).effective() / self.config.alert_burn_rate).min(1.0)
+    }
+
+    /// Apply the `ErrorAxis` mapping to a pressure sample
+    pub fn apply_to_pressure(&self, now_ms: f64, pressure: PressureVector) -> PressureVector {
+        match self.config.mapping {
+            BurnRateMapping::ErrorAxis => PressureVector {
+                error: pressure.error.max(self.pressure(now_ms)),
+                ..pressure
+            },
+            BurnRateMapping::Surcharge { .. } => pressure,
+        }
+    }
+
+    /// Resistance surcharge under the `Surcharge` mapping (0 otherwise)
+    pub fn surcharge(&self, now_ms: f64) -> f64 {
+        match self.config.mapping {
+            BurnRateMapping::Surcharge { ohms_per_burn } => {
+                ohms_per_burn * (self.burn_rates(now_ms).effective() - 1.0).max(0.0)
+            }
+            BurnRateMapping::ErrorAxis => 0.0,
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_outcomes_no_burn() {
+        let tracker = BurnRateTracker::new(BurnRateConfig::default());
+        assert_eq!(tracker.burn_rates(0.0), BurnRates::default());
+        assert_eq!(tracker.pressure(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_burn_rate_math() {
+        let mut tracker = BurnRateTracker::new(BurnRateConfig::default());
+        // 1% errors against a 99.9% SLO = 10x burn
+        tracker.record(1_000.0, 990, 10);
+
+        let rates = tracker.burn_rates(1_000.0);
+        assert!((rates.short - 10.0).abs() < 1e-9);
+        assert!((rates.long - 10.0).abs() < 1e-9);
+        assert!((tracker.pressure(1_000.0) - 10.0 / 14.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_non_positive_bucket_is_clamped() {
+        for bucket_ms in [0.0, -10.0] {
+            let mut tracker = BurnRateTracker::new(BurnRateConfig {
+                bucket_ms,
+                ..BurnRateConfig::default()
+            });
+            tracker.record(1_000.0, 990, 10);
+            tracker.record(1_000.0, 990, 10);
+
+            let rates = tracker.burn_rates(1_000.0);
+            assert!((rates.short - 10.0).abs() < 1e-9);
+            assert_eq!(tracker.buckets.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_short_window_recovers_first() {
+        let mut tracker = BurnRateTracker::new(BurnRateConfig::default());
+        tracker.record(0.0, 900, 100);
+        tracker.record(600_000.0, 1000, 0);
+
+        let rates = tracker.burn_rates(600_000.0);
+        assert_eq!(rates.short, 0.0);
+        assert!(rates.long > 0.0);
+        assert_eq!(rates.effective(), 0.0);
+    }
+
+    #[test]
+    fn test_mappings() {
+        let mut axis = BurnRateTracker::new(BurnRateConfig::default());
+        axis.record(0.0, 0, 100);
+        let p = axis.apply_to_pressure(0.0, PressureVector::new(0.1, 0.2, 0.3));
+        assert_eq!(p.error, 1.0);
+        assert_eq!(axis.surcharge(0.0), 0.0);
+
+        let mut surcharge = BurnRateTracker::new(BurnRateConfig {
+            mapping: BurnRateMapping::Surcharge { ohms_per_burn: 2.0 },
+            ..BurnRateConfig::default()
+        });
+        surcharge.record(0.0, 980, 20); // 20x burn
+        assert!((surcharge.surcharge(0.0) - 38.0).abs() < 1e-9);
+        let p = surcharge.apply_to_pressure(0.0, PressureVector::new(0.1, 0.2, 0.3));
+        assert_eq!(p.error, 0.2);
+    }
+}
@@ -0,0 +1,345 @@
+/**
+ * Shareable, interior-mutable engine for JS callers.
+ *
+ * `TargetEngine`'s bindings take `&mut self` for ticks, so JS code that
+ * ticks from one callback and reads from another ends up cloning state
+ * around, and a read that lands during a tick aborts with wasm-bindgen's
+ * "recursive use of an object" panic. `EngineCell` keeps the engine in an
+ * `Rc<RefCell<_>>` behind `&self` methods: `share()` hands out more JS
+ * objects for the same engine, and overlapping access becomes a catchable
+ * JS `Error` (`AtrionError::Reentrant`) instead of a trap.
+ *
+ * Event listeners (`onEvent`, `onProbeRequested`) run after the tick has
+ * released the engine, so they may query or even tick it again.
+ */
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::engine::TargetEngine;
+use crate::error::AtrionError;
+use crate::events::EngineEvent;
+use crate::types::{OperationalMode, PhysicsConfig, PressureVector, SensitivityWeights};
+
+type Listener = Rc<dyn Fn(&EngineEvent) -> Result<(), JsValue>>;
+
+/// `TargetEngine` shared between JS objects, mutable through `&self`
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct EngineCell {
+    engine: Rc<RefCell<TargetEngine>>,
+    listeners: Rc<RefCell<Vec<Listener>>>,
+}
+
+impl EngineCell {
+    pub fn from_engine(engine: TargetEngine) -> Self {
+        Self {
+            engine: Rc::new(RefCell::new(engine)),
+            listeners: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Run `f` on the engine, or fail if it is being mutated
+    pub fn read<R>(
+        &self,
+        operation: &'static str,
+        f: impl FnOnce(&TargetEngine) -> R,
+    ) -> Result<R, AtrionError> {
+        let engine = self
+            .engine
+            .try_borrow()
+            .map_err(|_| AtrionError::Reentrant { operation })?;
+        Ok(f(&engine))
+    }
+
+    /// Run `f` on the engine mutably, or fail if it is in use
+    pub fn write<R>(
+        &self,
+        operation: &'static str,
+        f: impl FnOnce(&mut TargetEngine) -> R,
+    ) -> Result<R, AtrionError> {
+        let mut engine = self
+            .engine
+            .try_borrow_mut()
+            .map_err(|_| AtrionError::Reentrant { operation })?;
+        Ok(f(&mut engine))
+    }
+
+    /// Call `listener` with each event after `dispatch`
+    ///
+    /// While any listener is registered, events go to listeners instead
+    /// of the engine's drainable buffer.
+    pub fn listen(&self, listener: impl Fn(&EngineEvent) -> Result<(), JsValue> + 'static) {
+        self.listeners.borrow_mut().push(Rc::new(listener));
+    }
+
+    /// Hand buffered events to the listeners, with the engine released;
+    /// returns how many events were dispatched
+    ///
+    /// Every listener sees every event; the first listener error is
+    /// returned once all have run.
+    pub fn dispatch(&self) -> Result<usize, JsValue> {
+        // Snapshot, so listeners may register more listeners
+        let listeners = self.listeners.borrow().clone();
+        if listeners.is_empty() {
+            return Ok(0);
+        }
+        let events = self
+            .write("dispatch", TargetEngine::drain_events)
+            .map_err(JsError::from)?;
+        let mut first_error = None;
+        for event in &events {
+            for listener in &listeners {
+                if let Err(err) = listener(event) {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(events.len()),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl EngineCell {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: PhysicsConfig, weights: SensitivityWeights) -> Self {
+        Self::from_engine(TargetEngine::new(config, weights))
+    }
+
+    /// Take over an existing engine (the JS `TargetEngine` is consumed)
+    #[wasm_bindgen(js_name = fromEngine)]
+    pub fn from_engine_js(engine: TargetEngine) -> Self {
+        Self::from_engine(engine)
+    }
+
+    /// Build from a plain `{ config?, weights?, state? }` object
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json_js(value: JsValue) -> Result<EngineCell, JsError> {
+        let document = serde_wasm_bindgen::from_value(value)?;
+        Ok(Self::from_engine(TargetEngine::from_document(document)))
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json_js(&self) -> Result<JsValue, JsError> {
+        let document = self.read("toJSON", TargetEngine::to_document)?;
+        plain(&document)
+    }
+
+    /// Another JS object for the same engine (state and listeners shared)
+    pub fn share(&self) -> EngineCell {
+        self.clone()
+    }
+
+    /// True while a call holds the engine (e.g. inside a tick)
+    #[wasm_bindgen(getter)]
+    pub fn busy(&self) -> bool {
+        self.engine.try_borrow_mut().is_err()
+    }
+
+    /// Advance the engine and notify listeners, returning the new resistance
+    ///
+    /// Throws if called while the engine is in use, or rethrows the first
+    /// listener exception (the tick itself has then already happened).
+    pub fn tick(&self, now_ms: f64, pressure: &PressureVector) -> Result<f64, JsValue> {
+        let state = self
+            .write("tick", |engine| engine.tick(now_ms, *pressure))
+            .map_err(JsError::from)?;
+        self.dispatch()?;
+        Ok(state.resistance.0)
+    }
+
+    /// Raise `ProbeRequested` if the engine is stale, notifying listeners
+    #[wasm_bindgen(js_name = checkStaleness)]
+    pub fn check_staleness_js(&self, now_ms: f64) -> Result<bool, JsValue> {
+        let requested = self
+            .write("checkStaleness", |engine| engine.check_staleness(now_ms))
+            .map_err(JsError::from)?;
+        self.dispatch()?;
+        Ok(requested)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mode(&self) -> Result<OperationalMode, JsError> {
+        Ok(self.read("mode", |engine| engine.state().mode)?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn resistance(&self) -> Result<f64, JsError> {
+        Ok(self.read("resistance", |engine| engine.state().resistance.0)?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn scar(&self) -> Result<f64, JsError> {
+        Ok(self.read("scar", |engine| engine.state().scar.0)?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn momentum(&self) -> Result<f64, JsError> {
+        Ok(self.read("momentum", |engine| engine.state().momentum.0)?)
+    }
+
+    /// Voltage gate: true if the request may pass
+    #[wasm_bindgen(js_name = tryAdmit)]
+    pub fn try_admit_js(&self, voltage: f64) -> Result<bool, JsError> {
+        Ok(self.read("tryAdmit", |engine| engine.try_admit(voltage).is_admitted())?)
+    }
+
+    /// Resistance breakdown as a plain object
+    pub fn explain(&self) -> Result<JsValue, JsError> {
+        let breakdown = self.read("explain", TargetEngine::explain)?;
+        plain(&breakdown)
+    }
+
+    /// Buffered events as plain objects (empty while listeners are set)
+    #[wasm_bindgen(js_name = drainEvents)]
+    pub fn drain_events_js(&self) -> Result<JsValue, JsError> {
+        let events = self.write("drainEvents", TargetEngine::drain_events)?;
+        plain(&events)
+    }
+
+    #[wasm_bindgen(js_name = forceModeUntil)]
+    pub fn force_mode_until_js(&self, mode: OperationalMode, until_ms: f64) -> Result<(), JsError> {
+        Ok(self.write("forceModeUntil", |engine| {
+            engine.force_mode_until(mode, until_ms)
+        })?)
+    }
+
+    #[wasm_bindgen(js_name = clearOverrides)]
+    pub fn clear_overrides_js(&self) -> Result<(), JsError> {
+        Ok(self.write("clearOverrides", TargetEngine::clear_overrides)?)
+    }
+
+    pub fn reset(&self) -> Result<(), JsError> {
+        Ok(self.write("reset", TargetEngine::reset)?)
+    }
+}
+
+/// `crate::to_js` for methods that throw `Error`s
+fn plain<T: serde::Serialize + ?Sized>(value: &T) -> Result<JsValue, JsError> {
+    crate::to_js(value).map_err(|e| JsError::new(&format!("{e:?}")))
+}
+
+// ============================================================================
+// LISTENER GLUE (WASM)
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod js {
+    use wasm_bindgen::prelude::*;
+
+    use super::EngineCell;
+    use crate::events::EngineEvent;
+
+    #[wasm_bindgen]
+    extern "C" {
+        /// `(event) => void`, called with each event as a plain object
+        #[wasm_bindgen(typescript_type = "(event: any) => void")]
+        pub type JsEventListener;
+
+        #[wasm_bindgen(method, catch, js_name = call)]
+        fn call(
+            this: &JsEventListener,
+            this_arg: &JsValue,
+            event: JsValue,
+        ) -> Result<JsValue, JsValue>;
+
+        /// `(staleMs) => void`, called when the engine wants a probe
+        #[wasm_bindgen(typescript_type = "(staleMs: number) => void")]
+        pub type JsProbeListener;
+
+        #[wasm_bindgen(method, catch, js_name = call)]
+        fn call(
+            this: &JsProbeListener,
+            this_arg: &JsValue,
+            stale_ms: f64,
+        ) -> Result<JsValue, JsValue>;
+    }
+
+    #[wasm_bindgen]
+    impl EngineCell {
+        /// Call `listener(event)` after each tick; it may use this engine
+        #[wasm_bindgen(js_name = onEvent)]
+        pub fn on_event_js(&self, listener: JsEventListener) {
+            self.listen(move |event| {
+                listener.call(&JsValue::UNDEFINED, crate::to_js(event)?)?;
+                Ok(())
+            });
+        }
+
+        /// Call `listener(staleMs)` for each `ProbeRequested`; answer it by
+        /// sending a synthetic request and ticking with the result
+        #[wasm_bindgen(js_name = onProbeRequested)]
+        pub fn on_probe_requested_js(&self, listener: JsProbeListener) {
+            self.listen(move |event| {
+                if let EngineEvent::ProbeRequested { stale_ms, .. } = event {
+                    listener.call(&JsValue::UNDEFINED, *stale_ms)?;
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn cell() -> EngineCell {
+        EngineCell::new(PhysicsConfig::default(), SensitivityWeights::default())
+    }
+
+    #[test]
+    fn test_listeners_can_use_the_engine() {
+        let cell = cell();
+        let seen = Rc::new(Cell::new(0));
+        let (shared, counter) = (cell.share(), seen.clone());
+        cell.listen(move |event| {
+            assert!(matches!(event, EngineEvent::ModeChanged { .. }));
+            let mode = shared.read("mode", |engine| engine.state().mode).unwrap();
+            assert_eq!(mode, OperationalMode::Operational);
+            // Re-entrant ticks are fine too: the tick has released the engine
+            shared
+                .write("tick", |engine| {
+                    engine.tick(2_000.0, PressureVector::new(0.1, 0.0, 0.1))
+                })
+                .unwrap();
+            counter.set(counter.get() + 1);
+            Ok(())
+        });
+
+        let calm = PressureVector::new(0.1, 0.0, 0.1);
+        for i in 0..10 {
+            cell.write("tick", |engine| engine.tick(i as f64 * 100.0, calm))
+                .unwrap();
+            cell.dispatch().unwrap();
+        }
+        assert_eq!(seen.get(), 1);
+        assert_eq!(cell.read("ticks", |e| e.state().tick_count).unwrap(), 11);
+        assert!(!cell.busy());
+    }
+
+    #[test]
+    fn test_overlapping_access_is_an_error() {
+        let cell = cell();
+        let shared = cell.share();
+        let nested = cell
+            .read("explain", |_| shared.write("tick", |_| ()))
+            .unwrap();
+        assert_eq!(nested, Err(AtrionError::Reentrant { operation: "tick" }));
+        let nested = cell
+            .write("reset", |_| shared.read("mode", |_| ()))
+            .unwrap();
+        assert_eq!(nested, Err(AtrionError::Reentrant { operation: "mode" }));
+        // Shared reads nest
+        assert_eq!(cell.read("a", |_| shared.read("b", |_| 1)).unwrap(), Ok(1));
+    }
+}
@@ -0,0 +1,199 @@
+/**
+ * Online change-point detection on pressure (two-sided CUSUM).
+ *
+ * A deploy that moves a target's latency from 0.2 to 0.4 for good is a
+ * new regime, not a trend. Momentum reads the step as a sharp rise and
+ * keeps pushing resistance up until it decays. The detector runs one
+ * CUSUM per axis against a slow EWMA reference level:
+ *
+ *   S⁺ = max(0, S⁺ + x − μ − drift)
+ *   S⁻ = max(0, S⁻ + μ − x − drift)
+ *
+ * and flags a change point when either sum exceeds `threshold`. The
+ * reference then restarts from the new level (after `warmup` samples)
+ * and, with `reset_momentum`, the engine measures momentum from the new
+ * level instead of across the step. Scar is never touched: trauma taken
+ * during the shift is still trauma.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::events::EngineEvent;
+use crate::types::PressureVector;
+
+const AXES: [&str; 3] = ["latency", "error", "saturation"];
+
+/// Change-point detection configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ChangePointConfig {
+    /// Per-sample slack before deviation accumulates (pressure units)
+    pub drift: f64,
+    /// Accumulated deviation that flags a change point
+    pub threshold: f64,
+    /// EWMA weight of each sample in the reference level
+    pub alpha: f64,
+    /// Samples that seed the reference level before sums accumulate
+    pub warmup: u32,
+    /// Restart momentum from the new level at a change point
+    #[serde(alias = "reset_momentum")]
+    pub reset_momentum: bool,
+}
+
+impl Default for ChangePointConfig {
+    fn default() -> Self {
+        Self {
+            drift: 0.05,
+            threshold: 0.5,
+            alpha: 0.02,
+            warmup: 20,
+            reset_momentum: true,
+        }
+    }
+}
+
+/// Reference level and CUSUM sums for one axis
+#[derive(Debug, Copy, Clone, Default)]
+struct Cusum {
+    mean: f64,
+    positive: f64,
+    negative: f64,
+}
+
+/// Per-axis CUSUM over a target's pressure stream
+#[derive(Debug, Clone)]
+pub struct ChangePointDetector {
+    config: ChangePointConfig,
+    axes: [Cusum; 3],
+    samples: u32,
+    detected: u64,
+}
+
+impl ChangePointDetector {
+    pub fn new(config: ChangePointConfig) -> Self {
+        Self {
+            config,
+            axes: [Cusum::default(); 3],
+            samples: 0,
+            detected: 0,
+        }
+    }
+
+    pub fn config(&self) -> &ChangePointConfig {
+        &self.config
+    }
+
+    /// Change points flagged so far
+    pub fn detected(&self) -> u64 {
+        self.detected
+    }
+
+    /// Feed one pressure sample; returns `ChangePointDetected` at a change
+    pub fn observe(&mut self, now_ms: f64, pressure: &PressureVector) -> Option<EngineEvent> {
+        let values = [pressure.latency, pressure.error, pressure.saturation];
+        self.samples += 1;
+        if self.samples <= self.config.warmup.max(1) {
+            // Seed the reference with the running mean
+            let n = self.samples as f64;
+            for (axis, value) in self.axes.iter_mut().zip(values) {
+                axis.mean += (value - axis.mean) / n;
+            }
+            return None;
+        }
+
+        let drift = self.config.drift.max(0.0);
+        let mut strongest: Option<(usize, f64)> = None;
+        for (index, (axis, value)) in self.axes.iter_mut().zip(values).enumerate() {
+            axis.positive = (axis.positive + value - axis.mean - drift).max(0.0);
+            axis.negative = (axis.negative + axis.mean - value - drift).max(0.0);
+            let sum = axis.positive.max(axis.negative);
+            if sum > self.config.threshold && strongest.is_none_or(|(_, best)| sum > best) {
+                strongest = Some((index, sum));
+            }
+            axis.mean += self.config.alpha.clamp(0.0, 1.0) * (value - axis.mean);
+        }
+
+        let (index, _) = strongest?;
+        let from = self.axes[index].mean;
+        self.detected += 1;
+        // Restart from the new regime
+        self.axes = [Cusum::default(); 3];
+        self.samples = 1;
+        for (axis, value) in self.axes.iter_mut().zip(values) {
+            axis.mean = value;
+        }
+        Some(EngineEvent::ChangePointDetected {
+            at_ms: now_ms,
+            axis: AXES[index].to_string(),
+            from,
+            to: values[index],
+            momentum_reset: self.config.reset_momentum,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    #[test]
+    fn test_flags_level_shift_not_noise() {
+        let mut detector = ChangePointDetector::new(ChangePointConfig::default());
+        let mut now = 0.0;
+        let mut events = Vec::new();
+        for i in 0..200 {
+            now += 100.0;
+            // ±0.04 jitter around 0.2 stays inside the drift
+            let latency = 0.2 + if i % 2 == 0 { 0.04 } else { -0.04 };
+            events.extend(detector.observe(now, &PressureVector::new(latency, 0.0, 0.1)));
+        }
+        assert!(events.is_empty());
+        for _ in 0..10 {
+            now += 100.0;
+            events.extend(detector.observe(now, &PressureVector::new(0.5, 0.0, 0.1)));
+        }
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            EngineEvent::ChangePointDetected { axis, from, to, .. } => {
+                assert_eq!(axis, "latency");
+                assert!((from - 0.2).abs() < 0.05);
+                assert_eq!(*to, 0.5);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_reset_drops_momentum_but_keeps_scar() {
+        let run = |detect: bool| {
+            let mut engine =
+                TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+            if detect {
+                engine.enable_change_detection(ChangePointConfig::default());
+            }
+            let mut now = 0.0;
+            for _ in 0..100 {
+                now += 100.0;
+                engine.tick(now, PressureVector::new(0.2, 0.0, 0.1));
+            }
+            // Deploy: latency settles at a new, higher level
+            for _ in 0..5 {
+                now += 100.0;
+                engine.tick(now, PressureVector::new(0.8, 0.0, 0.1));
+            }
+            *engine.state()
+        };
+        let plain = run(false);
+        let detected = run(true);
+        assert!(plain.momentum.0 > 0.0);
+        assert!(detected.momentum.0 < plain.momentum.0);
+        assert_eq!(detected.scar, plain.scar);
+    }
+}
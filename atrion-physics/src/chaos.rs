@@ -0,0 +1,218 @@
+/**
+ * Chaos injection for resilience testing (feature "chaos").
+ *
+ * Wraps an engine's input stream with the kinds of mess real telemetry
+ * pipelines produce: noisy pressure, dropped samples, skewed or jittered
+ * clocks and duplicate deliveries. Randomness comes from a seeded
+ * SplitMix64 generator so failing runs can be replayed exactly.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{TargetEngine, TargetState};
+use crate::types::PressureVector;
+
+/// What to inject, and how often
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChaosConfig {
+    pub seed: u64,
+    /// Uniform noise amplitude added to each pressure component
+    pub noise: f64,
+    /// Probability a sample never arrives
+    #[serde(alias = "drop_rate")]
+    pub drop_rate: f64,
+    /// Probability a sample is delivered twice
+    #[serde(alias = "duplicate_rate")]
+    pub duplicate_rate: f64,
+    /// Constant offset added to every timestamp
+    #[serde(alias = "clock_skew_ms")]
+    pub clock_skew_ms: f64,
+    /// Uniform ±jitter per timestamp (large values reorder samples)
+    #[serde(alias = "clock_jitter_ms")]
+    pub clock_jitter_ms: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0x5EED,
+            noise: 0.05,
+            drop_rate: 0.05,
+            duplicate_rate: 0.02,
+            clock_skew_ms: 0.0,
+            clock_jitter_ms: 10.0,
+        }
+    }
+}
+
+/// Counters of what was injected so far
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ChaosStats {
+    pub offered: u64,
+    pub delivered: u64,
+    pub dropped: u64,
+    pub duplicated: u64,
+}
+
+/// Seeded sample mangler
+#[derive(Debug, Clone)]
+pub struct Chaos {
+    config: ChaosConfig,
+    rng: u64,
+    stats: ChaosStats,
+}
+
+impl Chaos {
+    pub fn new(config: ChaosConfig) -> Self {
+        Self {
+            config,
+            rng: config.seed,
+            stats: ChaosStats::default(),
+        }
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    pub fn stats(&self) -> ChaosStats {
+        self.stats
+    }
+
+    /// Samples actually delivered for one offered sample (0, 1 or 2)
+    pub fn inject(&mut self, now_ms: f64, pressure: PressureVector) -> Vec<(f64, PressureVector)> {
+        self.stats.offered += 1;
+        if self.chance(self.config.drop_rate) {
+            self.stats.dropped += 1;
+            return Vec::new();
+        }
+        let copies = if self.chance(self.config.duplicate_rate) {
+            self.stats.duplicated += 1;
+            2
+        } else {
+            1
+        };
+        let timestamp =
+            now_ms + self.config.clock_skew_ms + self.spread(self.config.clock_jitter_ms);
+        let noisy = PressureVector::new(
+            pressure.latency + self.spread(self.config.noise),
+            pressure.error + self.spread(self.config.noise),
+            pressure.saturation + self.spread(self.config.noise),
+        );
+        self.stats.delivered += copies as u64;
+        vec![(timestamp, noisy); copies]
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        probability > 0.0 && self.unit() < probability
+    }
+
+    /// Uniform in `[-amplitude, amplitude]`
+    fn spread(&mut self, amplitude: f64) -> f64 {
+        if amplitude == 0.0 {
+            return 0.0;
+        }
+        (self.unit() * 2.0 - 1.0) * amplitude
+    }
+
+    /// Uniform in `[0, 1)` (SplitMix64)
+    fn unit(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Engine whose input goes through a `Chaos` injector
+pub struct ChaosEngine {
+    engine: TargetEngine,
+    chaos: Chaos,
+}
+
+impl ChaosEngine {
+    pub fn new(engine: TargetEngine, config: ChaosConfig) -> Self {
+        Self {
+            engine,
+            chaos: Chaos::new(config),
+        }
+    }
+
+    /// Offer one sample; returns the state after whatever was delivered
+    pub fn tick(&mut self, now_ms: f64, pressure: PressureVector) -> TargetState {
+        for (timestamp, sample) in self.chaos.inject(now_ms, pressure) {
+            self.engine.tick(timestamp, sample);
+        }
+        *self.engine.state()
+    }
+
+    pub fn engine(&self) -> &TargetEngine {
+        &self.engine
+    }
+
+    pub fn stats(&self) -> ChaosStats {
+        self.chaos.stats()
+    }
+
+    pub fn into_inner(self) -> TargetEngine {
+        self.engine
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{OperationalMode, PhysicsConfig, SensitivityWeights};
+
+    #[test]
+    fn test_seeded_runs_are_reproducible() {
+        let config = ChaosConfig {
+            drop_rate: 0.3,
+            duplicate_rate: 0.3,
+            ..ChaosConfig::default()
+        };
+        let run = || {
+            let mut chaos = Chaos::new(config);
+            (0..100)
+                .flat_map(|i| chaos.inject(i as f64 * 100.0, PressureVector::new(0.2, 0.1, 0.1)))
+                .map(|(t, p)| (t.to_bits(), p.latency.to_bits()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(), run());
+
+        let mut chaos = Chaos::new(config);
+        for i in 0..1000 {
+            chaos.inject(i as f64, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        let stats = chaos.stats();
+        assert_eq!(
+            stats.delivered,
+            stats.offered - stats.dropped + stats.duplicated
+        );
+        assert!((250..350).contains(&stats.dropped));
+    }
+
+    #[test]
+    fn test_engine_survives_messy_input() {
+        let engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut chaotic = ChaosEngine::new(
+            engine,
+            ChaosConfig {
+                clock_jitter_ms: 150.0,
+                clock_skew_ms: -5000.0,
+                ..ChaosConfig::default()
+            },
+        );
+        for i in 0..500 {
+            let state = chaotic.tick(i as f64 * 100.0, PressureVector::new(0.1, 0.0, 0.1));
+            assert!(state.resistance.0.is_finite());
+        }
+        assert_eq!(chaotic.engine().state().mode, OperationalMode::Operational);
+    }
+}
@@ -0,0 +1,305 @@
+/**
+ * Step / impulse response characterization.
+ *
+ * Drives a fresh engine with synthetic inputs and measures the
+ * resistance response in control-theory terms, so configs can be
+ * compared by rise time, overshoot and settling instead of by feel:
+ *
+ * - Step: pressure jumps from 0 to `step_pressure` (all axes) and holds,
+ *   then drops back to 0 once settled.
+ * - Impulse: a single tick at `impulse_pressure`, then silence.
+ *
+ * Time origins are the input edge; all durations are in ms.
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::engine::TargetEngine;
+use crate::types::{OperationalMode, PhysicsConfig, PressureVector, SensitivityWeights};
+
+/// Synthetic input parameters
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterizeOptions {
+    #[serde(alias = "tick_interval_ms")]
+    pub tick_interval_ms: f64,
+    /// Per-axis step level (default stays below the trauma threshold)
+    #[serde(alias = "step_pressure")]
+    pub step_pressure: f64,
+    /// Per-axis impulse level
+    #[serde(alias = "impulse_pressure")]
+    pub impulse_pressure: f64,
+    /// Max simulated time per phase
+    #[serde(alias = "horizon_ms")]
+    pub horizon_ms: f64,
+    /// Settling band as a fraction of the response amplitude
+    #[serde(alias = "settle_band")]
+    pub settle_band: f64,
+}
+
+impl Default for CharacterizeOptions {
+    fn default() -> Self {
+        Self {
+            tick_interval_ms: 100.0,
+            step_pressure: 0.3,
+            impulse_pressure: 1.0,
+            horizon_ms: 300_000.0,
+            settle_band: 0.02,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepResponse {
+    pub initial: f64,
+    #[serde(alias = "final_value")]
+    pub final_value: f64,
+    pub peak: f64,
+    /// 10% → 90% of the final change
+    #[serde(alias = "rise_time_ms")]
+    pub rise_time_ms: Option<f64>,
+    /// Last exit from the settling band
+    #[serde(alias = "settling_time_ms")]
+    pub settling_time_ms: Option<f64>,
+    /// (peak − final) / (final − initial), 0 when monotonic
+    pub overshoot: f64,
+    /// Return into the band around `initial` after the step is removed
+    #[serde(alias = "recovery_time_ms")]
+    pub recovery_time_ms: Option<f64>,
+    pub tripped: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpulseResponse {
+    pub baseline: f64,
+    pub peak: f64,
+    #[serde(alias = "peak_at_ms")]
+    pub peak_at_ms: f64,
+    /// Return into the band around `baseline`
+    #[serde(alias = "recovery_time_ms")]
+    pub recovery_time_ms: Option<f64>,
+    pub tripped: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseProfile {
+    #[serde(alias = "tick_interval_ms")]
+    pub tick_interval_ms: f64,
+    pub step: StepResponse,
+    pub impulse: ImpulseResponse,
+}
+
+/// Characterize `config` with default weights and options
+pub fn characterize(config: &PhysicsConfig) -> ResponseProfile {
+    characterize_with(
+        config,
+        &SensitivityWeights::default(),
+        &CharacterizeOptions::default(),
+    )
+}
+
+pub fn characterize_with(
+    config: &PhysicsConfig,
+    weights: &SensitivityWeights,
+    options: &CharacterizeOptions,
+) -> ResponseProfile {
+    let dt = options.tick_interval_ms.max(1.0);
+    let ticks = (options.horizon_ms / dt).ceil().max(1.0) as usize;
+    ResponseProfile {
+        tick_interval_ms: dt,
+        step: step_response(config, weights, options, dt, ticks),
+        impulse: impulse_response(config, weights, options, dt, ticks),
+    }
+}
+
+/// Engine past bootstrap at zero pressure, plus the next tick time
+fn warmed_up(config: &PhysicsConfig, weights: &SensitivityWeights, dt: f64) -> (TargetEngine, f64) {
+    let mut config = config.clone();
+    config.history_capacity = 0;
+    let mut engine = TargetEngine::new(config.clone(), weights.clone());
+    let zero = PressureVector::new(0.0, 0.0, 0.0);
+    let mut now = 0.0;
+    for _ in 0..config.bootstrap_ticks.max(1) {
+        engine.tick(now, zero);
+        now += dt;
+    }
+    (engine, now)
+}
+
+/// Resistance samples `(ms since edge, R)` while holding `pressure`
+fn run(
+    engine: &mut TargetEngine,
+    start: f64,
+    dt: f64,
+    ticks: usize,
+    pressure: impl Fn(usize) -> f64,
+    tripped: &mut bool,
+) -> Vec<(f64, f64)> {
+    (0..ticks)
+        .map(|i| {
+            let p = pressure(i);
+            let state = engine.tick(start + i as f64 * dt, PressureVector::new(p, p, p));
+            *tripped |= state.mode == OperationalMode::CircuitBreaker;
+            (i as f64 * dt, state.resistance.0)
+        })
+        .collect()
+}
+
+/// First time the series enters and stays inside `target ± band`
+fn settle_time(series: &[(f64, f64)], target: f64, band: f64) -> Option<f64> {
+    let last_outside = series.iter().rposition(|(_, r)| (r - target).abs() > band);
+    match last_outside {
+        None => series.first().map(|(t, _)| *t),
+        Some(i) => series.get(i + 1).map(|(t, _)| *t),
+    }
+}
+
+fn step_response(
+    config: &PhysicsConfig,
+    weights: &SensitivityWeights,
+    options: &CharacterizeOptions,
+    dt: f64,
+    ticks: usize,
+) -> StepResponse {
+    let (mut engine, start) = warmed_up(config, weights, dt);
+    let initial = engine.state().resistance.0;
+    let mut tripped = false;
+    let up = run(
+        &mut engine,
+        start,
+        dt,
+        ticks,
+        |_| options.step_pressure,
+        &mut tripped,
+    );
+
+    let final_value = up.last().map_or(initial, |(_, r)| *r);
+    let amplitude = final_value - initial;
+    let band = (options.settle_band * amplitude.abs()).max(1e-9);
+    let peak = up.iter().map(|(_, r)| *r).fold(initial, f64::max);
+
+    let crossing = |fraction: f64| {
+        up.iter()
+            .find(|(_, r)| (r - initial) >= fraction * amplitude)
+            .map(|(t, _)| *t)
+    };
+    let rise_time_ms = if amplitude > 0.0 {
+        crossing(0.9).zip(crossing(0.1)).map(|(hi, lo)| hi - lo)
+    } else {
+        None
+    };
+    let settling_time_ms = settle_time(&up, final_value, band);
+    let overshoot = if amplitude > 0.0 {
+        ((peak - final_value) / amplitude).max(0.0)
+    } else {
+        0.0
+    };
+
+    let down_start = start + ticks as f64 * dt;
+    let down = run(&mut engine, down_start, dt, ticks, |_| 0.0, &mut tripped);
+    let recovery_time_ms = settle_time(&down, initial, band);
+
+    StepResponse {
+        initial,
+        final_value,
+        peak,
+        rise_time_ms,
+        settling_time_ms,
+        overshoot,
+        recovery_time_ms,
+        tripped,
+    }
+}
+
+fn impulse_response(
+    config: &PhysicsConfig,
+    weights: &SensitivityWeights,
+    options: &CharacterizeOptions,
+    dt: f64,
+    ticks: usize,
+) -> ImpulseResponse {
+    let (mut engine, start) = warmed_up(config, weights, dt);
+    let baseline = engine.state().resistance.0;
+    let mut tripped = false;
+    let impulse = options.impulse_pressure;
+    let series = run(
+        &mut engine,
+        start,
+        dt,
+        ticks,
+        |i| if i == 0 { impulse } else { 0.0 },
+        &mut tripped,
+    );
+
+    let (peak_at_ms, peak) =
+        series.iter().copied().fold(
+            (0.0, baseline),
+            |best, s| if s.1 > best.1 { s } else { best },
+        );
+    let band = (options.settle_band * (peak - baseline)).max(1e-9);
+
+    ImpulseResponse {
+        baseline,
+        peak,
+        peak_at_ms,
+        recovery_time_ms: settle_time(&series, baseline, band),
+        tripped,
+    }
+}
+
+/// Response profile as a plain object (default weights and options)
+#[wasm_bindgen(js_name = characterize)]
+pub fn characterize_js(config: &PhysicsConfig) -> Result<JsValue, JsValue> {
+    crate::to_js(&characterize(config))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_step_response() {
+        let profile = characterize(&PhysicsConfig::default());
+        let step = profile.step;
+
+        // Steady state: base + 0.3 · ΣW
+        let weights = SensitivityWeights::default();
+        let expected = 10.0 + 0.3 * (weights.w_latency + weights.w_error + weights.w_saturation);
+        assert!((step.final_value - expected).abs() < 1e-6);
+        assert!(step.rise_time_ms.is_some());
+        assert!(step.settling_time_ms.is_some());
+        assert!(step.recovery_time_ms.is_some());
+        assert!(!step.tripped);
+    }
+
+    #[test]
+    fn test_impulse_recovery_follows_scar_decay() {
+        let profile = characterize(&PhysicsConfig::default());
+        let impulse = profile.impulse;
+
+        assert_eq!(impulse.peak_at_ms, 0.0);
+        // Peak includes one trauma increment (σ = 5)
+        assert!(impulse.peak - impulse.baseline > 5.0);
+        // Scar 5 → within 2% of the ~15Ω excursion takes tens of seconds
+        let recovery = impulse.recovery_time_ms.unwrap();
+        assert!(recovery > 10_000.0 && recovery < 60_000.0, "{recovery}");
+    }
+
+    #[test]
+    fn test_heavier_damping_overshoots_more() {
+        let light = characterize(&PhysicsConfig::default());
+        let heavy = characterize(&PhysicsConfig {
+            damping_factor: 2000.0,
+            ..PhysicsConfig::default()
+        });
+        assert!(heavy.step.overshoot > light.step.overshoot);
+        assert!(heavy.step.peak > light.step.peak);
+    }
+}
@@ -0,0 +1,300 @@
+/**
+ * Chunked snapshot encoding (export / import huge pools piecewise).
+ *
+ * A whole-pool `Snapshot` of 100k targets is one large allocation, which
+ * WASM's linear memory handles badly. Chunks carry a bounded number of
+ * targets each in a compact little-endian binary layout:
+ *
+ * - magic `ATRC`, u32 snapshot version, f64 taken_at_ms, u32 chunk index
+ * - u32 target count, then per target: string id, `TargetState`
+ * - u32 quarantine count, then per entry: string id, reason, since, until
+ *
+ * Strings are a u32 byte length plus UTF-8; options a u8 flag plus value.
+ * Quarantine entries travel in chunk 0 only.
+ */
+use wasm_bindgen::prelude::*;
+
+use crate::engine::TargetState;
+use crate::error::AtrionError;
+use crate::overrides::{ModeOverride, Overrides};
+use crate::quarantine::Quarantine;
+use crate::snapshot::{TargetSnapshot, SNAPSHOT_VERSION};
+use crate::types::*;
+
+const MAGIC: &[u8; 4] = b"ATRC";
+
+/// Decoded contents of one chunk
+#[derive(Debug, Clone)]
+pub struct SnapshotChunk {
+    pub index: u32,
+    pub taken_at_ms: f64,
+    pub targets: Vec<TargetSnapshot>,
+    pub quarantine: Vec<(String, Quarantine)>,
+}
+
+/// Position of a chunked export over a pool (see `EnginePool::next_chunk`)
+///
+/// Holds the target ids sorted at creation; targets removed since are
+/// skipped and targets added since are not exported.
+#[wasm_bindgen]
+pub struct ChunkCursor {
+    pub(crate) ids: Vec<String>,
+    pub(crate) position: usize,
+    pub(crate) chunk_size: usize,
+    pub(crate) index: u32,
+    pub(crate) taken_at_ms: f64,
+}
+
+#[wasm_bindgen]
+impl ChunkCursor {
+    /// Chunks still to come
+    #[wasm_bindgen(getter)]
+    pub fn remaining(&self) -> usize {
+        let left = self.ids.len() - self.position;
+        let first = usize::from(self.index == 0 && left == 0);
+        left.div_ceil(self.chunk_size) + first
+    }
+}
+
+// ============================================================================
+// ENCODING
+// ============================================================================
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, v: u8) {
+        self.0.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn f64(&mut self, v: f64) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn opt_f64(&mut self, v: Option<f64>) {
+        match v {
+            Some(v) => {
+                self.u8(1);
+                self.f64(v);
+            }
+            None => self.u8(0),
+        }
+    }
+
+    fn str(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.0.extend_from_slice(s.as_bytes());
+    }
+
+    fn pressure(&mut self, p: &PressureVector) {
+        self.f64(p.latency);
+        self.f64(p.error);
+        self.f64(p.saturation);
+    }
+
+    fn state(&mut self, s: &TargetState) {
+        self.u8(mode_tag(s.mode));
+        self.pressure(&s.pressure);
+        self.pressure(&s.previous_pressure);
+        self.f64(s.momentum.0);
+        self.f64(s.scar.0);
+        self.f64(s.slow_scar.0);
+        self.f64(s.resistance.0);
+        self.u32(s.tick_count);
+        self.f64(s.last_updated_ms);
+        match s.overrides.mode {
+            Some(pin) => {
+                self.u8(1);
+                self.u8(mode_tag(pin.mode));
+                self.f64(pin.until_ms);
+            }
+            None => self.u8(0),
+        }
+        self.opt_f64(s.overrides.trauma_paused_until_ms);
+    }
+}
+
+fn mode_tag(mode: OperationalMode) -> u8 {
+    match mode {
+        OperationalMode::Bootstrap => 0,
+        OperationalMode::Operational => 1,
+        OperationalMode::CircuitBreaker => 2,
+    }
+}
+
+/// Encode one chunk
+pub fn encode_chunk<'a>(
+    index: u32,
+    taken_at_ms: f64,
+    targets: &[(&str, &TargetState)],
+    quarantine: impl IntoIterator<Item = (&'a str, &'a Quarantine)>,
+) -> Vec<u8> {
+    let mut w = Writer(Vec::with_capacity(32 + targets.len() * 160));
+    w.0.extend_from_slice(MAGIC);
+    w.u32(SNAPSHOT_VERSION);
+    w.f64(taken_at_ms);
+    w.u32(index);
+
+    w.u32(targets.len() as u32);
+    for (id, state) in targets {
+        w.str(id);
+        w.state(state);
+    }
+
+    let quarantine: Vec<_> = quarantine.into_iter().collect();
+    w.u32(quarantine.len() as u32);
+    for (id, entry) in quarantine {
+        w.str(id);
+        w.str(&entry.reason);
+        w.f64(entry.since_ms);
+        w.opt_f64(entry.until_ms);
+    }
+    w.0
+}
+
+// ============================================================================
+// DECODING
+// ============================================================================
+
+struct Reader<'a>(&'a [u8]);
+
+fn truncated() -> AtrionError {
+    AtrionError::Parse("snapshot chunk truncated".into())
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], AtrionError> {
+        if self.0.len() < n {
+            return Err(truncated());
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, AtrionError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, AtrionError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().expect("4 bytes")))
+    }
+
+    fn f64(&mut self) -> Result<f64, AtrionError> {
+        let bytes = self.take(8)?;
+        Ok(f64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn flag(&mut self) -> Result<bool, AtrionError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(AtrionError::Parse(format!("bad option flag {other}"))),
+        }
+    }
+
+    fn opt_f64(&mut self) -> Result<Option<f64>, AtrionError> {
+        Ok(if self.flag()? {
+            Some(self.f64()?)
+        } else {
+            None
+        })
+    }
+
+    fn string(&mut self) -> Result<String, AtrionError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| AtrionError::Parse(e.to_string()))
+    }
+
+    fn mode(&mut self) -> Result<OperationalMode, AtrionError> {
+        match self.u8()? {
+            0 => Ok(OperationalMode::Bootstrap),
+            1 => Ok(OperationalMode::Operational),
+            2 => Ok(OperationalMode::CircuitBreaker),
+            other => Err(AtrionError::Parse(format!("bad mode tag {other}"))),
+        }
+    }
+
+    fn pressure(&mut self) -> Result<PressureVector, AtrionError> {
+        Ok(PressureVector::new(self.f64()?, self.f64()?, self.f64()?))
+    }
+
+    fn state(&mut self) -> Result<TargetState, AtrionError> {
+        Ok(TargetState {
+            mode: self.mode()?,
+            pressure: self.pressure()?,
+            previous_pressure: self.pressure()?,
+            momentum: Momentum(self.f64()?),
+            scar: Scar(self.f64()?),
+            slow_scar: Scar(self.f64()?),
+            resistance: Ohms(self.f64()?),
+            tick_count: self.u32()?,
+            last_updated_ms: self.f64()?,
+            overrides: Overrides {
+                mode: if self.flag()? {
+                    Some(ModeOverride {
+                        mode: self.mode()?,
+                        until_ms: self.f64()?,
+                    })
+                } else {
+                    None
+                },
+                trauma_paused_until_ms: self.opt_f64()?,
+            },
+        })
+    }
+}
+
+/// Decode one chunk, rejecting other format versions and trailing bytes
+pub fn decode_chunk(bytes: &[u8]) -> Result<SnapshotChunk, AtrionError> {
+    let mut r = Reader(bytes);
+    if r.take(4)? != MAGIC {
+        return Err(AtrionError::Parse("not a snapshot chunk".into()));
+    }
+    let version = r.u32()?;
+    if version != SNAPSHOT_VERSION {
+        return Err(AtrionError::SnapshotVersion {
+            found: version,
+            expected: SNAPSHOT_VERSION,
+        });
+    }
+    let taken_at_ms = r.f64()?;
+    let index = r.u32()?;
+
+    let count = r.u32()? as usize;
+    let mut targets = Vec::with_capacity(count.min(r.0.len()));
+    for _ in 0..count {
+        targets.push(TargetSnapshot {
+            id: r.string()?,
+            state: r.state()?,
+        });
+    }
+
+    let count = r.u32()? as usize;
+    let mut quarantine = Vec::with_capacity(count.min(r.0.len()));
+    for _ in 0..count {
+        let id = r.string()?;
+        let entry = Quarantine {
+            reason: r.string()?,
+            since_ms: r.f64()?,
+            until_ms: r.opt_f64()?,
+        };
+        quarantine.push((id, entry));
+    }
+
+    if !r.0.is_empty() {
+        return Err(AtrionError::Parse("trailing bytes after chunk".into()));
+    }
+    Ok(SnapshotChunk {
+        index,
+        taken_at_ms,
+        targets,
+        quarantine,
+    })
+}
@@ -0,0 +1,385 @@
+/**
+ * Leader-computed cluster pressure (authoritative breaker decisions).
+ *
+ * Independent breakers on every instance flap: half the fleet trips and
+ * half doesn't. In leader mode, followers report their local pressure
+ * per target, one leader ticks an engine on the fleet-wide mean and
+ * publishes breaker decisions, and followers mirror those decisions.
+ * Followers fall back to their own breaker when the leader goes quiet.
+ *
+ * Transport is pluggable (`LeaderTransport` / `FollowerTransport`);
+ * `MemoryBus` covers tests and single-process setups.
+ */
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::pool::EnginePool;
+use crate::types::{OperationalMode, PressureVector};
+
+/// Follower → leader: one node's pressure for one target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PressureReport {
+    pub node: String,
+    pub target: String,
+    pub pressure: PressureVector,
+    #[serde(alias = "timestamp_ms")]
+    pub timestamp_ms: f64,
+}
+
+/// Leader → followers: authoritative mode for one target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakerDecision {
+    pub target: String,
+    pub mode: OperationalMode,
+    pub resistance: f64,
+    /// Monotonic per leader; followers ignore older decisions
+    pub seq: u64,
+    #[serde(alias = "issued_ms")]
+    pub issued_ms: f64,
+}
+
+/// Leader side of the transport
+pub trait LeaderTransport {
+    type Error;
+
+    /// Reports received since the last call
+    fn recv_reports(&self) -> Result<Vec<PressureReport>, Self::Error>;
+    fn publish(&self, decisions: &[BreakerDecision]) -> Result<(), Self::Error>;
+}
+
+/// Follower side of the transport
+pub trait FollowerTransport {
+    type Error;
+
+    fn send_report(&self, report: &PressureReport) -> Result<(), Self::Error>;
+    /// Decisions published since the last call
+    fn recv_decisions(&self) -> Result<Vec<BreakerDecision>, Self::Error>;
+}
+
+/// Cluster timing configuration
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterConfig {
+    /// Reports older than this are left out of the aggregate
+    #[serde(alias = "max_report_age_ms")]
+    pub max_report_age_ms: f64,
+    /// Followers resume local breaker control after this much leader silence
+    #[serde(alias = "leader_timeout_ms")]
+    pub leader_timeout_ms: f64,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            max_report_age_ms: 5000.0,
+            leader_timeout_ms: 10_000.0,
+        }
+    }
+}
+
+// ============================================================================
+// LEADER
+// ============================================================================
+
+/// Aggregates follower reports and decides for the fleet
+pub struct ClusterLeader {
+    config: ClusterConfig,
+    pool: EnginePool,
+    /// target → node → latest report
+    reports: HashMap<String, HashMap<String, PressureReport>>,
+    seq: u64,
+}
+
+impl ClusterLeader {
+    /// The leader's pool holds the cluster-level engines
+    pub fn new(pool: EnginePool, config: ClusterConfig) -> Self {
+        Self {
+            config,
+            pool,
+            reports: HashMap::new(),
+            seq: 0,
+        }
+    }
+
+    pub fn pool(&self) -> &EnginePool {
+        &self.pool
+    }
+
+    /// Record one report (latest per node wins)
+    pub fn ingest(&mut self, report: PressureReport) {
+        let nodes = self.reports.entry(report.target.clone()).or_default();
+        match nodes.get(&report.node) {
+            Some(existing) if existing.timestamp_ms > report.timestamp_ms => {}
+            _ => {
+                nodes.insert(report.node.clone(), report);
+            }
+        }
+    }
+
+    /// Tick every reported target on the mean fresh pressure and decide
+    pub fn decide(&mut self, now_ms: f64) -> Vec<BreakerDecision> {
+        let mut targets: Vec<&String> = self.reports.keys().collect();
+        targets.sort();
+
+        let mut decisions = Vec::new();
+        for target in targets {
+            let fresh: Vec<&PressureReport> = self.reports[target]
+                .values()
+                .filter(|r| now_ms - r.timestamp_ms <= self.config.max_report_age_ms)
+                .collect();
+            if fresh.is_empty() {
+                continue;
+            }
+            let n = fresh.len() as f64;
+            let mean = PressureVector::new(
+                fresh.iter().map(|r| r.pressure.latency).sum::<f64>() / n,
+                fresh.iter().map(|r| r.pressure.error).sum::<f64>() / n,
+                fresh.iter().map(|r| r.pressure.saturation).sum::<f64>() / n,
+            );
+            let state = self.pool.tick(target, now_ms, mean);
+            self.seq += 1;
+            decisions.push(BreakerDecision {
+                target: target.clone(),
+                mode: state.mode,
+                resistance: state.resistance.0,
+                seq: self.seq,
+                issued_ms: now_ms,
+            });
+        }
+        decisions
+    }
+
+    /// Drain reports, decide and publish one round
+    pub fn round<T: LeaderTransport>(
+        &mut self,
+        transport: &T,
+        now_ms: f64,
+    ) -> Result<Vec<BreakerDecision>, T::Error> {
+        for report in transport.recv_reports()? {
+            self.ingest(report);
+        }
+        let decisions = self.decide(now_ms);
+        transport.publish(&decisions)?;
+        Ok(decisions)
+    }
+}
+
+// ============================================================================
+// FOLLOWER
+// ============================================================================
+
+/// Mirrors leader decisions onto a local pool
+pub struct ClusterFollower {
+    node: String,
+    config: ClusterConfig,
+    latest: HashMap<String, BreakerDecision>,
+}
+
+impl ClusterFollower {
+    pub fn new(node: impl Into<String>, config: ClusterConfig) -> Self {
+        Self {
+            node: node.into(),
+            config,
+            latest: HashMap::new(),
+        }
+    }
+
+    /// Latest decision for a target, if the leader is not stale
+    pub fn decision(&self, target: &str, now_ms: f64) -> Option<&BreakerDecision> {
+        self.latest
+            .get(target)
+            .filter(|d| now_ms - d.issued_ms <= self.config.leader_timeout_ms)
+    }
+
+    /// Report local pressure for a target
+    pub fn report<T: FollowerTransport>(
+        &self,
+        transport: &T,
+        target: &str,
+        pressure: PressureVector,
+        now_ms: f64,
+    ) -> Result<(), T::Error> {
+        transport.send_report(&PressureReport {
+            node: self.node.clone(),
+            target: target.to_string(),
+            pressure,
+            timestamp_ms: now_ms,
+        })
+    }
+
+    /// Absorb new decisions and force them onto the local pool
+    ///
+    /// Call after local ticks so the leader's mode wins. Targets whose
+    /// leader decision timed out keep their local mode.
+    pub fn apply<T: FollowerTransport>(
+        &mut self,
+        transport: &T,
+        pool: &mut EnginePool,
+        now_ms: f64,
+    ) -> Result<usize, T::Error> {
+        for decision in transport.recv_decisions()? {
+            match self.latest.get(&decision.target) {
+                Some(existing) if existing.seq >= decision.seq => {}
+                _ => {
+                    self.latest.insert(decision.target.clone(), decision);
+                }
+            }
+        }
+
+        let mut applied = 0;
+        for (target, decision) in &self.latest {
+            if now_ms - decision.issued_ms > self.config.leader_timeout_ms {
+                continue;
+            }
+            if let Some(engine) = pool.get_mut(target) {
+                engine.force_mode(decision.mode);
+                applied += 1;
+            }
+        }
+        Ok(applied)
+    }
+}
+
+// ============================================================================
+// IN-MEMORY TRANSPORT
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct BusState {
+    reports: Vec<PressureReport>,
+    decisions: Vec<BreakerDecision>,
+}
+
+/// In-process bus: one leader handle, any number of follower handles
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBus {
+    state: Arc<Mutex<BusState>>,
+}
+
+impl MemoryBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New follower handle that sees decisions published from now on
+    pub fn follower(&self) -> MemoryFollower {
+        let cursor = self.lock().decisions.len();
+        MemoryFollower {
+            bus: self.clone(),
+            cursor: Mutex::new(cursor),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BusState> {
+        self.state.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+impl LeaderTransport for MemoryBus {
+    type Error = std::convert::Infallible;
+
+    fn recv_reports(&self) -> Result<Vec<PressureReport>, Self::Error> {
+        Ok(std::mem::take(&mut self.lock().reports))
+    }
+
+    fn publish(&self, decisions: &[BreakerDecision]) -> Result<(), Self::Error> {
+        self.lock().decisions.extend_from_slice(decisions);
+        Ok(())
+    }
+}
+
+/// Follower handle on a `MemoryBus`
+#[derive(Debug)]
+pub struct MemoryFollower {
+    bus: MemoryBus,
+    cursor: Mutex<usize>,
+}
+
+impl FollowerTransport for MemoryFollower {
+    type Error = std::convert::Infallible;
+
+    fn send_report(&self, report: &PressureReport) -> Result<(), Self::Error> {
+        self.bus.lock().reports.push(report.clone());
+        Ok(())
+    }
+
+    fn recv_decisions(&self) -> Result<Vec<BreakerDecision>, Self::Error> {
+        let state = self.bus.lock();
+        let mut cursor = self.cursor.lock().unwrap_or_else(|p| p.into_inner());
+        let new = state.decisions[*cursor..].to_vec();
+        *cursor = state.decisions.len();
+        Ok(new)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    fn pool() -> EnginePool {
+        EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default())
+    }
+
+    #[test]
+    fn test_leader_aggregates_latest_fresh_reports() {
+        let mut leader = ClusterLeader::new(pool(), ClusterConfig::default());
+        let report = |node: &str, error: f64, at: f64| PressureReport {
+            node: node.into(),
+            target: "api".into(),
+            pressure: PressureVector::new(0.0, error, 0.0),
+            timestamp_ms: at,
+        };
+        leader.ingest(report("a", 1.0, 0.0));
+        leader.ingest(report("a", 0.2, 1000.0));
+        leader.ingest(report("a", 0.9, 500.0)); // out of order, ignored
+        leader.ingest(report("b", 0.6, 1000.0));
+
+        let decisions = leader.decide(1000.0);
+        assert_eq!(decisions.len(), 1);
+        let state = leader.pool().get("api").unwrap().state();
+        assert!((state.pressure.error - 0.4).abs() < 1e-10);
+        assert_eq!(leader.decide(1000.0 + 6000.0), vec![]);
+    }
+
+    #[test]
+    fn test_followers_mirror_leader_trip() {
+        let bus = MemoryBus::new();
+        let mut leader = ClusterLeader::new(pool(), ClusterConfig::default());
+        let (link_a, link_b) = (bus.follower(), bus.follower());
+        let mut a = ClusterFollower::new("a", ClusterConfig::default());
+        let mut b = ClusterFollower::new("b", ClusterConfig::default());
+        let (mut pool_a, mut pool_b) = (pool(), pool());
+
+        for i in 0..40 {
+            let now = i as f64 * 100.0;
+            // Only node a sees the storm; b's upstream view looks healthy
+            let storm = PressureVector::new(1.0, 1.0, 1.0);
+            pool_a.tick("api", now, storm);
+            pool_b.tick("api", now, PressureVector::new(0.0, 0.0, 0.0));
+            a.report(&link_a, "api", storm, now).unwrap();
+            b.report(&link_b, "api", storm, now).unwrap();
+            leader.round(&bus, now).unwrap();
+            a.apply(&link_a, &mut pool_a, now).unwrap();
+            b.apply(&link_b, &mut pool_b, now).unwrap();
+        }
+
+        assert_eq!(
+            leader.pool().mode("api"),
+            Some(OperationalMode::CircuitBreaker)
+        );
+        assert_eq!(pool_a.mode("api"), pool_b.mode("api"));
+        assert_eq!(pool_b.mode("api"), Some(OperationalMode::CircuitBreaker));
+
+        // Leader goes silent: followers fall back to local control
+        assert!(b.decision("api", 3900.0 + 20_000.0).is_none());
+    }
+}
@@ -0,0 +1,191 @@
+/**
+ * Per-axis SLO compliance over the engine history.
+ *
+ * Pressure axes are normalized against the host's SLOs, so "latency
+ * pressure above 0.7" already means "latency was outside what we
+ * promised". The report answers how much of the recent past each axis
+ * spent above its threshold. Samples are time-weighted: each history
+ * sample stands for the span since the previous one, so an irregular tick
+ * rate does not skew the fractions.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::history::History;
+use crate::scar::CRITICAL_PRESSURE;
+use crate::types::PressureVector;
+
+/// Per-axis pressure above which a span counts as out of SLO
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ComplianceThresholds {
+    pub latency: f64,
+    pub error: f64,
+    pub saturation: f64,
+}
+
+impl Default for ComplianceThresholds {
+    /// The trauma threshold on every axis
+    fn default() -> Self {
+        Self {
+            latency: CRITICAL_PRESSURE,
+            error: CRITICAL_PRESSURE,
+            saturation: CRITICAL_PRESSURE,
+        }
+    }
+}
+
+/// Compliance of one pressure axis
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AxisCompliance {
+    pub threshold: f64,
+    /// Fraction of observed time above the threshold
+    pub violation: f64,
+    /// Highest pressure seen in the window
+    pub peak: f64,
+}
+
+impl AxisCompliance {
+    /// Fraction of observed time within the threshold
+    pub fn compliance(&self) -> f64 {
+        1.0 - self.violation
+    }
+}
+
+/// SLO compliance over one window
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceReport {
+    pub window_ms: f64,
+    /// Time covered by history samples (≤ `window_ms`)
+    pub observed_ms: f64,
+    pub latency: AxisCompliance,
+    pub error: AxisCompliance,
+    pub saturation: AxisCompliance,
+    /// Fraction of observed time with any axis above its threshold
+    pub any: f64,
+}
+
+/// Compliance report for one pool target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetCompliance {
+    pub id: String,
+    pub report: ComplianceReport,
+}
+
+/// Compliance over `(now_ms - window_ms, now_ms]`
+///
+/// Returns zero fractions (with `observed_ms == 0`) when the window holds
+/// fewer than two samples' worth of history.
+pub fn compliance(
+    history: &History,
+    now_ms: f64,
+    window_ms: f64,
+    thresholds: &ComplianceThresholds,
+) -> ComplianceReport {
+    let start_ms = now_ms - window_ms;
+    let limits = [thresholds.latency, thresholds.error, thresholds.saturation];
+    let axes = |p: &PressureVector| [p.latency, p.error, p.saturation];
+    let mut observed_ms = 0.0;
+    let mut exceeded_ms = [0.0; 3];
+    let mut any_ms = 0.0;
+    let mut peak = [0.0f64; 3];
+    let mut previous_ms: Option<f64> = None;
+
+    for sample in history.iter() {
+        let at_ms = sample.timestamp_ms;
+        let since_ms = previous_ms.replace(at_ms);
+        if at_ms <= start_ms || at_ms > now_ms {
+            continue;
+        }
+        let values = axes(&sample.pressure);
+        for (peak, value) in peak.iter_mut().zip(values) {
+            *peak = peak.max(value);
+        }
+        let Some(since_ms) = since_ms else {
+            continue;
+        };
+        let span = at_ms - since_ms.max(start_ms);
+        observed_ms += span;
+        let mut any = false;
+        for axis in 0..3 {
+            if values[axis] > limits[axis] {
+                exceeded_ms[axis] += span;
+                any = true;
+            }
+        }
+        if any {
+            any_ms += span;
+        }
+    }
+
+    let fraction = |ms: f64| {
+        if observed_ms > 0.0 {
+            ms / observed_ms
+        } else {
+            0.0
+        }
+    };
+    let axis = |i: usize| AxisCompliance {
+        threshold: limits[i],
+        violation: fraction(exceeded_ms[i]),
+        peak: peak[i],
+    };
+    ComplianceReport {
+        window_ms,
+        observed_ms,
+        latency: axis(0),
+        error: axis(1),
+        saturation: axis(2),
+        any: fraction(any_ms),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::pool::EnginePool;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    #[test]
+    fn test_fractions_are_time_weighted() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut now = 0.0;
+        // 10 s healthy at 100 ms ticks, then 10 s of slow latency at 1 s ticks
+        for _ in 0..100 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.2, 0.0, 0.1));
+        }
+        for _ in 0..10 {
+            now += 1_000.0;
+            engine.tick(now, PressureVector::new(0.9, 0.0, 0.1));
+        }
+        let report = engine.compliance(20_000.0, &ComplianceThresholds::default());
+        assert!((report.observed_ms - 19_900.0).abs() < 1e-6);
+        assert!((report.latency.violation - 10_000.0 / 19_900.0).abs() < 1e-9);
+        assert_eq!(report.error.violation, 0.0);
+        assert_eq!(report.latency.peak, 0.9);
+        assert_eq!(report.any, report.latency.violation);
+        assert_eq!(report.error.compliance(), 1.0);
+    }
+
+    #[test]
+    fn test_pool_reports_every_target() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 1..=50 {
+            let now = i as f64 * 100.0;
+            pool.tick("db", now, PressureVector::new(0.0, 0.8, 0.0));
+            pool.tick("api", now, PressureVector::new(0.0, 0.1, 0.0));
+        }
+        let reports = pool.compliance(60_000.0, &ComplianceThresholds::default());
+        let ids: Vec<&str> = reports.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["api", "db"]);
+        assert_eq!(reports[0].report.error.violation, 0.0);
+        assert_eq!(reports[1].report.error.violation, 1.0);
+    }
+}
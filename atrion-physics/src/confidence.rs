@@ -0,0 +1,52 @@
+/**
+ * Sample confidence from observation counts.
+ *
+ * Pressure derived from 3 requests should move the engine less than
+ * pressure derived from 30,000. Confidence c = n / (n + n½) rises from 0
+ * towards 1, reaching 0.5 at `HALF_CONFIDENCE_SAMPLES`; the engine scales
+ * trauma and momentum contributions by it.
+ */
+use wasm_bindgen::prelude::*;
+
+/// Sample count at which confidence is 0.5
+pub const HALF_CONFIDENCE_SAMPLES: f64 = 100.0;
+
+/// Confidence in [0, 1) for a pressure derived from `samples` observations
+#[inline]
+pub fn from_sample_count(samples: f64) -> f64 {
+    from_sample_count_with(samples, HALF_CONFIDENCE_SAMPLES)
+}
+
+/// Same as `from_sample_count` with a custom half-confidence count
+#[inline]
+pub fn from_sample_count_with(samples: f64, half: f64) -> f64 {
+    let samples = samples.max(0.0);
+    if half <= 0.0 {
+        return 1.0;
+    }
+    samples / (samples + half)
+}
+
+/// Confidence for a pressure derived from `samples` observations
+#[wasm_bindgen(js_name = confidenceFromSampleCount)]
+pub fn from_sample_count_js(samples: f64) -> f64 {
+    from_sample_count(samples)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confidence_grows_with_samples() {
+        assert_eq!(from_sample_count(0.0), 0.0);
+        assert_eq!(from_sample_count(100.0), 0.5);
+        assert!(from_sample_count(3.0) < 0.03);
+        assert!(from_sample_count(30_000.0) > 0.99);
+        assert_eq!(from_sample_count_with(3.0, 0.0), 1.0);
+    }
+}
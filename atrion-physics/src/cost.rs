@@ -0,0 +1,139 @@
+/**
+ * Cost-aware admission (per-request weight).
+ *
+ * Instead of counting requests, each admitted request consumes its
+ * estimated cost (CPU-ms, bytes, ...) from a windowed budget. The budget
+ * shrinks with resistance (see `admission::admission_ratio`), so heavy
+ * requests are shed before cheap ones once the target is under stress.
+ */
+use serde::{Deserialize, Serialize};
+
+/// Estimates the cost of a request before admission
+///
+/// Implemented for any `Fn(&R) -> f64`, so a closure is enough for most
+/// callers.
+pub trait CostEstimator<R: ?Sized> {
+    fn estimate(&self, request: &R) -> f64;
+}
+
+impl<R: ?Sized, F: Fn(&R) -> f64> CostEstimator<R> for F {
+    #[inline]
+    fn estimate(&self, request: &R) -> f64 {
+        self(request)
+    }
+}
+
+/// Cost budget configuration
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostBudgetConfig {
+    /// Budget refill window
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Cost units a healthy target absorbs per window
+    #[serde(alias = "capacity_per_window")]
+    pub capacity_per_window: f64,
+}
+
+impl Default for CostBudgetConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 1000.0,
+            capacity_per_window: 1000.0,
+        }
+    }
+}
+
+/// Windowed cost budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostBudget {
+    config: CostBudgetConfig,
+    #[serde(alias = "window_start_ms")]
+    window_start_ms: f64,
+    consumed: f64,
+}
+
+impl CostBudget {
+    pub fn new(config: CostBudgetConfig) -> Self {
+        Self {
+            config,
+            window_start_ms: f64::NEG_INFINITY,
+            consumed: 0.0,
+        }
+    }
+
+    pub fn config(&self) -> &CostBudgetConfig {
+        &self.config
+    }
+
+    /// Cost consumed in the current window
+    pub fn consumed(&self) -> f64 {
+        self.consumed
+    }
+
+    /// Budget left in the current window at the given admission ratio
+    pub fn remaining(&self, ratio: f64) -> f64 {
+        (self.capacity(ratio) - self.consumed).max(0.0)
+    }
+
+    /// Try to consume `cost` from the budget
+    ///
+    /// Negative or non-finite costs are treated as zero.
+    pub fn try_consume(&mut self, now_ms: f64, cost: f64, ratio: f64) -> bool {
+        if now_ms - self.window_start_ms >= self.config.window_ms {
+            self.window_start_ms = now_ms;
+            self.consumed = 0.0;
+        }
+
+        let cost = if cost.is_finite() { cost.max(0.0) } else { 0.0 };
+        if self.consumed + cost <= self.capacity(ratio) {
+            self.consumed += cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn capacity(&self, ratio: f64) -> f64 {
+        self.config.capacity_per_window * ratio.clamp(0.0, 1.0)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heavy_requests_exhaust_budget_first() {
+        let mut budget = CostBudget::new(CostBudgetConfig::default());
+
+        // Half the budget available: 500 units
+        assert!(budget.try_consume(0.0, 400.0, 0.5));
+        assert!(!budget.try_consume(0.0, 200.0, 0.5));
+        assert!(budget.try_consume(0.0, 10.0, 0.5));
+        assert!((budget.remaining(0.5) - 90.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_window_refills_budget() {
+        let mut budget = CostBudget::new(CostBudgetConfig::default());
+        assert!(budget.try_consume(0.0, 1000.0, 1.0));
+        assert!(!budget.try_consume(500.0, 1.0, 1.0));
+        assert!(budget.try_consume(1000.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_closure_estimator() {
+        struct Request {
+            bytes: usize,
+        }
+        let estimator = |r: &Request| r.bytes as f64 / 1024.0;
+        assert_eq!(estimator.estimate(&Request { bytes: 2048 }), 2.0);
+    }
+}
@@ -0,0 +1,247 @@
+/**
+ * Counterfactual analysis ("would this config have tripped?").
+ *
+ * Replays a recorded trace under N candidate configs and reports, for
+ * each, when breakers tripped, how much offered traffic would have been
+ * shed, and peak scar/resistance. Candidates run in parallel on native
+ * targets and sequentially on WASM.
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::engine::TargetEngine;
+use crate::trace::{TargetTrace, Trace};
+use crate::types::{OperationalMode, PhysicsConfig, SensitivityWeights};
+
+/// Breaker transition observed during replay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TripEvent {
+    pub target: String,
+    #[serde(alias = "at_ms")]
+    pub at_ms: f64,
+    /// `true` for a trip, `false` for a recovery
+    pub opened: bool,
+}
+
+/// Replay outcome for one candidate config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateReport {
+    /// Position in the candidate list
+    pub index: usize,
+    /// Trips and recoveries in time order (per target, targets in trace order)
+    pub transitions: Vec<TripEvent>,
+    pub trips: u32,
+    #[serde(alias = "offered_volume")]
+    pub offered_volume: f64,
+    /// Offered traffic × (1 − admission ratio), summed over samples
+    #[serde(alias = "shed_volume")]
+    pub shed_volume: f64,
+    #[serde(alias = "peak_scar")]
+    pub peak_scar: f64,
+    #[serde(alias = "peak_resistance")]
+    pub peak_resistance: f64,
+    /// Mean resistance over all replayed samples
+    #[serde(alias = "mean_resistance")]
+    pub mean_resistance: f64,
+}
+
+impl CandidateReport {
+    /// Trip timestamps only
+    pub fn trip_times(&self) -> Vec<f64> {
+        self.transitions
+            .iter()
+            .filter(|e| e.opened)
+            .map(|e| e.at_ms)
+            .collect()
+    }
+
+    /// Mean time from a trip to the matching recovery (`None` if no
+    /// breaker both opened and closed during the trace)
+    pub fn mean_recovery_ms(&self) -> Option<f64> {
+        let mut open: Vec<(&str, f64)> = Vec::new();
+        let (mut total, mut count) = (0.0, 0);
+        for event in &self.transitions {
+            if event.opened {
+                open.push((&event.target, event.at_ms));
+            } else if let Some(i) = open.iter().position(|(t, _)| *t == event.target) {
+                total += event.at_ms - open.swap_remove(i).1;
+                count += 1;
+            }
+        }
+        (count > 0).then(|| total / count as f64)
+    }
+
+    /// Fraction of offered traffic shed
+    pub fn shed_fraction(&self) -> f64 {
+        if self.offered_volume > 0.0 {
+            self.shed_volume / self.offered_volume
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Replay `trace` under every candidate config
+pub fn analyze(
+    trace: &Trace,
+    weights: &SensitivityWeights,
+    candidates: &[PhysicsConfig],
+) -> Vec<CandidateReport> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = candidates
+                .iter()
+                .enumerate()
+                .map(|(index, config)| scope.spawn(move || replay(index, trace, weights, config)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("replay does not panic"))
+                .collect()
+        })
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        candidates
+            .iter()
+            .enumerate()
+            .map(|(index, config)| replay(index, trace, weights, config))
+            .collect()
+    }
+}
+
+/// Replay `trace` under a single config
+pub fn replay(
+    index: usize,
+    trace: &Trace,
+    weights: &SensitivityWeights,
+    config: &PhysicsConfig,
+) -> CandidateReport {
+    let mut report = CandidateReport {
+        index,
+        transitions: Vec::new(),
+        trips: 0,
+        offered_volume: 0.0,
+        shed_volume: 0.0,
+        peak_scar: 0.0,
+        peak_resistance: 0.0,
+        mean_resistance: 0.0,
+    };
+    for target in &trace.targets {
+        replay_target(target, weights, config, &mut report);
+    }
+    if !trace.is_empty() {
+        report.mean_resistance /= trace.len() as f64;
+    }
+    report
+}
+
+fn replay_target(
+    trace: &TargetTrace,
+    weights: &SensitivityWeights,
+    config: &PhysicsConfig,
+    report: &mut CandidateReport,
+) {
+    let mut config = config.clone();
+    config.history_capacity = 0;
+    let mut engine = TargetEngine::new(config, weights.clone());
+
+    for sample in &trace.samples {
+        let before = engine.state().mode;
+        let state = engine.tick(sample.timestamp_ms, sample.pressure);
+
+        let opened = state.mode == OperationalMode::CircuitBreaker;
+        if (before == OperationalMode::CircuitBreaker) != opened {
+            report.trips += opened as u32;
+            report.transitions.push(TripEvent {
+                target: trace.target.clone(),
+                at_ms: sample.timestamp_ms,
+                opened,
+            });
+        }
+        let offered = sample.offered.max(0.0);
+        report.offered_volume += offered;
+        report.shed_volume += offered * (1.0 - engine.admission_ratio());
+        report.peak_scar = report.peak_scar.max(state.scar.0);
+        report.peak_resistance = report.peak_resistance.max(state.resistance.0);
+        // Summed here, divided by the sample count in `replay`
+        report.mean_resistance += state.resistance.0;
+    }
+}
+
+/// Replay a plain trace object under an array of plain configs
+#[wasm_bindgen(js_name = analyzeCounterfactual)]
+pub fn analyze_js(
+    trace: JsValue,
+    weights: &SensitivityWeights,
+    candidates: JsValue,
+) -> Result<JsValue, JsValue> {
+    let trace: Trace = serde_wasm_bindgen::from_value(trace)?;
+    let candidates: Vec<PhysicsConfig> = serde_wasm_bindgen::from_value(candidates)?;
+    crate::to_js(&analyze(&trace, weights, &candidates))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::TraceSample;
+    use crate::types::PressureVector;
+
+    fn incident_trace() -> Trace {
+        let mut trace = Trace::new();
+        for i in 0..100 {
+            let p = if (20..50).contains(&i) { 0.9 } else { 0.1 };
+            trace.record(
+                "api",
+                TraceSample {
+                    timestamp_ms: i as f64 * 100.0,
+                    pressure: PressureVector::new(p, p, p),
+                    offered: 10.0,
+                },
+            );
+        }
+        trace
+    }
+
+    #[test]
+    fn test_candidates_differ_in_trips() {
+        let strict = PhysicsConfig::default();
+        let lenient = PhysicsConfig {
+            break_threshold: 10_000.0,
+            recovery_threshold: 5_000.0,
+            ..PhysicsConfig::default()
+        };
+        let reports = analyze(
+            &incident_trace(),
+            &SensitivityWeights::default(),
+            &[strict, lenient],
+        );
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].index, 0);
+        assert!(reports[0].trips >= 1);
+        assert!(reports[0].trip_times()[0] >= 2000.0);
+        assert_eq!(reports[1].trips, 0);
+        assert!(reports[0].shed_volume > reports[1].shed_volume);
+        assert_eq!(reports[0].offered_volume, 1000.0);
+        assert_eq!(reports[0].peak_scar, reports[1].peak_scar);
+    }
+
+    #[test]
+    fn test_replay_is_deterministic() {
+        let trace = incident_trace();
+        let weights = SensitivityWeights::default();
+        let config = PhysicsConfig::default();
+        assert_eq!(
+            replay(0, &trace, &weights, &config),
+            analyze(&trace, &weights, std::slice::from_ref(&config))[0]
+        );
+    }
+}
@@ -0,0 +1,230 @@
+/**
+ * Per-target adaptive critical pressure.
+ *
+ * Trauma is recorded whenever positive stress exceeds
+ * `scar::CRITICAL_PRESSURE` (0.7). A target whose healthy baseline
+ * already hovers around 0.7 — a chatty batch worker, a cache that runs
+ * hot by design — then scars on every other tick and never heals. With
+ * `PhysicsConfig::adaptive_critical` set the engine keeps a histogram of
+ * the target's positive-stress magnitude, exponentially forgotten over
+ * `window_ms`, and uses its `quantile` as the trauma threshold, clamped to
+ * `[min, max]`. Until `min_samples` samples have been seen the static
+ * threshold applies.
+ *
+ * A long outage teaches the histogram too; `max` bounds how far that can
+ * raise the threshold. Trauma and the breaker's scar-and-pressure recovery
+ * check use the adapted threshold; resistance is unaffected.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::scar::CRITICAL_PRESSURE;
+
+/// Histogram resolution (positive-stress units)
+pub const STRESS_BIN_WIDTH: f64 = 0.02;
+
+/// Bins cover [0, √3], the largest positive-stress magnitude
+const STRESS_BINS: usize = 87;
+
+/// Adaptive trauma threshold configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct AdaptiveCriticalConfig {
+    /// Quantile of the stress distribution used as threshold (0..1)
+    pub quantile: f64,
+    /// Time constant over which old samples are forgotten
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Lowest threshold the adaptation may pick
+    pub min: f64,
+    /// Highest threshold the adaptation may pick
+    pub max: f64,
+    /// Samples needed before the threshold adapts
+    #[serde(alias = "min_samples")]
+    pub min_samples: u32,
+}
+
+impl Default for AdaptiveCriticalConfig {
+    fn default() -> Self {
+        Self {
+            quantile: 0.95,
+            window_ms: 3_600_000.0,
+            min: CRITICAL_PRESSURE,
+            max: 0.9,
+            min_samples: 300,
+        }
+    }
+}
+
+/// Exponentially forgotten histogram of positive-stress magnitudes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StressDistribution {
+    bins: Vec<f64>,
+    samples: u64,
+    #[serde(alias = "last_ms")]
+    last_ms: Option<f64>,
+}
+
+impl Default for StressDistribution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StressDistribution {
+    pub fn new() -> Self {
+        Self {
+            bins: vec![0.0; STRESS_BINS],
+            samples: 0,
+            last_ms: None,
+        }
+    }
+
+    /// Samples learned so far
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Learn one positive-stress magnitude observed at `now_ms`
+    pub fn observe(&mut self, config: &AdaptiveCriticalConfig, now_ms: f64, stress: f64) {
+        let elapsed = self.last_ms.map_or(0.0, |last| (now_ms - last).max(0.0));
+        self.last_ms = Some(now_ms);
+        if elapsed > 0.0 && config.window_ms > 0.0 {
+            let decay = (-elapsed / config.window_ms).exp();
+            for bin in &mut self.bins {
+                *bin *= decay;
+            }
+        }
+        let index = ((stress.max(0.0) / STRESS_BIN_WIDTH) as usize).min(STRESS_BINS - 1);
+        self.bins[index] += 1.0;
+        self.samples += 1;
+    }
+
+    /// Upper edge of the bin holding quantile `q` (`None` when empty)
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let total: f64 = self.bins.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let target = q.clamp(0.0, 1.0) * total;
+        let mut seen = 0.0;
+        for (index, weight) in self.bins.iter().enumerate() {
+            seen += weight;
+            if seen >= target {
+                return Some((index + 1) as f64 * STRESS_BIN_WIDTH);
+            }
+        }
+        Some(STRESS_BINS as f64 * STRESS_BIN_WIDTH)
+    }
+
+    /// Trauma threshold under `config`
+    pub fn critical(&self, config: &AdaptiveCriticalConfig) -> f64 {
+        let learned = if self.samples >= config.min_samples as u64 {
+            self.quantile(config.quantile)
+        } else {
+            None
+        };
+        learned
+            .unwrap_or(CRITICAL_PRESSURE)
+            .clamp(config.min, config.max.max(config.min))
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{OperationalMode, PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_threshold_tracks_quantile_within_bounds() {
+        let config = AdaptiveCriticalConfig::default();
+        let mut stress = StressDistribution::new();
+        assert_eq!(stress.critical(&config), CRITICAL_PRESSURE);
+        // 90% of samples at 0.5, 10% at 0.81: p95 sits in the top bin
+        for i in 0..1_000 {
+            let value = if i % 10 == 0 { 0.81 } else { 0.5 };
+            stress.observe(&config, i as f64 * 100.0, value);
+        }
+        assert!((stress.critical(&config) - 0.82).abs() < 1e-9);
+        let capped = AdaptiveCriticalConfig {
+            max: 0.75,
+            ..config
+        };
+        assert_eq!(stress.critical(&capped), 0.75);
+        // A quiet target never drops below the floor
+        let mut quiet = StressDistribution::new();
+        for i in 0..1_000 {
+            quiet.observe(&config, i as f64 * 100.0, 0.1);
+        }
+        assert_eq!(quiet.critical(&config), CRITICAL_PRESSURE);
+    }
+
+    #[test]
+    fn test_noisy_healthy_target_stops_scarring() {
+        let run = |adaptive: bool| {
+            let config = PhysicsConfig {
+                adaptive_critical: adaptive.then(AdaptiveCriticalConfig::default),
+                ..PhysicsConfig::default()
+            };
+            let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+            let mut now = 0.0;
+            let mut scar = 0.0f64;
+            // Runs hot by design: latency alternates around the threshold
+            for i in 0..2_000 {
+                now += 100.0;
+                let latency = if i % 4 == 0 { 0.73 } else { 0.6 };
+                let state = engine.tick(now, PressureVector::new(latency, 0.0, 0.0));
+                if i >= 1_500 {
+                    scar = scar.max(state.scar.0);
+                }
+            }
+            (scar, engine.critical_pressure())
+        };
+        let (plain_scar, plain_critical) = run(false);
+        let (adaptive_scar, adaptive_critical) = run(true);
+        assert_eq!(plain_critical, CRITICAL_PRESSURE);
+        assert!(plain_scar > 1.0);
+        assert!(adaptive_critical > 0.73);
+        // Only the decaying remainder of the learning phase is left
+        assert!(adaptive_scar < 0.1, "{adaptive_scar}");
+    }
+
+    #[test]
+    fn test_breaker_recovers_below_adapted_threshold() {
+        let run = |adaptive: Option<AdaptiveCriticalConfig>| {
+            let config = PhysicsConfig {
+                adaptive_critical: adaptive,
+                // Only the scar-and-pressure check can close the breaker
+                recovery_threshold: 1.0,
+                ..PhysicsConfig::default()
+            };
+            let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+            let hot = PressureVector::new(0.78, 0.0, 0.0);
+            let mut now = 0.0;
+            for _ in 0..50 {
+                now += 100.0;
+                engine.tick(now, hot);
+            }
+            engine.force_mode(OperationalMode::CircuitBreaker);
+            now += 100.0;
+            let state = engine.tick(now, hot);
+            (state.mode, engine.critical_pressure())
+        };
+        let raised = AdaptiveCriticalConfig {
+            min: 0.8,
+            ..AdaptiveCriticalConfig::default()
+        };
+        let (static_mode, _) = run(None);
+        let (adaptive_mode, critical) = run(Some(raised));
+        assert!((critical - 0.8).abs() < 1e-9);
+        assert_eq!(static_mode, OperationalMode::CircuitBreaker);
+        assert_eq!(adaptive_mode, OperationalMode::Operational);
+    }
+}
@@ -0,0 +1,115 @@
+/**
+ * Deadline-aware admission: don't admit work you can't finish.
+ *
+ * A request that will sit in the target's queue past its caller's
+ * deadline only burns capacity on a response nobody reads. `QueueModel`
+ * predicts the queueing delay from saturation pressure, read as M/M/1
+ * utilization ρ:
+ *
+ *   wait = service_ms · ρ / (1 − ρ)
+ *
+ * with ρ capped at `max_utilization` so a fully saturated target predicts
+ * a long but finite wait. A request is rejected with
+ * `RejectReason::DeadlineUnmeetable` when wait plus one service time
+ * exceeds its remaining deadline.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::admission::{AdmissionDecision, RejectReason};
+use crate::engine::TargetState;
+
+/// Queue model behind deadline-aware admission
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct QueueModel {
+    /// Mean service time on an idle target
+    #[serde(alias = "service_ms")]
+    pub service_ms: f64,
+    /// Utilization cap (< 1) for the wait formula
+    #[serde(alias = "max_utilization")]
+    pub max_utilization: f64,
+}
+
+impl Default for QueueModel {
+    fn default() -> Self {
+        Self {
+            service_ms: 20.0,
+            max_utilization: 0.99,
+        }
+    }
+}
+
+impl QueueModel {
+    /// Utilization read from saturation pressure
+    pub fn utilization(&self, state: &TargetState) -> f64 {
+        let cap = self.max_utilization.clamp(0.0, 0.999_999);
+        state.pressure.saturation.clamp(0.0, cap)
+    }
+
+    /// Predicted time spent queueing before service starts
+    pub fn predicted_wait_ms(&self, state: &TargetState) -> f64 {
+        let rho = self.utilization(state);
+        self.service_ms.max(0.0) * rho / (1.0 - rho)
+    }
+
+    /// Reject when the predicted wait plus service overruns `remaining_ms`
+    pub fn decide(&self, state: &TargetState, remaining_ms: f64) -> AdmissionDecision {
+        let finish_ms = self.predicted_wait_ms(state) + self.service_ms.max(0.0);
+        if finish_ms > remaining_ms {
+            AdmissionDecision::Reject(RejectReason::DeadlineUnmeetable)
+        } else {
+            AdmissionDecision::Admit
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_wait_grows_with_saturation() {
+        let model = QueueModel::default();
+        let mut state = TargetState::bootstrap(&PhysicsConfig::default());
+        assert_eq!(model.predicted_wait_ms(&state), 0.0);
+        state.pressure = PressureVector::new(0.0, 0.0, 0.5);
+        assert_eq!(model.predicted_wait_ms(&state), 20.0);
+        state.pressure = PressureVector::new(0.0, 0.0, 1.0);
+        assert!((model.predicted_wait_ms(&state) - 1_980.0).abs() < 1e-6);
+        assert!(model.decide(&state, 2_000.0).is_admitted());
+        assert_eq!(
+            model.decide(&state, 1_000.0),
+            AdmissionDecision::Reject(RejectReason::DeadlineUnmeetable)
+        );
+    }
+
+    #[test]
+    fn test_engine_rejects_short_deadlines_under_load() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut now = 0.0;
+        for _ in 0..12 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.8));
+        }
+        // wait = 20 · 0.8 / 0.2 = 80 ms, plus 20 ms of service
+        let voltage = engine.state().resistance.0 + 1.0;
+        assert!(engine.try_admit_deadline(voltage, 150.0).is_admitted());
+        assert_eq!(
+            engine.try_admit_deadline(voltage, 50.0),
+            AdmissionDecision::Reject(RejectReason::DeadlineUnmeetable)
+        );
+        // The voltage gate still comes first
+        assert_eq!(
+            engine.try_admit_deadline(0.0, 50.0),
+            AdmissionDecision::Reject(RejectReason::InsufficientVoltage)
+        );
+    }
+}
@@ -0,0 +1,123 @@
+/**
+ * Debug panel data feed.
+ *
+ * A dev-tools panel redraws every animation frame, and stitching its view
+ * together from `state`, `explain()`, `historyBetween()` and `drainEvents()`
+ * costs four boundary crossings and several intermediate `Vec`s per frame
+ * (and draining would steal events from the real consumer). `DebugFrame`
+ * gathers the same data in one borrowed view: the history and event tails
+ * are slices of the engine's own ring buffers, so building a frame
+ * allocates nothing on the Rust side, and `debugFrame()` serializes it
+ * straight into one plain JS object.
+ */
+use std::collections::VecDeque;
+
+use serde::ser::{Serialize, Serializer};
+
+use crate::engine::TargetState;
+use crate::events::EngineEvent;
+use crate::explain::ResistanceBreakdown;
+use crate::history::HistorySample;
+
+/// Newest history samples included in a frame
+pub const DEBUG_HISTORY_TAIL: usize = 32;
+
+/// Newest events included in a frame (not drained)
+pub const DEBUG_EVENT_TAIL: usize = 16;
+
+/// The newest `count` items of a ring buffer, oldest first, borrowed
+#[derive(Debug, Copy, Clone)]
+pub struct Tail<'a, T> {
+    front: &'a [T],
+    back: &'a [T],
+}
+
+impl<'a, T> Tail<'a, T> {
+    pub fn new(items: &'a VecDeque<T>, count: usize) -> Self {
+        let (front, back) = items.as_slices();
+        let skip = items.len().saturating_sub(count);
+        if skip <= front.len() {
+            Self {
+                front: &front[skip..],
+                back,
+            }
+        } else {
+            Self {
+                front: &[],
+                back: &back[skip - front.len()..],
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        self.front.iter().chain(self.back)
+    }
+}
+
+impl<T: Serialize> Serialize for Tail<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// Everything a debug panel shows for one engine, in one object
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugFrame<'a> {
+    pub state: &'a TargetState,
+    pub breakdown: ResistanceBreakdown,
+    /// Trauma threshold in effect (see `adaptive_critical`)
+    pub critical_pressure: f64,
+    /// Current brownout level (0 without `brownout`)
+    pub degradation: u32,
+    pub history: Tail<'a, HistorySample>,
+    pub events: Tail<'a, EngineEvent>,
+    /// Events evicted from the log before being drained
+    pub dropped_events: u64,
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_frame_holds_newest_tails_without_draining() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut now = 0.0;
+        for _ in 0..100 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.2, 0.0, 0.1));
+        }
+        let frame = engine.debug_frame();
+        assert_eq!(frame.history.len(), DEBUG_HISTORY_TAIL);
+        assert_eq!(frame.history.iter().last().unwrap().timestamp_ms, now);
+        assert_eq!(frame.events.len(), 1);
+        assert_eq!(frame.breakdown, engine.explain());
+
+        let plain = serde_json::to_value(&frame).unwrap();
+        assert_eq!(
+            plain["history"].as_array().unwrap().len(),
+            DEBUG_HISTORY_TAIL
+        );
+        assert_eq!(plain["criticalPressure"], 0.7);
+        // Nested types follow the same casing
+        assert_eq!(plain["history"][0]["timestampMs"], now - 3_100.0);
+        assert!(plain["breakdown"].get("slowScar").is_some());
+        assert!(plain["events"][0].get("atMs").is_some());
+        assert_eq!(engine.events().len(), 1);
+    }
+}
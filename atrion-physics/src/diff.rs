@@ -0,0 +1,274 @@
+/**
+ * Snapshot diffing (state inspection).
+ *
+ * Compares two pool snapshots target by target, e.g. "before incident"
+ * vs "after incident" dumps, and reports which fields moved and by how
+ * much. `SnapshotDiff` implements `Display` as a human-readable report.
+ */
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::engine::TargetState;
+use crate::snapshot::Snapshot;
+use crate::types::OperationalMode;
+
+/// Changes smaller than this are treated as noise
+const EPSILON: f64 = 1e-9;
+
+/// How a target differs between snapshots
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One numeric field that moved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl FieldChange {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+
+    /// Relative change (`None` when `before` is zero)
+    pub fn relative(&self) -> Option<f64> {
+        (self.before.abs() > EPSILON).then(|| self.delta() / self.before.abs())
+    }
+}
+
+/// Differences for one target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetDiff {
+    pub id: String,
+    pub kind: DiffKind,
+    /// `(before, after)` when the mode changed
+    pub mode: Option<(OperationalMode, OperationalMode)>,
+    /// Numeric changes, largest absolute delta first
+    pub fields: Vec<FieldChange>,
+}
+
+/// Structured difference between two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    #[serde(alias = "from_ms")]
+    pub from_ms: f64,
+    #[serde(alias = "to_ms")]
+    pub to_ms: f64,
+    /// Only targets that differ, sorted by id
+    pub targets: Vec<TargetDiff>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&TargetDiff> {
+        self.targets.iter().find(|t| t.id == id)
+    }
+}
+
+fn numeric_fields(state: &TargetState) -> [(&'static str, f64); 9] {
+    [
+        ("resistance", state.resistance.0),
+        ("scar", state.scar.0),
+        ("slow_scar", state.slow_scar.0),
+        ("momentum", state.momentum.0),
+        ("pressure.latency", state.pressure.latency),
+        ("pressure.error", state.pressure.error),
+        ("pressure.saturation", state.pressure.saturation),
+        ("tick_count", state.tick_count as f64),
+        ("last_updated_ms", state.last_updated_ms),
+    ]
+}
+
+fn diff_states(id: &str, before: &TargetState, after: &TargetState) -> Option<TargetDiff> {
+    let mut fields: Vec<FieldChange> = numeric_fields(before)
+        .into_iter()
+        .zip(numeric_fields(after))
+        .filter(|((_, b), (_, a))| (a - b).abs() > EPSILON)
+        .map(|((field, before), (_, after))| FieldChange {
+            field: field.to_string(),
+            before,
+            after,
+        })
+        .collect();
+    fields.sort_by(|a, b| b.delta().abs().total_cmp(&a.delta().abs()));
+    let mode = (before.mode != after.mode).then_some((before.mode, after.mode));
+
+    (mode.is_some() || !fields.is_empty()).then(|| TargetDiff {
+        id: id.to_string(),
+        kind: DiffKind::Changed,
+        mode,
+        fields,
+    })
+}
+
+/// Diff `before` against `after`
+pub fn diff(before: &Snapshot, after: &Snapshot) -> SnapshotDiff {
+    let mut pairs: BTreeMap<&str, (Option<&TargetState>, Option<&TargetState>)> = BTreeMap::new();
+    for target in &before.targets {
+        pairs.entry(&target.id).or_default().0 = Some(&target.state);
+    }
+    for target in &after.targets {
+        pairs.entry(&target.id).or_default().1 = Some(&target.state);
+    }
+
+    let targets = pairs
+        .into_iter()
+        .filter_map(|(id, pair)| match pair {
+            (Some(b), Some(a)) => diff_states(id, b, a),
+            (None, Some(a)) => Some(TargetDiff {
+                id: id.to_string(),
+                kind: DiffKind::Added,
+                mode: None,
+                fields: whole_state(a, false),
+            }),
+            (Some(b), None) => Some(TargetDiff {
+                id: id.to_string(),
+                kind: DiffKind::Removed,
+                mode: None,
+                fields: whole_state(b, true),
+            }),
+            (None, None) => None,
+        })
+        .collect();
+
+    SnapshotDiff {
+        from_ms: before.taken_at_ms,
+        to_ms: after.taken_at_ms,
+        targets,
+    }
+}
+
+/// Fields of an added (from zero) or removed (to zero) target
+fn whole_state(state: &TargetState, removed: bool) -> Vec<FieldChange> {
+    numeric_fields(state)
+        .into_iter()
+        .map(|(field, value)| FieldChange {
+            field: field.to_string(),
+            before: if removed { value } else { 0.0 },
+            after: if removed { 0.0 } else { value },
+        })
+        .collect()
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "snapshot diff {} → {} ms", self.from_ms, self.to_ms)?;
+        if self.targets.is_empty() {
+            return writeln!(f, "  (no changes)");
+        }
+        for target in &self.targets {
+            let marker = match target.kind {
+                DiffKind::Added => '+',
+                DiffKind::Removed => '-',
+                DiffKind::Changed => '~',
+            };
+            write!(f, "{marker} {}", target.id)?;
+            if let Some((from, to)) = target.mode {
+                write!(f, "  [{} → {}]", from.as_str(), to.as_str())?;
+            }
+            writeln!(f)?;
+            if target.kind != DiffKind::Changed {
+                continue;
+            }
+            for change in &target.fields {
+                write!(
+                    f,
+                    "    {:<20} {:>12.3} → {:<12.3} ({:+.3}",
+                    change.field,
+                    change.before,
+                    change.after,
+                    change.delta()
+                )?;
+                match change.relative() {
+                    Some(rel) => writeln!(f, ", {:+.1}%)", rel * 100.0)?,
+                    None => writeln!(f, ")")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Diff two plain snapshot objects (as produced by `EnginePool.snapshot`)
+#[wasm_bindgen(js_name = diffSnapshots)]
+pub fn diff_snapshots_js(before: JsValue, after: JsValue) -> Result<JsValue, JsValue> {
+    let before: Snapshot = serde_wasm_bindgen::from_value(before)?;
+    let after: Snapshot = serde_wasm_bindgen::from_value(after)?;
+    crate::to_js(&diff(&before, &after))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::EnginePool;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_diff_before_after_incident() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 0..12 {
+            pool.tick("api", i as f64 * 100.0, PressureVector::new(0.1, 0.0, 0.0));
+            pool.tick("db", i as f64 * 100.0, PressureVector::new(0.1, 0.0, 0.0));
+        }
+        let before = pool.snapshot(1200.0);
+        pool.remove("db");
+        pool.tick("cache", 1300.0, PressureVector::new(0.0, 0.0, 0.0));
+        for i in 12..40 {
+            pool.tick("api", i as f64 * 100.0, PressureVector::new(1.0, 1.0, 1.0));
+        }
+        let after = pool.snapshot(4000.0);
+
+        let diff = before.diff(&after);
+        let ids: Vec<&str> = diff.targets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["api", "cache", "db"]);
+
+        let api = diff.get("api").unwrap();
+        assert_eq!(
+            api.mode,
+            Some((
+                OperationalMode::Operational,
+                OperationalMode::CircuitBreaker
+            ))
+        );
+        assert_eq!(api.fields[0].field, "last_updated_ms");
+        assert!(api
+            .fields
+            .iter()
+            .any(|c| c.field == "scar" && c.delta() > 0.0));
+        assert_eq!(diff.get("cache").unwrap().kind, DiffKind::Added);
+        assert_eq!(diff.get("db").unwrap().kind, DiffKind::Removed);
+
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        pool.tick("api", 0.0, PressureVector::new(0.0, 0.0, 0.0));
+        let before = pool.snapshot(0.0);
+        pool.tick("api", 100.0, PressureVector::new(0.5, 0.0, 0.0));
+
+        let text = before.diff(&pool.snapshot(100.0)).to_string();
+        assert!(text.starts_with("snapshot diff 0 → 100 ms"));
+        assert!(text.contains("~ api"));
+        assert!(text.contains("pressure.latency"));
+        assert!(text.contains("tick_count"));
+    }
+}
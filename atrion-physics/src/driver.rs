@@ -0,0 +1,325 @@
+/**
+ * Async control loop for native embedding (feature "tokio", native only).
+ *
+ * `EngineDriver` owns the loop most embedders otherwise write by hand:
+ * every `interval_ms` it asks an `AsyncPressureSource` for the current pressure
+ * of each target, ticks the shared pool, and broadcasts what happened
+ * (engine events per target, then a tick summary) to any subscribers.
+ *
+ * The pool lock is never held across an await, so the pool stays usable
+ * from request handlers while the driver runs. Synchronous
+ * `sources::PressureSource`s plug in through `Polling`.
+ */
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::events::EngineEvent;
+use crate::pool::EnginePool;
+use crate::sources::PressureSource;
+use crate::types::PressureVector;
+
+/// Where the driver gets pressure from (metrics scrape, local probes, ...)
+pub trait AsyncPressureSource: Send + 'static {
+    type Error: fmt::Display + Send;
+
+    /// Pressure per target for the tick at `now_ms`; targets left out are
+    /// not ticked, unknown ones are registered
+    fn sample(
+        &mut self,
+        now_ms: f64,
+    ) -> impl Future<Output = Result<Vec<(String, PressureVector)>, Self::Error>> + Send;
+}
+
+/// Polls a synchronous `PressureSource` for a fixed list of targets
+///
+/// Sample timestamps are dropped: the driver ticks on its own clock. The
+/// poll runs on the runtime thread, so blocking sources (Prometheus) want
+/// a multi-threaded runtime or a short timeout.
+pub struct Polling<P> {
+    pub source: P,
+    pub targets: Vec<String>,
+}
+
+impl<P: PressureSource + Send + 'static> AsyncPressureSource for Polling<P> {
+    type Error = Infallible;
+
+    async fn sample(&mut self, _now_ms: f64) -> Result<Vec<(String, PressureVector)>, Infallible> {
+        Ok(self
+            .targets
+            .iter()
+            .filter_map(|target| {
+                let sample = self.source.poll(target)?;
+                Some((target.clone(), sample.pressure))
+            })
+            .collect())
+    }
+}
+
+/// Driver configuration
+#[derive(Debug, Clone)]
+pub struct DriverConfig {
+    /// Time between ticks
+    pub interval_ms: u64,
+    /// Buffered events per subscriber before slow subscribers start skipping
+    pub event_buffer: usize,
+}
+
+impl Default for DriverConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 1_000,
+            event_buffer: 1024,
+        }
+    }
+}
+
+/// Broadcast to `EngineDriver::subscribe` receivers
+#[derive(Debug, Clone, PartialEq)]
+pub enum DriverEvent {
+    /// An engine event raised while ticking `target`
+    Engine { target: String, event: EngineEvent },
+    /// A tick finished
+    Ticked { now_ms: f64, targets: usize },
+    /// The source failed; the tick was skipped
+    SourceFailed { now_ms: f64, error: String },
+}
+
+/// Ticks a shared pool from an `AsyncPressureSource` on a fixed interval
+pub struct EngineDriver<S> {
+    pool: Arc<Mutex<EnginePool>>,
+    source: S,
+    config: DriverConfig,
+    events: broadcast::Sender<DriverEvent>,
+}
+
+impl<S: AsyncPressureSource> EngineDriver<S> {
+    pub fn new(pool: Arc<Mutex<EnginePool>>, source: S, config: DriverConfig) -> Self {
+        let (events, _) = broadcast::channel(config.event_buffer.max(1));
+        Self {
+            pool,
+            source,
+            config,
+            events,
+        }
+    }
+
+    pub fn pool(&self) -> Arc<Mutex<EnginePool>> {
+        Arc::clone(&self.pool)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DriverEvent> {
+        self.events.subscribe()
+    }
+
+    /// Run one tick at `now_ms`; returns how many targets were ticked
+    ///
+    /// Engine events of ticked targets are drained from the pool and
+    /// broadcast, so they no longer show up in `drain_events`.
+    pub async fn step(&mut self, now_ms: f64) -> Result<usize, S::Error> {
+        let samples = match self.source.sample(now_ms).await {
+            Ok(samples) => samples,
+            Err(error) => {
+                self.publish(DriverEvent::SourceFailed {
+                    now_ms,
+                    error: error.to_string(),
+                });
+                return Err(error);
+            }
+        };
+        let mut raised = Vec::new();
+        {
+            let mut pool = self.lock();
+            for (target, pressure) in &samples {
+                pool.tick(target, now_ms, *pressure);
+                if let Some(engine) = pool.get_mut(target) {
+                    raised.extend(engine.drain_events().into_iter().map(|event| {
+                        DriverEvent::Engine {
+                            target: target.clone(),
+                            event,
+                        }
+                    }));
+                }
+            }
+        }
+        raised.into_iter().for_each(|event| self.publish(event));
+        self.publish(DriverEvent::Ticked {
+            now_ms,
+            targets: samples.len(),
+        });
+        Ok(samples.len())
+    }
+
+    /// Tick every `interval_ms` on the wall clock, forever
+    ///
+    /// Source errors are broadcast and the loop carries on; ticks missed
+    /// while a slow source is awaited are skipped, not bunched up.
+    pub async fn run(mut self) {
+        let mut interval =
+            tokio::time::interval(Duration::from_millis(self.config.interval_ms.max(1)));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let _ = self.step(unix_ms()).await;
+        }
+    }
+
+    /// `run` on the current tokio runtime; abort the handle to stop
+    ///
+    /// Aborting lands between ticks or inside the source, never while the
+    /// pool is locked.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    fn publish(&self, event: DriverEvent) {
+        // No subscribers is not an error
+        let _ = self.events.send(event);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, EnginePool> {
+        self.pool
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn unix_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{OperationalMode, PhysicsConfig, SensitivityWeights};
+
+    /// Fixed pressure for "api"; fails when `fail` is set
+    struct Probe {
+        pressure: PressureVector,
+        fail: bool,
+    }
+
+    impl AsyncPressureSource for Probe {
+        type Error = String;
+
+        async fn sample(&mut self, _now_ms: f64) -> Result<Vec<(String, PressureVector)>, String> {
+            match self.fail {
+                true => Err("probe down".to_string()),
+                false => Ok(vec![("api".to_string(), self.pressure)]),
+            }
+        }
+    }
+
+    fn driver(config: DriverConfig) -> EngineDriver<Probe> {
+        let pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let probe = Probe {
+            pressure: PressureVector::new(0.1, 0.0, 0.1),
+            fail: false,
+        };
+        EngineDriver::new(Arc::new(Mutex::new(pool)), probe, config)
+    }
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_step_ticks_and_broadcasts_events() {
+        let mut driver = driver(DriverConfig::default());
+        let mut events = driver.subscribe();
+        runtime().block_on(async {
+            for i in 0..10 {
+                assert_eq!(driver.step(i as f64 * 100.0).await, Ok(1));
+            }
+            driver.source.fail = true;
+            assert!(driver.step(1_000.0).await.is_err());
+        });
+
+        let received: Vec<DriverEvent> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert_eq!(received.len(), 10 + 1 + 1);
+        assert!(received.iter().any(|event| matches!(
+            event,
+            DriverEvent::Engine {
+                target,
+                event: EngineEvent::ModeChanged { to: OperationalMode::Operational, .. },
+            } if target == "api"
+        )));
+        assert_eq!(
+            received.last(),
+            Some(&DriverEvent::SourceFailed {
+                now_ms: 1_000.0,
+                error: "probe down".to_string()
+            })
+        );
+        let pool = driver.pool();
+        let pool = pool.lock().unwrap();
+        assert_eq!(pool.get("api").unwrap().state().tick_count, 10);
+        assert!(pool.get("api").unwrap().events().is_empty());
+    }
+
+    #[test]
+    fn test_spawned_driver_ticks_on_interval() {
+        let driver = driver(DriverConfig {
+            interval_ms: 5,
+            ..DriverConfig::default()
+        });
+        let pool = driver.pool();
+        let mut events = driver.subscribe();
+        runtime().block_on(async {
+            let handle = driver.spawn();
+            let mut ticks = 0;
+            while ticks < 3 {
+                if let DriverEvent::Ticked { targets, .. } = events.recv().await.unwrap() {
+                    assert_eq!(targets, 1);
+                    ticks += 1;
+                }
+            }
+            handle.abort();
+        });
+        assert!(pool.lock().unwrap().get("api").unwrap().state().tick_count >= 3);
+    }
+
+    #[test]
+    fn test_polling_adapts_sync_sources() {
+        let mut latest = crate::sources::LatestPressure::new();
+        let calm = PressureVector::new(0.1, 0.0, 0.1);
+        latest.push(
+            "db",
+            crate::sources::TimestampedPressure {
+                timestamp_ms: 0.0,
+                pressure: calm,
+            },
+        );
+        let polling = Polling {
+            source: latest,
+            targets: vec!["api".to_string(), "db".to_string()],
+        };
+        let pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut driver =
+            EngineDriver::new(Arc::new(Mutex::new(pool)), polling, DriverConfig::default());
+        runtime().block_on(async {
+            assert_eq!(driver.step(5_000.0).await, Ok(1));
+            assert_eq!(driver.step(6_000.0).await, Ok(0));
+        });
+        let pool = driver.pool();
+        let pool = pool.lock().unwrap();
+        assert_eq!(pool.get("db").unwrap().state().last_updated_ms, 5_000.0);
+        assert!(pool.get("api").is_none());
+    }
+}
@@ -0,0 +1,3086 @@
+/**
+ * Stateful per-target engine (mode controller).
+ *
+ * Owns the dynamic physics state of one target and advances it tick by tick.
+ *
+ * MUST follow src/core/physics.ts updatePhysics() state machine:
+ * BOOTSTRAP → OPERATIONAL ⇄ CIRCUIT_BREAKER
+ */
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::admission::{self, AdmissionDecision, RejectReason};
+use crate::alarms::{AlarmConfig, Alarms};
+use crate::backfill::{Backfill, BackfillConfig};
+use crate::backoff::BreakerBackoff;
+use crate::burnrate::{BurnRateConfig, BurnRateTracker, BurnRates};
+use crate::changepoint::{ChangePointConfig, ChangePointDetector};
+use crate::compliance::{self, ComplianceReport, ComplianceThresholds};
+use crate::cost::{CostBudget, CostBudgetConfig, CostEstimator};
+use crate::critical::StressDistribution;
+use crate::deadline::QueueModel;
+use crate::debug::{DebugFrame, DEBUG_EVENT_TAIL, DEBUG_HISTORY_TAIL};
+use crate::events::{ClockAnomalyKind, EngineEvent, EventLog, ResetKind};
+use crate::explain::ResistanceBreakdown;
+use crate::fairness::{FairnessConfig, TenantAccount, TenantFairness};
+use crate::fixedtick::{DecayFactors, FixedTick};
+use crate::flap::{FlapConfig, FlapDetector};
+use crate::goodput::GoodputEstimator;
+use crate::healthcheck::{Probing, ProbingConfig};
+use crate::hedge::{self, HedgeAdvice, HedgePolicy};
+use crate::history::{History, HistorySample};
+use crate::journal::{Journal, JournalEntry, JournalRecord, JournalSink};
+use crate::markers::{AnnotatedExplanation, AnnotatedSample, Marker, MarkerKind};
+use crate::metastable::{MetastableConfig, MetastableDetector};
+use crate::overrides::{ModeOverride, Overrides};
+use crate::pid::{self, PidTerms};
+use crate::priority::{self, ShedCurve};
+use crate::reorder::{ReorderBuffer, ReorderConfig, ReorderStats, SequencedSample};
+use crate::resistance::ResistanceLane;
+use crate::retry::{self, Rejection, RetryAdvice};
+use crate::seasonal::SeasonalBaseline;
+use crate::softstart::Ramp;
+use crate::stats::{self, RollingStats, RollingSummary};
+use crate::store::SharedState;
+use crate::transitions::{
+    ModeTransition, TransitionEvidence, TransitionReason, TRANSITION_CAPACITY,
+};
+use crate::tuning::{OnlineTuner, TuningConfig};
+use crate::types::*;
+use crate::{momentum, resistance, scar, vector};
+
+/// Dynamic physics state of a single target
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct TargetState {
+    pub mode: OperationalMode,
+    pub pressure: PressureVector,
+    #[serde(alias = "previous_pressure")]
+    pub previous_pressure: PressureVector,
+    pub momentum: Momentum,
+    pub scar: Scar,
+    /// Chronic scar component (zero unless `PhysicsConfig::dual_scar` is set)
+    #[serde(default, alias = "slow_scar")]
+    pub slow_scar: Scar,
+    pub resistance: Ohms,
+    #[serde(alias = "tick_count")]
+    pub tick_count: u32,
+    #[serde(alias = "last_updated_ms")]
+    pub last_updated_ms: f64,
+    /// Operator overrides in effect
+    #[serde(default)]
+    pub overrides: Overrides,
+}
+
+impl TargetState {
+    /// Initial bootstrap state (matches TS createBootstrapState)
+    pub fn bootstrap(config: &PhysicsConfig) -> Self {
+        let zero = PressureVector::new(0.0, 0.0, 0.0);
+        Self {
+            mode: OperationalMode::Bootstrap,
+            pressure: zero,
+            previous_pressure: zero,
+            momentum: Momentum(0.0),
+            scar: Scar(0.0),
+            slow_scar: Scar(0.0),
+            resistance: Ohms(config.base_resistance * 1.2), // Conservative default
+            tick_count: 0,
+            last_updated_ms: 0.0,
+            overrides: Overrides::default(),
+        }
+    }
+}
+
+/// Plain-object form of an engine (config, weights and physics state)
+///
+/// Optional layers (fairness, budgets, tuning, ...) are not included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineDocument {
+    #[serde(default)]
+    pub config: PhysicsConfig,
+    #[serde(default)]
+    pub weights: SensitivityWeights,
+    /// Omitted = fresh bootstrap state
+    #[serde(default)]
+    pub state: Option<TargetState>,
+}
+
+/// A sample applied up to the resistance formula (see `begin_tick`)
+pub(crate) struct PendingTick {
+    now_ms: f64,
+    prev: TargetState,
+    next: TargetState,
+    /// Resistance inputs still to evaluate (`None`: `next` is complete)
+    pub(crate) lane: Option<ResistanceLane>,
+    /// `next.mode` still has to go through `next_mode`
+    transition: bool,
+}
+
+/// Per-tick engine layers outside `TargetState` that a backfill rewind
+/// restores along with it (see `backfill`)
+#[derive(Debug, Clone)]
+pub(crate) struct TickLayers {
+    ramp: Option<Ramp>,
+    flaps: Option<FlapDetector>,
+    metastable: Option<MetastableDetector>,
+    changes: Option<ChangePointDetector>,
+    backoff: BreakerBackoff,
+    recovery_due_ms: Option<f64>,
+    degradation: u32,
+    seasonal: Option<SeasonalBaseline>,
+    warmup_started_ms: Option<f64>,
+    stress: Option<StressDistribution>,
+}
+
+/// Stateful physics engine for a single target
+#[wasm_bindgen]
+pub struct TargetEngine {
+    config: PhysicsConfig,
+    weights: SensitivityWeights,
+    state: TargetState,
+    history: History,
+    burn_rate: Option<BurnRateTracker>,
+    goodput: GoodputEstimator,
+    fairness: Option<TenantFairness>,
+    shed_curve: ShedCurve,
+    queue_model: QueueModel,
+    cost_budget: Option<CostBudget>,
+    tuner: Option<OnlineTuner>,
+    reorder: Option<ReorderBuffer>,
+    journal: Option<Journal>,
+    alarms: Option<Alarms>,
+    events: EventLog,
+    fixed_tick: Option<FixedTick>,
+    backfill: Option<Backfill>,
+    probing: Option<Probing>,
+    /// Post-breaker resistance ramp in progress (`PhysicsConfig::soft_start`)
+    ramp: Option<Ramp>,
+    flaps: Option<FlapDetector>,
+    metastable: Option<MetastableDetector>,
+    changes: Option<ChangePointDetector>,
+    /// Trip count for `PhysicsConfig::breaker_backoff`
+    backoff: BreakerBackoff,
+    /// Selects this engine's share of `PhysicsConfig::recovery_jitter`
+    jitter_key: u64,
+    /// When recovery first came due while the jitter delay runs
+    recovery_due_ms: Option<f64>,
+    /// Current `PhysicsConfig::brownout` level
+    degradation: u32,
+    /// Learned `PhysicsConfig::seasonal` baseline
+    seasonal: Option<SeasonalBaseline>,
+    /// Start of the `PhysicsConfig::warmup` window (first tick or
+    /// `restart_warmup`)
+    warmup_started_ms: Option<f64>,
+    /// Learned `PhysicsConfig::adaptive_critical` stress distribution
+    stress: Option<StressDistribution>,
+    /// Newest `TRANSITION_CAPACITY` mode transitions
+    transitions: VecDeque<ModeTransition>,
+}
+
+impl TargetEngine {
+    /// Create an engine starting from an existing state (warm start, restore)
+    pub fn with_state(
+        config: PhysicsConfig,
+        weights: SensitivityWeights,
+        state: TargetState,
+    ) -> Self {
+        let mut engine = Self::new(config, weights);
+        engine.state = state;
+        engine
+    }
+
+    pub fn config(&self) -> &PhysicsConfig {
+        &self.config
+    }
+
+    pub fn to_document(&self) -> EngineDocument {
+        EngineDocument {
+            config: self.config.clone(),
+            weights: self.weights.clone(),
+            state: Some(self.state),
+        }
+    }
+
+    pub fn from_document(document: EngineDocument) -> Self {
+        match document.state {
+            Some(state) => Self::with_state(document.config, document.weights, state),
+            None => Self::new(document.config, document.weights),
+        }
+    }
+
+    pub fn weights(&self) -> &SensitivityWeights {
+        &self.weights
+    }
+
+    pub fn state(&self) -> &TargetState {
+        &self.state
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Advance the engine with a new pressure sample
+    ///
+    /// The first tick has Δt = 0; afterwards Δt = now - last update.
+    /// Samples older than the last update are ignored and Δt is capped at
+    /// `max_delta_t_ms`; both emit a `ClockAnomaly` event. With a reorder
+    /// window enabled the sample may be applied later (see `tick_sequenced`).
+    pub fn tick(&mut self, now_ms: f64, pressure: PressureVector) -> TargetState {
+        self.offer(SequencedSample {
+            seq: None,
+            timestamp_ms: now_ms,
+            pressure,
+            confidence: None,
+        })
+    }
+
+    /// Tick with an explicit confidence in [0, 1] scaling trauma and momentum
+    ///
+    /// Use `confidence::from_sample_count` when the pressure was derived
+    /// from a known number of requests.
+    pub fn tick_with_confidence(
+        &mut self,
+        now_ms: f64,
+        pressure: PressureVector,
+        confidence: f64,
+    ) -> TargetState {
+        self.offer(SequencedSample {
+            seq: None,
+            timestamp_ms: now_ms,
+            pressure,
+            confidence: Some(confidence),
+        })
+    }
+
+    /// Tick with a producer sequence number used for deduplication
+    pub fn tick_sequenced(
+        &mut self,
+        seq: u64,
+        now_ms: f64,
+        pressure: PressureVector,
+    ) -> TargetState {
+        self.offer(SequencedSample {
+            seq: Some(seq),
+            timestamp_ms: now_ms,
+            pressure,
+            confidence: None,
+        })
+    }
+
+    /// Append every state-changing input to `sink`, numbering from `next_seq`
+    pub fn enable_journal(&mut self, sink: Box<dyn JournalSink>, next_seq: u64) {
+        self.journal = Some(Journal::new(sink, next_seq));
+    }
+
+    pub fn journal_state(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Re-apply journal records newer than `after_seq` (all when `None`),
+    /// returning how many were applied
+    ///
+    /// Call on an engine restored from the snapshot the journal continues.
+    /// Replayed inputs are not journaled again.
+    pub fn replay(
+        &mut self,
+        records: impl IntoIterator<Item = JournalRecord>,
+        after_seq: Option<u64>,
+    ) -> usize {
+        let journal = self.journal.take();
+        let mut applied = 0;
+        for record in records {
+            if after_seq.is_some_and(|after| record.seq <= after) {
+                continue;
+            }
+            match record.entry {
+                JournalEntry::Sample { sample } => {
+                    self.advance(sample);
+                }
+                JournalEntry::Backfill { sample } => {
+                    self.backfill_sample(sample);
+                }
+                JournalEntry::Outcomes { at_ms, good, bad } => {
+                    self.record_outcomes(at_ms, good, bad)
+                }
+                JournalEntry::ForceMode { mode } => self.force_mode(mode),
+                JournalEntry::ForceModeUntil { mode, until_ms } => {
+                    self.force_mode_until(mode, until_ms)
+                }
+                JournalEntry::PauseTrauma { until_ms } => self.pause_trauma(until_ms),
+                JournalEntry::ClearOverrides => self.clear_overrides(),
+                JournalEntry::Reset => self.reset(),
+                JournalEntry::ResetScar => self.reset_scar(),
+                JournalEntry::ResetMomentum => self.reset_momentum(),
+                JournalEntry::ClearHistory => self.clear_history(),
+                JournalEntry::FastForward { now_ms } => {
+                    self.fast_forward(now_ms);
+                }
+                JournalEntry::Mark { kind, at_ms } => self.mark_event(kind, at_ms),
+                JournalEntry::RestartWarmup { at_ms } => self.restart_warmup(at_ms),
+                JournalEntry::Absorb { shared } => self.absorb_shared(&shared),
+                JournalEntry::State { state } => self.replace_state(state),
+                JournalEntry::Config { config } => self.set_config(*config),
+            }
+            applied += 1;
+        }
+        self.journal = journal;
+        applied
+    }
+
+    #[inline]
+    fn journal(&mut self, entry: impl FnOnce() -> JournalEntry) {
+        if let Some(journal) = &mut self.journal {
+            journal.append(entry());
+        }
+    }
+
+    /// Journal an input other than a sample; backfill never rewinds past it
+    fn external_input(&mut self, entry: impl FnOnce() -> JournalEntry) {
+        if let Some(backfill) = &mut self.backfill {
+            backfill.clear();
+        }
+        self.journal(entry);
+    }
+
+    /// Hold samples for a reorder window and drop duplicates
+    pub fn enable_reorder(&mut self, config: ReorderConfig) {
+        self.reorder = Some(ReorderBuffer::new(config));
+    }
+
+    /// Apply every sample still held by the reorder window
+    pub fn flush_reorder(&mut self) -> TargetState {
+        let ready = match &mut self.reorder {
+            Some(buffer) => buffer.flush(),
+            None => Vec::new(),
+        };
+        for sample in ready {
+            self.advance(sample);
+        }
+        self.state
+    }
+
+    pub fn reorder_stats(&self) -> Option<ReorderStats> {
+        self.reorder.as_ref().map(ReorderBuffer::stats)
+    }
+
+    /// Keep recent ticks so `tick_at` can slot late samples into the past
+    pub fn enable_backfill(&mut self, config: BackfillConfig) {
+        self.backfill = Some(Backfill::new(config));
+    }
+
+    pub fn disable_backfill(&mut self) {
+        self.backfill = None;
+    }
+
+    pub fn backfill(&self) -> Option<&Backfill> {
+        self.backfill.as_ref()
+    }
+
+    /// Damp breaker flapping: after `flaps` closes within `window_ms`,
+    /// widen the hysteresis band and hold the breaker open longer
+    pub fn enable_flap_detection(&mut self, config: FlapConfig) {
+        self.flaps = Some(FlapDetector::new(config));
+    }
+
+    pub fn disable_flap_detection(&mut self) {
+        self.flaps = None;
+    }
+
+    pub fn flap_detector(&self) -> Option<&FlapDetector> {
+        self.flaps.as_ref()
+    }
+
+    /// Report targets whose pressure does not drain despite sustained
+    /// shedding (`MetastableSuspected`)
+    pub fn enable_metastable_detection(&mut self, config: MetastableConfig) {
+        self.metastable = Some(MetastableDetector::new(config));
+    }
+
+    pub fn disable_metastable_detection(&mut self) {
+        self.metastable = None;
+    }
+
+    pub fn metastable_detector(&self) -> Option<&MetastableDetector> {
+        self.metastable.as_ref()
+    }
+
+    /// Flag regime shifts in the pressure stream (`ChangePointDetected`)
+    /// and optionally restart momentum at them
+    pub fn enable_change_detection(&mut self, config: ChangePointConfig) {
+        self.changes = Some(ChangePointDetector::new(config));
+    }
+
+    pub fn disable_change_detection(&mut self) {
+        self.changes = None;
+    }
+
+    pub fn change_detector(&self) -> Option<&ChangePointDetector> {
+        self.changes.as_ref()
+    }
+
+    /// Whether a metastable failure is suspected (false without detection)
+    pub fn metastable_suspected(&self) -> bool {
+        self.metastable
+            .as_ref()
+            .is_some_and(MetastableDetector::suspected)
+    }
+
+    /// Key hashed with `PhysicsConfig::recovery_jitter`'s seed; pools set
+    /// it from the target id (`jitter::key_for`)
+    pub fn set_jitter_key(&mut self, key: u64) {
+        self.jitter_key = key;
+    }
+
+    pub fn jitter_key(&self) -> u64 {
+        self.jitter_key
+    }
+
+    /// Brownout level (0 = full service; always 0 without
+    /// `PhysicsConfig::brownout`)
+    pub fn degradation_level(&self) -> u32 {
+        self.degradation
+    }
+
+    /// Share of traffic or features to keep at the current brownout level
+    pub fn degradation_admission(&self) -> f64 {
+        self.config
+            .brownout
+            .as_ref()
+            .map_or(1.0, |brownout| brownout.admission(self.degradation))
+    }
+
+    /// Baseline learned for `PhysicsConfig::seasonal` (none before the
+    /// first tick with it set)
+    pub fn seasonal_baseline(&self) -> Option<&SeasonalBaseline> {
+        self.seasonal.as_ref()
+    }
+
+    /// Positive stress above which trauma is recorded and below which an
+    /// open breaker may recover (the static `scar::CRITICAL_PRESSURE`
+    /// unless `adaptive_critical` is set)
+    pub fn critical_pressure(&self) -> f64 {
+        match (&self.config.adaptive_critical, &self.stress) {
+            (Some(config), Some(stress)) => stress.critical(config),
+            (Some(config), None) => StressDistribution::new().critical(config),
+            (None, _) => scar::CRITICAL_PRESSURE,
+        }
+    }
+
+    /// Trip count behind `PhysicsConfig::breaker_backoff`
+    pub fn breaker_backoff(&self) -> &BreakerBackoff {
+        &self.backoff
+    }
+
+    /// Raise `ProbeRequested` from `check_staleness` once this target has
+    /// gone `stale_after_ms` without a tick
+    pub fn enable_probing(&mut self, config: ProbingConfig) {
+        self.probing = Some(Probing::new(config));
+    }
+
+    pub fn disable_probing(&mut self) {
+        self.probing = None;
+    }
+
+    /// Push `ProbeRequested` if the target is stale at `now_ms` and no
+    /// request is outstanding; returns whether one was raised
+    ///
+    /// A request is outstanding until the next tick or for `retry_ms`,
+    /// whichever comes first. No-op without probing enabled.
+    pub fn check_staleness(&mut self, now_ms: f64) -> bool {
+        let last_updated_ms = self.state.last_updated_ms;
+        let Some(stale_ms) = self
+            .probing
+            .as_mut()
+            .and_then(|probing| probing.check(now_ms, last_updated_ms))
+        else {
+            return false;
+        };
+        self.events.push(EngineEvent::ProbeRequested {
+            at_ms: now_ms,
+            stale_ms,
+        });
+        true
+    }
+
+    /// Apply a sample taken at `timestamp_ms`, which may be in the past
+    ///
+    /// Within the backfill window the engine rewinds to just before
+    /// `timestamp_ms`, applies the sample and recomputes forward through
+    /// the newer ticks. Otherwise this is a plain `tick` (so samples older
+    /// than the window are dropped as a backwards clock). History is
+    /// rewritten from `timestamp_ms` on; events are not re-raised for the
+    /// replayed ticks, only a `ModeChanged` if the recomputed mode differs.
+    pub fn tick_at(&mut self, timestamp_ms: f64, pressure: PressureVector) -> TargetState {
+        self.backfill_sample(SequencedSample {
+            seq: None,
+            timestamp_ms,
+            pressure,
+            confidence: None,
+        })
+    }
+
+    fn backfill_sample(&mut self, sample: SequencedSample) -> TargetState {
+        let rewind = match &mut self.backfill {
+            Some(backfill) => backfill.rewind(sample.timestamp_ms),
+            None => None,
+        };
+        let Some((before, layers, replay)) = rewind else {
+            return self.offer(sample);
+        };
+        self.journal(|| JournalEntry::Backfill { sample });
+        let current = self.state;
+        // Replayed ticks were already journaled, observed and announced
+        let journal = self.journal.take();
+        let tuner = self.tuner.take();
+        let alarms = self.alarms.take();
+        let events = std::mem::replace(&mut self.events, EventLog::new(0));
+
+        // Replayed ticks record their transitions again, recomputed
+        self.history.drop_after(sample.timestamp_ms);
+        while self
+            .transitions
+            .back()
+            .is_some_and(|transition| transition.at_ms > sample.timestamp_ms)
+        {
+            self.transitions.pop_back();
+        }
+        self.state = before;
+        self.restore_tick_layers(layers);
+        self.advance(sample);
+        for sample in replay {
+            self.advance(sample);
+        }
+
+        self.journal = journal;
+        self.tuner = tuner;
+        self.alarms = alarms;
+        self.events = events;
+        if self.state.mode != current.mode {
+            let evidence =
+                self.transition_evidence(current.last_updated_ms, current.mode, self.state.mode);
+            self.announce_transition(
+                current.last_updated_ms,
+                current.mode,
+                self.state.mode,
+                evidence,
+            );
+        }
+        self.state
+    }
+
+    fn tick_layers(&self) -> TickLayers {
+        TickLayers {
+            ramp: self.ramp,
+            flaps: self.flaps.clone(),
+            metastable: self.metastable.clone(),
+            changes: self.changes.clone(),
+            backoff: self.backoff,
+            recovery_due_ms: self.recovery_due_ms,
+            degradation: self.degradation,
+            seasonal: self.seasonal.clone(),
+            warmup_started_ms: self.warmup_started_ms,
+            stress: self.stress.clone(),
+        }
+    }
+
+    fn restore_tick_layers(&mut self, layers: TickLayers) {
+        let TickLayers {
+            ramp,
+            flaps,
+            metastable,
+            changes,
+            backoff,
+            recovery_due_ms,
+            degradation,
+            seasonal,
+            warmup_started_ms,
+            stress,
+        } = layers;
+        self.ramp = ramp;
+        self.flaps = flaps;
+        self.metastable = metastable;
+        self.changes = changes;
+        self.backoff = backoff;
+        self.recovery_due_ms = recovery_due_ms;
+        self.degradation = degradation;
+        self.seasonal = seasonal;
+        self.warmup_started_ms = warmup_started_ms;
+        self.stress = stress;
+    }
+
+    fn offer(&mut self, sample: SequencedSample) -> TargetState {
+        let Some(buffer) = &mut self.reorder else {
+            return self.advance(sample);
+        };
+        for ready in buffer.push(sample) {
+            self.advance(ready);
+        }
+        self.state
+    }
+
+    /// Apply one sample to the physics state
+    ///
+    /// Without an explicit confidence, samples whose error axis was raised
+    /// by the burn-rate tracker take the tracker's outcome-count confidence;
+    /// everything else counts fully.
+    #[cfg_attr(not(feature = "profiling"), inline)]
+    #[cfg_attr(feature = "profiling", inline(never))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "atrion.advance", skip_all)
+    )]
+    fn advance(&mut self, sample: SequencedSample) -> TargetState {
+        let Some(pending) = self.begin_tick(sample) else {
+            return self.state;
+        };
+        let raw = pending.lane.map(|lane| lane.resistance());
+        self.finish_tick(pending, raw)
+    }
+
+    /// First half of `advance`: everything up to the resistance formula
+    ///
+    /// `None` when the sample is ignored (clock went backwards). The pool
+    /// evaluates the returned lanes in batches, then calls `finish_tick`.
+    pub(crate) fn begin_tick(&mut self, sample: SequencedSample) -> Option<PendingTick> {
+        self.journal(|| JournalEntry::Sample { sample });
+        let SequencedSample {
+            timestamp_ms: now_ms,
+            pressure: raw,
+            ..
+        } = sample;
+        let mut prev = self.state;
+        let delta_t = self.guard_delta_t(now_ms)?;
+        if prev.tick_count == 0 {
+            self.warmup_started_ms = Some(now_ms);
+        }
+        if self.backfill.is_some() {
+            let layers = self.tick_layers();
+            if let Some(backfill) = &mut self.backfill {
+                backfill.record(prev, layers, sample);
+            }
+        }
+        let raw = self.deseasonalize(now_ms, raw);
+        let (pressure, auto_confidence) = match &self.burn_rate {
+            Some(tracker) => {
+                let pressure = tracker.apply_to_pressure(now_ms, raw);
+                let confidence = if pressure.error > raw.error {
+                    tracker.confidence(now_ms)
+                } else {
+                    1.0
+                };
+                (pressure, confidence)
+            }
+            None => (raw, 1.0),
+        };
+        let confidence = sample.confidence.unwrap_or(auto_confidence);
+        if self.change_point_step(now_ms, &pressure) {
+            // Measure momentum from the new level, not across the step
+            prev.momentum = Momentum(0.0);
+            prev.pressure = pressure;
+        }
+        let trauma_pressure = self.critical_step(now_ms, &pressure);
+        let mut overrides = prev.overrides;
+        for kind in overrides.expire(now_ms) {
+            self.events.push(EngineEvent::OverrideExpired {
+                at_ms: now_ms,
+                kind,
+            });
+        }
+        let trauma_weight = if overrides.trauma_paused_at(now_ms) {
+            0.0
+        } else {
+            let deploy = match &self.config.deploy_grace {
+                Some(grace) => grace.trauma_scale(self.history.markers(), now_ms),
+                None => 1.0,
+            };
+            let warmup = match (&self.config.warmup, self.warmup_started_ms) {
+                (Some(warmup), Some(started_ms)) => warmup.trauma_scale(started_ms, now_ms),
+                _ => 1.0,
+            };
+            confidence * deploy * warmup
+        };
+        let slow_scar =
+            self.slow_scar_step(prev.slow_scar, &trauma_pressure, delta_t, trauma_weight);
+        let tick_count = prev.tick_count + 1;
+
+        let (mut next, lane, transition) = if prev.mode == OperationalMode::Bootstrap {
+            if tick_count < self.config.bootstrap_ticks {
+                // Collect data, don't compute full physics
+                let next = TargetState {
+                    pressure,
+                    previous_pressure: prev.pressure,
+                    tick_count,
+                    last_updated_ms: now_ms,
+                    ..prev
+                };
+                (next, None, false)
+            } else {
+                // Transition to operational (first tick has no momentum)
+                let (momentum, scar, resistance, lane) = match self.config.control_mode {
+                    ControlMode::OpenLoop => {
+                        let momentum = Momentum(0.0);
+                        let scar =
+                            self.scar_step(prev.scar, &trauma_pressure, delta_t, trauma_weight);
+                        let lane = self.lane(&pressure, momentum, scar, slow_scar);
+                        (momentum, scar, prev.resistance, Some(lane))
+                    }
+                    ControlMode::Pid => {
+                        let (momentum, scar, resistance) =
+                            self.pid_control(now_ms, &prev, &pressure, 0.0);
+                        (momentum, scar, resistance, None)
+                    }
+                };
+                let next = TargetState {
+                    mode: OperationalMode::Operational,
+                    pressure,
+                    previous_pressure: prev.pressure,
+                    momentum,
+                    scar,
+                    slow_scar,
+                    resistance,
+                    tick_count,
+                    last_updated_ms: now_ms,
+                    overrides,
+                };
+                (next, lane, false)
+            }
+        } else {
+            let (momentum, scar, resistance, lane) = match self.config.control_mode {
+                ControlMode::OpenLoop => {
+                    let momentum = self.momentum_step(&prev, &pressure, delta_t, confidence);
+                    let scar = self.scar_step(prev.scar, &trauma_pressure, delta_t, trauma_weight);
+                    let lane = self.lane(&pressure, momentum, scar, slow_scar);
+                    (momentum, scar, prev.resistance, Some(lane))
+                }
+                ControlMode::Pid => {
+                    let (momentum, scar, resistance) =
+                        self.pid_control(now_ms, &prev, &pressure, delta_t);
+                    (momentum, scar, resistance, None)
+                }
+            };
+            let next = TargetState {
+                mode: prev.mode,
+                pressure,
+                previous_pressure: prev.pressure,
+                momentum,
+                scar,
+                slow_scar,
+                resistance,
+                tick_count,
+                last_updated_ms: now_ms,
+                overrides,
+            };
+            (next, lane, true)
+        };
+        next.overrides = overrides;
+        Some(PendingTick {
+            now_ms,
+            prev,
+            next,
+            lane,
+            transition,
+        })
+    }
+
+    /// Second half of `advance`, given the raw resistance of the pending
+    /// lane (`None` when it had none): bounds, mode, overrides, events
+    pub(crate) fn finish_tick(&mut self, pending: PendingTick, raw: Option<Ohms>) -> TargetState {
+        let PendingTick {
+            now_ms,
+            prev,
+            mut next,
+            transition,
+            ..
+        } = pending;
+        if let Some(raw) = raw {
+            next.resistance = self.bound(Ohms(raw.0 + self.surcharge(now_ms)));
+        }
+        if transition {
+            next.mode = self.next_mode(
+                now_ms,
+                prev.mode,
+                &next.pressure,
+                next.scar,
+                next.resistance,
+            );
+            next.mode = self.jitter_recovery(now_ms, prev.mode, next.mode);
+        }
+        let overrides = next.overrides;
+        if next.mode != OperationalMode::Bootstrap {
+            if let Some(mode) = overrides.mode_at(now_ms) {
+                next.mode = mode;
+            }
+        }
+        let ramp_event = self.soft_start_step(now_ms, &prev, &mut next);
+
+        self.state = next;
+        self.history.push(HistorySample {
+            timestamp_ms: now_ms,
+            pressure: next.pressure,
+            resistance: next.resistance,
+            scar: next.scar,
+            momentum: next.momentum,
+            mode: next.mode,
+        });
+        if next.mode != prev.mode {
+            self.record_transition(now_ms, prev.mode, next.mode);
+        }
+        if let Some(event) = ramp_event {
+            self.events.push(event);
+        }
+        self.observe_transition(now_ms, prev.mode, next.mode);
+        self.brownout_step(now_ms, next.resistance);
+        self.metastable_step(now_ms);
+        if let Some(alarms) = &mut self.alarms {
+            for event in alarms.observe(now_ms, &prev, &next) {
+                self.events.push(event);
+            }
+        }
+        if next.mode != OperationalMode::Bootstrap {
+            self.observe_tuning(now_ms);
+        }
+        next
+    }
+
+    /// Start online tuning of scar_factor / damping_factor
+    ///
+    /// The tuner owns both parameters from here on: values set later via
+    /// `set_config` are overwritten at the next epoch boundary.
+    pub fn enable_tuning(&mut self, config: TuningConfig) {
+        let tuner = OnlineTuner::new(config, &self.config);
+        tuner.apply(&mut self.config);
+        self.tuner = Some(tuner);
+    }
+
+    pub fn tuner(&self) -> Option<&OnlineTuner> {
+        self.tuner.as_ref()
+    }
+
+    /// Buffered events, oldest first
+    pub fn events(&self) -> &EventLog {
+        &self.events
+    }
+
+    /// Take all buffered events
+    pub fn drain_events(&mut self) -> Vec<EngineEvent> {
+        self.events.drain()
+    }
+
+    /// Recent mode transitions with their evidence, oldest first
+    pub fn transitions(&self) -> impl Iterator<Item = &ModeTransition> {
+        self.transitions.iter()
+    }
+
+    /// Borrowed snapshot for a debug panel (see `debug`)
+    pub fn debug_frame(&self) -> DebugFrame<'_> {
+        DebugFrame {
+            state: &self.state,
+            breakdown: self.explain(),
+            critical_pressure: self.critical_pressure(),
+            degradation: self.degradation,
+            history: self.history.tail(DEBUG_HISTORY_TAIL),
+            events: self.events.tail(DEBUG_EVENT_TAIL),
+            dropped_events: self.events.dropped(),
+        }
+    }
+
+    /// Scar/momentum to publish to a shared store
+    pub fn shared_state(&self) -> SharedState {
+        SharedState {
+            scar: self.state.scar.0,
+            momentum: self.state.momentum.0,
+            updated_ms: self.state.last_updated_ms,
+        }
+    }
+
+    /// Absorb fleet state merged from a store
+    ///
+    /// Scar only ever grows here (max-merge), and resistance moves with
+    /// it so gates react before the next tick. Momentum is taken when the
+    /// shared value is newer than the local state.
+    pub fn absorb_shared(&mut self, shared: &SharedState) {
+        self.external_input(|| JournalEntry::Absorb { shared: *shared });
+        let scar = shared.scar_at(self.state.last_updated_ms);
+        if scar > self.state.scar.0 {
+            self.state.resistance =
+                self.bound(Ohms(self.state.resistance.0 + scar - self.state.scar.0));
+            self.state.scar = Scar(scar);
+        }
+        if shared.updated_ms > self.state.last_updated_ms {
+            self.state.momentum = Momentum(shared.momentum);
+        }
+    }
+
+    /// Replace the physics state, keeping config, history and every
+    /// optional layer (journal, probing, backfill, detectors, ...)
+    pub fn replace_state(&mut self, state: TargetState) {
+        self.external_input(|| JournalEntry::State { state });
+        self.state = state;
+    }
+
+    /// Forget accumulated trauma (fast and slow scar)
+    ///
+    /// For incidents known not to be the target's fault. Resistance drops
+    /// by the scar's share right away; the mode settles on the next tick.
+    pub fn reset_scar(&mut self) {
+        self.external_input(|| JournalEntry::ResetScar);
+        let share = self.effective_scar(self.state.scar, self.state.slow_scar).0;
+        let resistance = (self.state.resistance.0 - share).max(self.config.base_resistance);
+        self.state.resistance = self.bound(Ohms(resistance));
+        self.state.scar = Scar(0.0);
+        self.state.slow_scar = Scar(0.0);
+        self.audit_reset(ResetKind::Scar);
+    }
+
+    /// Back to a fresh bootstrap state, history and overrides included
+    ///
+    /// Config, weights and optional layers are kept.
+    pub fn reset(&mut self) {
+        self.external_input(|| JournalEntry::Reset);
+        let at_ms = self.state.last_updated_ms;
+        self.state = TargetState::bootstrap(&self.config);
+        self.history.clear();
+        self.ramp = None;
+        self.backoff = BreakerBackoff::default();
+        self.recovery_due_ms = None;
+        self.degradation = 0;
+        self.warmup_started_ms = None;
+        self.stress = None;
+        if let Some(flaps) = &mut self.flaps {
+            *flaps = FlapDetector::new(*flaps.config());
+        }
+        if let Some(metastable) = &mut self.metastable {
+            *metastable = MetastableDetector::new(*metastable.config());
+        }
+        if let Some(changes) = &mut self.changes {
+            *changes = ChangePointDetector::new(*changes.config());
+        }
+        self.events.push(EngineEvent::StateReset {
+            at_ms,
+            kind: ResetKind::Full,
+        });
+    }
+
+    /// Zero momentum and drop its share of resistance
+    pub fn reset_momentum(&mut self) {
+        self.external_input(|| JournalEntry::ResetMomentum);
+        let state = self.state;
+        if state.mode != OperationalMode::Bootstrap {
+            self.state.resistance = match self.config.control_mode {
+                ControlMode::OpenLoop => self.resistance(
+                    state.last_updated_ms,
+                    &state.pressure,
+                    Momentum(0.0),
+                    state.scar,
+                    state.slow_scar,
+                ),
+                // Momentum holds the D-term
+                ControlMode::Pid => self.bound(Ohms(
+                    (state.resistance.0 - state.momentum.0).max(self.config.base_resistance),
+                )),
+            };
+        }
+        self.state.momentum = Momentum(0.0);
+        self.audit_reset(ResetKind::Momentum);
+    }
+
+    /// Record an operational event (deploy, config change, failover)
+    /// next to the history; see `markers`
+    pub fn mark_event(&mut self, kind: MarkerKind, timestamp_ms: f64) {
+        self.external_input(|| JournalEntry::Mark {
+            kind,
+            at_ms: timestamp_ms,
+        });
+        self.history.mark(Marker { timestamp_ms, kind });
+    }
+
+    /// A new instance took over this target: restart the
+    /// `PhysicsConfig::warmup` window at `now_ms`, keeping the state
+    pub fn restart_warmup(&mut self, now_ms: f64) {
+        self.external_input(|| JournalEntry::RestartWarmup { at_ms: now_ms });
+        self.warmup_started_ms = Some(now_ms);
+    }
+
+    /// Drop recorded history samples (physics state is untouched)
+    pub fn clear_history(&mut self) {
+        self.external_input(|| JournalEntry::ClearHistory);
+        self.history.clear();
+        self.audit_reset(ResetKind::History);
+    }
+
+    fn audit_reset(&mut self, kind: ResetKind) {
+        self.events.push(EngineEvent::StateReset {
+            at_ms: self.state.last_updated_ms,
+            kind,
+        });
+    }
+
+    /// Decay scar and momentum through the time since the last update
+    ///
+    /// For state restored from an old snapshot: the elapsed time counts as
+    /// a quiet period (no trauma, no acceleration), resistance is recomputed
+    /// and circuit-breaker mode is left if the recovery checks pass.
+    /// Bootstrap and PID engines are left alone. Returns whether the mode
+    /// changed.
+    pub fn fast_forward(&mut self, now_ms: f64) -> bool {
+        let prev = self.state;
+        let elapsed = now_ms - prev.last_updated_ms;
+        if prev.mode == OperationalMode::Bootstrap
+            || self.config.control_mode == ControlMode::Pid
+            || elapsed.is_nan()
+            || elapsed <= 0.0
+        {
+            return false;
+        }
+        self.external_input(|| JournalEntry::FastForward { now_ms });
+        let quiet = PressureVector::new(0.0, 0.0, 0.0);
+        let scar = scar::update_scar_weighted(prev.scar, &quiet, elapsed, &self.config, 0.0);
+        let slow_scar = match &self.config.dual_scar {
+            Some(dual) => scar::update_slow_scar(
+                prev.slow_scar,
+                &quiet,
+                elapsed,
+                dual,
+                self.config.critical_comparison,
+                0.0,
+            ),
+            None => prev.slow_scar,
+        };
+        let momentum = momentum::update_momentum_weighted(
+            prev.momentum,
+            &prev.pressure,
+            &prev.pressure,
+            elapsed,
+            &self.config,
+            0.0,
+        );
+        let resistance = self.resistance(now_ms, &prev.pressure, momentum, scar, slow_scar);
+        let mode = self.next_mode(now_ms, prev.mode, &prev.pressure, scar, resistance);
+        let mut mode = self.jitter_recovery(now_ms, prev.mode, mode);
+        if let Some(pinned) = prev.overrides.mode_at(now_ms) {
+            mode = pinned;
+        }
+        self.state = TargetState {
+            mode,
+            momentum,
+            scar,
+            slow_scar,
+            resistance,
+            last_updated_ms: now_ms,
+            ..prev
+        };
+        if mode != prev.mode {
+            self.record_transition(now_ms, prev.mode, mode);
+        }
+        self.observe_transition(now_ms, prev.mode, mode);
+        self.brownout_step(now_ms, resistance);
+        self.metastable_step(now_ms);
+        mode != prev.mode
+    }
+
+    /// Override the mode on behalf of an external authority (gossip, leader)
+    ///
+    /// Bootstrap is left alone: a target without enough data has no
+    /// resistance worth defending yet.
+    ///
+    /// An active operator override (`force_mode_until`) takes precedence.
+    pub fn force_mode(&mut self, mode: OperationalMode) {
+        self.external_input(|| JournalEntry::ForceMode { mode });
+        let pinned = self
+            .state
+            .overrides
+            .mode_at(self.state.last_updated_ms)
+            .is_some();
+        if !pinned
+            && self.state.mode != OperationalMode::Bootstrap
+            && mode != OperationalMode::Bootstrap
+        {
+            self.state.mode = mode;
+        }
+    }
+
+    /// Operator override: hold `mode` until `until_ms`
+    ///
+    /// Takes effect immediately unless the target is still bootstrapping,
+    /// in which case it applies from the first operational tick.
+    pub fn force_mode_until(&mut self, mode: OperationalMode, until_ms: f64) {
+        self.external_input(|| JournalEntry::ForceModeUntil { mode, until_ms });
+        self.state.overrides.mode = Some(ModeOverride { mode, until_ms });
+        if self.state.mode != OperationalMode::Bootstrap && mode != OperationalMode::Bootstrap {
+            self.state.mode = mode;
+        }
+    }
+
+    /// Operator override: record no trauma until `until_ms`
+    pub fn pause_trauma(&mut self, until_ms: f64) {
+        self.external_input(|| JournalEntry::PauseTrauma { until_ms });
+        self.state.overrides.trauma_paused_until_ms = Some(until_ms);
+    }
+
+    /// Lift all operator overrides (the next tick recomputes the mode)
+    pub fn clear_overrides(&mut self) {
+        self.external_input(|| JournalEntry::ClearOverrides);
+        self.state.overrides = Overrides::default();
+    }
+
+    pub fn overrides(&self) -> &Overrides {
+        &self.state.overrides
+    }
+
+    /// Replace the live config (takes effect on the next tick)
+    ///
+    /// History is resized right away; shrinking it drops the oldest samples.
+    pub fn set_config(&mut self, config: PhysicsConfig) {
+        self.external_input(|| JournalEntry::Config {
+            config: Box::new(config.clone()),
+        });
+        self.config = config;
+        self.history
+            .set_capacity(self.config.history_capacity as usize);
+        if let Some(fixed) = &mut self.fixed_tick {
+            fixed.refresh(&self.config);
+        }
+    }
+
+    /// Tick on a fixed interval: decay factors for `interval_ms` are computed
+    /// once (and again on `set_config`) instead of on every tick
+    ///
+    /// Ticks arriving at any other interval still work, uncached.
+    pub fn enable_fixed_tick(&mut self, interval_ms: f64) {
+        self.fixed_tick = Some(FixedTick::new(interval_ms, &self.config));
+    }
+
+    pub fn disable_fixed_tick(&mut self) {
+        self.fixed_tick = None;
+    }
+
+    pub fn fixed_tick(&self) -> Option<&FixedTick> {
+        self.fixed_tick.as_ref()
+    }
+
+    /// Per-term breakdown of the current resistance
+    pub fn explain(&self) -> ResistanceBreakdown {
+        let mut breakdown = self.unbounded_breakdown();
+        breakdown.total = self.bound(Ohms(breakdown.total)).0;
+        breakdown
+    }
+
+    /// `explain` with the markers recorded in the `window_ms` up to the
+    /// last tick
+    pub fn explain_annotated(&self, window_ms: f64) -> AnnotatedExplanation {
+        let now_ms = self.state.last_updated_ms;
+        AnnotatedExplanation {
+            breakdown: self.explain(),
+            markers: self.history.markers_between(now_ms - window_ms, now_ms),
+        }
+    }
+
+    fn unbounded_breakdown(&self) -> ResistanceBreakdown {
+        if self.config.control_mode == ControlMode::Pid {
+            return ResistanceBreakdown::from_pid(
+                &self.pid_terms(),
+                &self.config,
+                self.surcharge(self.state.last_updated_ms),
+            );
+        }
+        let mut breakdown = ResistanceBreakdown::compute(
+            &self.state.pressure,
+            self.state.momentum,
+            self.effective_scar(self.state.scar, self.state.slow_scar),
+            &self.weights,
+            &self.config,
+            0.0,
+            self.surcharge(self.state.last_updated_ms),
+        );
+        if let Some(extra) = self
+            .ramp
+            .and_then(|ramp| ramp.surcharge(self.state.last_updated_ms))
+        {
+            breakdown.soft_start = extra;
+            breakdown.total += extra;
+        }
+        if let Some(dual) = &self.config.dual_scar {
+            let fast = dual.fast_weight * self.state.scar.0;
+            let slow = dual.slow_weight * self.state.slow_scar.0;
+            // A scar cap clips the combined term; split what is left
+            let share = match breakdown.caps.scar {
+                Some(_) if fast + slow > 0.0 => breakdown.scar / (fast + slow),
+                _ => 1.0,
+            };
+            breakdown.scar = fast * share;
+            breakdown.slow_scar = slow * share;
+        }
+        breakdown
+    }
+
+    /// Samples recorded in `[start_ms, end_ms]`
+    pub fn history_between(&self, start_ms: f64, end_ms: f64) -> Vec<HistorySample> {
+        self.history.between(start_ms, end_ms).copied().collect()
+    }
+
+    /// Peak resistance recorded at or after `since_ms`
+    pub fn max_resistance_since(&self, since_ms: f64) -> Option<Ohms> {
+        self.history.max_resistance_since(since_ms)
+    }
+
+    /// History downsampled into `bucket_ms` buckets (peak per bucket)
+    pub fn export_history(&self, bucket_ms: f64) -> Vec<HistorySample> {
+        self.history.downsample(bucket_ms)
+    }
+
+    /// `export_history` with the markers recorded in the `window_ms`
+    /// leading up to each sample
+    pub fn export_history_annotated(&self, bucket_ms: f64, window_ms: f64) -> Vec<AnnotatedSample> {
+        self.history
+            .downsample(bucket_ms)
+            .into_iter()
+            .map(|sample| AnnotatedSample {
+                markers: self
+                    .history
+                    .markers_between(sample.timestamp_ms - window_ms, sample.timestamp_ms),
+                sample,
+            })
+            .collect()
+    }
+
+    /// Resistance and goodput statistics over `window_ms` ending at the
+    /// last tick
+    pub fn rolling_stats(&self, window_ms: f64) -> RollingStats {
+        let now_ms = self.state.last_updated_ms;
+        RollingStats {
+            goodput: self.goodput.stats(now_ms, window_ms),
+            ..stats::rolling_stats(&self.history, now_ms, window_ms)
+        }
+    }
+
+    /// 1m / 5m / 15m statistics ending at the last tick
+    pub fn rolling_summary(&self) -> RollingSummary {
+        let now_ms = self.state.last_updated_ms;
+        let mut summary = stats::rolling_summary(&self.history, now_ms);
+        for window in [
+            &mut summary.one_minute,
+            &mut summary.five_minutes,
+            &mut summary.fifteen_minutes,
+        ] {
+            window.goodput = self.goodput.stats(now_ms, window.window_ms);
+        }
+        summary
+    }
+
+    /// Per-axis share of `window_ms` (ending at the last tick) spent above
+    /// `thresholds`
+    pub fn compliance(
+        &self,
+        window_ms: f64,
+        thresholds: &ComplianceThresholds,
+    ) -> ComplianceReport {
+        compliance::compliance(
+            &self.history,
+            self.state.last_updated_ms,
+            window_ms,
+            thresholds,
+        )
+    }
+
+    /// Voltage gate against the current state (TS decideFlow)
+    pub fn try_admit(&self, voltage: f64) -> AdmissionDecision {
+        self.enforce(self.would_admit(voltage))
+    }
+
+    /// Voltage decision `try_admit` would enforce outside observe-only mode
+    pub fn would_admit(&self, voltage: f64) -> AdmissionDecision {
+        admission::decide_flow(voltage, &self.state)
+    }
+
+    /// Observe-only engines report decisions but always admit
+    #[inline]
+    fn enforce(&self, decision: AdmissionDecision) -> AdmissionDecision {
+        if self.config.observe_only {
+            AdmissionDecision::Admit
+        } else {
+            decision
+        }
+    }
+
+    /// Fraction of offered load the target can currently absorb
+    pub fn admission_ratio(&self) -> f64 {
+        if self.state.mode == OperationalMode::CircuitBreaker {
+            return 0.0;
+        }
+        admission::admission_ratio(self.state.resistance, &self.config)
+    }
+
+    /// How long a caller shed at `shed_threshold` should wait before retrying
+    ///
+    /// Observe-only engines never shed, so never advise.
+    pub fn retry_advice(&self, shed_threshold: f64) -> Option<RetryAdvice> {
+        if self.config.observe_only {
+            return None;
+        }
+        retry::retry_advice(&self.state, &self.config, shed_threshold)
+    }
+
+    /// Whether to send a hedged duplicate request, and after what delay
+    pub fn hedge_advice(&self, policy: &HedgePolicy) -> HedgeAdvice {
+        hedge::hedge_advice(&self.state, &self.config, policy)
+    }
+
+    /// Set the queue model used by `try_admit_deadline`
+    pub fn set_queue_model(&mut self, model: QueueModel) {
+        self.queue_model = model;
+    }
+
+    pub fn queue_model(&self) -> &QueueModel {
+        &self.queue_model
+    }
+
+    /// Queueing delay a request admitted now is predicted to see
+    pub fn predicted_wait_ms(&self) -> f64 {
+        self.queue_model.predicted_wait_ms(&self.state)
+    }
+
+    /// Voltage gate, then reject work that cannot finish within
+    /// `remaining_ms` (the request's remaining deadline)
+    pub fn try_admit_deadline(&self, voltage: f64, remaining_ms: f64) -> AdmissionDecision {
+        let decision = match self.would_admit(voltage) {
+            AdmissionDecision::Admit => self.queue_model.decide(&self.state, remaining_ms),
+            rejected => rejected,
+        };
+        self.enforce(decision)
+    }
+
+    /// Typed rejection with a forecast retry-after for a denied `reason`
+    ///
+    /// `remaining_ms` is the request deadline, if it had one. An open
+    /// breaker held by `PhysicsConfig::breaker_backoff` advises at least
+    /// the remaining minimum open time.
+    pub fn rejection(
+        &self,
+        reason: RejectReason,
+        voltage: f64,
+        remaining_ms: Option<f64>,
+    ) -> Rejection {
+        let deadline = remaining_ms.map(|remaining| {
+            let finish_ms = self.predicted_wait_ms() + self.queue_model.service_ms.max(0.0);
+            (finish_ms, remaining)
+        });
+        let mut rejection = retry::rejection(
+            reason,
+            &self.state,
+            &self.config,
+            voltage,
+            1.0 - self.admission_ratio(),
+            deadline,
+        );
+        let open_until = match (&self.config.breaker_backoff, reason) {
+            (Some(config), RejectReason::CircuitOpen) => self.backoff.open_until(config),
+            _ => None,
+        };
+        if let Some(until) = open_until {
+            let held_ms = (until - self.state.last_updated_ms).min(retry::MAX_RETRY_MS);
+            rejection.retry_after_ms = rejection.retry_after_ms.max(held_ms);
+        }
+        rejection
+    }
+
+    /// Voltage gate (deadline-aware when `remaining_ms` is given) with a
+    /// typed rejection for HTTP layers
+    pub fn check_admission(
+        &self,
+        voltage: f64,
+        remaining_ms: Option<f64>,
+    ) -> Result<(), Rejection> {
+        let decision = match remaining_ms {
+            Some(remaining) => self.try_admit_deadline(voltage, remaining),
+            None => self.try_admit(voltage),
+        };
+        match decision {
+            AdmissionDecision::Admit => Ok(()),
+            AdmissionDecision::Reject(reason) => Err(self.rejection(reason, voltage, remaining_ms)),
+        }
+    }
+
+    /// Set the priority cutoff curve used by `try_admit_priority`
+    pub fn set_shed_curve(&mut self, curve: ShedCurve) {
+        self.shed_curve = curve;
+    }
+
+    /// Lowest priority currently admitted (`None` = everything shed)
+    pub fn min_admitted_priority(&self) -> Option<u8> {
+        if self.state.mode == OperationalMode::CircuitBreaker {
+            return None;
+        }
+        self.shed_curve
+            .min_admitted_priority(priority::band_position(self.admission_ratio()))
+    }
+
+    /// Admit a request of the given priority (0..=7, 7 = most important)
+    pub fn try_admit_priority(&self, priority: u8) -> AdmissionDecision {
+        let decision = if self.state.mode == OperationalMode::CircuitBreaker {
+            AdmissionDecision::Reject(RejectReason::CircuitOpen)
+        } else {
+            match self.min_admitted_priority() {
+                Some(min) if priority >= min => AdmissionDecision::Admit,
+                _ => AdmissionDecision::Reject(RejectReason::PriorityShed),
+            }
+        };
+        self.enforce(decision)
+    }
+
+    /// Enable cost-aware admission with a windowed budget
+    pub fn enable_cost_budget(&mut self, config: CostBudgetConfig) {
+        self.cost_budget = Some(CostBudget::new(config));
+    }
+
+    /// Admit a request consuming `cost` units of the resistance-scaled budget
+    ///
+    /// Without a cost budget this degrades to a breaker-only check.
+    pub fn try_admit_cost(&mut self, now_ms: f64, cost: f64) -> AdmissionDecision {
+        if self.state.mode == OperationalMode::CircuitBreaker {
+            return self.enforce(AdmissionDecision::Reject(RejectReason::CircuitOpen));
+        }
+        let ratio = self.admission_ratio();
+        let fits = match &mut self.cost_budget {
+            Some(budget) => budget.try_consume(now_ms, cost, ratio),
+            None => true,
+        };
+        self.enforce(if fits {
+            AdmissionDecision::Admit
+        } else {
+            AdmissionDecision::Reject(RejectReason::BudgetExhausted)
+        })
+    }
+
+    /// Admit `request` using a caller-supplied cost estimator
+    pub fn try_admit_with<R: ?Sized, E: CostEstimator<R>>(
+        &mut self,
+        now_ms: f64,
+        request: &R,
+        estimator: &E,
+    ) -> AdmissionDecision {
+        self.try_admit_cost(now_ms, estimator.estimate(request))
+    }
+
+    /// Cost budget left in the current window, if enabled
+    pub fn cost_budget_remaining(&self) -> Option<f64> {
+        let ratio = self.admission_ratio();
+        self.cost_budget.as_ref().map(|b| b.remaining(ratio))
+    }
+
+    /// Enable weighted max-min tenant fairness
+    pub fn enable_fairness(&mut self, config: FairnessConfig) {
+        self.fairness = Some(TenantFairness::new(config));
+    }
+
+    /// Admit a request on behalf of `tenant`
+    ///
+    /// Without a fairness layer this degrades to a breaker-only check.
+    pub fn try_admit_tenant(&mut self, now_ms: f64, tenant: &str) -> AdmissionDecision {
+        if self.state.mode == OperationalMode::CircuitBreaker {
+            return self.enforce(AdmissionDecision::Reject(RejectReason::CircuitOpen));
+        }
+        let ratio = self.admission_ratio();
+        let within_share = match &mut self.fairness {
+            Some(fairness) => fairness.admit(now_ms, tenant, ratio),
+            None => true,
+        };
+        self.enforce(if within_share {
+            AdmissionDecision::Admit
+        } else {
+            AdmissionDecision::Reject(RejectReason::FairShareExceeded)
+        })
+    }
+
+    /// Per-tenant accounting for the current fairness window
+    pub fn tenant_accounts(&self) -> Option<&HashMap<String, TenantAccount>> {
+        self.fairness.as_ref().map(|f| f.accounts())
+    }
+
+    /// Raise `AlarmRaised` / `AlarmCleared` events on physics thresholds
+    pub fn enable_alarms(&mut self, config: AlarmConfig) {
+        self.alarms = Some(Alarms::new(config));
+    }
+
+    pub fn alarms(&self) -> Option<&Alarms> {
+        self.alarms.as_ref()
+    }
+
+    /// Start tracking SLO burn rate from recorded outcomes
+    pub fn enable_burn_rate(&mut self, config: BurnRateConfig) {
+        self.burn_rate = Some(BurnRateTracker::new(config));
+    }
+
+    /// Ingest outcomes of admitted requests (`good`: succeeded, `bad`:
+    /// failed) for goodput, burn-rate tracking and goodput tuning
+    pub fn record_outcomes(&mut self, now_ms: f64, good: u64, bad: u64) {
+        self.journal(|| JournalEntry::Outcomes {
+            at_ms: now_ms,
+            good,
+            bad,
+        });
+        self.goodput.record(now_ms, good, bad);
+        if let Some(tracker) = &mut self.burn_rate {
+            tracker.record(now_ms, good, bad);
+        }
+        if let Some(tuner) = &mut self.tuner {
+            tuner.record_outcomes(good, bad);
+        }
+    }
+
+    /// Current short/long burn rates, if tracking is enabled
+    pub fn burn_rates(&self) -> Option<BurnRates> {
+        self.burn_rate
+            .as_ref()
+            .map(|tracker| tracker.burn_rates(self.state.last_updated_ms))
+    }
+
+    #[cfg_attr(not(feature = "profiling"), inline)]
+    #[cfg_attr(feature = "profiling", inline(never))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "atrion.resistance", skip_all)
+    )]
+    fn resistance(
+        &self,
+        now_ms: f64,
+        pressure: &PressureVector,
+        momentum: Momentum,
+        scar: Scar,
+        slow_scar: Scar,
+    ) -> Ohms {
+        let r = resistance::calculate_resistance(
+            pressure,
+            momentum,
+            self.effective_scar(scar, slow_scar),
+            &self.weights,
+            &self.config,
+            0.0,
+        );
+        self.bound(Ohms(r.0 + self.surcharge(now_ms)))
+    }
+
+    /// Cached decay factors for this Δt (fixed-tick mode only)
+    #[inline]
+    fn cached_decay(&self, delta_t: f64) -> Option<&DecayFactors> {
+        self.fixed_tick.as_ref()?.factors_for(delta_t)
+    }
+
+    fn scar_step(
+        &self,
+        current: Scar,
+        pressure: &PressureVector,
+        delta_t: f64,
+        weight: f64,
+    ) -> Scar {
+        match self.cached_decay(delta_t).and_then(|d| d.scar) {
+            Some(decay) => {
+                scar::update_scar_decayed(current, pressure, decay, &self.config, weight)
+            }
+            None => scar::update_scar_weighted(current, pressure, delta_t, &self.config, weight),
+        }
+    }
+
+    fn slow_scar_step(
+        &self,
+        current: Scar,
+        pressure: &PressureVector,
+        delta_t: f64,
+        weight: f64,
+    ) -> Scar {
+        let Some(dual) = &self.config.dual_scar else {
+            return Scar(0.0);
+        };
+        let comparison = self.config.critical_comparison;
+        match self.cached_decay(delta_t).and_then(|d| d.slow_scar) {
+            Some(decay) => {
+                scar::update_slow_scar_decayed(current, pressure, decay, dual, comparison, weight)
+            }
+            None => scar::update_slow_scar(current, pressure, delta_t, dual, comparison, weight),
+        }
+    }
+
+    fn momentum_step(
+        &self,
+        prev: &TargetState,
+        pressure: &PressureVector,
+        delta_t: f64,
+        confidence: f64,
+    ) -> Momentum {
+        match self.cached_decay(delta_t) {
+            Some(decay) => momentum::update_momentum_decayed(
+                prev.momentum,
+                &prev.pressure,
+                pressure,
+                delta_t,
+                decay.momentum,
+                confidence,
+            ),
+            None => momentum::update_momentum_weighted(
+                prev.momentum,
+                &prev.pressure,
+                pressure,
+                delta_t,
+                &self.config,
+                confidence,
+            ),
+        }
+    }
+
+    /// Inputs of `resistance` before bounds and surcharge, for batching
+    fn lane(
+        &self,
+        pressure: &PressureVector,
+        momentum: Momentum,
+        scar: Scar,
+        slow_scar: Scar,
+    ) -> ResistanceLane {
+        ResistanceLane::new(
+            pressure,
+            momentum,
+            self.effective_scar(scar, slow_scar),
+            &self.weights,
+            &self.config,
+            0.0,
+        )
+    }
+
+    /// Whether samples go straight to the physics (no reorder window), so
+    /// a pool may split the tick with `begin_tick` / `finish_tick`
+    pub(crate) fn applies_directly(&self) -> bool {
+        self.reorder.is_none()
+    }
+
+    /// Apply `resistance_ceiling` (the floor is `base_resistance`)
+    #[inline]
+    fn bound(&self, resistance: Ohms) -> Ohms {
+        match self.config.resistance_ceiling {
+            Some(ceiling) => Ohms(resistance.0.min(ceiling)),
+            None => resistance,
+        }
+    }
+
+    /// Scar as seen by the resistance formula (TS scar unless dual-timescale)
+    #[inline]
+    fn effective_scar(&self, scar: Scar, slow_scar: Scar) -> Scar {
+        match &self.config.dual_scar {
+            Some(dual) => Scar(dual.effective(scar, slow_scar)),
+            None => scar,
+        }
+    }
+
+    /// PID step: I-term → scar, D-term → momentum, R = base + P + I + D
+    #[cfg_attr(not(feature = "profiling"), inline)]
+    #[cfg_attr(feature = "profiling", inline(never))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "atrion.pid", skip_all)
+    )]
+    fn pid_control(
+        &self,
+        now_ms: f64,
+        prev: &TargetState,
+        pressure: &PressureVector,
+        delta_t: f64,
+    ) -> (Momentum, Scar, Ohms) {
+        let terms = pid::step(
+            &self.config.pid,
+            pressure.latency,
+            prev.pressure.latency,
+            prev.scar.0,
+            delta_t,
+        );
+        let raw = (self.config.base_resistance + terms.total()).max(self.config.base_resistance);
+        (
+            Momentum(terms.derivative),
+            Scar(terms.integral),
+            self.bound(Ohms(raw + self.surcharge(now_ms))),
+        )
+    }
+
+    /// PID terms of the current state
+    fn pid_terms(&self) -> PidTerms {
+        PidTerms {
+            proportional: self.config.pid.kp
+                * (self.state.pressure.latency - self.config.pid.setpoint),
+            integral: self.state.scar.0,
+            derivative: self.state.momentum.0,
+        }
+    }
+
+    /// Δt to apply for a sample at `now_ms`, or `None` to ignore it
+    fn guard_delta_t(&mut self, now_ms: f64) -> Option<f64> {
+        if self.state.tick_count == 0 {
+            return Some(0.0);
+        }
+        let delta_t = now_ms - self.state.last_updated_ms;
+        let max = self.config.max_delta_t_ms;
+        let (kind, applied) = if delta_t < 0.0 {
+            (ClockAnomalyKind::Backwards, 0.0)
+        } else if max > 0.0 && delta_t > max {
+            (ClockAnomalyKind::Jump, max)
+        } else {
+            return Some(delta_t);
+        };
+        self.events.push(EngineEvent::ClockAnomaly {
+            at_ms: now_ms,
+            kind,
+            delta_t_ms: delta_t,
+            applied_ms: applied,
+        });
+        match kind {
+            ClockAnomalyKind::Backwards => None,
+            ClockAnomalyKind::Jump => Some(applied),
+        }
+    }
+
+    fn observe_tuning(&mut self, now_ms: f64) {
+        let ratio = self.admission_ratio();
+        let Some(tuner) = &mut self.tuner else {
+            return;
+        };
+        let latency = self.state.pressure.latency;
+        if let Some(adjustment) = tuner.observe(now_ms, latency, ratio, &mut self.config) {
+            self.events.push(EngineEvent::ConfigAdjusted {
+                at_ms: now_ms,
+                parameter: adjustment.parameter.as_str().to_string(),
+                from: adjustment.from,
+                to: adjustment.to,
+                loss: adjustment.loss,
+            });
+        }
+    }
+
+    #[inline]
+    fn surcharge(&self, now_ms: f64) -> f64 {
+        match &self.burn_rate {
+            Some(tracker) => tracker.surcharge(now_ms),
+            None => 0.0,
+        }
+    }
+
+    /// Announce a mode change with its evidence and keep it for
+    /// `transitions`
+    fn record_transition(&mut self, at_ms: f64, from: OperationalMode, to: OperationalMode) {
+        let evidence = self.transition_evidence(at_ms, from, to);
+        if self.transitions.len() == TRANSITION_CAPACITY {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(ModeTransition {
+            at_ms,
+            from,
+            to,
+            evidence,
+        });
+        self.announce_transition(at_ms, from, to, evidence);
+    }
+
+    fn announce_transition(
+        &mut self,
+        at_ms: f64,
+        from: OperationalMode,
+        to: OperationalMode,
+        evidence: TransitionEvidence,
+    ) {
+        self.events.push(EngineEvent::ModeChanged {
+            at_ms,
+            from,
+            to,
+            dry_run: self.config.observe_only,
+            evidence: Some(Box::new(evidence)),
+        });
+    }
+
+    /// Which rule moved the mode, judged from the state after the move
+    fn transition_evidence(
+        &self,
+        at_ms: f64,
+        from: OperationalMode,
+        to: OperationalMode,
+    ) -> TransitionEvidence {
+        let state = &self.state;
+        let recovery_threshold = match &self.flaps {
+            Some(flaps) => flaps.recovery_threshold(self.config.recovery_threshold),
+            None => self.config.recovery_threshold,
+        };
+        let (reason, threshold) = if state.overrides.mode_at(at_ms) == Some(to) {
+            (TransitionReason::Override, None)
+        } else {
+            match (from, to) {
+                (OperationalMode::Bootstrap, _) => (TransitionReason::BootstrapComplete, None),
+                (_, OperationalMode::CircuitBreaker)
+                    if state.resistance.0 >= self.config.break_threshold =>
+                {
+                    (
+                        TransitionReason::BreakThreshold,
+                        Some(self.config.break_threshold),
+                    )
+                }
+                (OperationalMode::CircuitBreaker, OperationalMode::Operational)
+                    if state.resistance.0 < recovery_threshold =>
+                {
+                    (
+                        TransitionReason::ResistanceRecovered,
+                        Some(recovery_threshold),
+                    )
+                }
+                (OperationalMode::CircuitBreaker, OperationalMode::Operational) => (
+                    TransitionReason::ScarAndPressureRecovered,
+                    Some(self.config.scar_factor),
+                ),
+                _ => (TransitionReason::Other, None),
+            }
+        };
+        TransitionEvidence {
+            reason,
+            threshold,
+            resistance: state.resistance.0,
+            scar: state.scar.0,
+            pressure: vector::magnitude(&state.pressure),
+            breakdown: self.explain(),
+        }
+    }
+
+    /// Feed the final mode transition to the breaker backoff and flap
+    /// detector
+    fn observe_transition(&mut self, now_ms: f64, from: OperationalMode, to: OperationalMode) {
+        if let Some(config) = &self.config.breaker_backoff {
+            self.backoff.observe(config, now_ms, from, to);
+        }
+        let recovery_threshold = self.config.recovery_threshold;
+        if let Some(event) = self
+            .flaps
+            .as_mut()
+            .and_then(|flaps| flaps.observe(now_ms, from, to, recovery_threshold))
+        {
+            self.events.push(event);
+        }
+    }
+
+    /// Pressure as the trauma test should see it under `adaptive_critical`
+    ///
+    /// Scaling by `CRITICAL_PRESSURE / critical` moves the threshold without
+    /// touching the scar formulas. The sample is judged against the
+    /// distribution before it, then learned.
+    fn critical_step(&mut self, now_ms: f64, pressure: &PressureVector) -> PressureVector {
+        let Some(config) = &self.config.adaptive_critical else {
+            return *pressure;
+        };
+        let stress = self.stress.get_or_insert_with(StressDistribution::new);
+        let scale = scar::CRITICAL_PRESSURE / stress.critical(config);
+        stress.observe(config, now_ms, vector::positive_stress_magnitude(pressure));
+        PressureVector::new(
+            pressure.latency * scale,
+            pressure.error * scale,
+            pressure.saturation * scale,
+        )
+    }
+
+    /// Pressure relative to the seasonal baseline (learning from `raw`)
+    fn deseasonalize(&mut self, now_ms: f64, raw: PressureVector) -> PressureVector {
+        let Some(config) = &self.config.seasonal else {
+            return raw;
+        };
+        let baseline = match &mut self.seasonal {
+            Some(baseline) if baseline.period() == config.period => baseline,
+            slot => slot.insert(SeasonalBaseline::new(config.period)),
+        };
+        baseline.adjust(config, now_ms, raw)
+    }
+
+    /// Run change-point detection; true when momentum should restart
+    fn change_point_step(&mut self, now_ms: f64, pressure: &PressureVector) -> bool {
+        let Some(event) = self
+            .changes
+            .as_mut()
+            .and_then(|changes| changes.observe(now_ms, pressure))
+        else {
+            return false;
+        };
+        let reset = matches!(
+            event,
+            EngineEvent::ChangePointDetected {
+                momentum_reset: true,
+                ..
+            }
+        );
+        self.events.push(event);
+        reset
+    }
+
+    fn metastable_step(&mut self, now_ms: f64) {
+        let state = self.state;
+        if let Some(event) = self
+            .metastable
+            .as_mut()
+            .and_then(|metastable| metastable.observe(now_ms, &state))
+        {
+            self.events.push(event);
+        }
+    }
+
+    fn brownout_step(&mut self, now_ms: f64, resistance: Ohms) {
+        let Some(brownout) = &self.config.brownout else {
+            self.degradation = 0;
+            return;
+        };
+        let level = brownout.step(self.degradation, resistance.0);
+        if level != self.degradation {
+            self.events.push(EngineEvent::DegradationChanged {
+                at_ms: now_ms,
+                from: self.degradation,
+                to: level,
+                admission: brownout.admission(level),
+            });
+            self.degradation = level;
+        }
+    }
+
+    /// Hold a recovering breaker open for this engine's jitter delay,
+    /// counted from the first tick recovery came due
+    fn jitter_recovery(
+        &mut self,
+        now_ms: f64,
+        from: OperationalMode,
+        to: OperationalMode,
+    ) -> OperationalMode {
+        let recovering =
+            from == OperationalMode::CircuitBreaker && to == OperationalMode::Operational;
+        let (Some(jitter), true) = (&self.config.recovery_jitter, recovering) else {
+            self.recovery_due_ms = None;
+            return to;
+        };
+        let due_ms = *self.recovery_due_ms.get_or_insert(now_ms);
+        if now_ms - due_ms >= jitter.delay_ms(self.jitter_key) {
+            self.recovery_due_ms = None;
+            to
+        } else {
+            OperationalMode::CircuitBreaker
+        }
+    }
+
+    /// Start, apply or end the soft-start ramp once the mode is decided;
+    /// returns the event to raise, if any
+    fn soft_start_step(
+        &mut self,
+        now_ms: f64,
+        prev: &TargetState,
+        next: &mut TargetState,
+    ) -> Option<EngineEvent> {
+        let Some(mut config) = self.config.soft_start else {
+            self.ramp = None;
+            return None;
+        };
+        if let Some(jitter) = &self.config.recovery_jitter {
+            config.window_ms = jitter.window_ms(self.jitter_key, config.window_ms);
+        }
+        let mut event = None;
+        if prev.mode == OperationalMode::CircuitBreaker && next.mode == OperationalMode::Operational
+        {
+            self.ramp = Ramp::start(config, now_ms, prev.resistance.0, next.resistance.0);
+            event = self.ramp.map(|ramp| EngineEvent::SoftStartBegan {
+                at_ms: now_ms,
+                excess: ramp.excess(),
+                window_ms: config.window_ms,
+            });
+        }
+        let ramp = self.ramp?;
+        match ramp.surcharge(now_ms) {
+            Some(extra) if next.mode == OperationalMode::Operational => {
+                next.resistance = self.bound(Ohms(next.resistance.0 + extra));
+                event
+            }
+            // Window over, or the breaker tripped again
+            extra => {
+                self.ramp = None;
+                Some(EngineEvent::SoftStartEnded {
+                    at_ms: now_ms,
+                    completed: extra.is_none(),
+                })
+            }
+        }
+    }
+
+    /// Mode transition after full physics (TS: breakPoint / recovery checks)
+    #[cfg_attr(not(feature = "profiling"), inline)]
+    #[cfg_attr(feature = "profiling", inline(never))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", name = "atrion.next_mode", skip_all)
+    )]
+    fn next_mode(
+        &self,
+        now_ms: f64,
+        mode: OperationalMode,
+        pressure: &PressureVector,
+        scar: Scar,
+        resistance: Ohms,
+    ) -> OperationalMode {
+        match mode {
+            OperationalMode::Operational if resistance.0 >= self.config.break_threshold => {
+                OperationalMode::CircuitBreaker
+            }
+            OperationalMode::CircuitBreaker => {
+                // Flap damping widens the band and holds the breaker open
+                let (recovery_threshold, may_close) = match &self.flaps {
+                    Some(flaps) => (
+                        flaps.recovery_threshold(self.config.recovery_threshold),
+                        flaps.may_close(now_ms),
+                    ),
+                    None => (self.config.recovery_threshold, true),
+                };
+                let may_close = may_close
+                    && self
+                        .config
+                        .breaker_backoff
+                        .is_none_or(|backoff| self.backoff.may_close(&backoff, now_ms));
+                let scar_below = scar.0 < self.config.scar_factor;
+                let pressure_below = vector::magnitude(pressure) < self.critical_pressure();
+                let resistance_below = resistance.0 < recovery_threshold;
+                if may_close && ((scar_below && pressure_below) || resistance_below) {
+                    OperationalMode::Operational
+                } else {
+                    OperationalMode::CircuitBreaker
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl TargetEngine {
+    /// Create a stateful engine in bootstrap mode
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: PhysicsConfig, weights: SensitivityWeights) -> Self {
+        let state = TargetState::bootstrap(&config);
+        let history = History::new(config.history_capacity as usize);
+        Self {
+            config,
+            weights,
+            state,
+            history,
+            burn_rate: None,
+            goodput: GoodputEstimator::new(),
+            fairness: None,
+            shed_curve: ShedCurve::default(),
+            queue_model: QueueModel::default(),
+            cost_budget: None,
+            tuner: None,
+            reorder: None,
+            journal: None,
+            alarms: None,
+            events: EventLog::default(),
+            fixed_tick: None,
+            backfill: None,
+            probing: None,
+            ramp: None,
+            flaps: None,
+            metastable: None,
+            changes: None,
+            seasonal: None,
+            warmup_started_ms: None,
+            stress: None,
+            transitions: VecDeque::new(),
+            backoff: BreakerBackoff::default(),
+            jitter_key: 0,
+            recovery_due_ms: None,
+            degradation: 0,
+        }
+    }
+
+    /// Build from a plain `{ config?, weights?, state? }` object
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json_js(value: JsValue) -> Result<TargetEngine, JsValue> {
+        Ok(Self::from_document(serde_wasm_bindgen::from_value(value)?))
+    }
+
+    /// `{ config, weights, state }` (also picked up by `JSON.stringify`)
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.to_document())
+    }
+
+    /// Advance the engine, returning the new resistance
+    #[wasm_bindgen(js_name = tick)]
+    pub fn tick_js(&mut self, now_ms: f64, pressure: &PressureVector) -> f64 {
+        self.tick(now_ms, *pressure).resistance.0
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mode(&self) -> OperationalMode {
+        self.state.mode
+    }
+
+    #[wasm_bindgen(getter, js_name = resistance)]
+    pub fn resistance_js(&self) -> f64 {
+        self.state.resistance.0
+    }
+
+    #[wasm_bindgen(getter, js_name = scar)]
+    pub fn scar_js(&self) -> f64 {
+        self.state.scar.0
+    }
+
+    #[wasm_bindgen(getter, js_name = momentum)]
+    pub fn momentum_js(&self) -> f64 {
+        self.state.momentum.0
+    }
+
+    /// Resistance breakdown as a plain object
+    #[wasm_bindgen(js_name = explain)]
+    pub fn explain_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.explain())
+    }
+
+    /// History samples in `[startMs, endMs]` as plain objects
+    #[wasm_bindgen(js_name = historyBetween)]
+    pub fn history_between_js(&self, start_ms: f64, end_ms: f64) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.history_between(start_ms, end_ms))
+    }
+
+    #[wasm_bindgen(js_name = maxResistanceSince)]
+    pub fn max_resistance_since_js(&self, since_ms: f64) -> Option<f64> {
+        self.max_resistance_since(since_ms).map(|r| r.0)
+    }
+
+    /// Retry advice as a plain object, or null when not shedding
+    #[wasm_bindgen(js_name = retryAdvice)]
+    pub fn retry_advice_js(&self, shed_threshold: f64) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.retry_advice(shed_threshold))
+    }
+
+    /// Hedge advice as a plain object (`policy` undefined = defaults)
+    #[wasm_bindgen(js_name = hedgeAdvice)]
+    pub fn hedge_advice_js(&self, policy: JsValue) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.hedge_advice(&hedge::policy_from_js(policy)?))
+    }
+
+    /// Downsampled history as plain objects
+    #[wasm_bindgen(js_name = exportHistory)]
+    pub fn export_history_js(&self, bucket_ms: f64) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.export_history(bucket_ms))
+    }
+
+    /// History buckets, each with the markers from `windowMs` before it
+    #[wasm_bindgen(js_name = exportHistoryAnnotated)]
+    pub fn export_history_annotated_js(
+        &self,
+        bucket_ms: f64,
+        window_ms: f64,
+    ) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.export_history_annotated(bucket_ms, window_ms))
+    }
+
+    #[wasm_bindgen(js_name = explainAnnotated)]
+    pub fn explain_annotated_js(&self, window_ms: f64) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.explain_annotated(window_ms))
+    }
+
+    #[wasm_bindgen(js_name = markEvent)]
+    pub fn mark_event_js(&mut self, kind: MarkerKind, timestamp_ms: f64) {
+        self.mark_event(kind, timestamp_ms);
+    }
+
+    #[wasm_bindgen(js_name = restartWarmup)]
+    pub fn restart_warmup_js(&mut self, now_ms: f64) {
+        self.restart_warmup(now_ms);
+    }
+
+    /// Voltage gate: true if the request may pass
+    #[wasm_bindgen(js_name = tryAdmit)]
+    pub fn try_admit_js(&self, voltage: f64) -> bool {
+        self.try_admit(voltage).is_admitted()
+    }
+
+    /// Deadline-aware voltage gate; returns the rejection reason label
+    /// (e.g. "deadline_unmeetable"), or undefined when admitted
+    #[wasm_bindgen(js_name = tryAdmitDeadline)]
+    pub fn try_admit_deadline_js(&self, voltage: f64, remaining_ms: f64) -> Option<String> {
+        match self.try_admit_deadline(voltage, remaining_ms) {
+            AdmissionDecision::Admit => None,
+            AdmissionDecision::Reject(reason) => Some(reason.as_str().to_string()),
+        }
+    }
+
+    /// Typed rejection as a plain object, or undefined when admitted
+    #[wasm_bindgen(js_name = checkAdmission)]
+    pub fn check_admission_js(
+        &self,
+        voltage: f64,
+        remaining_ms: Option<f64>,
+    ) -> Result<JsValue, JsValue> {
+        match self.check_admission(voltage, remaining_ms) {
+            Ok(()) => Ok(JsValue::UNDEFINED),
+            Err(rejection) => crate::to_js(&rejection),
+        }
+    }
+
+    /// Set the queue model from a plain `QueueModel` value
+    #[wasm_bindgen(js_name = setQueueModel)]
+    pub fn set_queue_model_js(&mut self, model: JsValue) -> Result<(), JsValue> {
+        self.set_queue_model(serde_wasm_bindgen::from_value(model)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter, js_name = predictedWaitMs)]
+    pub fn predicted_wait_ms_js(&self) -> f64 {
+        self.predicted_wait_ms()
+    }
+
+    /// Set the priority cutoff curve from a plain `ShedCurve` value
+    #[wasm_bindgen(js_name = setShedCurve)]
+    pub fn set_shed_curve_js(&mut self, curve: JsValue) -> Result<(), JsValue> {
+        self.set_shed_curve(serde_wasm_bindgen::from_value(curve)?);
+        Ok(())
+    }
+
+    /// Lowest admitted priority, or undefined when everything is shed
+    #[wasm_bindgen(js_name = minAdmittedPriority)]
+    pub fn min_admitted_priority_js(&self) -> Option<u8> {
+        self.min_admitted_priority()
+    }
+
+    #[wasm_bindgen(js_name = tryAdmitPriority)]
+    pub fn try_admit_priority_js(&self, priority: u8) -> bool {
+        self.try_admit_priority(priority).is_admitted()
+    }
+
+    /// Enable cost-aware admission from a plain `CostBudgetConfig` object
+    #[wasm_bindgen(js_name = enableCostBudget)]
+    pub fn enable_cost_budget_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        self.enable_cost_budget(serde_wasm_bindgen::from_value(config)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = tryAdmitCost)]
+    pub fn try_admit_cost_js(&mut self, now_ms: f64, cost: f64) -> bool {
+        self.try_admit_cost(now_ms, cost).is_admitted()
+    }
+
+    #[wasm_bindgen(js_name = costBudgetRemaining)]
+    pub fn cost_budget_remaining_js(&self) -> Option<f64> {
+        self.cost_budget_remaining()
+    }
+
+    /// Enable tenant fairness from a plain `FairnessConfig` object
+    #[wasm_bindgen(js_name = enableFairness)]
+    pub fn enable_fairness_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        self.enable_fairness(serde_wasm_bindgen::from_value(config)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = tryAdmitTenant)]
+    pub fn try_admit_tenant_js(&mut self, now_ms: f64, tenant: &str) -> bool {
+        self.try_admit_tenant(now_ms, tenant).is_admitted()
+    }
+
+    /// `{ [tenant]: TenantAccount }` for the current window
+    #[wasm_bindgen(js_name = tenantAccounts)]
+    pub fn tenant_accounts_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.tenant_accounts())
+    }
+
+    /// Enable physics alarms from a plain `AlarmConfig` object (trauma
+    /// rate, scar growth, breaker time; omitted thresholds stay disabled)
+    #[wasm_bindgen(js_name = enableAlarms)]
+    pub fn enable_alarms_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        self.enable_alarms(serde_wasm_bindgen::from_value(config)?);
+        Ok(())
+    }
+
+    /// Current alarm signal values, or undefined without alarms
+    #[wasm_bindgen(js_name = alarmSignals)]
+    pub fn alarm_signals_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.alarms.as_ref().map(Alarms::signals))
+    }
+
+    /// Enable burn-rate tracking from a plain `BurnRateConfig` object
+    #[wasm_bindgen(js_name = enableBurnRate)]
+    pub fn enable_burn_rate_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        self.enable_burn_rate(serde_wasm_bindgen::from_value(config)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = recordOutcomes)]
+    pub fn record_outcomes_js(&mut self, now_ms: f64, good: u32, bad: u32) {
+        self.record_outcomes(now_ms, good as u64, bad as u64);
+    }
+
+    /// `{ short, long }` burn rates, or undefined when disabled
+    #[wasm_bindgen(js_name = burnRates)]
+    pub fn burn_rates_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.burn_rates())
+    }
+
+    /// Rolling statistics for one window as a plain object
+    #[wasm_bindgen(js_name = rollingStats)]
+    pub fn rolling_stats_js(&self, window_ms: f64) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.rolling_stats(window_ms))
+    }
+
+    /// 1m / 5m / 15m statistics as a plain object
+    #[wasm_bindgen(js_name = rollingSummary)]
+    pub fn rolling_summary_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.rolling_summary())
+    }
+
+    /// Per-axis SLO compliance (`thresholds` undefined = defaults)
+    #[wasm_bindgen(js_name = compliance)]
+    pub fn compliance_js(&self, window_ms: f64, thresholds: JsValue) -> Result<JsValue, JsValue> {
+        let thresholds = if thresholds.is_undefined() {
+            ComplianceThresholds::default()
+        } else {
+            serde_wasm_bindgen::from_value(thresholds)?
+        };
+        crate::to_js(&self.compliance(window_ms, &thresholds))
+    }
+
+    #[wasm_bindgen(js_name = forceModeUntil)]
+    pub fn force_mode_until_js(&mut self, mode: OperationalMode, until_ms: f64) {
+        self.force_mode_until(mode, until_ms);
+    }
+
+    #[wasm_bindgen(js_name = pauseTrauma)]
+    pub fn pause_trauma_js(&mut self, until_ms: f64) {
+        self.pause_trauma(until_ms);
+    }
+
+    #[wasm_bindgen(js_name = clearOverrides)]
+    pub fn clear_overrides_js(&mut self) {
+        self.clear_overrides();
+    }
+
+    #[wasm_bindgen(js_name = reset)]
+    pub fn reset_js(&mut self) {
+        self.reset();
+    }
+
+    #[wasm_bindgen(js_name = resetScar)]
+    pub fn reset_scar_js(&mut self) {
+        self.reset_scar();
+    }
+
+    #[wasm_bindgen(js_name = resetMomentum)]
+    pub fn reset_momentum_js(&mut self) {
+        self.reset_momentum();
+    }
+
+    #[wasm_bindgen(js_name = clearHistory)]
+    pub fn clear_history_js(&mut self) {
+        self.clear_history();
+    }
+
+    /// Advance with an explicit confidence in [0, 1], returning the new resistance
+    #[wasm_bindgen(js_name = tickWithConfidence)]
+    pub fn tick_with_confidence_js(
+        &mut self,
+        now_ms: f64,
+        pressure: &PressureVector,
+        confidence: f64,
+    ) -> f64 {
+        self.tick_with_confidence(now_ms, *pressure, confidence)
+            .resistance
+            .0
+    }
+
+    #[wasm_bindgen(js_name = tickSequenced)]
+    pub fn tick_sequenced_js(&mut self, seq: f64, now_ms: f64, pressure: &PressureVector) -> f64 {
+        self.tick_sequenced(seq as u64, now_ms, *pressure)
+            .resistance
+            .0
+    }
+
+    /// Enable the reorder window from a plain `ReorderConfig` object
+    #[wasm_bindgen(js_name = enableReorder)]
+    pub fn enable_reorder_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        self.enable_reorder(serde_wasm_bindgen::from_value(config)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = enableFixedTick)]
+    pub fn enable_fixed_tick_js(&mut self, interval_ms: f64) {
+        self.enable_fixed_tick(interval_ms);
+    }
+
+    #[wasm_bindgen(js_name = disableFixedTick)]
+    pub fn disable_fixed_tick_js(&mut self) {
+        self.disable_fixed_tick();
+    }
+
+    #[wasm_bindgen(js_name = flushReorder)]
+    pub fn flush_reorder_js(&mut self) -> f64 {
+        self.flush_reorder().resistance.0
+    }
+
+    /// Keep recent ticks for `tickAt` (`config` undefined = defaults)
+    #[wasm_bindgen(js_name = enableBackfill)]
+    pub fn enable_backfill_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config = if config.is_undefined() {
+            BackfillConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        self.enable_backfill(config);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = disableBackfill)]
+    pub fn disable_backfill_js(&mut self) {
+        self.disable_backfill();
+    }
+
+    /// Request probes once stale (`config` undefined = defaults)
+    #[wasm_bindgen(js_name = enableProbing)]
+    pub fn enable_probing_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config = if config.is_undefined() {
+            ProbingConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        self.enable_probing(config);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = disableProbing)]
+    pub fn disable_probing_js(&mut self) {
+        self.disable_probing();
+    }
+
+    /// Damp breaker flapping (`config` undefined = defaults)
+    #[wasm_bindgen(js_name = enableFlapDetection)]
+    pub fn enable_flap_detection_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config = if config.is_undefined() {
+            FlapConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        self.enable_flap_detection(config);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = disableFlapDetection)]
+    pub fn disable_flap_detection_js(&mut self) {
+        self.disable_flap_detection();
+    }
+
+    /// Report pressure that does not drain despite shedding (`config`
+    /// undefined = defaults)
+    #[wasm_bindgen(js_name = enableMetastableDetection)]
+    pub fn enable_metastable_detection_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config = if config.is_undefined() {
+            MetastableConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        self.enable_metastable_detection(config);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = disableMetastableDetection)]
+    pub fn disable_metastable_detection_js(&mut self) {
+        self.disable_metastable_detection();
+    }
+
+    /// Flag pressure regime shifts (`config` undefined = defaults)
+    #[wasm_bindgen(js_name = enableChangeDetection)]
+    pub fn enable_change_detection_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config = if config.is_undefined() {
+            ChangePointConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        self.enable_change_detection(config);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = disableChangeDetection)]
+    pub fn disable_change_detection_js(&mut self) {
+        self.disable_change_detection();
+    }
+
+    #[wasm_bindgen(getter, js_name = metastableSuspected)]
+    pub fn metastable_suspected_js(&self) -> bool {
+        self.metastable_suspected()
+    }
+
+    #[wasm_bindgen(getter, js_name = criticalPressure)]
+    pub fn critical_pressure_js(&self) -> f64 {
+        self.critical_pressure()
+    }
+
+    /// Earliest time the open breaker may close under
+    /// `breakerBackoff` (undefined while closed or without backoff)
+    #[wasm_bindgen(getter, js_name = breakerOpenUntil)]
+    pub fn breaker_open_until_js(&self) -> Option<f64> {
+        let config = self.config.breaker_backoff.as_ref()?;
+        self.backoff.open_until(config)
+    }
+
+    /// Brownout level (0 = full service)
+    #[wasm_bindgen(getter, js_name = degradationLevel)]
+    pub fn degradation_level_js(&self) -> u32 {
+        self.degradation_level()
+    }
+
+    #[wasm_bindgen(getter, js_name = degradationAdmission)]
+    pub fn degradation_admission_js(&self) -> f64 {
+        self.degradation_admission()
+    }
+
+    /// Flap damping level (undefined without flap detection)
+    #[wasm_bindgen(getter, js_name = flapLevel)]
+    pub fn flap_level_js(&self) -> Option<u32> {
+        self.flaps.as_ref().map(FlapDetector::level)
+    }
+
+    #[wasm_bindgen(js_name = checkStaleness)]
+    pub fn check_staleness_js(&mut self, now_ms: f64) -> bool {
+        self.check_staleness(now_ms)
+    }
+
+    /// Apply a possibly late sample, returning the new resistance
+    #[wasm_bindgen(js_name = tickAt)]
+    pub fn tick_at_js(&mut self, timestamp_ms: f64, pressure: &PressureVector) -> f64 {
+        self.tick_at(timestamp_ms, *pressure).resistance.0
+    }
+
+    /// Enable online tuning from a plain `TuningConfig` object
+    #[wasm_bindgen(js_name = enableTuning)]
+    pub fn enable_tuning_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        self.enable_tuning(serde_wasm_bindgen::from_value(config)?);
+        Ok(())
+    }
+
+    /// Buffered events as plain objects (tagged by `type`), clearing the buffer
+    #[wasm_bindgen(js_name = drainEvents)]
+    pub fn drain_events_js(&mut self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.drain_events())
+    }
+
+    /// State, breakdown, history tail and newest events in one plain
+    /// object, cheap enough to call every animation frame
+    #[wasm_bindgen(js_name = debugFrame)]
+    pub fn debug_frame_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.debug_frame())
+    }
+
+    /// Recent mode transitions with their evidence, oldest first
+    #[wasm_bindgen(js_name = transitions)]
+    pub fn transitions_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.transitions().collect::<Vec<_>>())
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::confidence;
+    use crate::overrides::OverrideKind;
+    use crate::scar::DualScarConfig;
+
+    fn engine() -> TargetEngine {
+        TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default())
+    }
+
+    #[test]
+    fn test_bootstrap_then_operational() {
+        let mut engine = engine();
+        let calm = PressureVector::new(0.1, 0.0, 0.1);
+
+        for i in 0..9 {
+            let state = engine.tick(i as f64 * 100.0, calm);
+            assert_eq!(state.mode, OperationalMode::Bootstrap);
+        }
+
+        let state = engine.tick(900.0, calm);
+        assert_eq!(state.mode, OperationalMode::Operational);
+        assert!(state.resistance.0 >= engine.config().base_resistance);
+    }
+
+    #[test]
+    fn test_sustained_stress_trips_and_recovers() {
+        let mut engine = engine();
+        let mut now = 0.0;
+        for _ in 0..10 {
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+            now += 100.0;
+        }
+
+        let storm = PressureVector::new(1.0, 1.0, 1.0);
+        while engine.state().mode != OperationalMode::CircuitBreaker {
+            engine.tick(now, storm);
+            now += 100.0;
+            assert!(now < 60_000.0, "breaker never tripped");
+        }
+
+        while engine.state().mode != OperationalMode::Operational {
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+            now += 1000.0;
+            assert!(now < 600_000.0, "breaker never recovered");
+        }
+    }
+
+    #[test]
+    fn test_explain_matches_state() {
+        let mut engine = engine();
+        for i in 0..15 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.9, 0.5, 0.2));
+        }
+        let breakdown = engine.explain();
+        assert!((breakdown.total - engine.state().resistance.0).abs() < 1e-9);
+        assert!(breakdown.scar > 0.0);
+    }
+
+    #[test]
+    fn test_pid_mode_tracks_latency_setpoint() {
+        let config = PhysicsConfig {
+            control_mode: ControlMode::Pid,
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        let mut now = 0.0;
+        let mut tick = |engine: &mut TargetEngine, latency: f64| {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(latency, 0.0, 0.0))
+        };
+
+        for _ in 0..10 {
+            tick(&mut engine, 0.3);
+        }
+        assert_eq!(engine.state().resistance.0, 10.0);
+
+        // Sustained latency above setpoint winds up the integral (scar)
+        let first = tick(&mut engine, 0.6);
+        assert!(first.momentum.0 > 0.0);
+        for _ in 0..20 {
+            tick(&mut engine, 0.6);
+        }
+        let state = *engine.state();
+        assert!(state.scar.0 > first.scar.0);
+        assert_eq!(state.momentum.0, 0.0);
+        // base + P (50 × 0.3) + I
+        assert!((state.resistance.0 - (25.0 + state.scar.0)).abs() < 1e-9);
+
+        let breakdown = engine.explain();
+        assert!((breakdown.total - state.resistance.0).abs() < 1e-9);
+        assert_eq!(breakdown.scar, state.scar.0);
+    }
+
+    #[test]
+    fn test_ticks_are_recorded_in_history() {
+        let mut engine = engine();
+        for i in 0..20 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.2, 0.1, 0.1));
+        }
+
+        assert_eq!(engine.history().len(), 20);
+        assert_eq!(engine.history_between(1000.0, 1900.0).len(), 10);
+        assert!(engine.max_resistance_since(0.0).is_some());
+    }
+
+    #[test]
+    fn test_set_config_resizes_history() {
+        let mut engine = engine();
+        for i in 0..20 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.2, 0.1, 0.1));
+        }
+
+        let mut config = engine.config().clone();
+        config.history_capacity = 5;
+        engine.set_config(config);
+
+        assert_eq!(engine.history().capacity(), 5);
+        assert_eq!(engine.history().len(), 5);
+        assert_eq!(engine.history().iter().next().unwrap().timestamp_ms, 1500.0);
+    }
+
+    #[test]
+    fn test_burn_rate_surcharge_raises_resistance() {
+        use crate::burnrate::BurnRateMapping;
+
+        let mut plain = engine();
+        let mut burning = engine();
+        burning.enable_burn_rate(BurnRateConfig {
+            mapping: BurnRateMapping::Surcharge { ohms_per_burn: 1.0 },
+            ..BurnRateConfig::default()
+        });
+        burning.record_outcomes(0.0, 900, 100); // 100x burn
+
+        let calm = PressureVector::new(0.1, 0.0, 0.1);
+        for i in 0..10 {
+            plain.tick(i as f64 * 100.0, calm);
+            burning.tick(i as f64 * 100.0, calm);
+        }
+
+        let extra = burning.state().resistance.0 - plain.state().resistance.0;
+        assert!((extra - 99.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_priority_shedding_tracks_resistance() {
+        let mut engine = engine();
+        for i in 0..10 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        assert_eq!(engine.min_admitted_priority(), Some(0));
+
+        // Halfway through the shedding band
+        engine.state.resistance = Ohms(75.0);
+        assert_eq!(engine.min_admitted_priority(), Some(4));
+        assert!(engine.try_admit_priority(7).is_admitted());
+        assert_eq!(
+            engine.try_admit_priority(3),
+            AdmissionDecision::Reject(RejectReason::PriorityShed)
+        );
+    }
+
+    #[test]
+    fn test_cost_budget_scales_with_resistance() {
+        let mut engine = engine();
+        engine.enable_cost_budget(CostBudgetConfig::default());
+        for i in 0..10 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+
+        engine.state.resistance = Ohms(75.0); // half the budget
+        let heavy = |cpu_ms: &f64| *cpu_ms;
+        assert!(engine.try_admit_with(1000.0, &450.0, &heavy).is_admitted());
+        assert_eq!(
+            engine.try_admit_cost(1000.0, 100.0),
+            AdmissionDecision::Reject(RejectReason::BudgetExhausted)
+        );
+        assert!(engine.try_admit_cost(1000.0, 50.0).is_admitted());
+        assert_eq!(engine.cost_budget_remaining(), Some(0.0));
+    }
+
+    #[test]
+    fn test_tenant_admission_follows_breaker_and_fairness() {
+        let mut engine = engine();
+        engine.enable_fairness(FairnessConfig::default());
+
+        for i in 0..10 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        assert_eq!(engine.admission_ratio(), 1.0);
+        assert!(engine.try_admit_tenant(1000.0, "a").is_admitted());
+        assert_eq!(engine.tenant_accounts().unwrap()["a"].admitted, 1);
+
+        engine.state.mode = OperationalMode::CircuitBreaker;
+        assert_eq!(
+            engine.try_admit_tenant(1000.0, "a"),
+            AdmissionDecision::Reject(RejectReason::CircuitOpen)
+        );
+    }
+
+    #[test]
+    fn test_clock_guards() {
+        let mut engine = engine();
+        for i in 0..12 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        engine.drain_events();
+
+        // Backwards sample is ignored outright
+        let before = *engine.state();
+        let state = engine.tick(500.0, PressureVector::new(1.0, 1.0, 1.0));
+        assert_eq!(state.tick_count, before.tick_count);
+        assert_eq!(state.last_updated_ms, 1100.0);
+
+        // A day-long gap decays the scar as if only max_delta_t_ms passed
+        engine.state.scar = Scar(10.0);
+        let state = engine.tick(86_400_000.0, PressureVector::new(0.0, 0.0, 0.0));
+        assert!(state.scar.0 > 0.0);
+        assert_eq!(state.last_updated_ms, 86_400_000.0);
+
+        let kinds: Vec<_> = engine
+            .drain_events()
+            .into_iter()
+            .map(|event| match event {
+                EngineEvent::ClockAnomaly {
+                    kind, applied_ms, ..
+                } => (kind, applied_ms),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (ClockAnomalyKind::Backwards, 0.0),
+                (ClockAnomalyKind::Jump, 60_000.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reorder_window_applies_trauma_once() {
+        let run = |samples: &[(u64, f64)]| {
+            let mut engine = engine();
+            engine.enable_reorder(ReorderConfig {
+                window_ms: 300.0,
+                ..ReorderConfig::default()
+            });
+            for (seq, t) in samples {
+                engine.tick_sequenced(*seq, *t, PressureVector::new(0.9, 0.9, 0.9));
+            }
+            engine.flush_reorder()
+        };
+        let ordered: Vec<_> = (0..20).map(|i| (i, i as f64 * 100.0)).collect();
+        let mut messy = ordered.clone();
+        messy.swap(12, 13);
+        messy.insert(15, (12, 1200.0));
+        messy.insert(5, (3, 300.0));
+
+        let clean = run(&ordered);
+        let replayed = run(&messy);
+        assert_eq!(replayed.tick_count, clean.tick_count);
+        assert_eq!(replayed.scar.0, clean.scar.0);
+        assert_eq!(replayed.resistance.0, clean.resistance.0);
+    }
+
+    #[test]
+    fn test_confidence_scales_trauma() {
+        let run = |confidence: Option<f64>| {
+            let mut engine = engine();
+            for i in 0..10 {
+                engine.tick(i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+            }
+            let stress = PressureVector::new(0.9, 0.9, 0.9);
+            match confidence {
+                Some(c) => engine.tick_with_confidence(1000.0, stress, c),
+                None => engine.tick(1000.0, stress),
+            }
+        };
+        let full = run(None);
+        let thin = run(Some(confidence::from_sample_count(3.0)));
+        assert!((run(Some(1.0)).scar.0 - full.scar.0).abs() < 1e-12);
+        assert!(thin.scar.0 < 0.05 * full.scar.0);
+        assert!(thin.momentum.0 < full.momentum.0);
+    }
+
+    #[test]
+    fn test_dual_scar_keeps_chronic_flapper_penalized() {
+        let mut engine = TargetEngine::new(
+            PhysicsConfig {
+                dual_scar: Some(DualScarConfig::default()),
+                ..PhysicsConfig::default()
+            },
+            SensitivityWeights::default(),
+        );
+        let calm = PressureVector::new(0.0, 0.0, 0.0);
+        let mut now = 0.0;
+        for _ in 0..10 {
+            engine.tick(now, calm);
+            now += 100.0;
+        }
+        // A flapper: short incidents every minute for ten minutes
+        for _ in 0..10 {
+            engine.tick(now, PressureVector::new(0.9, 0.9, 0.9));
+            now += 60_000.0;
+            engine.tick(now, calm);
+        }
+        let state = *engine.state();
+        assert!(state.scar.0 < 0.1);
+        assert!(state.slow_scar.0 > 4.0);
+
+        let breakdown = engine.explain();
+        assert_eq!(breakdown.dominant().0, "slow_scar");
+        assert!((breakdown.total - state.resistance.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_maintenance_window_overrides() {
+        let mut engine = engine();
+        for i in 0..10 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        engine.force_mode_until(OperationalMode::Operational, 60_000.0);
+        engine.pause_trauma(60_000.0);
+
+        // Planned failover: errors neither scar nor trip the breaker
+        let mut now = 1000.0;
+        while now < 60_000.0 {
+            let state = engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+            assert_eq!(state.mode, OperationalMode::Operational);
+            assert_eq!(state.scar.0, 0.0);
+            now += 1000.0;
+        }
+        // Gossip cannot open a pinned breaker either
+        engine.force_mode(OperationalMode::CircuitBreaker);
+        assert_eq!(engine.state().mode, OperationalMode::Operational);
+
+        let state = engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+        assert!(state.overrides.is_empty());
+        assert!(state.scar.0 > 0.0);
+        assert!(engine.drain_events().iter().any(|e| matches!(
+            e,
+            EngineEvent::OverrideExpired {
+                kind: OverrideKind::Mode,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_document_round_trip_accepts_partial_camel_case() {
+        let document: EngineDocument =
+            serde_json::from_str(r#"{ "config": { "baseResistance": 12, "scar_factor": 3 } }"#)
+                .unwrap();
+        let mut engine = TargetEngine::from_document(document);
+        assert_eq!(engine.config().base_resistance, 12.0);
+        assert_eq!(engine.config().scar_factor, 3.0);
+        assert_eq!(engine.config().bootstrap_ticks, 10);
+        assert_eq!(
+            engine.weights().w_error,
+            SensitivityWeights::default().w_error
+        );
+
+        for i in 0..12 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.9, 0.9, 0.9));
+        }
+        let json = serde_json::to_string(&engine.to_document()).unwrap();
+        let restored = TargetEngine::from_document(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.state().scar, engine.state().scar);
+        assert_eq!(restored.state().tick_count, 12);
+    }
+
+    #[test]
+    fn test_mode_change_events_and_tuning_probe() {
+        let mut engine = engine();
+        engine.enable_tuning(TuningConfig::default());
+        // First probe epoch runs scar_factor at +δ (5% of the 1..20 range)
+        assert!((engine.config().scar_factor - 5.95).abs() < 1e-9);
+
+        for i in 0..10 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        let evidence = engine.transitions().last().map(|t| Box::new(t.evidence));
+        assert_eq!(
+            engine.drain_events(),
+            vec![EngineEvent::ModeChanged {
+                at_ms: 900.0,
+                from: OperationalMode::Bootstrap,
+                to: OperationalMode::Operational,
+                dry_run: false,
+                evidence,
+            }]
+        );
+        assert!(engine.events().is_empty());
+    }
+
+    #[test]
+    fn test_partial_and_full_resets_are_audited() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 0..15 {
+            let load = if i < 10 { 0.1 } else { 1.0 };
+            engine.tick(i as f64 * 100.0, PressureVector::new(load, load, 0.0));
+        }
+        engine.drain_events();
+        let before = *engine.state();
+        assert!(before.momentum.0 > 0.0);
+
+        engine.reset_momentum();
+        assert_eq!(engine.state().momentum.0, 0.0);
+        assert!(engine.state().resistance.0 < before.resistance.0);
+        assert_eq!(engine.state().scar, before.scar);
+
+        engine.clear_history();
+        assert!(engine.history().is_empty());
+        assert_eq!(engine.state().tick_count, before.tick_count);
+
+        engine.reset();
+        assert_eq!(engine.state().mode, OperationalMode::Bootstrap);
+        assert_eq!(engine.state().tick_count, 0);
+
+        let kinds: Vec<_> = engine
+            .drain_events()
+            .into_iter()
+            .map(|event| match event {
+                EngineEvent::StateReset { at_ms, kind } => {
+                    assert_eq!(at_ms, 1400.0);
+                    kind
+                }
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [ResetKind::Momentum, ResetKind::History, ResetKind::Full]
+        );
+    }
+
+    #[test]
+    fn test_observe_only_reports_but_never_sheds() {
+        let config = PhysicsConfig {
+            observe_only: true,
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        let mut now = 0.0;
+        while engine.state().mode != OperationalMode::CircuitBreaker {
+            engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+            now += 100.0;
+        }
+
+        assert_eq!(engine.try_admit(1.0), AdmissionDecision::Admit);
+        assert_eq!(engine.try_admit_priority(0), AdmissionDecision::Admit);
+        assert_eq!(
+            engine.would_admit(1.0),
+            AdmissionDecision::Reject(RejectReason::CircuitOpen)
+        );
+        assert!(engine.retry_advice(0.0).is_none());
+        assert!(engine.drain_events().iter().any(|event| matches!(
+            event,
+            EngineEvent::ModeChanged {
+                to: OperationalMode::CircuitBreaker,
+                dry_run: true,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_fixed_tick_matches_uncached_ticks() {
+        let config = PhysicsConfig {
+            dual_scar: Some(DualScarConfig::default()),
+            ..PhysicsConfig::default()
+        };
+        let weights = SensitivityWeights::default();
+        let mut plain = TargetEngine::new(config.clone(), weights.clone());
+        let mut fixed = TargetEngine::new(config.clone(), weights);
+        fixed.enable_fixed_tick(100.0);
+
+        let mut now = 0.0;
+        for i in 0..60 {
+            if i == 30 {
+                let halflife = PhysicsConfig {
+                    momentum_halflife: config.momentum_halflife * 2.0,
+                    ..config.clone()
+                };
+                plain.set_config(halflife.clone());
+                fixed.set_config(halflife);
+            }
+            // A late tick takes the uncached path
+            now += if i == 40 { 250.0 } else { 100.0 };
+            let p = if (20..35).contains(&i) { 0.9 } else { 0.2 };
+            let pressure = PressureVector::new(p, p * 0.5, p);
+            plain.tick(now, pressure);
+            fixed.tick(now, pressure);
+            assert_eq!(
+                format!("{:?}", plain.state()),
+                format!("{:?}", fixed.state())
+            );
+        }
+        assert!(fixed.state().slow_scar.0 > 0.0);
+        let expected = momentum::momentum_decay(100.0, fixed.config());
+        assert_eq!(fixed.fixed_tick().unwrap().factors().momentum, expected);
+    }
+}
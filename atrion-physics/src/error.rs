@@ -0,0 +1,62 @@
+/**
+ * Error types for Atrion physics engine.
+ *
+ * Mirrors the AtrionError hierarchy in src/core/errors.ts.
+ */
+use std::fmt;
+
+use wasm_bindgen::JsValue;
+
+/// Base error for all fallible Atrion operations
+#[derive(Debug, Clone, PartialEq)]
+pub enum AtrionError {
+    /// Snapshot was produced by an incompatible format version
+    SnapshotVersion { found: u32, expected: u32 },
+    /// Serialized data could not be parsed
+    Parse(String),
+    /// A config value is out of range or inconsistent with another
+    InvalidConfig { field: &'static str, reason: String },
+    /// A journal sink failed to persist or read records
+    Journal(String),
+    /// The GPU scoring backend could not be opened or failed mid-batch
+    Gpu(String),
+    /// A shared engine was used while another call still held it
+    Reentrant { operation: &'static str },
+    /// A pressure source could not be reached or rejected the query
+    Source(String),
+    /// An exporter could not encode or write its output
+    Export(String),
+}
+
+impl fmt::Display for AtrionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AtrionError::SnapshotVersion { found, expected } => {
+                write!(
+                    f,
+                    "Unsupported snapshot version {found} (expected {expected})"
+                )
+            }
+            AtrionError::Parse(msg) => write!(f, "Failed to parse data: {msg}"),
+            AtrionError::InvalidConfig { field, reason } => {
+                write!(f, "Invalid config: {field} {reason}")
+            }
+            AtrionError::Journal(msg) => write!(f, "Journal error: {msg}"),
+            AtrionError::Gpu(msg) => write!(f, "GPU backend error: {msg}"),
+            AtrionError::Reentrant { operation } => write!(
+                f,
+                "Engine in use: {operation} called while another call holds it"
+            ),
+            AtrionError::Source(msg) => write!(f, "Pressure source error: {msg}"),
+            AtrionError::Export(msg) => write!(f, "Export error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for AtrionError {}
+
+impl From<AtrionError> for JsValue {
+    fn from(err: AtrionError) -> Self {
+        JsValue::from_str(&err.to_string())
+    }
+}
@@ -0,0 +1,286 @@
+/**
+ * Engine event log.
+ *
+ * Discrete, notable things an engine did (mode changes, self-tuning
+ * adjustments, ...) kept in a bounded buffer for callers to drain into
+ * their own logging or alerting.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::alarms::AlarmKind;
+use crate::debug::Tail;
+use crate::overrides::OverrideKind;
+use crate::transitions::TransitionEvidence;
+use crate::types::OperationalMode;
+
+/// Events retained before the oldest are dropped
+pub const EVENT_LOG_CAPACITY: usize = 128;
+
+/// Which way the clock misbehaved
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockAnomalyKind {
+    /// Timestamp earlier than the last applied one; sample ignored
+    Backwards,
+    /// Gap longer than `max_delta_t_ms`; Δt clamped
+    Jump,
+}
+
+/// What an operator reset cleared
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResetKind {
+    /// Whole physics state back to bootstrap, history included
+    Full,
+    Scar,
+    Momentum,
+    History,
+}
+
+/// Something the engine did
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum EngineEvent {
+    ModeChanged {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        from: OperationalMode,
+        to: OperationalMode,
+        /// Engine is observe-only: the transition did not affect admission
+        #[serde(default, alias = "dry_run")]
+        dry_run: bool,
+        /// What triggered the transition
+        #[serde(default)]
+        evidence: Option<Box<TransitionEvidence>>,
+    },
+    /// Non-monotonic or jumping sample timestamp
+    ClockAnomaly {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        kind: ClockAnomalyKind,
+        /// Δt as observed
+        #[serde(alias = "delta_t_ms")]
+        delta_t_ms: f64,
+        /// Δt actually applied (0 when the sample was ignored)
+        #[serde(alias = "applied_ms")]
+        applied_ms: f64,
+    },
+    /// An operator override reached its deadline
+    OverrideExpired {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        kind: OverrideKind,
+    },
+    /// Online tuning moved a config parameter
+    ConfigAdjusted {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        parameter: String,
+        from: f64,
+        to: f64,
+        /// Loss estimate that motivated the change
+        loss: f64,
+    },
+    /// State cleared by an explicit reset call (audit trail)
+    StateReset {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        kind: ResetKind,
+    },
+    /// A physics alarm crossed its threshold
+    AlarmRaised {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        alarm: AlarmKind,
+        value: f64,
+        threshold: f64,
+    },
+    /// A raised alarm dropped back below its threshold
+    AlarmCleared {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        alarm: AlarmKind,
+        value: f64,
+    },
+    /// No tick for `stale_ms`: the host should send a synthetic request
+    /// and feed the result back (see `healthcheck::HealthCheckSource`)
+    ProbeRequested {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        #[serde(alias = "stale_ms")]
+        stale_ms: f64,
+    },
+    /// The breaker closed; `excess` resistance ramps away over `window_ms`
+    SoftStartBegan {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        excess: f64,
+        #[serde(alias = "window_ms")]
+        window_ms: f64,
+    },
+    /// The ramp finished (`completed`) or was cut short by a re-trip
+    SoftStartEnded {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        completed: bool,
+    },
+    /// Flap damping changed level (`flaps` closes triggered a raise; 0
+    /// when relaxing)
+    FlapAdapted {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        level: u32,
+        flaps: usize,
+        #[serde(alias = "recovery_threshold")]
+        recovery_threshold: f64,
+        #[serde(alias = "min_open_ms")]
+        min_open_ms: f64,
+    },
+    /// Brownout level moved between resistance bands
+    DegradationChanged {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        from: u32,
+        to: u32,
+        admission: f64,
+    },
+    /// A pressure axis shifted to a new level (see `changepoint`)
+    ChangePointDetected {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        axis: String,
+        /// Reference level before the shift
+        from: f64,
+        to: f64,
+        #[serde(alias = "momentum_reset")]
+        momentum_reset: bool,
+    },
+    /// Pressure failed to drain over a window despite sustained shedding:
+    /// the breaker alone is unlikely to end this (cut retries, drop
+    /// queued work)
+    MetastableSuspected {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        /// Start of the window that failed to drain
+        #[serde(alias = "since_ms")]
+        since_ms: f64,
+        resistance: f64,
+        scar: f64,
+        /// Pressure magnitude at detection
+        pressure: f64,
+    },
+}
+
+impl EngineEvent {
+    pub fn at_ms(&self) -> f64 {
+        match self {
+            EngineEvent::ModeChanged { at_ms, .. }
+            | EngineEvent::ClockAnomaly { at_ms, .. }
+            | EngineEvent::OverrideExpired { at_ms, .. }
+            | EngineEvent::ConfigAdjusted { at_ms, .. }
+            | EngineEvent::StateReset { at_ms, .. }
+            | EngineEvent::AlarmRaised { at_ms, .. }
+            | EngineEvent::AlarmCleared { at_ms, .. }
+            | EngineEvent::ProbeRequested { at_ms, .. }
+            | EngineEvent::SoftStartBegan { at_ms, .. }
+            | EngineEvent::SoftStartEnded { at_ms, .. }
+            | EngineEvent::FlapAdapted { at_ms, .. }
+            | EngineEvent::DegradationChanged { at_ms, .. }
+            | EngineEvent::ChangePointDetected { at_ms, .. }
+            | EngineEvent::MetastableSuspected { at_ms, .. } => *at_ms,
+        }
+    }
+}
+
+/// Bounded FIFO of events (oldest dropped first)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLog {
+    capacity: usize,
+    events: VecDeque<EngineEvent>,
+    /// Events evicted before being drained
+    dropped: u64,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(EVENT_LOG_CAPACITY)
+    }
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, event: EngineEvent) {
+        if self.capacity == 0 {
+            self.dropped += 1;
+            return;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EngineEvent> {
+        self.events.iter()
+    }
+
+    /// The newest `count` events, borrowed (nothing is drained)
+    pub fn tail(&self, count: usize) -> Tail<'_, EngineEvent> {
+        Tail::new(&self.events, count)
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Remove and return all buffered events, oldest first
+    pub fn drain(&mut self) -> Vec<EngineEvent> {
+        self.events.drain(..).collect()
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_drain() {
+        let mut log = EventLog::new(2);
+        for i in 0..3 {
+            log.push(EngineEvent::ModeChanged {
+                at_ms: i as f64,
+                from: OperationalMode::Operational,
+                to: OperationalMode::CircuitBreaker,
+                dry_run: false,
+                evidence: None,
+            });
+        }
+        assert_eq!(log.dropped(), 1);
+        let drained = log.drain();
+        assert_eq!(
+            drained.iter().map(EngineEvent::at_ms).collect::<Vec<_>>(),
+            [1.0, 2.0]
+        );
+        assert!(log.is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+/**
+ * Resistance explanation (per-term breakdown).
+ *
+ * Decomposes R = R_base + P·W + μ||M|| + S + U (+ surcharge) into its
+ * terms so operators can see WHY a target is resisting. Terms are
+ * reported after `term_caps`; `caps` says which limits actually bit.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::pid::PidTerms;
+use crate::resistance::{self, weighted_terms, TermCaps};
+use crate::types::{Momentum, PhysicsConfig, PressureVector, Scar, SensitivityWeights};
+
+/// Contribution of each term to the resistance, in Ohms
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResistanceBreakdown {
+    pub base: f64,
+    pub latency: f64,
+    pub error: f64,
+    pub saturation: f64,
+    pub momentum: f64,
+    pub scar: f64,
+    /// Slow (chronic) scar under the dual-timescale model
+    #[serde(alias = "slow_scar")]
+    pub slow_scar: f64,
+    pub staleness: f64,
+    /// Extra resistance from auxiliary signals (e.g. burn-rate surcharge)
+    pub surcharge: f64,
+    /// What is left of the post-breaker soft-start ramp
+    #[serde(alias = "soft_start")]
+    pub soft_start: f64,
+    /// Final resistance (never below base)
+    pub total: f64,
+    /// Caps that clipped their term on this evaluation (others `None`)
+    pub caps: TermCaps,
+}
+
+impl ResistanceBreakdown {
+    /// Break down resistance exactly as `resistance::calculate_resistance` computes it
+    pub fn compute(
+        pressure: &PressureVector,
+        momentum: Momentum,
+        scar: Scar,
+        weights: &SensitivityWeights,
+        config: &PhysicsConfig,
+        staleness: f64,
+        surcharge: f64,
+    ) -> Self {
+        let [latency, error, saturation] = weighted_terms(pressure, weights, config.formula);
+        let mut breakdown = Self {
+            base: config.base_resistance,
+            latency,
+            error,
+            saturation,
+            momentum: config.damping_factor * momentum.0,
+            scar: scar.0,
+            slow_scar: 0.0,
+            staleness,
+            surcharge,
+            soft_start: 0.0,
+            total: 0.0,
+            caps: TermCaps::default(),
+        };
+        if let Some(caps) = &config.term_caps {
+            breakdown.apply_caps(caps);
+        }
+        let raw = breakdown.base
+            + (breakdown.latency + breakdown.error + breakdown.saturation)
+            + breakdown.momentum
+            + breakdown.scar
+            + breakdown.staleness;
+        breakdown.total = raw.max(config.base_resistance) + surcharge;
+        breakdown
+    }
+
+    /// Limit each term to its cap, recording the caps that clipped
+    fn apply_caps(&mut self, caps: &TermCaps) {
+        let terms = [
+            (&mut self.latency, caps.latency, &mut self.caps.latency),
+            (&mut self.error, caps.error, &mut self.caps.error),
+            (
+                &mut self.saturation,
+                caps.saturation,
+                &mut self.caps.saturation,
+            ),
+            (&mut self.momentum, caps.momentum, &mut self.caps.momentum),
+            (&mut self.scar, caps.scar, &mut self.caps.scar),
+            (
+                &mut self.staleness,
+                caps.staleness,
+                &mut self.caps.staleness,
+            ),
+        ];
+        for (term, cap, applied) in terms {
+            let capped = resistance::cap(*term, cap);
+            if capped != *term {
+                *term = capped;
+                *applied = cap;
+            }
+        }
+    }
+
+    /// Break down PID-mode resistance (P on latency, I as scar, D as momentum)
+    pub fn from_pid(terms: &PidTerms, config: &PhysicsConfig, surcharge: f64) -> Self {
+        let raw = config.base_resistance + terms.total();
+        Self {
+            base: config.base_resistance,
+            latency: terms.proportional,
+            momentum: terms.derivative,
+            scar: terms.integral,
+            surcharge,
+            total: raw.max(config.base_resistance) + surcharge,
+            ..Self::default()
+        }
+    }
+
+    /// Name and value of the largest non-base contributor
+    pub fn dominant(&self) -> (&'static str, f64) {
+        [
+            ("latency", self.latency),
+            ("error", self.error),
+            ("saturation", self.saturation),
+            ("momentum", self.momentum),
+            ("scar", self.scar),
+            ("slow_scar", self.slow_scar),
+            ("staleness", self.staleness),
+            ("surcharge", self.surcharge),
+            ("soft_start", self.soft_start),
+        ]
+        .into_iter()
+        .fold(
+            ("base", 0.0),
+            |best, term| {
+                if term.1 > best.1 {
+                    term
+                } else {
+                    best
+                }
+            },
+        )
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resistance::calculate_resistance;
+
+    #[test]
+    fn test_breakdown_matches_resistance() {
+        let pressure = PressureVector::new(0.5, 0.2, 0.3);
+        let config = PhysicsConfig::default();
+        let weights = SensitivityWeights::default();
+
+        let r = calculate_resistance(&pressure, Momentum(0.01), Scar(7.0), &weights, &config, 1.5);
+        let b = ResistanceBreakdown::compute(
+            &pressure,
+            Momentum(0.01),
+            Scar(7.0),
+            &weights,
+            &config,
+            1.5,
+            0.0,
+        );
+
+        assert!((b.total - r.0).abs() < 1e-10);
+        assert_eq!(b.dominant().0, "scar");
+    }
+
+    #[test]
+    fn test_breakdown_reports_applied_caps() {
+        let pressure = PressureVector::new(0.9, 0.2, 0.3);
+        let config = PhysicsConfig {
+            term_caps: Some(TermCaps {
+                scar: Some(5.0),
+                staleness: Some(20.0),
+                ..TermCaps::default()
+            }),
+            ..PhysicsConfig::default()
+        };
+        let weights = SensitivityWeights::default();
+
+        let r = calculate_resistance(
+            &pressure,
+            Momentum(0.01),
+            Scar(40.0),
+            &weights,
+            &config,
+            1.5,
+        );
+        let b = ResistanceBreakdown::compute(
+            &pressure,
+            Momentum(0.01),
+            Scar(40.0),
+            &weights,
+            &config,
+            1.5,
+            0.0,
+        );
+        assert_eq!(b.total.to_bits(), r.0.to_bits());
+        assert_eq!(b.scar, 5.0);
+        assert_eq!(b.staleness, 1.5);
+        assert_eq!(
+            b.caps,
+            TermCaps {
+                scar: Some(5.0),
+                ..TermCaps::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_healthy_breakdown_is_base() {
+        let b = ResistanceBreakdown::compute(
+            &PressureVector::new(0.0, 0.0, 0.0),
+            Momentum(0.0),
+            Scar(0.0),
+            &SensitivityWeights::default(),
+            &PhysicsConfig::default(),
+            0.0,
+            0.0,
+        );
+        assert_eq!(b.total, 10.0);
+        assert_eq!(b.dominant(), ("base", 0.0));
+    }
+}
@@ -0,0 +1,270 @@
+/**
+ * Column-per-signal export of history and simulation output.
+ *
+ * Analysts want runs in pandas or DuckDB, not nested JSON. `Table` lays
+ * an engine history (or a simlab `SimReport`) out as named columns, one
+ * per signal, and writes them as CSV (header row, shortest round-trip
+ * decimals, mode as its lowercase label) or — with the "parquet" feature
+ * on native targets — as a single-row-group Parquet file with Float64,
+ * UInt64 and Utf8 columns.
+ */
+use std::io::{self, Write};
+
+use crate::history::{History, HistorySample};
+#[cfg(any(test, feature = "simlab"))]
+use crate::simlab::{SimReport, SimTick};
+
+/// Values of one column
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    Float(Vec<f64>),
+    Count(Vec<u64>),
+    Label(Vec<&'static str>),
+}
+
+impl ColumnData {
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Float(values) => values.len(),
+            ColumnData::Count(values) => values.len(),
+            ColumnData::Label(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// One named signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: &'static str,
+    pub data: ColumnData,
+}
+
+/// Equal-length columns ready to write
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Table {
+    columns: Vec<Column>,
+}
+
+impl Table {
+    /// `timestamp_ms`, the pressure axes, resistance, scar, momentum, mode
+    pub fn from_history(history: &History) -> Self {
+        let float =
+            |f: fn(&HistorySample) -> f64| ColumnData::Float(history.iter().map(f).collect());
+        Self {
+            columns: vec![
+                column("timestamp_ms", float(|s| s.timestamp_ms)),
+                column("latency", float(|s| s.pressure.latency)),
+                column("error", float(|s| s.pressure.error)),
+                column("saturation", float(|s| s.pressure.saturation)),
+                column("resistance", float(|s| s.resistance.0)),
+                column("scar", float(|s| s.scar.0)),
+                column("momentum", float(|s| s.momentum.0)),
+                column(
+                    "mode",
+                    ColumnData::Label(history.iter().map(|s| s.mode.as_str()).collect()),
+                ),
+            ],
+        }
+    }
+
+    /// One row per simulation step, one column per `SimTick` field
+    #[cfg(any(test, feature = "simlab"))]
+    pub fn from_sim(report: &SimReport) -> Self {
+        let ticks = &report.ticks;
+        let float = |f: fn(&SimTick) -> f64| ColumnData::Float(ticks.iter().map(f).collect());
+        let count = |f: fn(&SimTick) -> usize| {
+            ColumnData::Count(ticks.iter().map(|t| f(t) as u64).collect())
+        };
+        Self {
+            columns: vec![
+                column("at_ms", float(|t| t.at_ms)),
+                column("offered", float(|t| t.offered)),
+                column("admitted", float(|t| t.admitted)),
+                column("shed", float(|t| t.shed)),
+                column("goodput", float(|t| t.goodput)),
+                column("failed", float(|t| t.failed)),
+                column("open_breakers", count(|t| t.open_breakers)),
+                column("metastable", count(|t| t.metastable)),
+                column("max_latency_ms", float(|t| t.max_latency_ms)),
+            ],
+        }
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.columns.first().map_or(0, |c| c.data.len())
+    }
+
+    /// Header plus one line per row; returns the row count
+    ///
+    /// Writes cell by cell, so wrap files in a `BufWriter`.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<usize> {
+        let header: Vec<&str> = self.columns.iter().map(|c| c.name).collect();
+        writeln!(writer, "{}", header.join(","))?;
+        for row in 0..self.rows() {
+            for (index, column) in self.columns.iter().enumerate() {
+                if index > 0 {
+                    writer.write_all(b",")?;
+                }
+                match &column.data {
+                    ColumnData::Float(values) => write!(writer, "{}", values[row])?,
+                    ColumnData::Count(values) => write!(writer, "{}", values[row])?,
+                    ColumnData::Label(values) => writer.write_all(values[row].as_bytes())?,
+                }
+            }
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(self.rows())
+    }
+}
+
+fn column(name: &'static str, data: ColumnData) -> Column {
+    Column { name, data }
+}
+
+// ============================================================================
+// PARQUET (feature "parquet", native only)
+// ============================================================================
+
+#[cfg(all(feature = "parquet", not(target_arch = "wasm32")))]
+mod parquet_writer {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, UInt64Array};
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+
+    use super::{ColumnData, Table};
+    use crate::error::AtrionError;
+
+    fn export_error(err: impl std::fmt::Display) -> AtrionError {
+        AtrionError::Export(err.to_string())
+    }
+
+    impl Table {
+        /// Write the table as one Parquet row group; returns the row count
+        pub fn write_parquet<W: Write + Send>(&self, writer: W) -> Result<usize, AtrionError> {
+            let mut fields = Vec::with_capacity(self.columns.len());
+            let mut arrays: Vec<ArrayRef> = Vec::with_capacity(self.columns.len());
+            for column in &self.columns {
+                let (data_type, array): (DataType, ArrayRef) = match &column.data {
+                    ColumnData::Float(values) => (
+                        DataType::Float64,
+                        Arc::new(Float64Array::from(values.clone())),
+                    ),
+                    ColumnData::Count(values) => (
+                        DataType::UInt64,
+                        Arc::new(UInt64Array::from(values.clone())),
+                    ),
+                    ColumnData::Label(values) => {
+                        (DataType::Utf8, Arc::new(StringArray::from(values.clone())))
+                    }
+                };
+                fields.push(Field::new(column.name, data_type, false));
+                arrays.push(array);
+            }
+            let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+                .map_err(export_error)?;
+            let mut writer =
+                ArrowWriter::try_new(writer, batch.schema(), None).map_err(export_error)?;
+            writer.write(&batch).map_err(export_error)?;
+            writer.close().map_err(export_error)?;
+            Ok(self.rows())
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    fn engine() -> TargetEngine {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 1..=20 {
+            engine.tick(i as f64 * 100.0, PressureVector::new(0.25, 0.0, 0.5));
+        }
+        engine
+    }
+
+    #[test]
+    fn test_history_csv_has_a_column_per_signal() {
+        let table = Table::from_history(engine().history());
+        let mut out = Vec::new();
+        assert_eq!(table.write_csv(&mut out).unwrap(), 20);
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 21);
+        assert_eq!(
+            lines[0],
+            "timestamp_ms,latency,error,saturation,resistance,scar,momentum,mode"
+        );
+        assert!(lines[1].starts_with("100,0.25,0,0.5,"));
+        assert!(lines[1].ends_with(",bootstrap"));
+        assert!(lines[20].ends_with(",operational"));
+    }
+
+    #[test]
+    fn test_sim_csv_keeps_counts_integral() {
+        let tick = |at_ms: f64, open_breakers: usize| SimTick {
+            at_ms,
+            offered: 20.0,
+            admitted: 18.5,
+            shed: 1.5,
+            goodput: 18.0,
+            failed: 0.5,
+            open_breakers,
+            metastable: 0,
+            max_latency_ms: 42.0,
+        };
+        let report = SimReport {
+            ticks: vec![tick(100.0, 0), tick(200.0, 3)],
+        };
+        let mut out = Vec::new();
+        Table::from_sim(&report).write_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "at_ms,offered,admitted,shed,goodput,failed,open_breakers,metastable,max_latency_ms\n\
+             100,20,18.5,1.5,18,0.5,0,0,42\n\
+             200,20,18.5,1.5,18,0.5,3,0,42\n"
+        );
+    }
+
+    #[cfg(all(feature = "parquet", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_parquet_round_trip() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let table = Table::from_history(engine().history());
+        let path =
+            std::env::temp_dir().join(format!("atrion-export-{}.parquet", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        assert_eq!(table.write_parquet(file).unwrap(), 20);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(Result::unwrap).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 20);
+        let schema = batches[0].schema();
+        let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names[0], "timestamp_ms");
+        assert_eq!(names[7], "mode");
+    }
+}
@@ -0,0 +1,353 @@
+/**
+ * Envoy ext_authz adapter (feature "envoy", native only).
+ *
+ * Implements `envoy.service.auth.v3.Authorization/Check` so an Envoy mesh
+ * can use atrion for admission control without a custom filter. Each
+ * check resolves a target (upstream) name and runs the pool's voltage
+ * gate against it.
+ *
+ * Only the fields used here are mirrored from
+ * envoy/service/auth/v3/external_auth.proto; prost skips the rest.
+ */
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tonic::codegen::{http, BoxFuture, Context, Poll, Service};
+use tonic::server::{Grpc, NamedService, UnaryService};
+use tonic::{Request, Response, Status};
+use tonic_prost::ProstCodec;
+
+use crate::admission::AdmissionDecision;
+use crate::pool::EnginePool;
+
+/// Fully-qualified gRPC service name
+pub const SERVICE_NAME: &str = "envoy.service.auth.v3.Authorization";
+
+/// google.rpc.Code values used in check responses
+const CODE_OK: i32 = 0;
+const CODE_UNAVAILABLE: i32 = 14;
+
+// ============================================================================
+// MESSAGES (subset of envoy.service.auth.v3)
+// ============================================================================
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CheckRequest {
+    #[prost(message, optional, tag = "1")]
+    pub attributes: Option<AttributeContext>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AttributeContext {
+    #[prost(message, optional, tag = "4")]
+    pub request: Option<AttributeRequest>,
+    /// Per-route `check_settings.context_extensions`
+    #[prost(map = "string, string", tag = "10")]
+    pub context_extensions: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AttributeRequest {
+    #[prost(message, optional, tag = "2")]
+    pub http: Option<HttpRequest>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HttpRequest {
+    #[prost(string, tag = "2")]
+    pub method: String,
+    /// Header names are lowercased by Envoy
+    #[prost(map = "string, string", tag = "3")]
+    pub headers: HashMap<String, String>,
+    #[prost(string, tag = "4")]
+    pub path: String,
+    #[prost(string, tag = "5")]
+    pub host: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HttpStatus {
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HeaderValue {
+    #[prost(string, tag = "1")]
+    pub key: String,
+    #[prost(string, tag = "2")]
+    pub value: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HeaderValueOption {
+    #[prost(message, optional, tag = "1")]
+    pub header: Option<HeaderValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeniedHttpResponse {
+    #[prost(message, optional, tag = "1")]
+    pub status: Option<HttpStatus>,
+    #[prost(message, repeated, tag = "2")]
+    pub headers: Vec<HeaderValueOption>,
+    #[prost(string, tag = "3")]
+    pub body: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct OkHttpResponse {}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum HttpResponse {
+    #[prost(message, tag = "2")]
+    DeniedResponse(DeniedHttpResponse),
+    #[prost(message, tag = "3")]
+    OkResponse(OkHttpResponse),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CheckResponse {
+    #[prost(message, optional, tag = "1")]
+    pub status: Option<RpcStatus>,
+    #[prost(oneof = "HttpResponse", tags = "2, 3")]
+    pub http_response: Option<HttpResponse>,
+}
+
+impl CheckResponse {
+    pub fn is_ok(&self) -> bool {
+        self.status.as_ref().is_some_and(|s| s.code == CODE_OK)
+    }
+}
+
+// ============================================================================
+// SERVICE
+// ============================================================================
+
+/// How a check request is mapped onto the pool
+#[derive(Debug, Clone)]
+pub struct ExtAuthzConfig {
+    /// Context extension naming the upstream (set per route in Envoy)
+    pub target_extension: String,
+    /// Request header naming the upstream (fallback)
+    pub target_header: String,
+    /// Request header carrying the request voltage
+    pub voltage_header: String,
+    /// Voltage when the header is missing or malformed
+    pub default_voltage: f64,
+    /// HTTP status returned to the client on rejection
+    pub reject_status: i32,
+}
+
+impl Default for ExtAuthzConfig {
+    fn default() -> Self {
+        Self {
+            target_extension: "atrion_target".to_string(),
+            target_header: "x-atrion-target".to_string(),
+            voltage_header: "x-atrion-voltage".to_string(),
+            default_voltage: 100.0,
+            reject_status: 503,
+        }
+    }
+}
+
+/// ext_authz check service over a shared EnginePool
+#[derive(Clone)]
+pub struct ExtAuthzServer {
+    pool: Arc<Mutex<EnginePool>>,
+    config: Arc<ExtAuthzConfig>,
+}
+
+impl ExtAuthzServer {
+    pub fn new(pool: Arc<Mutex<EnginePool>>, config: ExtAuthzConfig) -> Self {
+        Self {
+            pool,
+            config: Arc::new(config),
+        }
+    }
+
+    /// Resolve target and voltage from a check request
+    ///
+    /// Target precedence: context extension, then header, then `:authority`.
+    pub fn resolve(&self, request: &CheckRequest) -> (String, f64) {
+        let attributes = request.attributes.as_ref();
+        let http = attributes
+            .and_then(|a| a.request.as_ref())
+            .and_then(|r| r.http.as_ref());
+        let header = |name: &str| http.and_then(|h| h.headers.get(name));
+
+        let target = attributes
+            .and_then(|a| a.context_extensions.get(&self.config.target_extension))
+            .or_else(|| header(&self.config.target_header))
+            .or_else(|| http.map(|h| &h.host))
+            .cloned()
+            .unwrap_or_default();
+        let voltage = header(&self.config.voltage_header)
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|v| v.is_finite())
+            .unwrap_or(self.config.default_voltage);
+        (target, voltage)
+    }
+
+    /// Run the voltage gate for one check request
+    pub fn check(&self, request: &CheckRequest) -> CheckResponse {
+        let (target, voltage) = self.resolve(request);
+        match self.lock().try_admit(&target, voltage) {
+            AdmissionDecision::Admit => CheckResponse {
+                status: Some(RpcStatus {
+                    code: CODE_OK,
+                    message: String::new(),
+                }),
+                http_response: Some(HttpResponse::OkResponse(OkHttpResponse {})),
+            },
+            AdmissionDecision::Reject(reason) => CheckResponse {
+                status: Some(RpcStatus {
+                    code: CODE_UNAVAILABLE,
+                    message: format!("atrion: {} ({target})", reason.as_str()),
+                }),
+                http_response: Some(HttpResponse::DeniedResponse(DeniedHttpResponse {
+                    status: Some(HttpStatus {
+                        code: self.config.reject_status,
+                    }),
+                    headers: vec![HeaderValueOption {
+                        header: Some(HeaderValue {
+                            key: "x-atrion-reject".to_string(),
+                            value: reason.as_str().to_string(),
+                        }),
+                    }],
+                    body: String::new(),
+                })),
+            },
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, EnginePool> {
+        self.pool
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl NamedService for ExtAuthzServer {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+struct CheckMethod(ExtAuthzServer);
+
+impl UnaryService<CheckRequest> for CheckMethod {
+    type Response = CheckResponse;
+    type Future = BoxFuture<Response<CheckResponse>, Status>;
+
+    fn call(&mut self, request: Request<CheckRequest>) -> Self::Future {
+        let response = self.0.check(request.get_ref());
+        Box::pin(async move { Ok(Response::new(response)) })
+    }
+}
+
+impl<B> Service<http::Request<B>> for ExtAuthzServer
+where
+    B: tonic::codegen::Body + Send + 'static,
+    B::Error: Into<tonic::codegen::StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let server = self.clone();
+        match req.uri().path() {
+            "/envoy.service.auth.v3.Authorization/Check" => Box::pin(async move {
+                let mut grpc = Grpc::new(ProstCodec::default());
+                Ok(grpc.unary(CheckMethod(server), req).await)
+            }),
+            _ => Box::pin(async move { Ok(Status::unimplemented("").into_http()) }),
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    fn request(extension: Option<&str>, headers: &[(&str, &str)]) -> CheckRequest {
+        CheckRequest {
+            attributes: Some(AttributeContext {
+                request: Some(AttributeRequest {
+                    http: Some(HttpRequest {
+                        method: "GET".into(),
+                        headers: headers
+                            .iter()
+                            .map(|(k, v)| (k.to_string(), v.to_string()))
+                            .collect(),
+                        path: "/".into(),
+                        host: "frontend".into(),
+                    }),
+                }),
+                context_extensions: extension
+                    .map(|t| HashMap::from([("atrion_target".to_string(), t.to_string())]))
+                    .unwrap_or_default(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_resolve_target_precedence() {
+        let pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let server = ExtAuthzServer::new(Arc::new(Mutex::new(pool)), ExtAuthzConfig::default());
+
+        let headers = [("x-atrion-target", "orders"), ("x-atrion-voltage", "42")];
+        assert_eq!(
+            server.resolve(&request(Some("payments"), &headers)),
+            ("payments".into(), 42.0)
+        );
+        assert_eq!(
+            server.resolve(&request(None, &headers)),
+            ("orders".into(), 42.0)
+        );
+        assert_eq!(
+            server.resolve(&request(None, &[])),
+            ("frontend".into(), 100.0)
+        );
+    }
+
+    #[test]
+    fn test_check_denies_open_circuit() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 0..40 {
+            pool.tick(
+                "payments",
+                i as f64 * 100.0,
+                PressureVector::new(1.0, 1.0, 1.0),
+            );
+        }
+        let server = ExtAuthzServer::new(Arc::new(Mutex::new(pool)), ExtAuthzConfig::default());
+
+        assert!(server.check(&request(Some("search"), &[])).is_ok());
+        let denied = server.check(&request(Some("payments"), &[]));
+        assert!(!denied.is_ok());
+        match denied.http_response {
+            Some(HttpResponse::DeniedResponse(d)) => {
+                assert_eq!(d.status.unwrap().code, 503);
+                assert_eq!(d.headers[0].header.as_ref().unwrap().value, "circuit_open");
+            }
+            other => panic!("expected denied response, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+/**
+ * Per-tenant fairness (weighted max-min sharing).
+ *
+ * When resistance shrinks the admission budget, the budget is split across
+ * tenants by weighted max-min fairness over the current window's demand:
+ * light tenants get everything they ask for, heavy tenants share the rest
+ * in proportion to their weights. A noisy tenant can no longer starve
+ * quiet ones.
+ */
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Fairness layer configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairnessConfig {
+    /// Accounting window length (counters reset every window)
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Weight for tenants without an explicit entry
+    #[serde(alias = "default_weight")]
+    pub default_weight: f64,
+    /// Explicit tenant weights
+    pub weights: HashMap<String, f64>,
+}
+
+impl Default for FairnessConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 1000.0,
+            default_weight: 1.0,
+            weights: HashMap::new(),
+        }
+    }
+}
+
+/// Per-tenant accounting for the current window
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TenantAccount {
+    pub weight: f64,
+    /// Requests offered this window
+    pub demand: u64,
+    /// Requests admitted this window
+    pub admitted: u64,
+    /// Requests rejected by the fairness layer this window
+    pub rejected: u64,
+}
+
+/// Weighted max-min fair allocator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantFairness {
+    config: FairnessConfig,
+    #[serde(alias = "window_start_ms")]
+    window_start_ms: f64,
+    tenants: HashMap<String, TenantAccount>,
+}
+
+impl TenantFairness {
+    pub fn new(config: FairnessConfig) -> Self {
+        Self {
+            config,
+            window_start_ms: f64::NEG_INFINITY,
+            tenants: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &FairnessConfig {
+        &self.config
+    }
+
+    /// Accounting for every tenant seen in the current window
+    pub fn accounts(&self) -> &HashMap<String, TenantAccount> {
+        &self.tenants
+    }
+
+    /// Record a request from `tenant` and decide whether it fits its share
+    ///
+    /// `ratio` is the fraction of total demand the target can absorb
+    /// (see `admission::admission_ratio`).
+    pub fn admit(&mut self, now_ms: f64, tenant: &str, ratio: f64) -> bool {
+        if now_ms - self.window_start_ms >= self.config.window_ms {
+            self.window_start_ms = now_ms;
+            self.tenants.clear();
+        }
+
+        let weight = self.weight_of(tenant);
+        let account = self
+            .tenants
+            .entry(tenant.to_string())
+            .or_insert(TenantAccount {
+                weight,
+                ..TenantAccount::default()
+            });
+        account.demand += 1;
+
+        let total_demand: u64 = self.tenants.values().map(|a| a.demand).sum();
+        let capacity = ratio.clamp(0.0, 1.0) * total_demand as f64;
+        let share = allocation_for(&self.tenants, tenant, capacity);
+
+        let account = self.tenants.get_mut(tenant).expect("tenant inserted above");
+        if account.admitted as f64 + 1.0 <= share + 1e-9 {
+            account.admitted += 1;
+            true
+        } else {
+            account.rejected += 1;
+            false
+        }
+    }
+
+    fn weight_of(&self, tenant: &str) -> f64 {
+        self.config
+            .weights
+            .get(tenant)
+            .copied()
+            .unwrap_or(self.config.default_weight)
+            .max(f64::MIN_POSITIVE)
+    }
+}
+
+/// Weighted max-min allocation of `capacity` for a single tenant
+fn allocation_for(tenants: &HashMap<String, TenantAccount>, tenant: &str, capacity: f64) -> f64 {
+    // Progressive filling: satisfy tenants in order of demand/weight
+    let mut order: Vec<(&String, &TenantAccount)> = tenants.iter().collect();
+    order.sort_by(|(_, a), (_, b)| {
+        (a.demand as f64 / a.weight).total_cmp(&(b.demand as f64 / b.weight))
+    });
+
+    let mut remaining = capacity;
+    let mut remaining_weight: f64 = order.iter().map(|(_, a)| a.weight).sum();
+
+    for (name, account) in order {
+        let fair = remaining * account.weight / remaining_weight;
+        let allocation = (account.demand as f64).min(fair);
+        if name == tenant {
+            return allocation;
+        }
+        remaining -= allocation;
+        remaining_weight -= account.weight;
+    }
+
+    0.0
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unconstrained_admits_everything() {
+        let mut fairness = TenantFairness::new(FairnessConfig::default());
+        for _ in 0..100 {
+            assert!(fairness.admit(0.0, "noisy", 1.0));
+        }
+    }
+
+    #[test]
+    fn test_quiet_tenant_not_starved() {
+        let mut fairness = TenantFairness::new(FairnessConfig::default());
+
+        // Noisy tenant floods, quiet tenant sends a trickle; half the budget
+        let mut quiet_admitted = 0;
+        for i in 0..200 {
+            fairness.admit(0.0, "noisy", 0.5);
+            if i % 20 == 19 && fairness.admit(0.0, "quiet", 0.5) {
+                quiet_admitted += 1;
+            }
+        }
+
+        assert_eq!(quiet_admitted, 10);
+        let noisy = fairness.accounts()["noisy"];
+        assert!(noisy.rejected > 0);
+        assert!(noisy.admitted <= 105);
+    }
+
+    #[test]
+    fn test_weights_split_contended_budget() {
+        let mut config = FairnessConfig::default();
+        config.weights.insert("gold".to_string(), 3.0);
+        let mut fairness = TenantFairness::new(config);
+
+        for _ in 0..100 {
+            fairness.admit(0.0, "gold", 0.4);
+            fairness.admit(0.0, "bronze", 0.4);
+        }
+
+        let gold = fairness.accounts()["gold"].admitted as f64;
+        let bronze = fairness.accounts()["bronze"].admitted as f64;
+        assert!((gold / bronze - 3.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_window_resets_accounting() {
+        let mut fairness = TenantFairness::new(FairnessConfig::default());
+        fairness.admit(0.0, "a", 0.0);
+        assert_eq!(fairness.accounts()["a"].rejected, 1);
+
+        fairness.admit(1000.0, "b", 1.0);
+        assert!(!fairness.accounts().contains_key("a"));
+    }
+}
@@ -0,0 +1,106 @@
+/**
+ * Fast-math kernels for the tick hot path.
+ *
+ * Every tick pays for two `exp()` calls (scar and momentum decay) and a
+ * floor clamp on resistance. With the `fast-math` feature those go
+ * through `exp_approx` (exp2 range reduction plus a degree-7 polynomial,
+ * relative error below 1e-8) and `max_branchless`, which skips the NaN
+ * handling of `f64::max`. Without the feature `exp` and `max` are the
+ * libm/std calls, so default builds stay bit-identical to other ports.
+ */
+use std::f64::consts::{LN_2, LOG2_E};
+
+/// `exp_approx` clamps its input to this range (normal f64 results)
+const EXP_MIN: f64 = -708.0;
+const EXP_MAX: f64 = 709.0;
+
+/// 1.5 × 2^52: adding it rounds to an integer held in the low mantissa bits
+const ROUND_SHIFT: f64 = 6_755_399_441_055_744.0;
+
+/// e^x as used by the physics stages
+#[inline]
+pub fn exp(x: f64) -> f64 {
+    if cfg!(feature = "fast-math") {
+        exp_approx(x)
+    } else {
+        x.exp()
+    }
+}
+
+/// Larger of `a` and `b` as used by the physics stages
+#[inline]
+pub fn max(a: f64, b: f64) -> f64 {
+    if cfg!(feature = "fast-math") {
+        max_branchless(a, b)
+    } else {
+        a.max(b)
+    }
+}
+
+/// e^x with relative error below 1e-8 on [−708, 709]
+///
+/// Inputs outside that range are clamped, so very negative exponents give
+/// about 3e−308 rather than 0. Splits x·log2(e) into an integer n and a
+/// remainder in [−½, ½], builds 2^n from exponent bits and evaluates
+/// e^(remainder·ln 2) by Horner; no branches or libm calls.
+#[inline]
+pub fn exp_approx(x: f64) -> f64 {
+    let x = max_branchless(x, EXP_MIN);
+    let x = if x < EXP_MAX { x } else { EXP_MAX };
+    let scaled = x * LOG2_E;
+    let shifted = scaled + ROUND_SHIFT;
+    let n = shifted - ROUND_SHIFT;
+    let t = (scaled - n) * LN_2;
+    // Taylor series of e^t, |t| ≤ ln2/2
+    let p = 1.0
+        + t * (1.0
+            + t * (1.0 / 2.0
+                + t * (1.0 / 6.0
+                    + t * (1.0 / 24.0
+                        + t * (1.0 / 120.0 + t * (1.0 / 720.0 + t * (1.0 / 5040.0)))))));
+    // Low mantissa bits of `shifted` hold n in two's complement
+    let two_n = f64::from_bits(((shifted.to_bits() as i32 as i64 + 1023) as u64) << 52);
+    p * two_n
+}
+
+/// `a.max(b)` without NaN handling: compiles to a single `maxsd`/`fmax`
+///
+/// Returns `b` when either side is NaN.
+#[inline]
+pub fn max_branchless(a: f64, b: f64) -> f64 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exp_approx_relative_error() {
+        let mut worst: f64 = 0.0;
+        for i in -70_000..=7_000 {
+            let x = i as f64 * 0.01;
+            let exact = x.exp();
+            worst = worst.max(((exp_approx(x) - exact) / exact).abs());
+        }
+        assert!(worst < 1e-8, "worst relative error {worst}");
+        assert_eq!(exp_approx(0.0), 1.0);
+        assert!(exp_approx(-1e6) < 1e-300);
+    }
+
+    #[test]
+    fn test_max_branchless() {
+        assert_eq!(max_branchless(1.0, 2.0), 2.0);
+        assert_eq!(max_branchless(3.0, -2.0), 3.0);
+        assert_eq!(max_branchless(f64::NAN, 2.0), 2.0);
+        assert_eq!(max(0.5, 0.1), 0.5);
+    }
+}
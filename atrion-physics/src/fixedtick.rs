@@ -0,0 +1,78 @@
+/**
+ * Fixed-interval ticking with cached decay factors.
+ *
+ * Scar, slow scar and momentum each decay by e^(−λΔt) per tick. When the
+ * caller ticks on a fixed interval, Δt never changes, so `FixedTick`
+ * computes the three factors once per config and ticks whose Δt equals
+ * the interval reuse them; any other Δt (late, early or first ticks)
+ * falls back to computing its own. The cached factors are the exact
+ * values the uncached path would produce, so results are bit-identical.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::momentum;
+use crate::scar;
+use crate::types::{PhysicsConfig, ScarPrecision};
+
+/// Decay factors for one Δt under one config
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecayFactors {
+    /// `None` under fixed-point scar accounting, which has its own table
+    pub scar: Option<f64>,
+    /// `None` without dual scar
+    #[serde(alias = "slow_scar")]
+    pub slow_scar: Option<f64>,
+    pub momentum: f64,
+}
+
+impl DecayFactors {
+    pub fn new(delta_t_ms: f64, config: &PhysicsConfig) -> Self {
+        Self {
+            scar: (config.scar_precision == ScarPrecision::Float)
+                .then(|| scar::scar_decay(delta_t_ms)),
+            slow_scar: config
+                .dual_scar
+                .as_ref()
+                .map(|dual| scar::slow_scar_decay(dual, delta_t_ms)),
+            momentum: momentum::momentum_decay(delta_t_ms, config),
+        }
+    }
+}
+
+/// Tick interval plus the decay factors cached for it
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixedTick {
+    #[serde(alias = "interval_ms")]
+    interval_ms: f64,
+    factors: DecayFactors,
+}
+
+impl FixedTick {
+    pub fn new(interval_ms: f64, config: &PhysicsConfig) -> Self {
+        Self {
+            interval_ms,
+            factors: DecayFactors::new(interval_ms, config),
+        }
+    }
+
+    pub fn interval_ms(&self) -> f64 {
+        self.interval_ms
+    }
+
+    pub fn factors(&self) -> &DecayFactors {
+        &self.factors
+    }
+
+    /// Cached factors if `delta_t_ms` is the fixed interval
+    #[inline]
+    pub fn factors_for(&self, delta_t_ms: f64) -> Option<&DecayFactors> {
+        (delta_t_ms == self.interval_ms).then_some(&self.factors)
+    }
+
+    /// Recompute the factors after a config change
+    pub fn refresh(&mut self, config: &PhysicsConfig) {
+        self.factors = DecayFactors::new(self.interval_ms, config);
+    }
+}
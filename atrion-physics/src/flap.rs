@@ -0,0 +1,251 @@
+/**
+ * Breaker flap detection and adaptive hysteresis.
+ *
+ * A breaker that closes, re-trips and closes again every few seconds is
+ * worse than one that stays open: each close lets a burst through. The
+ * detector counts closes (CircuitBreaker → Operational) in a sliding
+ * window; after `flaps` of them it raises its damping level, which
+ *
+ * - lowers the effective recovery threshold by `widen` per level
+ *   (a wider hysteresis band: resistance must fall further to close), and
+ * - holds the breaker open for at least `min_open_ms` per level.
+ *
+ * The level steps back down after `relax_ms` without a flap. Every change
+ * is reported as a `FlapAdapted` event.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::EngineEvent;
+use crate::types::OperationalMode;
+
+/// Flap detection configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct FlapConfig {
+    /// Closes within `window_ms` that count as flapping (K)
+    pub flaps: usize,
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Fraction the recovery threshold drops per level (0..1)
+    pub widen: f64,
+    /// Minimum open time added per level
+    #[serde(alias = "min_open_ms")]
+    pub min_open_ms: f64,
+    #[serde(alias = "max_level")]
+    pub max_level: u32,
+    /// Quiet time before the level steps back down
+    #[serde(alias = "relax_ms")]
+    pub relax_ms: f64,
+}
+
+impl Default for FlapConfig {
+    fn default() -> Self {
+        Self {
+            flaps: 3,
+            window_ms: 60_000.0,
+            widen: 0.25,
+            min_open_ms: 5_000.0,
+            max_level: 3,
+            relax_ms: 300_000.0,
+        }
+    }
+}
+
+/// Close history and current damping level
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlapDetector {
+    config: FlapConfig,
+    /// Recent closes, oldest first
+    closes: VecDeque<f64>,
+    level: u32,
+    /// Last flap or level change (relax timer)
+    changed_ms: f64,
+    opened_ms: Option<f64>,
+}
+
+impl FlapDetector {
+    pub fn new(config: FlapConfig) -> Self {
+        Self {
+            config,
+            closes: VecDeque::new(),
+            level: 0,
+            changed_ms: 0.0,
+            opened_ms: None,
+        }
+    }
+
+    pub fn config(&self) -> &FlapConfig {
+        &self.config
+    }
+
+    /// Damping level (0 = not flapping)
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// Recovery threshold after widening the band `base` starts from
+    pub fn recovery_threshold(&self, base: f64) -> f64 {
+        base * (1.0 - self.config.widen.clamp(0.0, 1.0)).powi(self.level as i32)
+    }
+
+    /// Minimum time the breaker stays open at the current level
+    pub fn min_open_ms(&self) -> f64 {
+        self.config.min_open_ms * self.level as f64
+    }
+
+    /// Whether an open breaker has been open long enough to close
+    pub fn may_close(&self, now_ms: f64) -> bool {
+        self.opened_ms
+            .is_none_or(|opened| now_ms - opened >= self.min_open_ms())
+    }
+
+    /// Record a tick's mode transition; returns a `FlapAdapted` event when
+    /// the level changes
+    pub fn observe(
+        &mut self,
+        now_ms: f64,
+        from: OperationalMode,
+        to: OperationalMode,
+        base_recovery: f64,
+    ) -> Option<EngineEvent> {
+        match (from, to) {
+            (from, OperationalMode::CircuitBreaker) if from != OperationalMode::CircuitBreaker => {
+                self.opened_ms = Some(now_ms);
+            }
+            (OperationalMode::CircuitBreaker, to) if to != OperationalMode::CircuitBreaker => {
+                self.opened_ms = None;
+                self.closes.push_back(now_ms);
+                self.changed_ms = now_ms;
+            }
+            _ => {}
+        }
+        let horizon = now_ms - self.config.window_ms;
+        while self.closes.front().is_some_and(|&close| close < horizon) {
+            self.closes.pop_front();
+        }
+
+        let flaps = self.closes.len();
+        let level = if flaps >= self.config.flaps.max(1) {
+            self.closes.clear();
+            (self.level + 1).min(self.config.max_level)
+        } else if self.level > 0 && now_ms - self.changed_ms >= self.config.relax_ms {
+            self.level - 1
+        } else {
+            return None;
+        };
+        self.changed_ms = now_ms;
+        if level == self.level {
+            return None;
+        }
+        self.level = level;
+        Some(EngineEvent::FlapAdapted {
+            at_ms: now_ms,
+            level,
+            flaps,
+            recovery_threshold: self.recovery_threshold(base_recovery),
+            min_open_ms: self.min_open_ms(),
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    use OperationalMode::{CircuitBreaker, Operational};
+
+    #[test]
+    fn test_level_rises_with_flaps_and_relaxes() {
+        let mut detector = FlapDetector::new(FlapConfig::default());
+        let mut events = Vec::new();
+        for i in 0..6 {
+            let t = i as f64 * 10_000.0;
+            events.extend(detector.observe(t, Operational, CircuitBreaker, 50.0));
+            events.extend(detector.observe(t + 5_000.0, CircuitBreaker, Operational, 50.0));
+        }
+        assert_eq!(detector.level(), 2);
+        assert_eq!(detector.recovery_threshold(50.0), 50.0 * 0.75 * 0.75);
+        assert!(matches!(
+            events[0],
+            EngineEvent::FlapAdapted {
+                level: 1,
+                flaps: 3,
+                min_open_ms: 5_000.0,
+                ..
+            }
+        ));
+
+        detector.observe(100_000.0, Operational, CircuitBreaker, 50.0);
+        assert!(!detector.may_close(109_000.0));
+        assert!(detector.may_close(110_000.0));
+
+        detector.observe(110_000.0, CircuitBreaker, Operational, 50.0);
+        assert!(detector
+            .observe(420_000.0, Operational, Operational, 50.0)
+            .is_some());
+        assert_eq!(detector.level(), 1);
+    }
+
+    #[test]
+    fn test_engine_holds_flapping_breaker_open() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_flap_detection(FlapConfig {
+            flaps: 2,
+            ..FlapConfig::default()
+        });
+        let mut now = 0.0;
+        for _ in 0..10 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        let mut open_spans = Vec::new();
+        for _ in 0..3 {
+            while engine.state().mode != CircuitBreaker {
+                now += 100.0;
+                engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+            }
+            let opened = now;
+            while engine.state().mode == CircuitBreaker {
+                now += 1_000.0;
+                engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+            }
+            open_spans.push(now - opened);
+        }
+        assert_eq!(engine.flap_detector().unwrap().level(), 1);
+        // Widened band: the third opening has to drain further to close
+        assert!(open_spans[2] > open_spans[1]);
+        assert!(engine
+            .drain_events()
+            .iter()
+            .any(|event| matches!(event, EngineEvent::FlapAdapted { level: 1, .. })));
+    }
+
+    #[test]
+    fn test_config_json_is_camel_case_and_accepts_snake_case() {
+        let config = FlapConfig {
+            min_open_ms: 2_000.0,
+            ..FlapConfig::default()
+        };
+        let json = serde_json::to_value(config).unwrap();
+        assert_eq!(json["minOpenMs"], 2_000.0);
+        assert!(json.get("min_open_ms").is_none());
+        assert_eq!(serde_json::from_value::<FlapConfig>(json).unwrap(), config);
+
+        let legacy: FlapConfig = serde_json::from_str(
+            r#"{ "flaps": 3, "window_ms": 60000, "widen": 0.25,
+                "min_open_ms": 2000, "max_level": 3, "relax_ms": 300000 }"#,
+        )
+        .unwrap();
+        assert_eq!(legacy, config);
+    }
+}
@@ -0,0 +1,147 @@
+/**
+ * Goodput estimation from request outcomes.
+ *
+ * Resistance says how hard the engine pushes back; goodput says how much
+ * useful work got through. Hosts report what happened to admitted
+ * requests through `TargetEngine::record_outcomes` (`good`: succeeded,
+ * `bad`: failed or timed out). The estimator keeps those counts in
+ * `GOODPUT_BUCKET_MS` buckets over the longest stats window, and
+ * `RollingStats::goodput` reports them per window as totals, success
+ * ratio and successful requests per second.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::stats::WINDOW_15M_MS;
+
+/// Outcome aggregation granularity
+pub const GOODPUT_BUCKET_MS: f64 = 10_000.0;
+
+/// Goodput over a single time window
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoodputStats {
+    /// Admitted requests that succeeded
+    pub succeeded: u64,
+    /// Admitted requests that failed
+    pub failed: u64,
+    /// `succeeded / (succeeded + failed)` (0 without outcomes)
+    pub ratio: f64,
+    /// Successful requests per second over the window
+    #[serde(alias = "per_second")]
+    pub per_second: f64,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OutcomeBucket {
+    #[serde(alias = "start_ms")]
+    start_ms: f64,
+    good: u64,
+    bad: u64,
+}
+
+/// Bucketed admitted-request outcomes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GoodputEstimator {
+    buckets: VecDeque<OutcomeBucket>,
+}
+
+impl GoodputEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ingest a batch of outcomes observed at `now_ms`
+    pub fn record(&mut self, now_ms: f64, good: u64, bad: u64) {
+        let start_ms = (now_ms / GOODPUT_BUCKET_MS).floor() * GOODPUT_BUCKET_MS;
+        match self.buckets.back_mut() {
+            Some(last) if last.start_ms >= start_ms => {
+                last.good += good;
+                last.bad += bad;
+            }
+            _ => self.buckets.push_back(OutcomeBucket {
+                start_ms,
+                good,
+                bad,
+            }),
+        }
+        let horizon = now_ms - WINDOW_15M_MS;
+        while self
+            .buckets
+            .front()
+            .is_some_and(|b| b.start_ms + GOODPUT_BUCKET_MS <= horizon)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Goodput over `window_ms` ending at `now_ms`
+    pub fn stats(&self, now_ms: f64, window_ms: f64) -> GoodputStats {
+        let start_ms = now_ms - window_ms;
+        let (succeeded, failed) = self
+            .buckets
+            .iter()
+            .filter(|b| b.start_ms + GOODPUT_BUCKET_MS > start_ms && b.start_ms <= now_ms)
+            .fold((0u64, 0u64), |(g, e), b| (g + b.good, e + b.bad));
+        let total = succeeded + failed;
+        GoodputStats {
+            succeeded,
+            failed,
+            ratio: if total > 0 {
+                succeeded as f64 / total as f64
+            } else {
+                0.0
+            },
+            per_second: if window_ms > 0.0 {
+                succeeded as f64 * 1000.0 / window_ms
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::stats::WINDOW_1M_MS;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_windows_and_rates() {
+        let mut estimator = GoodputEstimator::new();
+        assert_eq!(estimator.stats(0.0, WINDOW_1M_MS), GoodputStats::default());
+        // 90 good / 10 bad per second for ten minutes
+        for second in 0..600 {
+            estimator.record(second as f64 * 1000.0, 90, 10);
+        }
+        let minute = estimator.stats(600_000.0, WINDOW_1M_MS);
+        assert_eq!(minute.succeeded, 5_400);
+        assert_eq!(minute.failed, 600);
+        assert!((minute.ratio - 0.9).abs() < 1e-12);
+        assert!((minute.per_second - 90.0).abs() < 1e-9);
+        assert_eq!(estimator.stats(600_000.0, WINDOW_15M_MS).succeeded, 54_000);
+    }
+
+    #[test]
+    fn test_engine_reports_goodput_in_stats() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 1..=600 {
+            let now = i as f64 * 100.0;
+            engine.tick(now, PressureVector::new(0.1, 0.0, 0.1));
+            engine.record_outcomes(now, 8, 2);
+        }
+        let summary = engine.rolling_summary();
+        assert_eq!(summary.one_minute.goodput.succeeded, 4_800);
+        assert_eq!(summary.one_minute.goodput.failed, 1_200);
+        assert!((summary.one_minute.goodput.ratio - 0.8).abs() < 1e-12);
+        assert_eq!(engine.rolling_stats(WINDOW_1M_MS), summary.one_minute);
+    }
+}
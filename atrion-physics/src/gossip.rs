@@ -0,0 +1,363 @@
+/**
+ * Gossip-based peer state exchange (feature "gossip", native only).
+ *
+ * For fleets without Redis: every `interval_ms` each instance sends a
+ * compact digest (scar, mode, timestamp per target) to `fan_out` peers
+ * over UDP. Received digests are merged with a staleness weight
+ * w = 0.5^(age / staleness_halflife_ms), so a fresh breaker trip on one
+ * gateway propagates quickly while old news fades out.
+ *
+ * `encode`/`decode` are transport-agnostic if UDP is not an option.
+ */
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+
+use crate::engine::TargetEngine;
+use crate::metrics::mode_value;
+use crate::pool::EnginePool;
+use crate::types::OperationalMode;
+
+const MAGIC: &[u8; 2] = b"AG";
+const VERSION: u8 = 1;
+
+/// Max payload per datagram (fits a 1500-byte MTU after IP/UDP headers)
+const MAX_PACKET_BYTES: usize = 1432;
+
+/// Gossip configuration
+#[derive(Debug, Clone)]
+pub struct GossipConfig {
+    /// Peers contacted per round
+    pub fan_out: usize,
+    /// Round interval (the caller drives rounds; informational for loops)
+    pub interval_ms: f64,
+    /// Age at which a digest carries half weight
+    pub staleness_halflife_ms: f64,
+    /// Digests older than this are ignored
+    pub max_staleness_ms: f64,
+    /// Minimum weight for a peer's open breaker to trip ours
+    pub trip_weight: f64,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            fan_out: 3,
+            interval_ms: 1000.0,
+            staleness_halflife_ms: 5000.0,
+            max_staleness_ms: 30_000.0,
+            trip_weight: 0.5,
+        }
+    }
+}
+
+/// Compact per-target digest
+#[derive(Debug, Clone, PartialEq)]
+pub struct Digest {
+    pub target: String,
+    pub scar: f32,
+    pub mode: OperationalMode,
+    pub updated_ms: f64,
+}
+
+impl Digest {
+    fn len(&self) -> usize {
+        1 + self.target.len() + 4 + 1 + 8
+    }
+}
+
+/// Digests for every target in the pool (targets sorted by id)
+pub fn digests(pool: &EnginePool) -> Vec<Digest> {
+    let mut digests: Vec<Digest> = pool
+        .iter()
+        .filter(|(id, _)| id.len() <= u8::MAX as usize)
+        .map(|(id, engine)| Digest {
+            target: id.clone(),
+            scar: engine.state().scar.0 as f32,
+            mode: engine.state().mode,
+            updated_ms: engine.state().last_updated_ms,
+        })
+        .collect();
+    digests.sort_by(|a, b| a.target.cmp(&b.target));
+    digests
+}
+
+/// Encode digests into datagrams of at most `MAX_PACKET_BYTES`
+pub fn encode(origin: &str, digests: &[Digest]) -> Vec<Vec<u8>> {
+    let origin = &origin.as_bytes()[..origin.len().min(u8::MAX as usize)];
+    let header_len = MAGIC.len() + 2 + origin.len();
+    let header = |packet: &mut Vec<u8>| {
+        packet.extend_from_slice(MAGIC);
+        packet.push(VERSION);
+        packet.push(origin.len() as u8);
+        packet.extend_from_slice(origin);
+    };
+
+    let mut packets = Vec::new();
+    let mut packet = Vec::with_capacity(MAX_PACKET_BYTES);
+    header(&mut packet);
+    for digest in digests {
+        if packet.len() > header_len && packet.len() + digest.len() > MAX_PACKET_BYTES {
+            packets.push(std::mem::take(&mut packet));
+            header(&mut packet);
+        }
+        packet.push(digest.target.len() as u8);
+        packet.extend_from_slice(digest.target.as_bytes());
+        packet.extend_from_slice(&digest.scar.to_le_bytes());
+        packet.push(mode_value(digest.mode));
+        packet.extend_from_slice(&digest.updated_ms.to_le_bytes());
+    }
+    if packet.len() > header_len {
+        packets.push(packet);
+    }
+    packets
+}
+
+/// Decode one datagram into `(origin, digests)`; `None` if malformed
+pub fn decode(packet: &[u8]) -> Option<(String, Vec<Digest>)> {
+    let mut reader = Reader(packet);
+    if reader.take(2)? != MAGIC || reader.u8()? != VERSION {
+        return None;
+    }
+    let origin_len = reader.u8()? as usize;
+    let origin = String::from_utf8(reader.take(origin_len)?.to_vec()).ok()?;
+
+    let mut digests = Vec::new();
+    while !reader.0.is_empty() {
+        let len = reader.u8()? as usize;
+        let target = String::from_utf8(reader.take(len)?.to_vec()).ok()?;
+        let scar = f32::from_le_bytes(reader.take(4)?.try_into().ok()?);
+        let mode = match reader.u8()? {
+            0 => OperationalMode::Bootstrap,
+            1 => OperationalMode::Operational,
+            2 => OperationalMode::CircuitBreaker,
+            _ => return None,
+        };
+        let updated_ms = f64::from_le_bytes(reader.take(8)?.try_into().ok()?);
+        digests.push(Digest {
+            target,
+            scar,
+            mode,
+            updated_ms,
+        });
+    }
+    Some((origin, digests))
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+}
+
+/// Staleness weight of a digest received at `now_ms` (0 when too old)
+pub fn staleness_weight(digest: &Digest, now_ms: f64, config: &GossipConfig) -> f64 {
+    let age = (now_ms - digest.updated_ms).max(0.0);
+    if age > config.max_staleness_ms {
+        return 0.0;
+    }
+    0.5f64.powf(age / config.staleness_halflife_ms.max(1e-9))
+}
+
+/// Merge a peer digest into a local engine
+///
+/// Weighted peer scar acts as a floor (see `TargetEngine::absorb_shared`);
+/// a fresh enough open breaker trips the local target too.
+pub fn merge_digest(
+    engine: &mut TargetEngine,
+    digest: &Digest,
+    now_ms: f64,
+    config: &GossipConfig,
+) {
+    let weight = staleness_weight(digest, now_ms, config);
+    if weight <= 0.0 {
+        return;
+    }
+    engine.absorb_shared(&crate::store::SharedState {
+        scar: digest.scar as f64 * weight,
+        momentum: engine.state().momentum.0,
+        updated_ms: engine.state().last_updated_ms,
+    });
+    if digest.mode == OperationalMode::CircuitBreaker && weight >= config.trip_weight {
+        engine.force_mode(OperationalMode::CircuitBreaker);
+    }
+}
+
+/// UDP gossip participant
+pub struct GossipNode {
+    origin: String,
+    config: GossipConfig,
+    peers: Vec<SocketAddr>,
+    socket: UdpSocket,
+    round: usize,
+}
+
+impl GossipNode {
+    /// Bind `local` (non-blocking) and gossip with `peers`
+    pub fn bind(
+        origin: impl Into<String>,
+        local: SocketAddr,
+        peers: Vec<SocketAddr>,
+        config: GossipConfig,
+    ) -> io::Result<Self> {
+        let socket = UdpSocket::bind(local)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            origin: origin.into(),
+            config,
+            peers,
+            socket,
+            round: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn config(&self) -> &GossipConfig {
+        &self.config
+    }
+
+    /// Send the pool digest to the next `fan_out` peers (round-robin)
+    ///
+    /// Returns the peers contacted.
+    pub fn gossip(&mut self, pool: &EnginePool) -> io::Result<Vec<SocketAddr>> {
+        if self.peers.is_empty() {
+            return Ok(Vec::new());
+        }
+        let packets = encode(&self.origin, &digests(pool));
+        let fan_out = self.config.fan_out.min(self.peers.len());
+        let targets: Vec<SocketAddr> = (0..fan_out)
+            .map(|i| self.peers[(self.round * fan_out + i) % self.peers.len()])
+            .collect();
+        self.round = self.round.wrapping_add(1);
+
+        for peer in &targets {
+            for packet in &packets {
+                self.socket.send_to(packet, peer)?;
+            }
+        }
+        Ok(targets)
+    }
+
+    /// Drain pending datagrams and merge them into known targets
+    ///
+    /// Returns the number of digests merged. Unknown targets are not
+    /// created: gossip shares memory about upstreams, it does not register them.
+    pub fn receive(&self, pool: &mut EnginePool, now_ms: f64) -> io::Result<usize> {
+        let mut buf = [0u8; MAX_PACKET_BYTES];
+        let mut merged = 0;
+        loop {
+            let n = match self.socket.recv_from(&mut buf) {
+                Ok((n, _)) => n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(merged),
+                Err(e) => return Err(e),
+            };
+            let Some((origin, digests)) = decode(&buf[..n]) else {
+                continue;
+            };
+            if origin == self.origin {
+                continue;
+            }
+            for digest in &digests {
+                if let Some(engine) = pool.get_mut(&digest.target) {
+                    merge_digest(engine, digest, now_ms, &self.config);
+                    merged += 1;
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    fn pool(pressure: f64) -> EnginePool {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 0..40 {
+            let p = PressureVector::new(pressure, pressure, pressure);
+            pool.tick("api", i as f64 * 100.0, p);
+        }
+        pool
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let many: Vec<Digest> = (0..200)
+            .map(|i| Digest {
+                target: format!("target-{i}"),
+                scar: i as f32,
+                mode: OperationalMode::Operational,
+                updated_ms: 1000.0,
+            })
+            .collect();
+        let packets = encode("node-a", &many);
+        assert!(packets.len() > 1);
+        assert!(packets.iter().all(|p| p.len() <= MAX_PACKET_BYTES));
+
+        let decoded: Vec<Digest> = packets.iter().flat_map(|p| decode(p).unwrap().1).collect();
+        assert_eq!(decoded, many);
+        assert_eq!(decode(b"XX\x01"), None);
+    }
+
+    #[test]
+    fn test_staleness_weight() {
+        let config = GossipConfig::default();
+        let digest = Digest {
+            target: "api".into(),
+            scar: 10.0,
+            mode: OperationalMode::CircuitBreaker,
+            updated_ms: 0.0,
+        };
+        assert_eq!(staleness_weight(&digest, 0.0, &config), 1.0);
+        assert!((staleness_weight(&digest, 5000.0, &config) - 0.5).abs() < 1e-10);
+        assert_eq!(staleness_weight(&digest, 60_000.0, &config), 0.0);
+    }
+
+    #[test]
+    fn test_breaker_trip_propagates_over_udp() {
+        let tripped = pool(1.0);
+        let mut healthy = pool(0.0);
+        assert_eq!(tripped.mode("api"), Some(OperationalMode::CircuitBreaker));
+
+        let localhost: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let b = GossipNode::bind("b", localhost, vec![], GossipConfig::default()).unwrap();
+        let mut a = GossipNode::bind(
+            "a",
+            localhost,
+            vec![b.local_addr().unwrap()],
+            GossipConfig::default(),
+        )
+        .unwrap();
+
+        a.gossip(&tripped).unwrap();
+        let mut merged = 0;
+        for _ in 0..100 {
+            merged += b.receive(&mut healthy, 3900.0).unwrap();
+            if merged > 0 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(merged, 1);
+        assert_eq!(healthy.mode("api"), Some(OperationalMode::CircuitBreaker));
+        assert!(healthy.get("api").unwrap().state().scar.0 > 0.0);
+    }
+}
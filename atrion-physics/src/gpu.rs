@@ -0,0 +1,407 @@
+/**
+ * Experimental compute-shader batch scoring (`webgpu` feature).
+ *
+ * Scores `ResistanceLane`s on the GPU through wgpu: Vulkan/Metal/DX12 on
+ * native, WebGPU in the browser. Meant for pools far past CPU budgets
+ * (hundreds of thousands of candidates per decision); below that the
+ * upload and readback cost more than they save. WGSL has no portable
+ * f64, so lanes are scored in f32 and results differ from the CPU path in
+ * the last few digits; use the CPU path wherever bit parity matters.
+ *
+ * `BatchScorer` is the entry point: it scores on the GPU when one is
+ * available and the batch is large enough, and otherwise (or when the
+ * GPU fails) falls back to `calculate_resistance_batch`.
+ */
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::error::AtrionError;
+use crate::resistance::{calculate_resistance_batch, ResistanceLane};
+use crate::types::Ohms;
+
+/// f32 words per packed lane (11 inputs plus padding)
+const LANE_WORDS: usize = 12;
+const WORKGROUP_SIZE: usize = 64;
+/// Largest batch one dispatch covers (65535 workgroups per dimension)
+const MAX_DISPATCH_LANES: usize = 65_535 * WORKGROUP_SIZE;
+
+const SHADER: &str = r#"
+@group(0) @binding(0) var<storage, read> lanes: array<f32>;
+@group(0) @binding(1) var<storage, read_write> out: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= arrayLength(&out)) {
+        return;
+    }
+    let b = i * 12u;
+    let weighted = lanes[b] * lanes[b + 3u]
+        + lanes[b + 1u] * lanes[b + 4u]
+        + lanes[b + 2u] * lanes[b + 5u];
+    let base = lanes[b + 9u];
+    let total = base + weighted + lanes[b + 10u] * lanes[b + 6u] + lanes[b + 7u] + lanes[b + 8u];
+    out[i] = max(total, base);
+}
+"#;
+
+/// Which path scored a batch
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScoringBackend {
+    Cpu,
+    Gpu,
+}
+
+/// Compute pipeline for lane scoring on one device
+pub struct GpuScorer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    adapter: String,
+}
+
+impl GpuScorer {
+    /// Open the default high-performance adapter and build the pipeline
+    pub async fn new_async() -> Result<Self, AtrionError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            })
+            .await
+            .map_err(|err| AtrionError::Gpu(err.to_string()))?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("atrion-scoring"),
+                ..Default::default()
+            })
+            .await
+            .map_err(|err| AtrionError::Gpu(err.to_string()))?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("atrion-resistance"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("atrion-resistance"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            adapter: adapter.get_info().name,
+        })
+    }
+
+    /// Blocking `new_async` (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new() -> Result<Self, AtrionError> {
+        pollster::block_on(Self::new_async())
+    }
+
+    /// Name of the adapter in use
+    pub fn adapter(&self) -> &str {
+        &self.adapter
+    }
+
+    /// Score every lane into `out` (must be as long as `lanes`)
+    pub async fn score_async(
+        &self,
+        lanes: &[ResistanceLane],
+        out: &mut [Ohms],
+    ) -> Result<(), AtrionError> {
+        debug_assert_eq!(lanes.len(), out.len());
+        for (lanes, out) in lanes
+            .chunks(MAX_DISPATCH_LANES)
+            .zip(out.chunks_mut(MAX_DISPATCH_LANES))
+        {
+            self.dispatch(lanes, out).await?;
+        }
+        Ok(())
+    }
+
+    /// Blocking `score_async` (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn score(&self, lanes: &[ResistanceLane], out: &mut [Ohms]) -> Result<(), AtrionError> {
+        pollster::block_on(self.score_async(lanes, out))
+    }
+
+    async fn dispatch(
+        &self,
+        lanes: &[ResistanceLane],
+        out: &mut [Ohms],
+    ) -> Result<(), AtrionError> {
+        if lanes.is_empty() {
+            return Ok(());
+        }
+        let input = pack(lanes);
+        let output_size = (lanes.len() * std::mem::size_of::<f32>()) as u64;
+        let input = wgpu::util::DeviceExt::create_buffer_init(
+            &self.device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("atrion-lanes"),
+                contents: &input,
+                usage: wgpu::BufferUsages::STORAGE,
+            },
+        );
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("atrion-resistance"),
+            size: output_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("atrion-readback"),
+            size: output_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("atrion-scoring"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: input.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(lanes.len().div_ceil(WORKGROUP_SIZE) as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, output_size);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = staging.slice(..);
+        let mapped = Oneshot::default();
+        let sender = mapped.clone();
+        slice.map_async(wgpu::MapMode::Read, move |result| sender.send(result));
+        #[cfg(not(target_arch = "wasm32"))]
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|err| AtrionError::Gpu(err.to_string()))?;
+        mapped
+            .await
+            .map_err(|err| AtrionError::Gpu(err.to_string()))?;
+
+        let bytes = slice.get_mapped_range();
+        for (r, word) in out.iter_mut().zip(bytes.chunks_exact(4)) {
+            *r = Ohms(f32::from_le_bytes([word[0], word[1], word[2], word[3]]) as f64);
+        }
+        drop(bytes);
+        staging.unmap();
+        Ok(())
+    }
+}
+
+/// Lanes as little-endian f32 words, `LANE_WORDS` per lane
+fn pack(lanes: &[ResistanceLane]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(lanes.len() * LANE_WORDS * 4);
+    for lane in lanes {
+        let [l, e, s] = lane.shaped;
+        let [wl, we, ws] = lane.weights;
+        let words = [
+            l,
+            e,
+            s,
+            wl,
+            we,
+            ws,
+            lane.momentum,
+            lane.scar,
+            lane.staleness,
+            lane.base_resistance,
+            lane.damping_factor,
+            0.0,
+        ];
+        for word in words {
+            bytes.extend_from_slice(&(word as f32).to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// Single-value channel completed from a wgpu callback
+struct Oneshot<T>(Arc<Mutex<(Option<T>, Option<Waker>)>>);
+
+impl<T> Default for Oneshot<T> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new((None, None))))
+    }
+}
+
+impl<T> Clone for Oneshot<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Oneshot<T> {
+    fn send(&self, value: T) {
+        let mut slot = self.0.lock().expect("oneshot lock");
+        slot.0 = Some(value);
+        if let Some(waker) = slot.1.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for Oneshot<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut slot = self.0.lock().expect("oneshot lock");
+        match slot.0.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                slot.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Batch scoring with GPU offload and CPU fallback
+pub struct BatchScorer {
+    gpu: Option<GpuScorer>,
+    /// Smaller batches stay on the CPU
+    min_gpu_lanes: usize,
+}
+
+impl BatchScorer {
+    /// Batches this large are worth the upload by default
+    pub const DEFAULT_MIN_GPU_LANES: usize = 65_536;
+
+    /// Use the GPU if one can be opened, else the CPU only (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(min_gpu_lanes: usize) -> Self {
+        Self {
+            gpu: GpuScorer::new().ok(),
+            min_gpu_lanes,
+        }
+    }
+
+    pub fn with_gpu(gpu: GpuScorer, min_gpu_lanes: usize) -> Self {
+        Self {
+            gpu: Some(gpu),
+            min_gpu_lanes,
+        }
+    }
+
+    pub fn cpu_only() -> Self {
+        Self {
+            gpu: None,
+            min_gpu_lanes: usize::MAX,
+        }
+    }
+
+    pub fn gpu(&self) -> Option<&GpuScorer> {
+        self.gpu.as_ref()
+    }
+
+    fn wants_gpu(&self, lanes: usize) -> Option<&GpuScorer> {
+        self.gpu.as_ref().filter(|_| lanes >= self.min_gpu_lanes)
+    }
+
+    /// Score every lane into `out`, returning the backend that did it
+    pub async fn score_async(&self, lanes: &[ResistanceLane], out: &mut [Ohms]) -> ScoringBackend {
+        if let Some(gpu) = self.wants_gpu(lanes.len()) {
+            if gpu.score_async(lanes, out).await.is_ok() {
+                return ScoringBackend::Gpu;
+            }
+        }
+        calculate_resistance_batch(lanes, out);
+        ScoringBackend::Cpu
+    }
+
+    /// Blocking `score_async` (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn score(&self, lanes: &[ResistanceLane], out: &mut [Ohms]) -> ScoringBackend {
+        if let Some(gpu) = self.wants_gpu(lanes.len()) {
+            if gpu.score(lanes, out).is_ok() {
+                return ScoringBackend::Gpu;
+            }
+        }
+        calculate_resistance_batch(lanes, out);
+        ScoringBackend::Cpu
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Momentum, PhysicsConfig, PressureVector, Scar, SensitivityWeights};
+
+    fn lanes(n: usize) -> Vec<ResistanceLane> {
+        let (weights, config) = (SensitivityWeights::default(), PhysicsConfig::default());
+        (0..n)
+            .map(|i| {
+                let f = (i % 100) as f64 / 100.0;
+                ResistanceLane::new(
+                    &PressureVector::new(f, f * 0.5, 1.0 - f),
+                    Momentum(f * 0.1),
+                    Scar(f * 3.0),
+                    &weights,
+                    &config,
+                    0.0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_packs_twelve_words_per_lane() {
+        let lanes = lanes(3);
+        let bytes = pack(&lanes);
+        assert_eq!(bytes.len(), 3 * LANE_WORDS * 4);
+        let word = |i: usize| f32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap());
+        assert_eq!(word(LANE_WORDS + 9), lanes[1].base_resistance as f32);
+        assert_eq!(word(LANE_WORDS + 11), 0.0);
+    }
+
+    #[test]
+    fn test_falls_back_to_cpu() {
+        let lanes = lanes(100);
+        let mut expected = vec![Ohms(0.0); lanes.len()];
+        calculate_resistance_batch(&lanes, &mut expected);
+
+        let mut out = vec![Ohms(0.0); lanes.len()];
+        assert_eq!(
+            BatchScorer::cpu_only().score(&lanes, &mut out),
+            ScoringBackend::Cpu
+        );
+        assert_eq!(out, expected);
+
+        // Runs on machines with an adapter; sandboxes without one skip it
+        if let Ok(gpu) = GpuScorer::new() {
+            let scorer = BatchScorer::with_gpu(gpu, 1);
+            assert_eq!(scorer.score(&lanes, &mut out), ScoringBackend::Gpu);
+            for (gpu, cpu) in out.iter().zip(&expected) {
+                assert!((gpu.0 - cpu.0).abs() <= 1e-5 * cpu.0.abs().max(1.0));
+            }
+        }
+    }
+}
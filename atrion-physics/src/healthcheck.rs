@@ -0,0 +1,367 @@
+/**
+ * Active health checks as a pressure input.
+ *
+ * A target that receives no traffic produces no passive metrics, so its
+ * state only ages. `HealthCheckSource` turns probe results into synthetic
+ * samples: round-trip time scales onto latency pressure, the failure
+ * rate over the last `window` probes becomes error pressure (a failed
+ * probe also counts as a full-limit RTT). Probes cannot see saturation.
+ *
+ * While passive metrics are fresh (within `passive_fresh_ms` of the
+ * newest probe) each axis blends them with the probe view by
+ * `probe_weight`; saturation comes from passive metrics alone. Once they
+ * go stale the probe view stands on its own.
+ *
+ * To close the loop, engines with probing enabled raise `ProbeRequested`
+ * from `check_staleness` once they have gone `stale_after_ms` without a
+ * tick; the host answers with a synthetic request and `record`s it here.
+ */
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::pool::EnginePool;
+use crate::sources::{self, PressureSource, TimestampedPressure};
+use crate::types::PressureVector;
+
+/// Probe-to-pressure configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HealthCheckConfig {
+    /// RTT at zero latency pressure
+    #[serde(alias = "rtt_baseline_ms")]
+    pub rtt_baseline_ms: f64,
+    /// RTT at latency pressure 1 (and the RTT charged for a failed probe)
+    #[serde(alias = "rtt_limit_ms")]
+    pub rtt_limit_ms: f64,
+    /// Probes counted toward the failure rate
+    pub window: usize,
+    /// Share of the probe view while passive metrics are fresh (0..=1)
+    #[serde(alias = "probe_weight")]
+    pub probe_weight: f64,
+    /// How long a passive sample counts as fresh
+    #[serde(alias = "passive_fresh_ms")]
+    pub passive_fresh_ms: f64,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            rtt_baseline_ms: 50.0,
+            rtt_limit_ms: 1_000.0,
+            window: 5,
+            probe_weight: 0.25,
+            passive_fresh_ms: 15_000.0,
+        }
+    }
+}
+
+/// Outcome of one active health check
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbeResult {
+    pub timestamp_ms: f64,
+    pub ok: bool,
+    pub rtt_ms: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TargetProbes {
+    /// Newest last
+    probes: VecDeque<ProbeResult>,
+    passive: Option<TimestampedPressure>,
+    /// Something arrived since the last poll
+    fresh: bool,
+}
+
+/// Blends probe results with passive metrics per target
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct HealthCheckSource {
+    config: HealthCheckConfig,
+    targets: HashMap<String, TargetProbes>,
+}
+
+impl HealthCheckSource {
+    pub fn new(config: HealthCheckConfig) -> Self {
+        Self {
+            config,
+            targets: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &HealthCheckConfig {
+        &self.config
+    }
+
+    /// Record a probe result for `target`
+    pub fn record(&mut self, target: &str, result: ProbeResult) {
+        let entry = self.targets.entry(target.to_string()).or_default();
+        entry.probes.push_back(result);
+        while entry.probes.len() > self.config.window.max(1) {
+            entry.probes.pop_front();
+        }
+        entry.fresh = true;
+    }
+
+    /// Record a passive (traffic-derived) sample for `target`
+    pub fn observe(&mut self, target: &str, sample: TimestampedPressure) {
+        let entry = self.targets.entry(target.to_string()).or_default();
+        if entry
+            .passive
+            .is_none_or(|passive| passive.timestamp_ms <= sample.timestamp_ms)
+        {
+            entry.passive = Some(sample);
+            entry.fresh = true;
+        }
+    }
+
+    /// Current blended sample for `target`, without consuming it
+    pub fn current(&self, target: &str) -> Option<TimestampedPressure> {
+        let entry = self.targets.get(target)?;
+        let Some(newest) = entry.probes.back() else {
+            return entry.passive;
+        };
+        let probe = self.probe_pressure(&entry.probes);
+        let passive = entry
+            .passive
+            .filter(|p| newest.timestamp_ms - p.timestamp_ms <= self.config.passive_fresh_ms);
+        let Some(passive) = passive else {
+            return Some(TimestampedPressure {
+                timestamp_ms: newest.timestamp_ms,
+                pressure: probe,
+            });
+        };
+        let w = self.config.probe_weight.clamp(0.0, 1.0);
+        let blend = |passive: f64, probe: f64| (1.0 - w) * passive + w * probe;
+        Some(TimestampedPressure {
+            timestamp_ms: newest.timestamp_ms.max(passive.timestamp_ms),
+            pressure: PressureVector::new(
+                blend(passive.pressure.latency, probe.latency),
+                blend(passive.pressure.error, probe.error),
+                passive.pressure.saturation,
+            ),
+        })
+    }
+
+    fn probe_pressure(&self, probes: &VecDeque<ProbeResult>) -> PressureVector {
+        let span = self.config.rtt_limit_ms - self.config.rtt_baseline_ms;
+        let rtt_pressure = |probe: &ProbeResult| match (probe.ok, span > 0.0) {
+            (false, _) => 1.0,
+            (true, true) => ((probe.rtt_ms - self.config.rtt_baseline_ms) / span).max(0.0),
+            (true, false) => 0.0,
+        };
+        let newest = probes.back().map_or(0.0, rtt_pressure);
+        let failures = probes.iter().filter(|probe| !probe.ok).count();
+        PressureVector::new(newest, failures as f64 / probes.len().max(1) as f64, 0.0)
+    }
+}
+
+impl PressureSource for HealthCheckSource {
+    /// The blended sample, once per batch of new probes or passive samples
+    fn poll(&mut self, target: &str) -> Option<TimestampedPressure> {
+        let entry = self.targets.get_mut(target)?;
+        if !std::mem::take(&mut entry.fresh) {
+            return None;
+        }
+        self.current(target)
+    }
+}
+
+#[wasm_bindgen]
+impl HealthCheckSource {
+    /// Source from a plain `HealthCheckConfig` object (undefined = defaults)
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(config: JsValue) -> Result<HealthCheckSource, JsValue> {
+        let config = if config.is_undefined() {
+            HealthCheckConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        Ok(Self::new(config))
+    }
+
+    #[wasm_bindgen(js_name = record)]
+    pub fn record_js(&mut self, target: &str, timestamp_ms: f64, ok: bool, rtt_ms: f64) {
+        self.record(
+            target,
+            ProbeResult {
+                timestamp_ms,
+                ok,
+                rtt_ms,
+            },
+        );
+    }
+
+    #[wasm_bindgen(js_name = observe)]
+    pub fn observe_js(&mut self, target: &str, timestamp_ms: f64, pressure: &PressureVector) {
+        self.observe(
+            target,
+            TimestampedPressure {
+                timestamp_ms,
+                pressure: *pressure,
+            },
+        );
+    }
+
+    /// Tick every pool target with fresh input; returns how many were ticked
+    #[wasm_bindgen(js_name = tickPool)]
+    pub fn tick_pool_js(&mut self, pool: &mut EnginePool) -> usize {
+        sources::tick_pool(pool, self)
+    }
+}
+
+// ============================================================================
+// STALENESS PROBING
+// ============================================================================
+
+/// When a target counts as stale enough to probe
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProbingConfig {
+    /// Time without a tick before a probe is requested
+    #[serde(alias = "stale_after_ms")]
+    pub stale_after_ms: f64,
+    /// Ask again if the target is still stale this long after a request
+    #[serde(alias = "retry_ms")]
+    pub retry_ms: f64,
+}
+
+impl Default for ProbingConfig {
+    fn default() -> Self {
+        Self {
+            stale_after_ms: 10_000.0,
+            retry_ms: 5_000.0,
+        }
+    }
+}
+
+/// Per-engine probe request bookkeeping
+#[derive(Debug, Clone)]
+pub struct Probing {
+    config: ProbingConfig,
+    requested_ms: Option<f64>,
+}
+
+impl Probing {
+    pub fn new(config: ProbingConfig) -> Self {
+        Self {
+            config,
+            requested_ms: None,
+        }
+    }
+
+    pub fn config(&self) -> &ProbingConfig {
+        &self.config
+    }
+
+    /// Staleness to report if a probe is due at `now_ms`
+    pub(crate) fn check(&mut self, now_ms: f64, last_updated_ms: f64) -> Option<f64> {
+        let stale_ms = now_ms - last_updated_ms;
+        if stale_ms < self.config.stale_after_ms {
+            return None;
+        }
+        let outstanding = self.requested_ms.is_some_and(|requested| {
+            requested >= last_updated_ms && now_ms - requested < self.config.retry_ms
+        });
+        if outstanding {
+            return None;
+        }
+        self.requested_ms = Some(now_ms);
+        Some(stale_ms)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::events::EngineEvent;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    fn probe(timestamp_ms: f64, ok: bool, rtt_ms: f64) -> ProbeResult {
+        ProbeResult {
+            timestamp_ms,
+            ok,
+            rtt_ms,
+        }
+    }
+
+    #[test]
+    fn test_idle_target_stays_fresh_on_probes_alone() {
+        let mut source = HealthCheckSource::new(HealthCheckConfig::default());
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        pool.add_target("idle");
+        for i in 0..4 {
+            source.record("idle", probe(i as f64 * 5_000.0, i != 3, 525.0));
+            assert_eq!(sources::tick_pool(&mut pool, &mut source), 1);
+        }
+        assert_eq!(sources::tick_pool(&mut pool, &mut source), 0);
+        let engine = pool.get("idle").unwrap();
+        assert_eq!(engine.state().last_updated_ms, 15_000.0);
+
+        let sample = source.current("idle").unwrap();
+        assert_eq!(sample.pressure.latency, 1.0);
+        assert_eq!(sample.pressure.error, 0.25);
+        assert_eq!(sample.pressure.saturation, 0.0);
+    }
+
+    #[test]
+    fn test_fresh_passive_metrics_dominate() {
+        let mut source = HealthCheckSource::new(HealthCheckConfig::default());
+        let passive = PressureVector::new(0.2, 0.0, 0.6);
+        source.observe(
+            "api",
+            TimestampedPressure {
+                timestamp_ms: 10_000.0,
+                pressure: passive,
+            },
+        );
+        source.record("api", probe(12_000.0, true, 525.0));
+        let blended = source.poll("api").unwrap();
+        assert_eq!(blended.timestamp_ms, 12_000.0);
+        assert!((blended.pressure.latency - (0.75 * 0.2 + 0.25 * 0.5)).abs() < 1e-12);
+        assert_eq!(blended.pressure.saturation, 0.6);
+
+        // Passive data 15 s behind the newest probe no longer counts
+        source.record("api", probe(26_000.0, true, 525.0));
+        let alone = source.poll("api").unwrap();
+        assert_eq!(alone.pressure.latency, 0.5);
+        assert_eq!(alone.pressure.saturation, 0.0);
+    }
+
+    #[test]
+    fn test_stale_engine_requests_probe_until_fed() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_probing(ProbingConfig::default());
+        let calm = PressureVector::new(0.1, 0.0, 0.1);
+        engine.tick(1_000.0, calm);
+        assert!(!engine.check_staleness(10_000.0));
+        assert!(engine.check_staleness(11_000.0));
+        // Outstanding until retry_ms passes
+        assert!(!engine.check_staleness(15_000.0));
+        assert!(engine.check_staleness(16_000.0));
+
+        // The probe result feeds back through the health-check source
+        let mut source = HealthCheckSource::new(HealthCheckConfig::default());
+        source.record("api", probe(16_500.0, true, 60.0));
+        let sample = source.poll("api").unwrap();
+        engine.tick(sample.timestamp_ms, sample.pressure);
+        assert!(!engine.check_staleness(17_000.0));
+
+        let stale: Vec<f64> = engine
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                EngineEvent::ProbeRequested { stale_ms, .. } => Some(stale_ms),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(stale, vec![10_000.0, 15_000.0]);
+    }
+}
@@ -0,0 +1,185 @@
+/**
+ * Hedging advice for latency-sensitive clients.
+ *
+ * A hedged request is a duplicate sent after a delay when the first is
+ * slow. It cuts tail latency on a target that is slow but has headroom,
+ * and makes things worse on one that is saturating. The advice couples
+ * that policy to the physics:
+ *
+ * - hedge only when latency pressure is elevated
+ * - never while the breaker is open, the admission ratio is low, or
+ *   pressure is still climbing (momentum trend)
+ * - wait longer before hedging as resistance rises
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::admission::admission_ratio;
+use crate::engine::TargetState;
+use crate::types::{OperationalMode, PhysicsConfig};
+use crate::vector;
+
+/// Thresholds for `hedge_advice`
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HedgePolicy {
+    /// Hedge delay at base resistance (typically the healthy p95 latency)
+    #[serde(alias = "base_delay_ms")]
+    pub base_delay_ms: f64,
+    /// Longest advised delay
+    #[serde(alias = "max_delay_ms")]
+    pub max_delay_ms: f64,
+    /// Latency pressure at which the tail is worth hedging
+    #[serde(alias = "min_latency_pressure")]
+    pub min_latency_pressure: f64,
+    /// Admission ratio below which the target has no room for duplicates
+    #[serde(alias = "min_admission_ratio")]
+    pub min_admission_ratio: f64,
+    /// Momentum (pressure change per ms) that counts as still climbing
+    #[serde(alias = "rising_momentum")]
+    pub rising_momentum: f64,
+}
+
+impl Default for HedgePolicy {
+    fn default() -> Self {
+        Self {
+            base_delay_ms: 50.0,
+            max_delay_ms: 1_000.0,
+            min_latency_pressure: 0.3,
+            min_admission_ratio: 0.5,
+            rising_momentum: 1e-4, // 0.1 pressure per second
+        }
+    }
+}
+
+/// Why the advice came out the way it did
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HedgeReason {
+    /// Elevated latency with headroom: hedge
+    SlowTail,
+    /// Latency pressure below the policy threshold
+    Healthy,
+    /// Not enough data yet
+    Bootstrap,
+    CircuitOpen,
+    /// Target is on the pool's quarantine list
+    Quarantined,
+    /// Admission ratio below the policy minimum
+    Overloaded,
+    /// Pressure still building; duplicates would feed it
+    RisingPressure,
+}
+
+/// Whether to send a hedged duplicate, and when
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HedgeAdvice {
+    pub hedge: bool,
+    /// Delay before the duplicate (reported even when not hedging)
+    #[serde(alias = "delay_ms")]
+    pub delay_ms: f64,
+    pub reason: HedgeReason,
+}
+
+/// Advice for a target in `state`
+pub fn hedge_advice(
+    state: &TargetState,
+    config: &PhysicsConfig,
+    policy: &HedgePolicy,
+) -> HedgeAdvice {
+    let scale = if config.base_resistance > 0.0 {
+        (state.resistance.0 / config.base_resistance).max(1.0)
+    } else {
+        1.0
+    };
+    let delay_ms = (policy.base_delay_ms * scale).min(policy.max_delay_ms);
+
+    let rising = vector::magnitude(&state.pressure) > vector::magnitude(&state.previous_pressure)
+        && state.momentum.0 >= policy.rising_momentum;
+    let reason = match state.mode {
+        OperationalMode::Bootstrap => HedgeReason::Bootstrap,
+        OperationalMode::CircuitBreaker => HedgeReason::CircuitOpen,
+        OperationalMode::Operational => {
+            if admission_ratio(state.resistance, config) < policy.min_admission_ratio {
+                HedgeReason::Overloaded
+            } else if rising {
+                HedgeReason::RisingPressure
+            } else if state.pressure.latency < policy.min_latency_pressure {
+                HedgeReason::Healthy
+            } else {
+                HedgeReason::SlowTail
+            }
+        }
+    };
+    HedgeAdvice {
+        hedge: reason == HedgeReason::SlowTail,
+        delay_ms,
+        reason,
+    }
+}
+
+/// `HedgePolicy` from a plain object; undefined means defaults
+pub(crate) fn policy_from_js(
+    value: wasm_bindgen::JsValue,
+) -> Result<HedgePolicy, wasm_bindgen::JsValue> {
+    if value.is_undefined() {
+        Ok(HedgePolicy::default())
+    } else {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Momentum, Ohms, PressureVector};
+
+    fn state(resistance: f64, latency: f64, previous_latency: f64, momentum: f64) -> TargetState {
+        let mut state = TargetState::bootstrap(&PhysicsConfig::default());
+        state.mode = OperationalMode::Operational;
+        state.resistance = Ohms(resistance);
+        state.pressure = PressureVector::new(latency, 0.0, 0.0);
+        state.previous_pressure = PressureVector::new(previous_latency, 0.0, 0.0);
+        state.momentum = Momentum(momentum);
+        state
+    }
+
+    #[test]
+    fn test_hedges_slow_tail_with_headroom() {
+        let config = PhysicsConfig::default();
+        let policy = HedgePolicy::default();
+
+        let slow = hedge_advice(&state(20.0, 0.5, 0.5, 0.0), &config, &policy);
+        assert!(slow.hedge);
+        assert_eq!(slow.reason, HedgeReason::SlowTail);
+        assert_eq!(slow.delay_ms, 100.0);
+
+        let fast = hedge_advice(&state(10.0, 0.1, 0.1, 0.0), &config, &policy);
+        assert_eq!(fast.reason, HedgeReason::Healthy);
+        assert_eq!(fast.delay_ms, 50.0);
+    }
+
+    #[test]
+    fn test_no_hedge_when_saturating() {
+        let config = PhysicsConfig::default();
+        let policy = HedgePolicy::default();
+
+        let climbing = hedge_advice(&state(20.0, 0.6, 0.4, 2e-4), &config, &policy);
+        assert_eq!(climbing.reason, HedgeReason::RisingPressure);
+        assert!(!climbing.hedge);
+
+        let loaded = hedge_advice(&state(90.0, 0.6, 0.6, 0.0), &config, &policy);
+        assert_eq!(loaded.reason, HedgeReason::Overloaded);
+        assert_eq!(loaded.delay_ms, 450.0);
+
+        let mut open = state(20.0, 0.6, 0.6, 0.0);
+        open.mode = OperationalMode::CircuitBreaker;
+        assert_eq!(
+            hedge_advice(&open, &config, &policy).reason,
+            HedgeReason::CircuitOpen
+        );
+    }
+}
@@ -0,0 +1,278 @@
+/**
+ * Bounded tick history (ring buffer).
+ *
+ * Keeps the last N engine samples in memory so dashboards can query
+ * recent behavior without maintaining an external time-series store.
+ */
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::debug::Tail;
+use crate::markers::{Marker, MARKER_CAPACITY};
+use crate::types::{Momentum, Ohms, OperationalMode, PressureVector, Scar};
+
+/// Samples reserved up front; larger histories grow as they fill
+const INITIAL_RESERVE: usize = 1024;
+
+/// Single recorded engine tick
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySample {
+    #[serde(alias = "timestamp_ms")]
+    pub timestamp_ms: f64,
+    pub pressure: PressureVector,
+    pub resistance: Ohms,
+    pub scar: Scar,
+    pub momentum: Momentum,
+    pub mode: OperationalMode,
+}
+
+/// Fixed-capacity ring buffer of history samples (oldest evicted first)
+///
+/// Operational markers are kept alongside in their own ring of
+/// `MARKER_CAPACITY`; clearing or rewinding samples leaves them alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct History {
+    capacity: usize,
+    samples: VecDeque<HistorySample>,
+    #[serde(default)]
+    markers: VecDeque<Marker>,
+}
+
+impl History {
+    /// Create an empty history holding at most `capacity` samples
+    ///
+    /// A capacity of 0 disables recording.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity.min(INITIAL_RESERVE)),
+            markers: VecDeque::new(),
+        }
+    }
+
+    /// Record a sample, evicting the oldest one when full
+    #[inline]
+    pub fn push(&mut self, sample: HistorySample) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Record an operational marker, keeping markers in time order
+    pub fn mark(&mut self, marker: Marker) {
+        let at = self
+            .markers
+            .iter()
+            .rposition(|m| m.timestamp_ms <= marker.timestamp_ms)
+            .map_or(0, |i| i + 1);
+        self.markers.insert(at, marker);
+        if self.markers.len() > MARKER_CAPACITY {
+            self.markers.pop_front();
+        }
+    }
+
+    pub fn markers(&self) -> impl Iterator<Item = &Marker> {
+        self.markers.iter()
+    }
+
+    /// Markers with `start_ms <= timestamp <= end_ms`
+    pub fn markers_between(&self, start_ms: f64, end_ms: f64) -> Vec<Marker> {
+        self.markers
+            .iter()
+            .filter(|m| m.timestamp_ms >= start_ms && m.timestamp_ms <= end_ms)
+            .copied()
+            .collect()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity, dropping the oldest samples that no longer fit
+    pub fn set_capacity(&mut self, capacity: usize) {
+        let excess = self.samples.len().saturating_sub(capacity);
+        self.samples.drain(..excess);
+        self.capacity = capacity;
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Drop all recorded samples
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Drop samples newer than `timestamp_ms` (before recomputing them)
+    pub fn drop_after(&mut self, timestamp_ms: f64) {
+        while self
+            .samples
+            .back()
+            .is_some_and(|sample| sample.timestamp_ms > timestamp_ms)
+        {
+            self.samples.pop_back();
+        }
+    }
+
+    /// Iterate samples from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &HistorySample> {
+        self.samples.iter()
+    }
+
+    /// The newest `count` samples, borrowed
+    pub fn tail(&self, count: usize) -> Tail<'_, HistorySample> {
+        Tail::new(&self.samples, count)
+    }
+
+    /// Samples with `start_ms <= timestamp <= end_ms`
+    pub fn between(&self, start_ms: f64, end_ms: f64) -> impl Iterator<Item = &HistorySample> {
+        self.samples
+            .iter()
+            .filter(move |s| s.timestamp_ms >= start_ms && s.timestamp_ms <= end_ms)
+    }
+
+    /// Peak resistance recorded at or after `since_ms`
+    pub fn max_resistance_since(&self, since_ms: f64) -> Option<Ohms> {
+        self.samples
+            .iter()
+            .filter(|s| s.timestamp_ms >= since_ms)
+            .map(|s| s.resistance)
+            .fold(None, |peak, r| match peak {
+                Some(p) if p >= r => Some(p),
+                _ => Some(r),
+            })
+    }
+
+    /// Downsample into fixed time buckets
+    ///
+    /// Each bucket is represented by its peak-resistance sample, so short
+    /// spikes survive the reduction. A non-positive `bucket_ms` returns
+    /// every sample unchanged.
+    pub fn downsample(&self, bucket_ms: f64) -> Vec<HistorySample> {
+        if bucket_ms <= 0.0 {
+            return self.samples.iter().copied().collect();
+        }
+
+        let mut out: Vec<HistorySample> = Vec::new();
+        let mut current_bucket = f64::NAN;
+
+        for sample in &self.samples {
+            let bucket = (sample.timestamp_ms / bucket_ms).floor();
+            match out.last_mut() {
+                Some(last) if bucket == current_bucket => {
+                    if sample.resistance > last.resistance {
+                        *last = *sample;
+                    }
+                }
+                _ => {
+                    current_bucket = bucket;
+                    out.push(*sample);
+                }
+            }
+        }
+
+        out
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp_ms: f64, resistance: f64) -> HistorySample {
+        HistorySample {
+            timestamp_ms,
+            pressure: PressureVector::new(0.0, 0.0, 0.0),
+            resistance: Ohms(resistance),
+            scar: Scar(0.0),
+            momentum: Momentum(0.0),
+            mode: OperationalMode::Operational,
+        }
+    }
+
+    #[test]
+    fn test_evicts_oldest_when_full() {
+        let mut history = History::new(3);
+        for i in 0..5 {
+            history.push(sample(i as f64, 10.0));
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.iter().next().unwrap().timestamp_ms, 2.0);
+    }
+
+    #[test]
+    fn test_set_capacity_drops_oldest() {
+        let mut history = History::new(5);
+        for i in 0..5 {
+            history.push(sample(i as f64, 10.0));
+        }
+
+        history.set_capacity(2);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.iter().next().unwrap().timestamp_ms, 3.0);
+
+        history.set_capacity(4);
+        for i in 5..8 {
+            history.push(sample(i as f64, 10.0));
+        }
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.iter().next().unwrap().timestamp_ms, 4.0);
+    }
+
+    #[test]
+    fn test_huge_capacity_does_not_reserve_up_front() {
+        let mut history = History::new(u32::MAX as usize);
+        history.push(sample(0.0, 10.0));
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_recording() {
+        let mut history = History::new(0);
+        history.push(sample(0.0, 10.0));
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_between_and_max_resistance_since() {
+        let mut history = History::new(10);
+        history.push(sample(100.0, 12.0));
+        history.push(sample(200.0, 40.0));
+        history.push(sample(300.0, 15.0));
+
+        assert_eq!(history.between(150.0, 300.0).count(), 2);
+        assert_eq!(history.max_resistance_since(0.0), Some(Ohms(40.0)));
+        assert_eq!(history.max_resistance_since(250.0), Some(Ohms(15.0)));
+        assert_eq!(history.max_resistance_since(400.0), None);
+    }
+
+    #[test]
+    fn test_downsample_keeps_bucket_peak() {
+        let mut history = History::new(10);
+        history.push(sample(0.0, 10.0));
+        history.push(sample(500.0, 30.0));
+        history.push(sample(1000.0, 20.0));
+        history.push(sample(1500.0, 11.0));
+
+        let buckets = history.downsample(1000.0);
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].resistance, Ohms(30.0));
+        assert_eq!(buckets[1].resistance, Ohms(20.0));
+    }
+}
@@ -0,0 +1,627 @@
+/**
+ * Trace ingestion from JSONL, CSV and OTLP metrics exports.
+ *
+ * A `TraceMapping` names the raw fields that carry the timestamp, target
+ * and offered count, and maps up to three fields onto the pressure axes
+ * (`(value - baseline) / (limit - baseline)`, floored at 0). Readers
+ * stream: one line buffer is reused, JSON and CSV fields are borrowed
+ * from it, and each record goes straight to a sink, so multi-GB traces
+ * never sit in memory unless the caller collects them (`load`).
+ *
+ * - JSONL: one flat object per line, unmapped keys skipped
+ * - CSV: header row names the columns; quoted commas are not supported
+ * - OTLP: one `ExportMetricsServiceRequest` per line (collector file
+ *   exporter). Gauge and sum points are mapped by metric name, the target
+ *   comes from a data point or resource attribute, and points sharing a
+ *   target and `timeUnixNano` within a batch form one sample.
+ */
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AtrionError;
+use crate::trace::{Trace, TraceSample};
+use crate::types::PressureVector;
+
+/// One raw field mapped onto a pressure axis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AxisMapping {
+    pub field: String,
+    /// Raw value at zero pressure
+    #[serde(default)]
+    pub baseline: f64,
+    /// Raw value at pressure 1
+    #[serde(default = "default_limit")]
+    pub limit: f64,
+}
+
+fn default_limit() -> f64 {
+    1.0
+}
+
+impl AxisMapping {
+    pub fn new(field: &str, baseline: f64, limit: f64) -> Self {
+        Self {
+            field: field.to_string(),
+            baseline,
+            limit,
+        }
+    }
+
+    pub(crate) fn pressure(&self, value: f64) -> f64 {
+        let span = self.limit - self.baseline;
+        if span > 0.0 {
+            ((value - self.baseline) / span).max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Which raw fields make up a trace sample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TraceMapping {
+    /// Timestamp field (OTLP uses `timeUnixNano` instead)
+    pub timestamp: String,
+    /// Multiplier to milliseconds (1000 for seconds, 0.001 for µs)
+    #[serde(alias = "timestamp_scale")]
+    pub timestamp_scale: f64,
+    /// Target field (OTLP: attribute key, e.g. `service.name`)
+    pub target: String,
+    /// Target for records without one
+    #[serde(alias = "default_target")]
+    pub default_target: String,
+    pub latency: Option<AxisMapping>,
+    pub error: Option<AxisMapping>,
+    pub saturation: Option<AxisMapping>,
+    /// Offered request count field (1 per sample when absent)
+    pub offered: Option<String>,
+}
+
+impl Default for TraceMapping {
+    fn default() -> Self {
+        // Matches the field names of `TraceSample` flattened
+        Self {
+            timestamp: "timestamp_ms".to_string(),
+            timestamp_scale: 1.0,
+            target: "target".to_string(),
+            default_target: "default".to_string(),
+            latency: Some(AxisMapping::new("latency", 0.0, 1.0)),
+            error: Some(AxisMapping::new("error", 0.0, 1.0)),
+            saturation: Some(AxisMapping::new("saturation", 0.0, 1.0)),
+            offered: Some("offered".to_string()),
+        }
+    }
+}
+
+/// Mapped role of a raw field
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Slot {
+    Timestamp,
+    Target,
+    /// Index into `Row::values` (latency, error, saturation, offered)
+    Value(usize),
+}
+
+impl TraceMapping {
+    fn slot(&self, field: &str) -> Option<Slot> {
+        if field == self.timestamp {
+            return Some(Slot::Timestamp);
+        }
+        if field == self.target {
+            return Some(Slot::Target);
+        }
+        self.value_slot(field).map(Slot::Value)
+    }
+
+    fn value_slot(&self, field: &str) -> Option<usize> {
+        let axes = [&self.latency, &self.error, &self.saturation];
+        axes.iter()
+            .position(|axis| axis.as_ref().is_some_and(|a| a.field == field))
+            .or_else(|| (self.offered.as_deref() == Some(field)).then_some(3))
+    }
+
+    /// Sample from `row`, timestamps multiplied by `scale` to reach ms
+    fn sample(&self, row: &Row, scale: f64) -> Result<TraceSample, String> {
+        let timestamp = row
+            .timestamp
+            .ok_or_else(|| format!("missing timestamp field `{}`", self.timestamp))?;
+        let axis = |mapping: &Option<AxisMapping>, value: Option<f64>| match (mapping, value) {
+            (Some(mapping), Some(value)) => mapping.pressure(value),
+            _ => 0.0,
+        };
+        Ok(TraceSample {
+            timestamp_ms: timestamp * scale,
+            pressure: PressureVector::new(
+                axis(&self.latency, row.values[0]),
+                axis(&self.error, row.values[1]),
+                axis(&self.saturation, row.values[2]),
+            ),
+            offered: row.values[3].unwrap_or(1.0),
+        })
+    }
+
+    fn target<'r>(&'r self, row: &'r Row) -> &'r str {
+        row.target.as_deref().unwrap_or(&self.default_target)
+    }
+}
+
+/// Mapped fields of one record, borrowing from the line buffer
+#[derive(Debug, Default)]
+struct Row<'a> {
+    timestamp: Option<f64>,
+    target: Option<Cow<'a, str>>,
+    values: [Option<f64>; 4],
+}
+
+/// Input format of a trace file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraceFormat {
+    Jsonl,
+    Csv,
+    Otlp,
+}
+
+/// Stream `reader` into `sink(target, sample)`; returns the sample count
+pub fn read<R: BufRead>(
+    format: TraceFormat,
+    reader: R,
+    mapping: &TraceMapping,
+    sink: impl FnMut(&str, TraceSample),
+) -> Result<u64, AtrionError> {
+    match format {
+        TraceFormat::Jsonl => read_jsonl(reader, mapping, sink),
+        TraceFormat::Csv => read_csv(reader, mapping, sink),
+        TraceFormat::Otlp => read_otlp(reader, mapping, sink),
+    }
+}
+
+/// Collect a whole file into a `Trace`
+pub fn load<R: BufRead>(
+    format: TraceFormat,
+    reader: R,
+    mapping: &TraceMapping,
+) -> Result<Trace, AtrionError> {
+    let mut trace = Trace::new();
+    read(format, reader, mapping, |target, sample| {
+        trace.record(target, sample)
+    })?;
+    Ok(trace)
+}
+
+fn line_error(number: u64, msg: impl fmt::Display) -> AtrionError {
+    AtrionError::Parse(format!("line {number}: {msg}"))
+}
+
+/// Calls `each(number, line)` for every non-blank line, reusing one buffer
+fn for_each_line<R: BufRead>(
+    mut reader: R,
+    mut each: impl FnMut(u64, &str) -> Result<(), AtrionError>,
+) -> Result<(), AtrionError> {
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|err| line_error(number + 1, err))?;
+        if read == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let text = line.trim();
+        if !text.is_empty() {
+            each(number, text)?;
+        }
+    }
+}
+
+// ============================================================================
+// JSONL
+// ============================================================================
+
+/// Stream one flat JSON object per line
+pub fn read_jsonl<R: BufRead>(
+    reader: R,
+    mapping: &TraceMapping,
+    mut sink: impl FnMut(&str, TraceSample),
+) -> Result<u64, AtrionError> {
+    let mut count = 0;
+    for_each_line(reader, |number, text| {
+        let mut de = serde_json::Deserializer::from_str(text);
+        let row = RowSeed(mapping)
+            .deserialize(&mut de)
+            .and_then(|row| de.end().map(|_| row))
+            .map_err(|err| line_error(number, err))?;
+        let sample = mapping
+            .sample(&row, mapping.timestamp_scale)
+            .map_err(|err| line_error(number, err))?;
+        sink(mapping.target(&row), sample);
+        count += 1;
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+/// Deserializes only the mapped keys of an object into a `Row`
+struct RowSeed<'m>(&'m TraceMapping);
+
+impl<'de> DeserializeSeed<'de> for RowSeed<'_> {
+    type Value = Row<'de>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Row<'de>, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RowSeed<'_> {
+    type Value = Row<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a flat JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Row<'de>, A::Error> {
+        let mut row = Row::default();
+        while let Some(Text(key)) = map.next_key()? {
+            match self.0.slot(&key) {
+                Some(Slot::Timestamp) => row.timestamp = map.next_value()?,
+                Some(Slot::Target) => row.target = map.next_value::<Option<Text>>()?.map(|t| t.0),
+                Some(Slot::Value(i)) => row.values[i] = map.next_value()?,
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(row)
+    }
+}
+
+/// String borrowed from the input when it has no escapes
+struct Text<'a>(Cow<'a, str>);
+
+impl<'de> Deserialize<'de> for Text<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl<'de> Visitor<'de> for TextVisitor {
+            type Value = Text<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Text<'de>, E> {
+                Ok(Text(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Text<'de>, E> {
+                Ok(Text(Cow::Owned(v.to_string())))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Text<'de>, E> {
+                Ok(Text(Cow::Owned(v.to_string())))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Text<'de>, E> {
+                Ok(Text(Cow::Owned(v.to_string())))
+            }
+        }
+
+        deserializer.deserialize_str(TextVisitor)
+    }
+}
+
+// ============================================================================
+// CSV
+// ============================================================================
+
+/// Stream a CSV file whose first line is the header
+pub fn read_csv<R: BufRead>(
+    reader: R,
+    mapping: &TraceMapping,
+    mut sink: impl FnMut(&str, TraceSample),
+) -> Result<u64, AtrionError> {
+    let mut columns: Option<Vec<Option<Slot>>> = None;
+    let mut count = 0;
+    for_each_line(reader, |number, text| {
+        let Some(columns) = &columns else {
+            columns = Some(text.split(',').map(|h| mapping.slot(unquote(h))).collect());
+            return Ok(());
+        };
+        let mut row = Row::default();
+        for (cell, slot) in text.split(',').map(unquote).zip(columns) {
+            let number_in = |cell: &str| -> Result<Option<f64>, AtrionError> {
+                if cell.is_empty() {
+                    return Ok(None);
+                }
+                cell.parse()
+                    .map(Some)
+                    .map_err(|err| line_error(number, format!("`{cell}`: {err}")))
+            };
+            match slot {
+                Some(Slot::Timestamp) => row.timestamp = number_in(cell)?,
+                Some(Slot::Target) if !cell.is_empty() => row.target = Some(Cow::Borrowed(cell)),
+                Some(Slot::Value(i)) => row.values[*i] = number_in(cell)?,
+                _ => {}
+            }
+        }
+        let sample = mapping
+            .sample(&row, mapping.timestamp_scale)
+            .map_err(|err| line_error(number, err))?;
+        sink(mapping.target(&row), sample);
+        count += 1;
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+fn unquote(cell: &str) -> &str {
+    let cell = cell.trim();
+    cell.strip_prefix('"')
+        .and_then(|c| c.strip_suffix('"'))
+        .unwrap_or(cell)
+}
+
+// ============================================================================
+// OTLP
+// ============================================================================
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpRequest<'a> {
+    #[serde(borrow, default)]
+    resource_metrics: Vec<OtlpResourceMetrics<'a>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpResourceMetrics<'a> {
+    #[serde(borrow, default)]
+    resource: OtlpResource<'a>,
+    #[serde(borrow, default)]
+    scope_metrics: Vec<OtlpScopeMetrics<'a>>,
+}
+
+#[derive(Default, Deserialize)]
+struct OtlpResource<'a> {
+    #[serde(borrow, default)]
+    attributes: Vec<OtlpKeyValue<'a>>,
+}
+
+#[derive(Deserialize)]
+struct OtlpScopeMetrics<'a> {
+    #[serde(borrow, default)]
+    metrics: Vec<OtlpMetric<'a>>,
+}
+
+#[derive(Deserialize)]
+struct OtlpMetric<'a> {
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(borrow)]
+    gauge: Option<OtlpPoints<'a>>,
+    #[serde(borrow)]
+    sum: Option<OtlpPoints<'a>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpPoints<'a> {
+    #[serde(borrow, default)]
+    data_points: Vec<OtlpDataPoint<'a>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpDataPoint<'a> {
+    #[serde(borrow, default)]
+    attributes: Vec<OtlpKeyValue<'a>>,
+    time_unix_nano: OtlpInt,
+    as_double: Option<f64>,
+    as_int: Option<OtlpInt>,
+}
+
+#[derive(Deserialize)]
+struct OtlpKeyValue<'a> {
+    #[serde(borrow)]
+    key: Cow<'a, str>,
+    #[serde(borrow)]
+    value: OtlpAnyValue<'a>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpAnyValue<'a> {
+    #[serde(borrow)]
+    string_value: Option<Cow<'a, str>>,
+}
+
+/// Protobuf JSON encodes 64-bit integers as strings; accept both
+struct OtlpInt(f64);
+
+impl<'de> Deserialize<'de> for OtlpInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IntVisitor;
+
+        impl Visitor<'_> for IntVisitor {
+            type Value = OtlpInt;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an integer or integer string")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<OtlpInt, E> {
+                Ok(OtlpInt(v as f64))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<OtlpInt, E> {
+                Ok(OtlpInt(v as f64))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<OtlpInt, E> {
+                Ok(OtlpInt(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<OtlpInt, E> {
+                v.parse::<i64>()
+                    .map(|i| OtlpInt(i as f64))
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(IntVisitor)
+    }
+}
+
+fn attribute<'a>(attributes: &'a [OtlpKeyValue<'a>], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|kv| kv.key == key)
+        .and_then(|kv| kv.value.string_value.as_deref())
+}
+
+/// Stream OTLP/JSON metrics exports, one request per line
+pub fn read_otlp<R: BufRead>(
+    reader: R,
+    mapping: &TraceMapping,
+    mut sink: impl FnMut(&str, TraceSample),
+) -> Result<u64, AtrionError> {
+    let mut count = 0;
+    for_each_line(reader, |number, text| {
+        let request: OtlpRequest =
+            serde_json::from_str(text).map_err(|err| line_error(number, err))?;
+        // (target, timestamp bits) → row; bits of positive f64 sort numerically
+        let mut rows: BTreeMap<(&str, u64), Row> = BTreeMap::new();
+        for resource in &request.resource_metrics {
+            let resource_target = attribute(&resource.resource.attributes, &mapping.target);
+            for metric in resource.scope_metrics.iter().flat_map(|s| &s.metrics) {
+                let Some(slot) = mapping.value_slot(&metric.name) else {
+                    continue;
+                };
+                let points = metric.gauge.iter().chain(&metric.sum);
+                for point in points.flat_map(|p| &p.data_points) {
+                    let target = attribute(&point.attributes, &mapping.target)
+                        .or(resource_target)
+                        .unwrap_or(&mapping.default_target);
+                    let timestamp_ms = point.time_unix_nano.0 / 1e6;
+                    let row = rows.entry((target, timestamp_ms.to_bits())).or_default();
+                    row.timestamp = Some(timestamp_ms);
+                    row.values[slot] = point.as_double.or(point.as_int.as_ref().map(|i| i.0));
+                }
+            }
+        }
+        for ((target, _), row) in &rows {
+            // Already in ms: `timestamp_scale` does not apply
+            let sample = mapping
+                .sample(row, 1.0)
+                .map_err(|err| line_error(number, err))?;
+            sink(target, sample);
+            count += 1;
+        }
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latency_mapping() -> TraceMapping {
+        TraceMapping {
+            timestamp: "ts".to_string(),
+            timestamp_scale: 1000.0,
+            target: "service".to_string(),
+            latency: Some(AxisMapping::new("p99_ms", 50.0, 550.0)),
+            error: Some(AxisMapping::new("error_rate", 0.0, 0.1)),
+            saturation: None,
+            offered: Some("requests".to_string()),
+            ..TraceMapping::default()
+        }
+    }
+
+    #[test]
+    fn test_jsonl_maps_fields_and_reports_line() {
+        let input = concat!(
+            "{\"ts\": 1, \"service\": \"api\", \"p99_ms\": 300, \"error_rate\": 0.01, \"requests\": 40, \"extra\": [1, {\"a\": 2}]}\n",
+            "\n",
+            "{\"ts\": 2, \"service\": \"b\\u0069lling\", \"p99_ms\": 10}\n",
+        );
+        let trace = load(TraceFormat::Jsonl, input.as_bytes(), &latency_mapping()).unwrap();
+        assert_eq!(trace.len(), 2);
+        let api = &trace.targets[0];
+        assert_eq!(api.target, "api");
+        assert_eq!(api.samples[0].timestamp_ms, 1000.0);
+        assert_eq!(api.samples[0].pressure.latency, 0.5);
+        assert!((api.samples[0].pressure.error - 0.1).abs() < 1e-12);
+        assert_eq!(api.samples[0].offered, 40.0);
+        let billing = &trace.targets[1];
+        assert_eq!(billing.target, "billing");
+        assert_eq!(billing.samples[0].pressure.latency, 0.0);
+        assert_eq!(billing.samples[0].offered, 1.0);
+
+        let err = load(
+            TraceFormat::Jsonl,
+            "{\"ts\": 1}\n{\"p99_ms\": 1}\n".as_bytes(),
+            &latency_mapping(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_csv_header_and_default_target() {
+        let input = "ts,\"p99_ms\",requests,service\n1,550,10,api\n2,,5,\n";
+        let mut seen = Vec::new();
+        let count = read_csv(input.as_bytes(), &latency_mapping(), |target, sample| {
+            seen.push((target.to_string(), sample.pressure.latency, sample.offered))
+        })
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(seen[0], ("api".to_string(), 1.0, 10.0));
+        assert_eq!(seen[1], ("default".to_string(), 0.0, 5.0));
+
+        let err = read_csv("ts\nsoon\n".as_bytes(), &latency_mapping(), |_, _| {}).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_otlp_groups_metrics_by_target_and_time() {
+        let mapping = TraceMapping {
+            target: "service.name".to_string(),
+            ..latency_mapping()
+        };
+        let point = |service: &str, ns: &str, value: &str| {
+            format!(
+                "{{\"attributes\": [{{\"key\": \"service.name\", \"value\": {{\"stringValue\": \"{service}\"}}}}], \"timeUnixNano\": \"{ns}\", {value}}}"
+            )
+        };
+        let line = format!(
+            "{{\"resourceMetrics\": [{{\"resource\": {{\"attributes\": []}}, \"scopeMetrics\": [{{\"metrics\": [\
+             {{\"name\": \"p99_ms\", \"gauge\": {{\"dataPoints\": [{}, {}]}}}}, \
+             {{\"name\": \"requests\", \"sum\": {{\"dataPoints\": [{}]}}}}, \
+             {{\"name\": \"ignored\", \"histogram\": {{}}}}]}}]}}]}}\n",
+            point("api", "2000000", "\"asDouble\": 300.0"),
+            point("db", "2000000", "\"asDouble\": 50.0"),
+            point("api", "2000000", "\"asInt\": \"12\""),
+        );
+        let trace = load(TraceFormat::Otlp, line.as_bytes(), &mapping).unwrap();
+        assert_eq!(trace.len(), 2);
+        let api = &trace.targets[0];
+        assert_eq!(api.target, "api");
+        assert_eq!(api.samples[0].timestamp_ms, 2.0);
+        assert_eq!(api.samples[0].pressure.latency, 0.5);
+        assert_eq!(api.samples[0].offered, 12.0);
+        assert_eq!(trace.targets[1].samples[0].offered, 1.0);
+    }
+}
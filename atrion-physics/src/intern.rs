@@ -0,0 +1,91 @@
+/**
+ * String interning for target ids.
+ *
+ * Callers that only have string ids resolve each one to a `TargetHandle`
+ * once and pass the handle on the hot path. Unlike slab handles, an
+ * interned handle never goes stale: it names the id, not the engine, so a
+ * target that is removed and registered again keeps the same handle.
+ */
+use std::collections::HashMap;
+
+/// Interned target id
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TargetHandle(u32);
+
+impl TargetHandle {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+}
+
+/// Append-only id ↔ handle table
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    names: Vec<String>,
+    handles: HashMap<String, TargetHandle>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Handle for `id`, interning it on first sight
+    pub fn resolve(&mut self, id: &str) -> TargetHandle {
+        if let Some(&handle) = self.handles.get(id) {
+            return handle;
+        }
+        let handle = TargetHandle(self.names.len() as u32);
+        self.names.push(id.to_string());
+        self.handles.insert(id.to_string(), handle);
+        handle
+    }
+
+    /// Handle for an already interned id
+    pub fn get(&self, id: &str) -> Option<TargetHandle> {
+        self.handles.get(id).copied()
+    }
+
+    pub fn name(&self, handle: TargetHandle) -> Option<&str> {
+        self.names.get(handle.index()).map(String::as_str)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_is_idempotent() {
+        let mut interner = Interner::new();
+        let api = interner.resolve("api");
+        let db = interner.resolve("db");
+        assert_ne!(api, db);
+        assert_eq!(interner.resolve("api"), api);
+        assert_eq!(interner.get("db"), Some(db));
+        assert_eq!(interner.get("cache"), None);
+        assert_eq!(interner.name(db), Some("db"));
+        assert_eq!(interner.name(TargetHandle::from_bits(9)), None);
+        assert_eq!(interner.len(), 2);
+    }
+}
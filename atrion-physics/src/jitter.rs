@@ -0,0 +1,160 @@
+/**
+ * Jittered breaker recovery.
+ *
+ * Targets that trip together (a shared dependency failing) also recover
+ * together, and every pool (or fleet instance) that sees the recovery
+ * re-admits in the same tick. With `PhysicsConfig::recovery_jitter` set
+ * each engine
+ *
+ * - holds the breaker open for a fixed share of `max_delay_ms` once it
+ *   would otherwise close, and
+ * - stretches or shortens its soft-start window by up to `slope_spread`.
+ *
+ * The shares are deterministic: they hash the config `seed` with the
+ * engine's jitter key, which `EnginePool` derives from the target id. Give
+ * each fleet instance its own seed to spread the same target across
+ * instances; keep the seed to make runs reproducible.
+ */
+use serde::{Deserialize, Serialize};
+
+/// Recovery jitter configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct RecoveryJitterConfig {
+    pub seed: u64,
+    /// Longest extra time the breaker stays open once recovery is due
+    #[serde(alias = "max_delay_ms")]
+    pub max_delay_ms: f64,
+    /// Soft-start windows vary by up to ± this fraction (0..1)
+    #[serde(alias = "slope_spread")]
+    pub slope_spread: f64,
+}
+
+impl Default for RecoveryJitterConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0x5EED,
+            max_delay_ms: 2_000.0,
+            slope_spread: 0.25,
+        }
+    }
+}
+
+/// Separates the slope draw from the delay draw
+const SLOPE_SALT: u64 = 0xA5A5_5A5A_C3C3_3C3C;
+
+impl RecoveryJitterConfig {
+    /// Recovery delay for the engine with jitter `key`
+    pub fn delay_ms(&self, key: u64) -> f64 {
+        self.max_delay_ms.max(0.0) * unit(self.seed ^ key)
+    }
+
+    /// Jittered soft-start window for the engine with jitter `key`
+    pub fn window_ms(&self, key: u64, window_ms: f64) -> f64 {
+        let spread = self.slope_spread.clamp(0.0, 1.0);
+        window_ms * (1.0 + spread * (2.0 * unit(self.seed ^ key ^ SLOPE_SALT) - 1.0))
+    }
+}
+
+/// Jitter key for a target id (64-bit FNV-1a)
+pub fn key_for(id: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in id.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// Uniform in `[0, 1)` from one SplitMix64 step
+fn unit(state: u64) -> f64 {
+    let mut z = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::EnginePool;
+    use crate::types::{OperationalMode, PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_draws_are_deterministic_and_bounded() {
+        let config = RecoveryJitterConfig::default();
+        let delays: Vec<f64> = (0..100)
+            .map(|i| config.delay_ms(key_for(&format!("svc-{i}"))))
+            .collect();
+        assert!(delays.iter().all(|d| (0.0..2_000.0).contains(d)));
+        assert!(delays.iter().any(|&d| d < 500.0) && delays.iter().any(|&d| d > 1_500.0));
+        assert_eq!(
+            config.delay_ms(key_for("api")),
+            config.delay_ms(key_for("api"))
+        );
+
+        let other = RecoveryJitterConfig { seed: 7, ..config };
+        assert_ne!(
+            config.delay_ms(key_for("api")),
+            other.delay_ms(key_for("api"))
+        );
+        let window = config.window_ms(key_for("api"), 10_000.0);
+        assert!((7_500.0..=12_500.0).contains(&window));
+    }
+
+    #[test]
+    fn test_pool_targets_recover_at_different_times() {
+        let config = PhysicsConfig {
+            recovery_jitter: Some(RecoveryJitterConfig {
+                max_delay_ms: 20_000.0,
+                ..RecoveryJitterConfig::default()
+            }),
+            ..PhysicsConfig::default()
+        };
+        let mut pool = EnginePool::new(config, SensitivityWeights::default());
+        let ids = ["a", "b", "c", "d"];
+        let mut now = 0.0;
+        let mut tick_all = |pool: &mut EnginePool, pressure, dt| {
+            now += dt;
+            for id in ids {
+                pool.tick(id, now, pressure);
+            }
+            now
+        };
+        for _ in 0..10 {
+            tick_all(&mut pool, PressureVector::new(0.0, 0.0, 0.0), 100.0);
+        }
+        for _ in 0..20 {
+            tick_all(&mut pool, PressureVector::new(1.0, 1.0, 1.0), 100.0);
+        }
+        let mode = |pool: &EnginePool, id| pool.get(id).unwrap().state().mode;
+        assert!(ids
+            .iter()
+            .all(|id| mode(&pool, id) == OperationalMode::CircuitBreaker));
+
+        let mut recovered_at = Vec::new();
+        while recovered_at.len() < ids.len() {
+            let at = tick_all(&mut pool, PressureVector::new(0.0, 0.0, 0.0), 1_000.0);
+            for id in ids {
+                if mode(&pool, id) == OperationalMode::Operational
+                    && !recovered_at.iter().any(|(seen, _)| *seen == id)
+                {
+                    recovered_at.push((id, at));
+                }
+            }
+        }
+        let first = recovered_at
+            .iter()
+            .map(|(_, at)| *at)
+            .fold(f64::MAX, f64::min);
+        let last = recovered_at.iter().map(|(_, at)| *at).fold(0.0, f64::max);
+        assert!(last > first);
+    }
+}
@@ -0,0 +1,387 @@
+/**
+ * Write-ahead journal (crash recovery).
+ *
+ * An engine with a journal appends every state-changing input (applied
+ * samples, outcomes, overrides, scar resets, fast-forwards, fleet merges,
+ * config swaps)
+ * to a `JournalSink` before acting on it. After a crash, restore the last
+ * snapshot and `TargetEngine::replay` the records written after it.
+ *
+ * Sinks: `MemoryJournal` everywhere, `FileJournal` (JSON lines) on native
+ * with the `journal` feature, and a JS object with an `append(record)`
+ * method (e.g. backed by IndexedDB) on WASM.
+ *
+ * Optional layers (burn rate, tuning, ...) are not journaled themselves:
+ * enable the same layers before replaying.
+ */
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::TargetState;
+use crate::error::AtrionError;
+use crate::markers::MarkerKind;
+use crate::reorder::SequencedSample;
+use crate::store::SharedState;
+use crate::types::{OperationalMode, PhysicsConfig};
+
+/// `Send` on native (pools are shared across threads), nothing on WASM
+/// (JS-backed sinks are single-threaded)
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send> MaybeSend for T {}
+
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T {}
+
+/// One state-changing input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum JournalEntry {
+    /// Sample handed to the physics (after any reorder window)
+    Sample {
+        sample: SequencedSample,
+    },
+    /// Late sample slotted into the past (`TargetEngine::tick_at`)
+    Backfill {
+        sample: SequencedSample,
+    },
+    Outcomes {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        good: u64,
+        bad: u64,
+    },
+    /// External mode authority (`TargetEngine::force_mode`)
+    ForceMode {
+        mode: OperationalMode,
+    },
+    /// Operator override (`TargetEngine::force_mode_until`)
+    ForceModeUntil {
+        mode: OperationalMode,
+        #[serde(alias = "until_ms")]
+        until_ms: f64,
+    },
+    PauseTrauma {
+        #[serde(alias = "until_ms")]
+        until_ms: f64,
+    },
+    ClearOverrides,
+    Reset,
+    ResetScar,
+    ResetMomentum,
+    ClearHistory,
+    /// Quiet period decayed through (`TargetEngine::fast_forward`)
+    FastForward {
+        #[serde(alias = "now_ms")]
+        now_ms: f64,
+    },
+    /// Operational marker (`TargetEngine::mark_event`)
+    Mark {
+        kind: MarkerKind,
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+    },
+    /// New instance behind the target (`TargetEngine::restart_warmup`)
+    RestartWarmup {
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+    },
+    Absorb {
+        shared: SharedState,
+    },
+    /// State loaded from a snapshot or delta (`TargetEngine::replace_state`)
+    State {
+        state: TargetState,
+    },
+    Config {
+        config: Box<PhysicsConfig>,
+    },
+}
+
+/// A journal entry with its sequence number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub entry: JournalEntry,
+}
+
+/// Durable destination for journal records
+pub trait JournalSink: MaybeSend {
+    fn append(&mut self, record: &JournalRecord) -> Result<(), AtrionError>;
+}
+
+/// In-memory sink; clones share the same buffer
+pub type MemoryJournal = Arc<Mutex<Vec<JournalRecord>>>;
+
+impl JournalSink for MemoryJournal {
+    fn append(&mut self, record: &JournalRecord) -> Result<(), AtrionError> {
+        self.lock()
+            .map_err(|_| AtrionError::Journal("memory journal poisoned".into()))?
+            .push(record.clone());
+        Ok(())
+    }
+}
+
+/// Sequencing and failure accounting around a sink
+pub struct Journal {
+    sink: Box<dyn JournalSink>,
+    next_seq: u64,
+    failures: u64,
+}
+
+impl Journal {
+    pub fn new(sink: Box<dyn JournalSink>, next_seq: u64) -> Self {
+        Self {
+            sink,
+            next_seq,
+            failures: 0,
+        }
+    }
+
+    /// Append an entry; a failed write is counted and its seq still used,
+    /// so replay can tell a gap from a clean journal
+    pub fn append(&mut self, entry: JournalEntry) {
+        let record = JournalRecord {
+            seq: self.next_seq,
+            entry,
+        };
+        self.next_seq += 1;
+        if self.sink.append(&record).is_err() {
+            self.failures += 1;
+        }
+    }
+
+    /// Sequence number the next record will get
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// Records the sink failed to persist
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+}
+
+// ============================================================================
+// FILE SINK (native)
+// ============================================================================
+
+#[cfg(all(feature = "journal", not(target_arch = "wasm32")))]
+pub use file::FileJournal;
+
+#[cfg(all(feature = "journal", not(target_arch = "wasm32")))]
+mod file {
+    use std::fs::{File, OpenOptions};
+    use std::io::{BufRead, BufReader, BufWriter, Write};
+    use std::path::Path;
+
+    use super::{JournalRecord, JournalSink};
+    use crate::error::AtrionError;
+
+    fn io_error(err: std::io::Error) -> AtrionError {
+        AtrionError::Journal(err.to_string())
+    }
+
+    /// Append-only JSON-lines file
+    ///
+    /// Each record is flushed to the OS before `append` returns; with
+    /// `with_fsync(true)` it is also synced to disk (survives power loss).
+    pub struct FileJournal {
+        writer: BufWriter<File>,
+        fsync: bool,
+    }
+
+    impl FileJournal {
+        pub fn open(path: impl AsRef<Path>) -> Result<Self, AtrionError> {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(io_error)?;
+            Ok(Self {
+                writer: BufWriter::new(file),
+                fsync: false,
+            })
+        }
+
+        pub fn with_fsync(mut self, fsync: bool) -> Self {
+            self.fsync = fsync;
+            self
+        }
+
+        /// Read every record; a torn final line (crash mid-write) is dropped
+        pub fn read(path: impl AsRef<Path>) -> Result<Vec<JournalRecord>, AtrionError> {
+            let file = File::open(path).map_err(io_error)?;
+            let lines: Vec<String> = BufReader::new(file)
+                .lines()
+                .collect::<Result<_, _>>()
+                .map_err(io_error)?;
+            let last = lines.len().saturating_sub(1);
+            let mut records = Vec::with_capacity(lines.len());
+            for (i, line) in lines.iter().enumerate() {
+                match serde_json::from_str(line) {
+                    Ok(record) => records.push(record),
+                    Err(_) if i == last => break,
+                    Err(err) => return Err(AtrionError::Parse(err.to_string())),
+                }
+            }
+            Ok(records)
+        }
+    }
+
+    impl JournalSink for FileJournal {
+        fn append(&mut self, record: &JournalRecord) -> Result<(), AtrionError> {
+            serde_json::to_writer(&mut self.writer, record)
+                .map_err(|err| AtrionError::Journal(err.to_string()))?;
+            self.writer.write_all(b"\n").map_err(io_error)?;
+            self.writer.flush().map_err(io_error)?;
+            if self.fsync {
+                self.writer.get_ref().sync_data().map_err(io_error)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+// ============================================================================
+// JS SINK (WASM)
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+mod js {
+    use wasm_bindgen::prelude::*;
+
+    use super::{JournalRecord, JournalSink};
+    use crate::engine::TargetEngine;
+    use crate::error::AtrionError;
+
+    #[wasm_bindgen]
+    extern "C" {
+        /// Any object with `append(record)`, e.g. an IndexedDB writer
+        #[wasm_bindgen(typescript_type = "{ append(record: any): void }")]
+        pub type JsJournalSink;
+
+        #[wasm_bindgen(method, catch, js_name = append)]
+        fn append_js(this: &JsJournalSink, record: JsValue) -> Result<(), JsValue>;
+    }
+
+    impl JournalSink for JsJournalSink {
+        fn append(&mut self, record: &JournalRecord) -> Result<(), AtrionError> {
+            let value = crate::to_js(record).map_err(|e| AtrionError::Journal(format!("{e:?}")))?;
+            self.append_js(value)
+                .map_err(|e| AtrionError::Journal(format!("{e:?}")))
+        }
+    }
+
+    #[wasm_bindgen]
+    impl TargetEngine {
+        /// Journal state changes to `sink.append(record)`, numbering from `nextSeq`
+        #[wasm_bindgen(js_name = enableJournal)]
+        pub fn enable_journal_js(&mut self, sink: JsJournalSink, next_seq: f64) {
+            self.enable_journal(Box::new(sink), next_seq as u64);
+        }
+
+        /// Replay plain journal records newer than `afterSeq`
+        #[wasm_bindgen(js_name = replayJournal)]
+        pub fn replay_js(
+            &mut self,
+            records: JsValue,
+            after_seq: Option<f64>,
+        ) -> Result<usize, JsValue> {
+            let records: Vec<JournalRecord> = serde_wasm_bindgen::from_value(records)?;
+            Ok(self.replay(records, after_seq.map(|s| s as u64)))
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PressureVector, SensitivityWeights};
+
+    fn drive(engine: &mut TargetEngine, from: u32, to: u32) {
+        for i in from..to {
+            let load = if i % 5 == 0 { 0.9 } else { 0.2 };
+            engine.tick(i as f64 * 100.0, PressureVector::new(load, 0.1, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_replay_after_snapshot_reconstructs_state() {
+        let sink = MemoryJournal::default();
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_journal(Box::new(sink.clone()), 0);
+
+        drive(&mut engine, 0, 20);
+        let checkpoint = engine.to_document();
+        let after_seq = engine.journal_state().unwrap().next_seq() - 1;
+
+        engine.force_mode_until(OperationalMode::CircuitBreaker, 2500.0);
+        drive(&mut engine, 20, 30);
+        engine.reset_scar();
+        drive(&mut engine, 30, 40);
+
+        // Crash: rebuild from the checkpoint plus the journal tail
+        let mut recovered = TargetEngine::from_document(checkpoint);
+        let records = sink.lock().unwrap().clone();
+        let applied = recovered.replay(records, Some(after_seq));
+        assert_eq!(applied, 22);
+
+        let (a, b) = (engine.state(), recovered.state());
+        assert_eq!(a.resistance, b.resistance);
+        assert_eq!(a.scar, b.scar);
+        assert_eq!(a.mode, b.mode);
+        assert_eq!(a.tick_count, b.tick_count);
+    }
+
+    #[test]
+    fn test_replay_repeats_fast_forward() {
+        let sink = MemoryJournal::default();
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_journal(Box::new(sink.clone()), 0);
+        drive(&mut engine, 0, 20);
+        engine.fast_forward(600_000.0);
+        assert_eq!(engine.state().last_updated_ms, 600_000.0);
+
+        let mut recovered =
+            TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        recovered.replay(sink.lock().unwrap().clone(), None);
+        assert_eq!(recovered.state().last_updated_ms, 600_000.0);
+        assert_eq!(recovered.state().scar, engine.state().scar);
+        assert_eq!(recovered.state().momentum, engine.state().momentum);
+    }
+
+    #[cfg(all(feature = "journal", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_file_journal_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("atrion-journal-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_journal(Box::new(FileJournal::open(&path).unwrap()), 0);
+        drive(&mut engine, 0, 15);
+
+        // A torn last line from a crash mid-write is ignored
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| std::io::Write::write_all(&mut f, b"{\"seq\":15,\"op\""))
+            .unwrap();
+
+        let mut recovered =
+            TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        recovered.replay(FileJournal::read(&path).unwrap(), None);
+        assert_eq!(recovered.state().resistance, engine.state().resistance);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,3 +1,5 @@
+// Nightly-only: `core::simd` backend for vector math (see vector.rs)
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
 /**
  * Atrion Physics Engine - WASM Entry Point
  *
@@ -12,13 +14,126 @@ static ALLOC: lol_alloc::AssumeSingleThreaded<lol_alloc::FreeListAllocator> =
 
 use wasm_bindgen::prelude::*;
 
+#[cfg(all(feature = "admin", not(target_arch = "wasm32")))]
+pub mod admin;
+pub mod admission;
+pub mod alarms;
+pub mod anneal;
+pub mod audit;
+pub mod backfill;
+pub mod backoff;
+pub mod balance;
+pub mod brownout;
+pub mod builder;
+pub mod burnrate;
+pub mod cell;
+pub mod changepoint;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod characterize;
+pub mod chunk;
+pub mod cluster;
+pub mod compliance;
+pub mod confidence;
+pub mod cost;
+pub mod counterfactual;
+pub mod critical;
+pub mod deadline;
+pub mod debug;
+pub mod diff;
+#[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+pub mod driver;
+pub mod engine;
+pub mod error;
+pub mod events;
+pub mod explain;
+pub mod export;
+#[cfg(all(feature = "envoy", not(target_arch = "wasm32")))]
+pub mod extauthz;
+pub mod fairness;
+pub mod fastmath;
+pub mod fixedtick;
+pub mod flap;
+pub mod goodput;
+#[cfg(all(feature = "gossip", not(target_arch = "wasm32")))]
+pub mod gossip;
+#[cfg(feature = "webgpu")]
+pub mod gpu;
+pub mod healthcheck;
+pub mod hedge;
+pub mod history;
+#[cfg(all(feature = "ingest", not(target_arch = "wasm32")))]
+pub mod ingest;
+pub mod intern;
+pub mod jitter;
+pub mod journal;
+pub mod markers;
+pub mod merge;
+pub mod metastable;
+pub mod metrics;
 pub mod momentum;
+pub mod montecarlo;
+#[cfg(all(feature = "otel", not(target_arch = "wasm32")))]
+pub mod otel;
+pub mod overrides;
+pub mod pacing;
+pub mod parity;
+pub mod persist;
+pub mod pid;
+pub mod pool;
+pub mod priority;
+#[cfg(all(feature = "prometheus", not(target_arch = "wasm32")))]
+pub mod prometheus;
+pub mod quarantine;
+#[cfg(all(feature = "distributed", not(target_arch = "wasm32")))]
+pub mod redis_store;
+pub mod reorder;
+#[cfg(all(feature = "report", not(target_arch = "wasm32")))]
+pub mod report;
 pub mod resistance;
+pub mod retry;
 pub mod scar;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod seasonal;
+pub mod sensitivity;
+#[cfg(all(feature = "server", not(target_arch = "wasm32")))]
+pub mod server;
+#[cfg(all(feature = "shared", not(target_arch = "wasm32")))]
+pub mod shared;
+#[cfg(all(feature = "shed", not(target_arch = "wasm32")))]
+pub mod shed;
+#[cfg(any(test, feature = "simlab"))]
+pub mod simlab;
+pub mod slab;
+pub mod snapshot;
+pub mod softstart;
+pub mod sources;
+pub mod stability;
+pub mod stats;
+#[cfg(all(feature = "statsd", not(target_arch = "wasm32")))]
+pub mod statsd;
+pub mod store;
+pub mod subset;
+#[cfg(all(feature = "tower", not(target_arch = "wasm32")))]
+pub mod tower;
+pub mod trace;
+pub mod transitions;
+pub mod tuning;
 pub mod types;
 pub mod vector;
+pub mod version;
+pub mod warmstart;
+pub mod warmup;
+pub mod workloads;
 
 use types::*;
+pub use version::{version_info, VersionInfo};
+
+/// Serialize to a plain JS value (maps become objects, not ES `Map`s)
+pub(crate) fn to_js<T: serde::Serialize + ?Sized>(value: &T) -> Result<JsValue, JsValue> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
 
 /// Main physics engine for WASM
 #[wasm_bindgen]
@@ -27,6 +142,12 @@ pub struct PhysicsEngine {
     weights: SensitivityWeights,
 }
 
+impl Default for PhysicsEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl PhysicsEngine {
     /// Create new physics engine
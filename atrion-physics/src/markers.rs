@@ -0,0 +1,163 @@
+/**
+ * Operational event markers (deploys, config changes, failovers).
+ *
+ * A resistance spike reads very differently when a deploy went out a
+ * minute before it. Hosts record such events with
+ * `TargetEngine::mark_event` (or `EnginePool::mark_event`); the engine
+ * keeps the newest `MARKER_CAPACITY` of them next to its tick history, and
+ * the annotated history / explain calls attach to each sample the markers
+ * from the window leading up to it.
+ *
+ * With `PhysicsConfig::deploy_grace` set, trauma is scaled down for a
+ * short window after every deploy marker: a rollout's cold caches and
+ * connection churn still raise resistance, but leave less scar behind.
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::explain::ResistanceBreakdown;
+use crate::history::HistorySample;
+
+/// Markers retained per target (oldest dropped first)
+pub const MARKER_CAPACITY: usize = 64;
+
+/// What happened
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[wasm_bindgen]
+pub enum MarkerKind {
+    Deploy,
+    ConfigChange,
+    Failover,
+}
+
+/// One recorded operational event
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Marker {
+    #[serde(alias = "timestamp_ms")]
+    pub timestamp_ms: f64,
+    pub kind: MarkerKind,
+}
+
+/// Reduced trauma after deploy markers
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DeployGraceConfig {
+    /// How long after a deploy the grace applies
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Multiplier on trauma during the grace window (0..=1)
+    #[serde(alias = "trauma_scale")]
+    pub trauma_scale: f64,
+}
+
+impl Default for DeployGraceConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 120_000.0,
+            trauma_scale: 0.25,
+        }
+    }
+}
+
+impl DeployGraceConfig {
+    /// Trauma multiplier at `now_ms` given the recorded markers
+    pub fn trauma_scale<'a>(
+        &self,
+        markers: impl IntoIterator<Item = &'a Marker>,
+        now_ms: f64,
+    ) -> f64 {
+        let in_grace = markers.into_iter().any(|marker| {
+            marker.kind == MarkerKind::Deploy
+                && now_ms >= marker.timestamp_ms
+                && now_ms - marker.timestamp_ms < self.window_ms
+        });
+        if in_grace {
+            self.trauma_scale.clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// History sample with the markers that preceded it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotatedSample {
+    #[serde(flatten)]
+    pub sample: HistorySample,
+    pub markers: Vec<Marker>,
+}
+
+/// Current resistance breakdown with the markers that preceded it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotatedExplanation {
+    pub breakdown: ResistanceBreakdown,
+    pub markers: Vec<Marker>,
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_history_is_annotated_with_preceding_markers() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut now = 0.0;
+        for i in 0..100 {
+            now += 100.0;
+            if i == 60 {
+                engine.mark_event(MarkerKind::Deploy, now);
+            }
+            let p = if i >= 60 { 0.8 } else { 0.1 };
+            engine.tick(now, PressureVector::new(p, 0.0, 0.0));
+        }
+        let annotated = engine.export_history_annotated(1_000.0, 1_000.0);
+        let tagged: Vec<f64> = annotated
+            .iter()
+            .filter(|entry| !entry.markers.is_empty())
+            .map(|entry| entry.sample.timestamp_ms)
+            .collect();
+        assert!(!tagged.is_empty());
+        assert!(tagged.iter().all(|at| (6_100.0..=7_100.0).contains(at)));
+
+        let explained = engine.explain_annotated(5_000.0);
+        assert_eq!(explained.markers[0].kind, MarkerKind::Deploy);
+        assert_eq!(explained.breakdown, engine.explain());
+        assert!(engine.explain_annotated(1_000.0).markers.is_empty());
+    }
+
+    #[test]
+    fn test_deploy_grace_reduces_trauma() {
+        let run = |grace: Option<DeployGraceConfig>| {
+            let config = PhysicsConfig {
+                deploy_grace: grace,
+                ..PhysicsConfig::default()
+            };
+            let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+            let mut now = 0.0;
+            for _ in 0..10 {
+                now += 100.0;
+                engine.tick(now, PressureVector::new(0.1, 0.0, 0.0));
+            }
+            engine.mark_event(MarkerKind::Deploy, now);
+            for _ in 0..20 {
+                now += 100.0;
+                engine.tick(now, PressureVector::new(0.9, 0.5, 0.5));
+            }
+            engine.state().scar.0
+        };
+        let plain = run(None);
+        let graced = run(Some(DeployGraceConfig::default()));
+        assert!(plain > 0.0);
+        assert!(graced < plain * 0.5, "{graced} vs {plain}");
+    }
+}
@@ -0,0 +1,356 @@
+/**
+ * Watermark-based merging of samples from several collectors.
+ *
+ * When more than one collector reports pressure for the same target,
+ * ticking on every report double-counts trauma. `SampleMerger` collects
+ * reports into time buckets per target, combines them per axis (max or
+ * mean), and releases a bucket only once the watermark has passed its
+ * end. The watermark is the oldest "newest timestamp" among the active
+ * sources minus `lateness_ms`, so one slow collector holds buckets open
+ * instead of having its reports dropped; a source silent for longer than
+ * `idle_ms` stops holding the watermark back.
+ *
+ * A source reporting twice into one bucket replaces its earlier value.
+ * Reports for buckets already released are dropped and counted as late.
+ */
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::pool::EnginePool;
+use crate::types::PressureVector;
+
+/// How one pressure axis is combined across sources
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AxisMerge {
+    Max,
+    Mean,
+}
+
+impl AxisMerge {
+    fn combine(self, values: impl Iterator<Item = f64>) -> f64 {
+        match self {
+            AxisMerge::Max => values.fold(0.0, f64::max),
+            AxisMerge::Mean => {
+                let (sum, count) = values.fold((0.0, 0), |(sum, n), v| (sum + v, n + 1));
+                if count == 0 {
+                    0.0
+                } else {
+                    sum / count as f64
+                }
+            }
+        }
+    }
+}
+
+/// Merger configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MergeConfig {
+    /// Bucket width; one merged sample per target per bucket
+    #[serde(alias = "bucket_ms")]
+    pub bucket_ms: f64,
+    /// How far the watermark trails the slowest active source
+    #[serde(alias = "lateness_ms")]
+    pub lateness_ms: f64,
+    /// Sources silent this long (behind the newest report) stop holding
+    /// the watermark back
+    #[serde(alias = "idle_ms")]
+    pub idle_ms: f64,
+    pub latency: AxisMerge,
+    pub error: AxisMerge,
+    pub saturation: AxisMerge,
+}
+
+impl Default for MergeConfig {
+    fn default() -> Self {
+        Self {
+            bucket_ms: 1_000.0,
+            lateness_ms: 0.0,
+            idle_ms: 10_000.0,
+            latency: AxisMerge::Max,
+            error: AxisMerge::Max,
+            saturation: AxisMerge::Mean,
+        }
+    }
+}
+
+/// One combined sample, stamped with the end of its bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedSample {
+    pub target: String,
+    pub timestamp_ms: f64,
+    pub pressure: PressureVector,
+    /// Sources that reported into the bucket
+    pub sources: u32,
+}
+
+/// What happened to pushed reports
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MergeStats {
+    pub merged: u64,
+    /// Same source, same bucket: replaced the earlier report
+    pub replaced: u64,
+    /// Bucket already released
+    pub late: u64,
+}
+
+/// Buckets reports from several sources and releases them by watermark
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct SampleMerger {
+    config: MergeConfig,
+    /// Source → newest timestamp it reported
+    sources: HashMap<String, f64>,
+    /// (bucket, target) → reports by source
+    buckets: BTreeMap<(i64, String), Vec<(String, PressureVector)>>,
+    /// Highest bucket released so far
+    released: Option<i64>,
+    stats: MergeStats,
+}
+
+impl SampleMerger {
+    pub fn new(config: MergeConfig) -> Self {
+        Self {
+            config,
+            sources: HashMap::new(),
+            buckets: BTreeMap::new(),
+            released: None,
+            stats: MergeStats::default(),
+        }
+    }
+
+    pub fn config(&self) -> &MergeConfig {
+        &self.config
+    }
+
+    pub fn stats(&self) -> MergeStats {
+        self.stats
+    }
+
+    /// Buckets still waiting for the watermark
+    pub fn pending(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Time up to which buckets are complete (`None` before any report)
+    pub fn watermark(&self) -> Option<f64> {
+        let newest = self
+            .sources
+            .values()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let slowest = self
+            .sources
+            .values()
+            .copied()
+            .filter(|&seen| newest - seen <= self.config.idle_ms)
+            .fold(f64::INFINITY, f64::min);
+        slowest
+            .is_finite()
+            .then_some(slowest - self.config.lateness_ms)
+    }
+
+    /// Record a report; returns the merged samples it completed, oldest
+    /// bucket first
+    pub fn push(
+        &mut self,
+        source: &str,
+        target: &str,
+        timestamp_ms: f64,
+        pressure: PressureVector,
+    ) -> Vec<MergedSample> {
+        let bucket = self.bucket_of(timestamp_ms);
+        if self.released.is_some_and(|released| bucket <= released) {
+            self.stats.late += 1;
+        } else {
+            let reports = self
+                .buckets
+                .entry((bucket, target.to_string()))
+                .or_default();
+            match reports.iter_mut().find(|(from, _)| from == source) {
+                Some((_, earlier)) => {
+                    *earlier = pressure;
+                    self.stats.replaced += 1;
+                }
+                None => reports.push((source.to_string(), pressure)),
+            }
+        }
+        let seen = self
+            .sources
+            .entry(source.to_string())
+            .or_insert(timestamp_ms);
+        *seen = seen.max(timestamp_ms);
+        match self.watermark() {
+            Some(watermark) => self.release(|end| end <= watermark),
+            None => Vec::new(),
+        }
+    }
+
+    /// Release every open bucket regardless of the watermark
+    pub fn flush(&mut self) -> Vec<MergedSample> {
+        self.release(|_| true)
+    }
+
+    /// Push a report and tick `pool` with whatever it completed; returns
+    /// how many ticks were applied
+    pub fn push_into(
+        &mut self,
+        pool: &mut EnginePool,
+        source: &str,
+        target: &str,
+        timestamp_ms: f64,
+        pressure: PressureVector,
+    ) -> usize {
+        let merged = self.push(source, target, timestamp_ms, pressure);
+        for sample in &merged {
+            pool.tick(&sample.target, sample.timestamp_ms, sample.pressure);
+        }
+        merged.len()
+    }
+
+    fn bucket_of(&self, timestamp_ms: f64) -> i64 {
+        (timestamp_ms / self.config.bucket_ms.max(f64::MIN_POSITIVE)).floor() as i64
+    }
+
+    fn release(&mut self, complete: impl Fn(f64) -> bool) -> Vec<MergedSample> {
+        let mut merged = Vec::new();
+        while let Some(entry) = self.buckets.first_entry() {
+            let bucket = entry.key().0;
+            let end_ms = (bucket + 1) as f64 * self.config.bucket_ms;
+            if !complete(end_ms) {
+                break;
+            }
+            let ((_, target), reports) = entry.remove_entry();
+            let axis = |merge: AxisMerge, get: fn(&PressureVector) -> f64| {
+                merge.combine(reports.iter().map(|(_, pressure)| get(pressure)))
+            };
+            merged.push(MergedSample {
+                target,
+                timestamp_ms: end_ms,
+                pressure: PressureVector::new(
+                    axis(self.config.latency, |p| p.latency),
+                    axis(self.config.error, |p| p.error),
+                    axis(self.config.saturation, |p| p.saturation),
+                ),
+                sources: reports.len() as u32,
+            });
+            self.released = Some(self.released.map_or(bucket, |r| r.max(bucket)));
+        }
+        self.stats.merged += merged.len() as u64;
+        merged
+    }
+}
+
+#[wasm_bindgen]
+impl SampleMerger {
+    /// Merger from a plain `MergeConfig` object (undefined = defaults)
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(config: JsValue) -> Result<SampleMerger, JsValue> {
+        let config = if config.is_undefined() {
+            MergeConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        Ok(Self::new(config))
+    }
+
+    /// Record a report; returns completed merged samples as plain objects
+    #[wasm_bindgen(js_name = push)]
+    pub fn push_js(
+        &mut self,
+        source: &str,
+        target: &str,
+        timestamp_ms: f64,
+        pressure: &PressureVector,
+    ) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.push(source, target, timestamp_ms, *pressure))
+    }
+
+    /// Record a report and tick `pool` with what it completed
+    #[wasm_bindgen(js_name = pushInto)]
+    pub fn push_into_js(
+        &mut self,
+        pool: &mut EnginePool,
+        source: &str,
+        target: &str,
+        timestamp_ms: f64,
+        pressure: &PressureVector,
+    ) -> usize {
+        self.push_into(pool, source, target, timestamp_ms, *pressure)
+    }
+
+    #[wasm_bindgen(js_name = flush)]
+    pub fn flush_js(&mut self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.flush())
+    }
+
+    #[wasm_bindgen(getter, js_name = watermark)]
+    pub fn watermark_js(&self) -> Option<f64> {
+        self.watermark()
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(latency: f64, saturation: f64) -> PressureVector {
+        PressureVector::new(latency, 0.0, saturation)
+    }
+
+    #[test]
+    fn test_buckets_wait_for_the_slowest_source() {
+        let mut merger = SampleMerger::new(MergeConfig::default());
+        assert!(merger.push("a", "api", 100.0, p(0.2, 0.2)).is_empty());
+        assert!(merger.push("b", "api", 200.0, p(0.6, 0.4)).is_empty());
+        // Duplicate from "a" replaces its report instead of double-counting
+        assert!(merger.push("a", "api", 300.0, p(0.3, 0.2)).is_empty());
+        // "a" moves on; "b" still holds bucket 0 open
+        assert!(merger.push("a", "api", 1_100.0, p(0.1, 0.1)).is_empty());
+
+        let merged = merger.push("b", "api", 1_200.0, p(0.1, 0.1));
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].timestamp_ms, 1_000.0);
+        assert_eq!(merged[0].sources, 2);
+        assert_eq!(merged[0].pressure.latency, 0.6);
+        assert!((merged[0].pressure.saturation - 0.3).abs() < 1e-12);
+
+        assert!(merger.push("b", "api", 900.0, p(1.0, 1.0)).is_empty());
+        assert_eq!(
+            merger.stats(),
+            MergeStats {
+                merged: 1,
+                replaced: 1,
+                late: 1
+            }
+        );
+        assert_eq!(merger.flush().len(), 1);
+    }
+
+    #[test]
+    fn test_idle_source_stops_holding_the_watermark() {
+        let config = MergeConfig {
+            idle_ms: 5_000.0,
+            ..MergeConfig::default()
+        };
+        let mut merger = SampleMerger::new(config);
+        let mut pool = EnginePool::new(Default::default(), Default::default());
+        merger.push_into(&mut pool, "dead", "api", 0.0, p(0.5, 0.5));
+        let mut ticks = 0;
+        for i in 1..=8 {
+            ticks += merger.push_into(&mut pool, "live", "api", i as f64 * 1_000.0, p(0.1, 0.1));
+        }
+        // Buckets release once "dead" is 5 s behind, then keep pace
+        assert_eq!(merger.watermark(), Some(8_000.0));
+        assert_eq!(ticks, 8);
+        assert_eq!(pool.get("api").unwrap().state().tick_count, 8);
+    }
+}
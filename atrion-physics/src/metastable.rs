@@ -0,0 +1,181 @@
+/**
+ * Metastable failure detection.
+ *
+ * A metastable failure outlives its trigger: the fault is gone, but the
+ * work it left behind (retries, timed-out requests still being served, a
+ * cold cache) keeps the target overloaded. The breaker sheds, yet pressure
+ * never drains, so closing it lets the same storm back in. The usual way
+ * out is a stronger intervention than shedding: stop retries upstream,
+ * drop queued work, or hold the target open until load is cut.
+ *
+ * The detector watches for that shape. While resistance is at least
+ * `resistance` (the target is shedding) and scar is at least `scar` (the
+ * trouble is sustained, not a blip), it compares pressure magnitude across
+ * `window_ms` windows. A window in which pressure fell by less than
+ * `min_decline` raises a `MetastableSuspected` event; one that drained
+ * starts a new window. Leaving the shedding regime clears the suspicion,
+ * so a later episode is reported again.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::engine::TargetState;
+use crate::events::EngineEvent;
+use crate::vector;
+
+/// Metastable detection configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct MetastableConfig {
+    /// Resistance at which the target counts as shedding
+    pub resistance: f64,
+    /// Scar at which the trouble counts as sustained
+    pub scar: f64,
+    /// Span over which pressure is expected to drain
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Relative pressure drop per window that counts as draining (0..1)
+    #[serde(alias = "min_decline")]
+    pub min_decline: f64,
+}
+
+impl Default for MetastableConfig {
+    fn default() -> Self {
+        Self {
+            resistance: 50.0,
+            scar: 5.0,
+            window_ms: 10_000.0,
+            min_decline: 0.1,
+        }
+    }
+}
+
+/// Current observation window and suspicion
+#[derive(Debug, Clone)]
+pub struct MetastableDetector {
+    config: MetastableConfig,
+    /// Window start and pressure magnitude at that time
+    window: Option<(f64, f64)>,
+    /// Start of the window that failed to drain
+    suspected_since: Option<f64>,
+}
+
+impl MetastableDetector {
+    pub fn new(config: MetastableConfig) -> Self {
+        Self {
+            config,
+            window: None,
+            suspected_since: None,
+        }
+    }
+
+    pub fn config(&self) -> &MetastableConfig {
+        &self.config
+    }
+
+    /// Whether a metastable failure is currently suspected
+    pub fn suspected(&self) -> bool {
+        self.suspected_since.is_some()
+    }
+
+    /// Feed the state after a tick; returns `MetastableSuspected` when a
+    /// new episode is detected
+    pub fn observe(&mut self, now_ms: f64, state: &TargetState) -> Option<EngineEvent> {
+        let pressure = vector::magnitude(&state.pressure);
+        if state.resistance.0 < self.config.resistance || state.scar.0 < self.config.scar {
+            self.window = None;
+            self.suspected_since = None;
+            return None;
+        }
+        let Some((started_ms, baseline)) = self.window else {
+            self.window = Some((now_ms, pressure));
+            return None;
+        };
+        if now_ms - started_ms < self.config.window_ms {
+            return None;
+        }
+        self.window = Some((now_ms, pressure));
+        let decline = self.config.min_decline.clamp(0.0, 1.0);
+        if pressure < baseline * (1.0 - decline) {
+            // Draining: shedding is working
+            self.suspected_since = None;
+            return None;
+        }
+        if self.suspected_since.is_some() {
+            return None;
+        }
+        self.suspected_since = Some(started_ms);
+        Some(EngineEvent::MetastableSuspected {
+            at_ms: now_ms,
+            since_ms: started_ms,
+            resistance: state.resistance.0,
+            scar: state.scar.0,
+            pressure,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::simlab::{FailureScript, Fault, ServerSpec, SimConfig, VirtualCluster};
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_draining_pressure_is_not_suspected() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        engine.enable_metastable_detection(MetastableConfig::default());
+        let mut now = 0.0;
+        for _ in 0..10 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        // A storm that decays steadily while the breaker sheds
+        for i in 0..300 {
+            now += 100.0;
+            let p = 1.0 - i as f64 / 300.0;
+            engine.tick(now, PressureVector::new(p, p, p));
+        }
+        assert!(!engine.metastable_suspected());
+        assert!(!engine
+            .drain_events()
+            .iter()
+            .any(|event| matches!(event, EngineEvent::MetastableSuspected { .. })));
+    }
+
+    #[test]
+    fn test_retry_storm_is_suspected_in_closed_loop() {
+        let cluster = |gate| {
+            VirtualCluster::new(
+                SimConfig {
+                    gate,
+                    ..SimConfig::default()
+                },
+                vec![ServerSpec::default(); 2],
+            )
+            .fail(FailureScript {
+                server: 0,
+                from_ms: 10_000.0,
+                until_ms: 20_000.0,
+                fault: Fault::Degraded { factor: 0.3 },
+            })
+            .detect_metastable(MetastableConfig::default())
+        };
+        // Ungated clients keep retrying into the degraded server well
+        // after the fault ends
+        let storm = cluster(false).run();
+        assert!(storm
+            .ticks
+            .iter()
+            .any(|tick| tick.at_ms >= 20_000.0 && tick.metastable > 0));
+        // Gated clients shed and drain: nothing to escalate
+        let gated = cluster(true).run();
+        assert!(gated.ticks.iter().all(|tick| tick.metastable == 0));
+    }
+}
@@ -0,0 +1,89 @@
+/**
+ * Prometheus text exposition for an EnginePool.
+ *
+ * Dependency-free renderer shared by the admin endpoint and by callers
+ * that expose metrics through their own HTTP stack.
+ */
+use std::fmt::Write;
+
+use crate::engine::TargetState;
+use crate::pool::EnginePool;
+use crate::types::OperationalMode;
+
+/// Gauge definition: name, help text, value extractor
+type Gauge = (&'static str, &'static str, fn(&TargetState) -> f64);
+
+/// Numeric encoding of the operational mode for gauges
+#[inline]
+pub fn mode_value(mode: OperationalMode) -> u8 {
+    match mode {
+        OperationalMode::Bootstrap => 0,
+        OperationalMode::Operational => 1,
+        OperationalMode::CircuitBreaker => 2,
+    }
+}
+
+/// Render per-target gauges in Prometheus text format (targets sorted by id)
+pub fn render_prometheus(pool: &EnginePool) -> String {
+    let mut targets: Vec<_> = pool.iter().collect();
+    targets.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut out = String::new();
+    let gauges: [Gauge; 4] = [
+        ("atrion_resistance_ohms", "Current resistance", |s| {
+            s.resistance.0
+        }),
+        ("atrion_scar", "Accumulated scar tissue", |s| s.scar.0),
+        ("atrion_momentum", "Pressure momentum", |s| s.momentum.0),
+        (
+            "atrion_mode",
+            "Operational mode (0=bootstrap, 1=operational, 2=circuit_breaker)",
+            |s| mode_value(s.mode) as f64,
+        ),
+    ];
+
+    for (name, help, value) in gauges {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} gauge");
+        for (id, engine) in &targets {
+            let _ = writeln!(
+                out,
+                "{name}{{target=\"{}\"}} {}",
+                escape_label(id),
+                value(engine.state())
+            );
+        }
+    }
+    out
+}
+
+/// Escape a label value per the exposition format
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    #[test]
+    fn test_render_prometheus() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        pool.tick("b", 0.0, PressureVector::new(0.0, 0.0, 0.0));
+        pool.tick("a\"x", 0.0, PressureVector::new(0.0, 0.0, 0.0));
+
+        let text = render_prometheus(&pool);
+        assert!(text.contains("# TYPE atrion_resistance_ohms gauge"));
+        assert!(text.contains("atrion_resistance_ohms{target=\"b\"} 12"));
+        assert!(text.contains("atrion_mode{target=\"a\\\"x\"} 0"));
+        assert!(text.find("a\\\"x").unwrap() < text.find("target=\"b\"").unwrap());
+    }
+}
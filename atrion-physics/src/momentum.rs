@@ -3,6 +3,7 @@
  *
  * Momentum = weighted moving average of pressure changes.
  */
+use crate::fastmath;
 use crate::types::{Momentum, PhysicsConfig, PressureVector};
 use crate::vector;
 
@@ -21,9 +22,58 @@ pub fn update_momentum(
     delta_t: f64,
     config: &PhysicsConfig,
 ) -> Momentum {
-    // Exponential decay factor
-    let decay = (-delta_t / config.momentum_halflife).exp();
+    update_momentum_weighted(
+        current_momentum,
+        previous_pressure,
+        current_pressure,
+        delta_t,
+        config,
+        1.0,
+    )
+}
+
+/// Update momentum with the new acceleration scaled by confidence in [0, 1]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", name = "atrion.momentum", skip_all)
+)]
+pub fn update_momentum_weighted(
+    current_momentum: Momentum,
+    previous_pressure: &PressureVector,
+    current_pressure: &PressureVector,
+    delta_t: f64,
+    config: &PhysicsConfig,
+    confidence: f64,
+) -> Momentum {
+    let decay = momentum_decay(delta_t, config);
+    update_momentum_decayed(
+        current_momentum,
+        previous_pressure,
+        current_pressure,
+        delta_t,
+        decay,
+        confidence,
+    )
+}
 
+/// Momentum decay factor e^(-Δt/halflife)
+#[inline]
+pub fn momentum_decay(delta_t: f64, config: &PhysicsConfig) -> f64 {
+    fastmath::exp(-delta_t / config.momentum_halflife)
+}
+
+/// `update_momentum_weighted` with a precomputed `momentum_decay` factor
+#[inline]
+pub fn update_momentum_decayed(
+    current_momentum: Momentum,
+    previous_pressure: &PressureVector,
+    current_pressure: &PressureVector,
+    delta_t: f64,
+    decay: f64,
+    confidence: f64,
+) -> Momentum {
     // Calculate pressure delta
     let delta_pressure = PressureVector {
         latency: current_pressure.latency - previous_pressure.latency,
@@ -39,7 +89,8 @@ pub fn update_momentum(
     };
 
     // Exponentially weighted moving average
-    let new_value = current_momentum.0 * decay + acceleration * (1.0 - decay);
+    let new_value =
+        current_momentum.0 * decay + confidence.clamp(0.0, 1.0) * acceleration * (1.0 - decay);
 
     Momentum(new_value)
 }
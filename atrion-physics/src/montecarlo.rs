@@ -0,0 +1,232 @@
+/**
+ * Monte Carlo capacity planning.
+ *
+ * A single replay answers "what would this trace have done"; planners
+ * want "how likely is a trip if next week looks roughly like this". Each
+ * run replays a seeded variation of the trace: the whole trace is scaled
+ * by a random load multiplier (offered traffic included) and every
+ * pressure component gets independent uniform noise. Trip counts and
+ * shed volume are summarized as distributions with a central interval.
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::anneal::Rng;
+use crate::counterfactual::replay;
+use crate::stats::percentile;
+use crate::trace::Trace;
+use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+/// Variation and sampling settings
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MonteCarloConfig {
+    pub runs: u32,
+    pub seed: u64,
+    /// Load multiplier drawn from [1 − jitter, 1 + jitter] per run
+    #[serde(alias = "load_jitter")]
+    pub load_jitter: f64,
+    /// Additive noise drawn from [−noise, noise] per pressure component
+    pub noise: f64,
+    /// Coverage of the reported interval (0.9 = 5th..95th percentile)
+    pub confidence: f64,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            runs: 200,
+            seed: 0x5EED,
+            load_jitter: 0.2,
+            noise: 0.05,
+            confidence: 0.9,
+        }
+    }
+}
+
+/// Summary of one outcome across runs
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Distribution {
+    pub mean: f64,
+    #[serde(alias = "std_dev")]
+    pub std_dev: f64,
+    pub min: f64,
+    pub p50: f64,
+    pub max: f64,
+    /// Central interval holding `confidence` of the runs
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl Distribution {
+    fn from_samples(mut samples: Vec<f64>, confidence: f64) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let tail = (1.0 - confidence.clamp(0.0, 1.0)) / 2.0;
+        Self {
+            mean,
+            std_dev: variance.sqrt(),
+            min: samples[0],
+            p50: percentile(&samples, 0.5),
+            max: samples[samples.len() - 1],
+            lower: percentile(&samples, tail),
+            upper: percentile(&samples, 1.0 - tail),
+        }
+    }
+}
+
+/// Outcome distributions over all runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonteCarloReport {
+    pub runs: u32,
+    pub confidence: f64,
+    pub trips: Distribution,
+    #[serde(alias = "shed_volume")]
+    pub shed_volume: Distribution,
+    #[serde(alias = "shed_fraction")]
+    pub shed_fraction: Distribution,
+    /// Fraction of runs with at least one trip
+    #[serde(alias = "trip_probability")]
+    pub trip_probability: f64,
+}
+
+/// Replay `runs` randomized variations of `trace` under `config`
+pub fn simulate(
+    trace: &Trace,
+    weights: &SensitivityWeights,
+    config: &PhysicsConfig,
+    settings: &MonteCarloConfig,
+) -> MonteCarloReport {
+    let mut rng = Rng(settings.seed);
+    let (mut trips, mut shed_volume, mut shed_fraction) = (Vec::new(), Vec::new(), Vec::new());
+    for _ in 0..settings.runs {
+        let variant = vary(trace, settings, &mut rng);
+        let report = replay(0, &variant, weights, config);
+        trips.push(report.trips as f64);
+        shed_volume.push(report.shed_volume);
+        shed_fraction.push(report.shed_fraction());
+    }
+    let tripped = trips.iter().filter(|&&t| t > 0.0).count();
+    MonteCarloReport {
+        runs: settings.runs,
+        confidence: settings.confidence,
+        trip_probability: if settings.runs > 0 {
+            tripped as f64 / settings.runs as f64
+        } else {
+            0.0
+        },
+        trips: Distribution::from_samples(trips, settings.confidence),
+        shed_volume: Distribution::from_samples(shed_volume, settings.confidence),
+        shed_fraction: Distribution::from_samples(shed_fraction, settings.confidence),
+    }
+}
+
+/// One randomized copy of `trace`
+fn vary(trace: &Trace, settings: &MonteCarloConfig, rng: &mut Rng) -> Trace {
+    let load = (1.0 + settings.load_jitter * (2.0 * rng.unit() - 1.0)).max(0.0);
+    let mut noisy = |x: f64| (x * load + settings.noise * (2.0 * rng.unit() - 1.0)).max(0.0);
+    let mut variant = trace.clone();
+    for sample in variant
+        .targets
+        .iter_mut()
+        .flat_map(|t| t.samples.iter_mut())
+    {
+        let p = sample.pressure;
+        sample.pressure =
+            PressureVector::new(noisy(p.latency), noisy(p.error), noisy(p.saturation));
+        sample.offered *= load;
+    }
+    variant
+}
+
+/// Monte Carlo report for a plain trace object, config and settings
+/// (undefined settings = defaults)
+#[wasm_bindgen(js_name = simulateCapacity)]
+pub fn simulate_js(
+    trace: JsValue,
+    weights: &SensitivityWeights,
+    config: JsValue,
+    settings: JsValue,
+) -> Result<JsValue, JsValue> {
+    let trace: Trace = serde_wasm_bindgen::from_value(trace)?;
+    let config: PhysicsConfig = serde_wasm_bindgen::from_value(config)?;
+    let settings = if settings.is_undefined() {
+        MonteCarloConfig::default()
+    } else {
+        serde_wasm_bindgen::from_value(settings)?
+    };
+    crate::to_js(&simulate(&trace, weights, &config, &settings))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::TraceSample;
+
+    /// Steady load with a spike that sits near the trip threshold
+    fn borderline_trace(spike: f64) -> Trace {
+        let mut trace = Trace::new();
+        for i in 0..200 {
+            let p = if (20..40).contains(&i) { spike } else { 0.1 };
+            trace.record(
+                "api",
+                TraceSample {
+                    timestamp_ms: i as f64 * 100.0,
+                    pressure: PressureVector::new(p, p, p),
+                    offered: 10.0,
+                },
+            );
+        }
+        trace
+    }
+
+    #[test]
+    fn test_distribution_and_determinism() {
+        let trace = borderline_trace(0.7);
+        let weights = SensitivityWeights::default();
+        let config = PhysicsConfig::default();
+        let settings = MonteCarloConfig {
+            runs: 50,
+            load_jitter: 0.5,
+            ..MonteCarloConfig::default()
+        };
+
+        let report = simulate(&trace, &weights, &config, &settings);
+        assert_eq!(report.runs, 50);
+        assert_eq!(report, simulate(&trace, &weights, &config, &settings));
+        let t = report.trips;
+        assert!(t.min <= t.lower && t.lower <= t.p50 && t.p50 <= t.upper && t.upper <= t.max);
+        assert!(report.shed_volume.std_dev > 0.0);
+        assert!(report.trip_probability > 0.0 && report.trip_probability < 1.0);
+    }
+
+    #[test]
+    fn test_no_variation_matches_single_replay() {
+        let trace = borderline_trace(0.9);
+        let weights = SensitivityWeights::default();
+        let config = PhysicsConfig::default();
+        let settings = MonteCarloConfig {
+            runs: 5,
+            load_jitter: 0.0,
+            noise: 0.0,
+            ..MonteCarloConfig::default()
+        };
+
+        let single = replay(0, &trace, &weights, &config);
+        let report = simulate(&trace, &weights, &config, &settings);
+        assert_eq!(report.trips.mean, single.trips as f64);
+        assert_eq!(report.trips.std_dev, 0.0);
+        assert_eq!(report.shed_volume.p50, single.shed_volume);
+    }
+}
@@ -0,0 +1,194 @@
+/**
+ * OpenTelemetry export (feature "otel", native only).
+ *
+ * Registers per-target gauges (resistance, scar, momentum) and trauma /
+ * mode-change counters against a caller-supplied `Meter`, and wraps ticks
+ * in an `atrion.tick` span. Only the API crate is used; the SDK and OTLP
+ * exporter are whatever the application installs.
+ */
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use opentelemetry::global::{self, BoxedTracer};
+use opentelemetry::metrics::{Counter, Meter, ObservableGauge};
+use opentelemetry::trace::{Span, Tracer};
+use opentelemetry::KeyValue;
+
+use crate::engine::TargetState;
+use crate::pool::EnginePool;
+use crate::types::{OperationalMode, PressureVector};
+
+/// Instrumentation scope name used for the tracer
+pub const SCOPE: &str = "atrion";
+
+/// Notable transitions between two consecutive states of a target
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct TickEvents {
+    /// Scar grew on this tick
+    pub trauma: bool,
+    /// Mode changed on this tick: `(from, to)`
+    pub mode_change: Option<(OperationalMode, OperationalMode)>,
+}
+
+impl TickEvents {
+    /// Compare a target's state before and after a tick
+    ///
+    /// A newly registered target (`before == None`) reports no events.
+    pub fn between(before: Option<&TargetState>, after: &TargetState) -> Self {
+        let Some(before) = before else {
+            return Self::default();
+        };
+        Self {
+            trauma: after.scar.0 > before.scar.0,
+            mode_change: (before.mode != after.mode).then_some((before.mode, after.mode)),
+        }
+    }
+}
+
+/// Registered OpenTelemetry instruments for one pool
+pub struct OtelInstruments {
+    trauma: Counter<u64>,
+    mode_changes: Counter<u64>,
+    tracer: BoxedTracer,
+    // Observable gauges stop reporting once dropped
+    _gauges: [ObservableGauge<f64>; 3],
+}
+
+impl OtelInstruments {
+    /// Register gauges that observe `pool` on every collection
+    pub fn register(meter: &Meter, pool: Arc<Mutex<EnginePool>>) -> Self {
+        let gauge = |name: &'static str, unit: &'static str, value: fn(&TargetState) -> f64| {
+            let pool = Arc::clone(&pool);
+            meter
+                .f64_observable_gauge(name)
+                .with_unit(unit)
+                .with_callback(move |observer| {
+                    for (id, engine) in lock(&pool).iter() {
+                        observer.observe(value(engine.state()), &attributes(id, engine.state()));
+                    }
+                })
+                .build()
+        };
+        let gauges = [
+            gauge("atrion.resistance", "Ohm", |s| s.resistance.0),
+            gauge("atrion.scar", "1", |s| s.scar.0),
+            gauge("atrion.momentum", "1", |s| s.momentum.0),
+        ];
+
+        Self {
+            trauma: meter
+                .u64_counter("atrion.trauma")
+                .with_description("Ticks where scar tissue grew")
+                .build(),
+            mode_changes: meter
+                .u64_counter("atrion.mode_changes")
+                .with_description("Operational mode transitions")
+                .build(),
+            tracer: global::tracer(SCOPE),
+            _gauges: gauges,
+        }
+    }
+
+    /// Tick a target inside an `atrion.tick` span and record counters
+    pub fn tick(
+        &self,
+        pool: &mut EnginePool,
+        id: &str,
+        now_ms: f64,
+        pressure: PressureVector,
+    ) -> TargetState {
+        let mut span = self.tracer.start("atrion.tick");
+        let before = pool.get(id).map(|engine| *engine.state());
+        let after = pool.tick(id, now_ms, pressure);
+        let events = TickEvents::between(before.as_ref(), &after);
+
+        span.set_attributes([
+            KeyValue::new("atrion.target", id.to_string()),
+            KeyValue::new("atrion.pressure.latency", pressure.latency),
+            KeyValue::new("atrion.pressure.error", pressure.error),
+            KeyValue::new("atrion.pressure.saturation", pressure.saturation),
+            KeyValue::new("atrion.resistance", after.resistance.0),
+            KeyValue::new("atrion.mode", after.mode.as_str()),
+        ]);
+        self.record(id, &events, &after);
+        if let Some((from, to)) = events.mode_change {
+            span.add_event(
+                "atrion.mode_change",
+                vec![
+                    KeyValue::new("from", from.as_str()),
+                    KeyValue::new("to", to.as_str()),
+                ],
+            );
+        }
+        span.end();
+        after
+    }
+
+    /// Record counters for events observed outside `tick` (e.g. the gRPC ingest path)
+    pub fn record(&self, id: &str, events: &TickEvents, after: &TargetState) {
+        let attrs = attributes(id, after);
+        if events.trauma {
+            self.trauma.add(1, &attrs);
+        }
+        if events.mode_change.is_some() {
+            self.mode_changes.add(1, &attrs);
+        }
+    }
+}
+
+fn attributes(id: &str, state: &TargetState) -> [KeyValue; 2] {
+    [
+        KeyValue::new("target", id.to_string()),
+        KeyValue::new("mode", state.mode.as_str()),
+    ]
+}
+
+fn lock(pool: &Mutex<EnginePool>) -> MutexGuard<'_, EnginePool> {
+    pool.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    #[test]
+    fn test_tick_events_between() {
+        let config = PhysicsConfig::default();
+        let before = TargetState::bootstrap(&config);
+        let mut after = before;
+        assert_eq!(TickEvents::between(None, &after), TickEvents::default());
+
+        after.scar.0 += 1.0;
+        after.mode = OperationalMode::Operational;
+        let events = TickEvents::between(Some(&before), &after);
+        assert!(events.trauma);
+        assert_eq!(
+            events.mode_change,
+            Some((OperationalMode::Bootstrap, OperationalMode::Operational))
+        );
+    }
+
+    #[test]
+    fn test_tick_with_noop_provider() {
+        let pool = Arc::new(Mutex::new(EnginePool::new(
+            PhysicsConfig::default(),
+            SensitivityWeights::default(),
+        )));
+        let instruments = OtelInstruments::register(&global::meter(SCOPE), Arc::clone(&pool));
+
+        let mut guard = pool.lock().unwrap();
+        for i in 0..12 {
+            instruments.tick(
+                &mut guard,
+                "api",
+                i as f64 * 100.0,
+                PressureVector::new(0.1, 0.0, 0.0),
+            );
+        }
+        assert_eq!(guard.mode("api"), Some(OperationalMode::Operational));
+    }
+}
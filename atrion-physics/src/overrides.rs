@@ -0,0 +1,105 @@
+/**
+ * Operator overrides with automatic expiry.
+ *
+ * Maintenance windows and incident response: pin a target's mode (keep
+ * the breaker closed during a planned failover, or hold it open while an
+ * upstream is being fixed) and/or pause trauma so expected errors don't
+ * leave scar behind. Overrides live in `TargetState` so they show up in
+ * snapshots and state exports, and lapse on the first tick past `until_ms`.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::types::OperationalMode;
+
+/// Mode pinned until a deadline
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ModeOverride {
+    pub mode: OperationalMode,
+    #[serde(alias = "until_ms")]
+    pub until_ms: f64,
+}
+
+/// Active operator overrides for one target
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Overrides {
+    pub mode: Option<ModeOverride>,
+    /// Trauma is not recorded before this time (scar still decays)
+    #[serde(alias = "trauma_paused_until_ms")]
+    pub trauma_paused_until_ms: Option<f64>,
+}
+
+/// Which override lapsed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverrideKind {
+    Mode,
+    TraumaPause,
+}
+
+impl Overrides {
+    pub fn is_empty(&self) -> bool {
+        self.mode.is_none() && self.trauma_paused_until_ms.is_none()
+    }
+
+    /// Pinned mode, if one is active at `now_ms`
+    pub fn mode_at(&self, now_ms: f64) -> Option<OperationalMode> {
+        self.mode.filter(|o| now_ms < o.until_ms).map(|o| o.mode)
+    }
+
+    pub fn trauma_paused_at(&self, now_ms: f64) -> bool {
+        self.trauma_paused_until_ms
+            .is_some_and(|until| now_ms < until)
+    }
+
+    /// Drop overrides whose deadline has passed, returning what lapsed
+    pub fn expire(&mut self, now_ms: f64) -> Vec<OverrideKind> {
+        let mut expired = Vec::new();
+        if self.mode.is_some_and(|o| now_ms >= o.until_ms) {
+            self.mode = None;
+            expired.push(OverrideKind::Mode);
+        }
+        if self
+            .trauma_paused_until_ms
+            .is_some_and(|until| now_ms >= until)
+        {
+            self.trauma_paused_until_ms = None;
+            expired.push(OverrideKind::TraumaPause);
+        }
+        expired
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_expire() {
+        let mut overrides = Overrides {
+            mode: Some(ModeOverride {
+                mode: OperationalMode::CircuitBreaker,
+                until_ms: 1000.0,
+            }),
+            trauma_paused_until_ms: Some(2000.0),
+        };
+        assert_eq!(
+            overrides.mode_at(999.0),
+            Some(OperationalMode::CircuitBreaker)
+        );
+        assert!(overrides.expire(999.0).is_empty());
+
+        assert_eq!(overrides.expire(1500.0), [OverrideKind::Mode]);
+        assert!(overrides.trauma_paused_at(1500.0));
+        assert_eq!(overrides.expire(2000.0), [OverrideKind::TraumaPause]);
+        assert!(overrides.is_empty());
+    }
+}
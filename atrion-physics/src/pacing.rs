@@ -0,0 +1,186 @@
+/**
+ * Consumer pacing for pull-based workloads.
+ *
+ * Queue consumers cannot "reject" a message they already fetched; they
+ * need rate shaping instead. `Pacer` turns the admission ratio into a
+ * per-poll record limit and a pause/resume signal with hysteresis, so a
+ * consumer backs off as resistance climbs and stops fetching while the
+ * downstream is tripped.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::engine::TargetEngine;
+
+/// Pacing configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PacingConfig {
+    /// Records per poll on a healthy target
+    #[serde(alias = "max_poll_records")]
+    pub max_poll_records: u32,
+    /// Floor while not paused
+    #[serde(alias = "min_poll_records")]
+    pub min_poll_records: u32,
+    /// Pause once the admission ratio falls to or below this
+    #[serde(alias = "pause_below")]
+    pub pause_below: f64,
+    /// Resume once the admission ratio recovers to or above this
+    #[serde(alias = "resume_above")]
+    pub resume_above: f64,
+}
+
+impl Default for PacingConfig {
+    fn default() -> Self {
+        Self {
+            max_poll_records: 500,
+            min_poll_records: 1,
+            pause_below: 0.0,
+            resume_above: 0.2,
+        }
+    }
+}
+
+/// What the consumer should do before its next poll
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+pub enum PacingSignal {
+    /// Fetch at most this many records
+    Poll {
+        #[serde(alias = "max_records")]
+        max_records: u32,
+    },
+    /// Stop fetching (keep the group membership alive)
+    Pause,
+}
+
+/// Consumer that can be paced (implement for your client library)
+pub trait PacedConsumer {
+    type Error;
+
+    fn pause(&mut self) -> Result<(), Self::Error>;
+    fn resume(&mut self) -> Result<(), Self::Error>;
+    fn set_max_poll_records(&mut self, max_records: u32);
+}
+
+/// Stateful pacer (tracks paused state for hysteresis)
+#[derive(Debug, Clone, Default)]
+pub struct Pacer {
+    config: PacingConfig,
+    paused: bool,
+}
+
+impl Pacer {
+    pub fn new(config: PacingConfig) -> Self {
+        Self {
+            config,
+            paused: false,
+        }
+    }
+
+    pub fn config(&self) -> &PacingConfig {
+        &self.config
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Signal for the given admission ratio
+    pub fn signal(&mut self, ratio: f64) -> PacingSignal {
+        let ratio = ratio.clamp(0.0, 1.0);
+        if self.paused {
+            self.paused = ratio < self.config.resume_above;
+        } else {
+            self.paused = ratio <= self.config.pause_below;
+        }
+        if self.paused {
+            return PacingSignal::Pause;
+        }
+
+        let max = self.config.max_poll_records as f64;
+        let records = (max * ratio).floor() as u32;
+        PacingSignal::Poll {
+            max_records: records.clamp(self.config.min_poll_records, self.config.max_poll_records),
+        }
+    }
+
+    /// Signal from an engine's current admission ratio
+    pub fn signal_for(&mut self, engine: &TargetEngine) -> PacingSignal {
+        self.signal(engine.admission_ratio())
+    }
+
+    /// Apply the signal to a consumer, calling pause/resume only on transitions
+    pub fn apply<C: PacedConsumer>(
+        &mut self,
+        ratio: f64,
+        consumer: &mut C,
+    ) -> Result<PacingSignal, C::Error> {
+        let was_paused = self.paused;
+        let signal = self.signal(ratio);
+        match signal {
+            PacingSignal::Pause if !was_paused => consumer.pause()?,
+            PacingSignal::Poll { max_records } => {
+                if was_paused {
+                    consumer.resume()?;
+                }
+                consumer.set_max_poll_records(max_records);
+            }
+            PacingSignal::Pause => {}
+        }
+        Ok(signal)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+    }
+
+    impl PacedConsumer for Recorder {
+        type Error = ();
+
+        fn pause(&mut self) -> Result<(), ()> {
+            self.calls.push("pause".into());
+            Ok(())
+        }
+
+        fn resume(&mut self) -> Result<(), ()> {
+            self.calls.push("resume".into());
+            Ok(())
+        }
+
+        fn set_max_poll_records(&mut self, max_records: u32) {
+            self.calls.push(format!("max={max_records}"));
+        }
+    }
+
+    #[test]
+    fn test_records_scale_with_ratio() {
+        let mut pacer = Pacer::default();
+        assert_eq!(pacer.signal(1.0), PacingSignal::Poll { max_records: 500 });
+        assert_eq!(pacer.signal(0.5), PacingSignal::Poll { max_records: 250 });
+        assert_eq!(pacer.signal(0.001), PacingSignal::Poll { max_records: 1 });
+        assert_eq!(pacer.signal(0.0), PacingSignal::Pause);
+    }
+
+    #[test]
+    fn test_pause_resume_hysteresis() {
+        let mut pacer = Pacer::default();
+        let mut consumer = Recorder::default();
+        for ratio in [0.8, 0.0, 0.1, 0.0, 0.3] {
+            pacer.apply(ratio, &mut consumer).unwrap();
+        }
+        assert_eq!(
+            consumer.calls,
+            ["max=400", "pause", "resume", "max=150"].map(String::from)
+        );
+    }
+}
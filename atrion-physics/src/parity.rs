@@ -0,0 +1,310 @@
+/**
+ * Cross-language parity vectors.
+ *
+ * Formalizes the "MUST match TS" comments: `generate` evaluates the core
+ * formulas over a grid of edge-case inputs (zeros, ±0.0, negatives, the
+ * critical threshold and its neighbours, subnormals, huge values) and
+ * records the outputs. The `gen-vectors` binary writes the file for the
+ * TS suite; `check` replays a file against this build.
+ *
+ * JSON has no NaN or infinity, so cases whose output is not finite are
+ * left out of the grid.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::momentum::update_momentum;
+use crate::resistance::calculate_resistance;
+use crate::scar::{update_scar_with_decay, CRITICAL_PRESSURE};
+use crate::types::{Momentum, PhysicsConfig, PressureVector, Scar, SensitivityWeights};
+use crate::vector::positive_stress_magnitude;
+
+/// Component values every grid axis walks through
+pub const EDGE_VALUES: [f64; 12] = [
+    0.0,
+    -0.0,
+    -1.0,
+    0.5,
+    CRITICAL_PRESSURE,
+    0.700_000_000_000_000_1, // next f64 above the threshold
+    0.699_999_999_999_999_9, // next f64 below the threshold
+    1.0,
+    1e-310, // subnormal
+    5e-324, // smallest subnormal
+    1e12,
+    1e154, // squares to ~1e308
+];
+
+/// Relative tolerance for replay (libm `exp` may differ by an ulp)
+pub const TOLERANCE: f64 = 1e-12;
+
+/// One input/output pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "fn", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ParityCase {
+    Resistance {
+        pressure: PressureVector,
+        momentum: f64,
+        scar: f64,
+        staleness: f64,
+        expected: f64,
+    },
+    ScarWithDecay {
+        scar: f64,
+        pressure: PressureVector,
+        delta_t_ms: f64,
+        expected: f64,
+    },
+    Momentum {
+        momentum: f64,
+        previous: PressureVector,
+        current: PressureVector,
+        delta_t: f64,
+        expected: f64,
+    },
+    PositiveStress {
+        pressure: PressureVector,
+        expected: f64,
+    },
+}
+
+impl ParityCase {
+    pub fn expected(&self) -> f64 {
+        match self {
+            ParityCase::Resistance { expected, .. }
+            | ParityCase::ScarWithDecay { expected, .. }
+            | ParityCase::Momentum { expected, .. }
+            | ParityCase::PositiveStress { expected, .. } => *expected,
+        }
+    }
+
+    /// Output of this build for the case's inputs
+    pub fn evaluate(&self, config: &PhysicsConfig, weights: &SensitivityWeights) -> f64 {
+        match self {
+            ParityCase::Resistance {
+                pressure,
+                momentum,
+                scar,
+                staleness,
+                ..
+            } => {
+                calculate_resistance(
+                    pressure,
+                    Momentum(*momentum),
+                    Scar(*scar),
+                    weights,
+                    config,
+                    *staleness,
+                )
+                .0
+            }
+            ParityCase::ScarWithDecay {
+                scar,
+                pressure,
+                delta_t_ms,
+                ..
+            } => update_scar_with_decay(Scar(*scar), pressure, *delta_t_ms, config).0,
+            ParityCase::Momentum {
+                momentum,
+                previous,
+                current,
+                delta_t,
+                ..
+            } => update_momentum(Momentum(*momentum), previous, current, *delta_t, config).0,
+            ParityCase::PositiveStress { pressure, .. } => positive_stress_magnitude(pressure),
+        }
+    }
+}
+
+/// A complete vector file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParityVectors {
+    /// Crate version that produced the file
+    pub generator: String,
+    pub config: PhysicsConfig,
+    pub weights: SensitivityWeights,
+    pub cases: Vec<ParityCase>,
+}
+
+/// A case whose replayed output differs from the recorded one
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub index: usize,
+    pub expected: f64,
+    pub actual: f64,
+}
+
+fn matches(expected: f64, actual: f64) -> bool {
+    if expected == 0.0 {
+        // Sign of zero is part of the contract
+        return actual == 0.0 && expected.is_sign_negative() == actual.is_sign_negative();
+    }
+    ((actual - expected) / expected).abs() <= TOLERANCE
+}
+
+impl ParityVectors {
+    /// Replay every case; empty when this build matches the file
+    pub fn check(&self) -> Vec<Mismatch> {
+        self.cases
+            .iter()
+            .enumerate()
+            .filter_map(|(index, case)| {
+                let actual = case.evaluate(&self.config, &self.weights);
+                let expected = case.expected();
+                (!matches(expected, actual)).then_some(Mismatch {
+                    index,
+                    expected,
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Every `[a, b, c]` combination of `EDGE_VALUES`
+fn pressure_cube() -> impl Iterator<Item = PressureVector> {
+    EDGE_VALUES.into_iter().flat_map(|l| {
+        EDGE_VALUES.into_iter().flat_map(move |e| {
+            EDGE_VALUES
+                .into_iter()
+                .map(move |s| PressureVector::new(l, e, s))
+        })
+    })
+}
+
+/// Uniform `[v, v, v]` and single-axis `[v, 0, 0]` vectors
+fn pressure_line() -> Vec<PressureVector> {
+    EDGE_VALUES
+        .into_iter()
+        .flat_map(|v| {
+            [
+                PressureVector::new(v, v, v),
+                PressureVector::new(v, 0.0, 0.0),
+            ]
+        })
+        .collect()
+}
+
+/// Evaluate the grid under the given config and weights
+pub fn generate(config: PhysicsConfig, weights: SensitivityWeights) -> ParityVectors {
+    let mut cases = Vec::new();
+
+    for pressure in pressure_cube() {
+        for (momentum, scar) in [(0.0, 0.0), (0.5, 0.0), (0.0, 10.0), (0.5, 10.0)] {
+            let mut case = ParityCase::Resistance {
+                pressure,
+                momentum,
+                scar,
+                staleness: 0.0,
+                expected: 0.0,
+            };
+            push(&mut cases, &mut case, &config, &weights);
+        }
+        for scar in [0.0, 5.0] {
+            for delta_t_ms in [0.0, 100.0, 1e9] {
+                let mut case = ParityCase::ScarWithDecay {
+                    scar,
+                    pressure,
+                    delta_t_ms,
+                    expected: 0.0,
+                };
+                push(&mut cases, &mut case, &config, &weights);
+            }
+        }
+        let mut case = ParityCase::PositiveStress {
+            pressure,
+            expected: 0.0,
+        };
+        push(&mut cases, &mut case, &config, &weights);
+    }
+
+    let line = pressure_line();
+    for previous in &line {
+        for current in &line {
+            for delta_t in [0.0, 1.0, 1000.0] {
+                let mut case = ParityCase::Momentum {
+                    momentum: 0.0,
+                    previous: *previous,
+                    current: *current,
+                    delta_t,
+                    expected: 0.0,
+                };
+                push(&mut cases, &mut case, &config, &weights);
+            }
+        }
+    }
+
+    ParityVectors {
+        generator: env!("CARGO_PKG_VERSION").to_string(),
+        config,
+        weights,
+        cases,
+    }
+}
+
+/// Fill in `expected` and keep the case if the output is representable
+fn push(
+    cases: &mut Vec<ParityCase>,
+    case: &mut ParityCase,
+    config: &PhysicsConfig,
+    weights: &SensitivityWeights,
+) {
+    let output = case.evaluate(config, weights);
+    if !output.is_finite() {
+        return;
+    }
+    match case {
+        ParityCase::Resistance { expected, .. }
+        | ParityCase::ScarWithDecay { expected, .. }
+        | ParityCase::Momentum { expected, .. }
+        | ParityCase::PositiveStress { expected, .. } => *expected = output,
+    }
+    cases.push(case.clone());
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replays `ATRION_PARITY_VECTORS` (e.g. a file exported by the TS
+    /// suite) when set; otherwise round-trips a freshly generated grid.
+    #[test]
+    fn test_parity_vectors() {
+        let json = match std::env::var("ATRION_PARITY_VECTORS") {
+            Ok(path) => std::fs::read_to_string(&path).expect("read vectors file"),
+            Err(_) => serde_json::to_string(&generate(
+                PhysicsConfig::default(),
+                SensitivityWeights::default(),
+            ))
+            .unwrap(),
+        };
+        let vectors: ParityVectors = serde_json::from_str(&json).unwrap();
+        assert!(vectors.cases.len() > 1000);
+
+        let mismatches = vectors.check();
+        assert!(mismatches.is_empty(), "parity mismatches: {mismatches:?}");
+    }
+
+    #[test]
+    fn test_threshold_neighbours() {
+        let config = PhysicsConfig::default();
+        let at = update_scar_with_decay(
+            Scar(0.0),
+            &PressureVector::new(CRITICAL_PRESSURE, 0.0, 0.0),
+            0.0,
+            &config,
+        );
+        let above = update_scar_with_decay(
+            Scar(0.0),
+            &PressureVector::new(EDGE_VALUES[5], 0.0, 0.0),
+            0.0,
+            &config,
+        );
+        assert_eq!(at.0, 0.0);
+        assert_eq!(above.0, config.scar_factor);
+    }
+}
@@ -0,0 +1,240 @@
+/**
+ * Debounced snapshot persistence (browser deployments).
+ *
+ * Client-side admission control (hedging in the browser) loses its scar
+ * on every reload unless pool snapshots are written somewhere durable.
+ * `AutoSave` decides *when* to save: after trauma (scar growth or a mode
+ * change), once things have been quiet for `debounce_ms`, and at least
+ * every `max_delay_ms` while trauma keeps arriving.
+ *
+ * On WASM, `BrowserAutoSave` hands snapshots to a JS store object whose
+ * `save(key, snapshot)` writes IndexedDB. IndexedDB reads are async, so
+ * restoring stays on the JS side: `pool.restore(await store.load(key))`.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::engine::TargetState;
+
+/// When to persist after trauma
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct AutoSavePolicy {
+    /// Quiet period after the last trauma before saving
+    #[serde(alias = "debounce_ms")]
+    pub debounce_ms: f64,
+    /// Upper bound on how long trauma may stay unsaved
+    #[serde(alias = "max_delay_ms")]
+    pub max_delay_ms: f64,
+}
+
+impl Default for AutoSavePolicy {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 1_000.0,
+            max_delay_ms: 10_000.0,
+        }
+    }
+}
+
+/// Trailing debounce with a maximum wait
+#[derive(Debug, Clone, Default)]
+pub struct AutoSave {
+    policy: AutoSavePolicy,
+    /// First unsaved trauma
+    pending_since_ms: Option<f64>,
+    last_trauma_ms: f64,
+    saves: u64,
+}
+
+/// Whether moving from `before` to `after` is worth persisting
+pub fn is_trauma(before: &TargetState, after: &TargetState) -> bool {
+    after.scar.0 > before.scar.0
+        || after.slow_scar.0 > before.slow_scar.0
+        || after.mode != before.mode
+}
+
+impl AutoSave {
+    pub fn new(policy: AutoSavePolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+
+    pub fn policy(&self) -> &AutoSavePolicy {
+        &self.policy
+    }
+
+    /// Record a trauma event
+    pub fn note_trauma(&mut self, now_ms: f64) {
+        self.pending_since_ms.get_or_insert(now_ms);
+        self.last_trauma_ms = self.last_trauma_ms.max(now_ms);
+    }
+
+    /// True when unsaved trauma has gone quiet or waited too long
+    pub fn due(&self, now_ms: f64) -> bool {
+        self.pending_since_ms.is_some_and(|since| {
+            now_ms - self.last_trauma_ms >= self.policy.debounce_ms
+                || now_ms - since >= self.policy.max_delay_ms
+        })
+    }
+
+    /// Record that a snapshot was written
+    pub fn saved(&mut self) {
+        self.pending_since_ms = None;
+        self.saves += 1;
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending_since_ms.is_some()
+    }
+
+    pub fn saves(&self) -> u64 {
+        self.saves
+    }
+}
+
+// ============================================================================
+// INDEXEDDB GLUE (WASM)
+// ============================================================================
+
+#[cfg(target_arch = "wasm32")]
+pub use browser::{BrowserAutoSave, JsSnapshotStore};
+
+#[cfg(target_arch = "wasm32")]
+mod browser {
+    use wasm_bindgen::prelude::*;
+
+    use super::{is_trauma, AutoSave, AutoSavePolicy};
+    use crate::pool::EnginePool;
+    use crate::types::PressureVector;
+
+    #[wasm_bindgen]
+    extern "C" {
+        /// Any object with `save(key, snapshot)`, typically an IndexedDB writer
+        #[wasm_bindgen(typescript_type = "{ save(key: string, snapshot: any): void }")]
+        pub type JsSnapshotStore;
+
+        #[wasm_bindgen(method, catch)]
+        fn save(this: &JsSnapshotStore, key: &str, snapshot: JsValue) -> Result<(), JsValue>;
+    }
+
+    /// Ticks a pool and saves its snapshot to a JS store after trauma
+    #[wasm_bindgen]
+    pub struct BrowserAutoSave {
+        store: JsSnapshotStore,
+        key: String,
+        autosave: AutoSave,
+    }
+
+    #[wasm_bindgen]
+    impl BrowserAutoSave {
+        #[wasm_bindgen(constructor)]
+        pub fn new(
+            store: JsSnapshotStore,
+            key: String,
+            debounce_ms: f64,
+            max_delay_ms: f64,
+        ) -> Self {
+            Self {
+                store,
+                key,
+                autosave: AutoSave::new(AutoSavePolicy {
+                    debounce_ms,
+                    max_delay_ms,
+                }),
+            }
+        }
+
+        /// `pool.tick` that notes trauma and saves when due; returns resistance
+        pub fn tick(
+            &mut self,
+            pool: &mut EnginePool,
+            id: &str,
+            now_ms: f64,
+            pressure: &PressureVector,
+        ) -> Result<f64, JsValue> {
+            let before = pool.get(id).map(|engine| *engine.state());
+            let after = pool.tick(id, now_ms, *pressure);
+            if before.is_some_and(|before| is_trauma(&before, &after)) {
+                self.autosave.note_trauma(now_ms);
+            }
+            self.poll(pool, now_ms)?;
+            Ok(after.resistance.0)
+        }
+
+        /// Save if a debounced save is due (call from a timer when idle)
+        pub fn poll(&mut self, pool: &EnginePool, now_ms: f64) -> Result<bool, JsValue> {
+            if !self.autosave.due(now_ms) {
+                return Ok(false);
+            }
+            self.save_now(pool, now_ms)?;
+            Ok(true)
+        }
+
+        /// Save immediately (e.g. on `visibilitychange` / `pagehide`)
+        #[wasm_bindgen(js_name = saveNow)]
+        pub fn save_now(&mut self, pool: &EnginePool, now_ms: f64) -> Result<(), JsValue> {
+            let snapshot = crate::to_js(&pool.snapshot(now_ms))?;
+            self.store.save(&self.key, snapshot)?;
+            self.autosave.saved();
+            Ok(())
+        }
+
+        #[wasm_bindgen(getter)]
+        pub fn pending(&self) -> bool {
+            self.autosave.is_pending()
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::EnginePool;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_debounce_with_max_delay() {
+        let mut autosave = AutoSave::new(AutoSavePolicy {
+            debounce_ms: 1_000.0,
+            max_delay_ms: 5_000.0,
+        });
+        assert!(!autosave.due(0.0));
+
+        // Trauma every 500ms never goes quiet, so max_delay forces a save
+        let mut saved_at = None;
+        for i in 0..20 {
+            let now = i as f64 * 500.0;
+            autosave.note_trauma(now);
+            if autosave.due(now) {
+                autosave.saved();
+                saved_at = Some(now);
+                break;
+            }
+        }
+        assert_eq!(saved_at, Some(5_000.0));
+
+        autosave.note_trauma(6_000.0);
+        assert!(!autosave.due(6_900.0));
+        assert!(autosave.due(7_000.0));
+    }
+
+    #[test]
+    fn test_trauma_detection() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 0..10 {
+            pool.tick("api", i as f64 * 100.0, PressureVector::new(0.1, 0.0, 0.0));
+        }
+        let calm = *pool.get("api").unwrap().state();
+        let hurt = pool.tick("api", 1_000.0, PressureVector::new(1.0, 1.0, 1.0));
+        assert!(is_trauma(&calm, &hurt));
+        assert!(!is_trauma(&hurt, &hurt));
+    }
+}
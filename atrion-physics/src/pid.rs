@@ -0,0 +1,117 @@
+/**
+ * PID control mode (latency setpoint).
+ *
+ * Alternative to open-loop resistance: the engine steers admitted rate
+ * toward a latency setpoint. The three PID terms land in the familiar
+ * state fields so gates, history and explain keep working:
+ *
+ * - P (proportional error)  → pressure term of resistance
+ * - I (accumulated error)   → scar (one-sided, clamped: anti-windup)
+ * - D (rate of error change) → momentum
+ *
+ * R = R_base + P + I + D, with e = latency − setpoint.
+ */
+use serde::{Deserialize, Serialize};
+
+/// PID gains and setpoint
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PidConfig {
+    /// Target normalized latency (same scale as `PressureVector::latency`)
+    pub setpoint: f64,
+    /// Ohms per unit of error
+    pub kp: f64,
+    /// Ohms per unit of error-second
+    pub ki: f64,
+    /// Ohms per unit of error per second
+    pub kd: f64,
+    /// Upper bound of the integral term (anti-windup)
+    #[serde(alias = "integral_max")]
+    pub integral_max: f64,
+}
+
+impl Default for PidConfig {
+    fn default() -> Self {
+        Self {
+            setpoint: 0.3,
+            kp: 50.0,
+            ki: 20.0,
+            kd: 5.0,
+            integral_max: 100.0,
+        }
+    }
+}
+
+/// Output of one controller step
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PidTerms {
+    pub proportional: f64,
+    pub integral: f64,
+    pub derivative: f64,
+}
+
+impl PidTerms {
+    pub fn total(&self) -> f64 {
+        self.proportional + self.integral + self.derivative
+    }
+}
+
+/// Advance the controller
+///
+/// `previous_integral` is the last integral term (the engine's scar);
+/// `delta_t_ms` of 0 skips integration and differentiation.
+pub fn step(
+    config: &PidConfig,
+    latency: f64,
+    previous_latency: f64,
+    previous_integral: f64,
+    delta_t_ms: f64,
+) -> PidTerms {
+    let error = latency - config.setpoint;
+    let dt_seconds = delta_t_ms / 1000.0;
+
+    let (integral, derivative) = if dt_seconds > 0.0 {
+        let integral = previous_integral + config.ki * error * dt_seconds;
+        let derivative = config.kd * (latency - previous_latency) / dt_seconds;
+        (integral, derivative)
+    } else {
+        (previous_integral, 0.0)
+    };
+
+    PidTerms {
+        proportional: config.kp * error,
+        integral: integral.clamp(0.0, config.integral_max.max(0.0)),
+        derivative,
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terms() {
+        let config = PidConfig::default();
+        // 0.5 latency vs 0.3 setpoint over 1s, rising from 0.3
+        let terms = step(&config, 0.5, 0.3, 0.0, 1000.0);
+        assert!((terms.proportional - 10.0).abs() < 1e-10);
+        assert!((terms.integral - 4.0).abs() < 1e-10);
+        assert!((terms.derivative - 1.0).abs() < 1e-10);
+        assert!((terms.total() - 15.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_integral_anti_windup() {
+        let config = PidConfig::default();
+        let high = step(&config, 1.0, 1.0, 99.0, 10_000.0);
+        assert_eq!(high.integral, config.integral_max);
+        let low = step(&config, 0.0, 0.0, 1.0, 10_000.0);
+        assert_eq!(low.integral, 0.0);
+    }
+}
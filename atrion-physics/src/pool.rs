@@ -0,0 +1,1988 @@
+/**
+ * Engine pool (many targets, one config).
+ *
+ * Maps target ids to stateful engines. Unknown targets are registered on
+ * first use, with warm-start priors applied at creation. Engines sit in a
+ * slab (stable slots, separate id index), so whole-pool passes such as
+ * `tick_all` run in memory order.
+ */
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::admission::{AdmissionDecision, RejectReason};
+use crate::audit::{AdmissionAudit, AuditConfig, AuditInterval};
+use crate::chunk::{self, ChunkCursor};
+use crate::compliance::{ComplianceThresholds, TargetCompliance};
+use crate::engine::{TargetEngine, TargetState};
+use crate::error::AtrionError;
+use crate::explain::ResistanceBreakdown;
+use crate::healthcheck::ProbingConfig;
+use crate::hedge::{self, HedgeAdvice, HedgePolicy, HedgeReason};
+use crate::intern::{Interner, TargetHandle};
+use crate::jitter;
+use crate::markers::MarkerKind;
+use crate::quarantine::{Quarantine, QuarantineList};
+use crate::reorder::SequencedSample;
+use crate::resistance::{self, ResistanceLane};
+use crate::retry::{self, Denial, Rejection, RetryAdvice};
+use crate::slab::{Handle, Slab};
+use crate::snapshot::{DeltaSnapshot, Snapshot, TargetSnapshot, SNAPSHOT_VERSION};
+use crate::stats::WINDOW_1M_MS;
+use crate::store::StateStore;
+use crate::types::*;
+use crate::warmstart::{SeedStrategy, WarmStartPriors};
+
+/// Per-target resistance bounds layered over the pool config
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetBounds {
+    /// Floor replacing `base_resistance` (distrust an experimental upstream)
+    #[serde(alias = "base_resistance")]
+    pub base_resistance: Option<f64>,
+    /// Ceiling (never fully block a critical dependency)
+    pub ceiling: Option<f64>,
+}
+
+impl TargetBounds {
+    /// `config` with these bounds layered on top
+    pub fn apply(&self, config: &PhysicsConfig) -> PhysicsConfig {
+        PhysicsConfig {
+            base_resistance: self.base_resistance.unwrap_or(config.base_resistance),
+            resistance_ceiling: self.ceiling.or(config.resistance_ceiling),
+            ..config.clone()
+        }
+    }
+}
+
+/// Aggregate view of one target group
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupSummary {
+    /// Assigned targets, including ones not yet registered
+    pub targets: usize,
+    pub bootstrap: usize,
+    pub operational: usize,
+    #[serde(alias = "circuit_breaker")]
+    pub circuit_breaker: usize,
+    pub quarantined: usize,
+    /// Over registered targets (0 when none are)
+    #[serde(alias = "mean_resistance")]
+    pub mean_resistance: f64,
+    #[serde(alias = "max_resistance")]
+    pub max_resistance: f64,
+    #[serde(alias = "total_scar")]
+    pub total_scar: f64,
+}
+
+/// What `EnginePool::rank_targets` orders by
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub enum RankMetric {
+    Resistance,
+    Scar,
+    /// Trauma events over the last minute of history
+    TraumaRate,
+}
+
+/// One entry of a worst-first ranking
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankedTarget {
+    pub id: String,
+    /// Value of the ranking metric
+    pub value: f64,
+    pub mode: OperationalMode,
+    pub quarantined: bool,
+    pub breakdown: ResistanceBreakdown,
+}
+
+/// `EnginePool::touch` on split borrows (usable while iterating targets)
+fn mark_dirty(dirty: &mut HashSet<String>, removed: &mut HashSet<String>, id: &str) {
+    removed.remove(id);
+    if !dirty.contains(id) {
+        dirty.insert(id.to_string());
+    }
+}
+
+/// Pool of per-target engines sharing config and weights
+#[wasm_bindgen]
+pub struct EnginePool {
+    config: PhysicsConfig,
+    weights: SensitivityWeights,
+    priors: WarmStartPriors,
+    targets: Slab<TargetEngine>,
+    bounds: HashMap<String, TargetBounds>,
+    /// Target id → group (region, cluster, ...)
+    groups: HashMap<String, String>,
+    quarantine: QuarantineList,
+    /// Latest timestamp seen (ticks, quarantine calls); drives TTL expiry
+    clock_ms: f64,
+    /// Targets whose state changed since the last checkpoint
+    dirty: HashSet<String>,
+    /// Targets removed since the last checkpoint
+    removed: HashSet<String>,
+    last_checkpoint_ms: Option<f64>,
+    interner: Interner,
+    /// Interned handle → slab handle of its engine (refreshed when stale)
+    interned: Vec<Option<Handle>>,
+    /// Sampled admission decisions (`enable_admission_audit`)
+    audit: Option<Mutex<AdmissionAudit>>,
+    /// Staleness probing applied to every engine (`enable_probing`)
+    probing: Option<ProbingConfig>,
+}
+
+fn lock(audit: &Mutex<AdmissionAudit>) -> MutexGuard<'_, AdmissionAudit> {
+    audit
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl EnginePool {
+    pub fn config(&self) -> &PhysicsConfig {
+        &self.config
+    }
+
+    /// Replace the config for the pool and every registered target
+    ///
+    /// Per-target bounds stay layered on top.
+    pub fn set_config(&mut self, config: PhysicsConfig) {
+        self.config = config;
+        for (id, engine) in self.targets.iter_mut() {
+            let config = match self.bounds.get(id) {
+                Some(bounds) => bounds.apply(&self.config),
+                None => self.config.clone(),
+            };
+            engine.set_config(config);
+        }
+    }
+
+    /// Override base resistance and/or ceiling for one target
+    ///
+    /// Applies to the engine now (if registered) and whenever it is
+    /// created or restored later.
+    pub fn set_bounds(&mut self, id: &str, bounds: TargetBounds) {
+        if let Some(engine) = self.targets.get_mut(id) {
+            engine.set_config(bounds.apply(&self.config));
+        }
+        self.bounds.insert(id.to_string(), bounds);
+    }
+
+    /// Drop a target's bounds, reverting it to the pool config
+    pub fn clear_bounds(&mut self, id: &str) -> Option<TargetBounds> {
+        let bounds = self.bounds.remove(id)?;
+        if let Some(engine) = self.targets.get_mut(id) {
+            engine.set_config(self.config.clone());
+        }
+        Some(bounds)
+    }
+
+    pub fn bounds(&self, id: &str) -> Option<&TargetBounds> {
+        self.bounds.get(id)
+    }
+
+    /// Effective config for a target (pool config plus its bounds)
+    pub fn config_for(&self, id: &str) -> PhysicsConfig {
+        match self.bounds.get(id) {
+            Some(bounds) => bounds.apply(&self.config),
+            None => self.config.clone(),
+        }
+    }
+
+    /// Engine for `id` starting from `state`, keyed for recovery jitter
+    fn build_engine(&self, id: &str, state: TargetState) -> TargetEngine {
+        let mut engine = TargetEngine::with_state(self.config_for(id), self.weights.clone(), state);
+        engine.set_jitter_key(jitter::key_for(id));
+        if let Some(probing) = self.probing {
+            engine.enable_probing(probing);
+        }
+        engine
+    }
+
+    pub fn priors(&self) -> &WarmStartPriors {
+        &self.priors
+    }
+
+    pub fn set_priors(&mut self, priors: WarmStartPriors) {
+        self.priors = priors;
+    }
+
+    pub fn get(&self, id: &str) -> Option<&TargetEngine> {
+        self.targets.get(id)
+    }
+
+    /// Mutable access (marks the target dirty for the next checkpoint)
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut TargetEngine> {
+        if self.targets.contains_key(id) {
+            self.touch(id);
+        }
+        self.targets.get_mut(id)
+    }
+
+    /// Load `state` into a target, in place when it is already registered
+    ///
+    /// Existing engines keep their layers (journal, probing, alarms, ...);
+    /// only unknown ids get a fresh engine.
+    fn load_state(&mut self, id: &str, state: TargetState) {
+        match self.targets.get_mut(id) {
+            Some(engine) => engine.replace_state(state),
+            None => {
+                let engine = self.build_engine(id, state);
+                self.targets.insert(id.to_string(), engine);
+            }
+        }
+        self.touch(id);
+    }
+
+    /// Record that a target's state changed
+    fn touch(&mut self, id: &str) {
+        mark_dirty(&mut self.dirty, &mut self.removed, id);
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.targets.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Iterate `(id, engine)` pairs in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &TargetEngine)> {
+        self.targets.iter()
+    }
+
+    /// Register a target seeded from the whole pool (if priors ask for it)
+    pub fn add_target(&mut self, id: &str) -> &mut TargetEngine {
+        if !self.targets.contains_key(id) {
+            let engine = self.seeded_engine(id, self.targets.values().map(TargetEngine::state));
+            self.targets.insert(id.to_string(), engine);
+        }
+        self.get_mut(id).expect("target registered above")
+    }
+
+    /// Register a target seeded from the given peers (group or parent)
+    ///
+    /// Existing targets are returned unchanged.
+    pub fn add_target_from<S: AsRef<str>>(&mut self, id: &str, peers: &[S]) -> &mut TargetEngine {
+        if !self.targets.contains_key(id) {
+            let peer_states = peers
+                .iter()
+                .filter_map(|p| self.targets.get(p.as_ref()))
+                .map(TargetEngine::state);
+            let engine = self.seeded_engine(id, peer_states);
+            self.targets.insert(id.to_string(), engine);
+        }
+        self.get_mut(id).expect("target registered above")
+    }
+
+    /// New engine for `id` with the priors applied; `peers` are only
+    /// walked under `SeedStrategy::PeerAverage`
+    fn seeded_engine<'a>(
+        &self,
+        id: &str,
+        peers: impl Iterator<Item = &'a TargetState>,
+    ) -> TargetEngine {
+        let bootstrap = TargetState::bootstrap(&self.config_for(id));
+        let state = match self.priors.seed {
+            SeedStrategy::PeerAverage => self.priors.apply(bootstrap, peers),
+            SeedStrategy::Fixed => self.priors.apply(bootstrap, []),
+        };
+        self.build_engine(id, state)
+    }
+
+    /// Remove a target, returning its engine
+    pub fn remove(&mut self, id: &str) -> Option<TargetEngine> {
+        let engine = self.targets.remove(id)?;
+        self.dirty.remove(id);
+        self.removed.insert(id.to_string());
+        Some(engine)
+    }
+
+    /// Tick a target, registering it on first sight
+    pub fn tick(&mut self, id: &str, now_ms: f64, pressure: PressureVector) -> TargetState {
+        self.clock_ms = self.clock_ms.max(now_ms);
+        if !self.targets.contains_key(id) {
+            self.add_target(id);
+        }
+        self.get_mut(id)
+            .expect("target registered above")
+            .tick(now_ms, pressure)
+    }
+
+    /// Tick a target with a sample that may be late (see
+    /// `TargetEngine::tick_at`), registering it on first sight
+    pub fn tick_at(
+        &mut self,
+        id: &str,
+        timestamp_ms: f64,
+        pressure: PressureVector,
+    ) -> TargetState {
+        self.clock_ms = self.clock_ms.max(timestamp_ms);
+        if !self.targets.contains_key(id) {
+            self.add_target(id);
+        }
+        self.get_mut(id)
+            .expect("target registered above")
+            .tick_at(timestamp_ms, pressure)
+    }
+
+    /// Tick every registered target in storage order, skipping those
+    /// `pressure_of` returns `None` for; returns how many were ticked
+    ///
+    /// Cheaper than `tick` per id on large pools: no id lookups, and
+    /// engines are visited in memory order.
+    pub fn tick_all(
+        &mut self,
+        now_ms: f64,
+        mut pressure_of: impl FnMut(&str) -> Option<PressureVector>,
+    ) -> usize {
+        self.clock_ms = self.clock_ms.max(now_ms);
+        let mut ticked = 0;
+        for (id, engine) in self.targets.iter_mut() {
+            let Some(pressure) = pressure_of(id) else {
+                continue;
+            };
+            engine.tick(now_ms, pressure);
+            mark_dirty(&mut self.dirty, &mut self.removed, id);
+            ticked += 1;
+        }
+        ticked
+    }
+
+    /// Handle to a registered target (invalidated when it is removed)
+    pub fn handle_of(&self, id: &str) -> Option<Handle> {
+        self.targets.handle_of(id)
+    }
+
+    /// Register a target (see `add_target`) and return its handle
+    pub fn register(&mut self, id: &str) -> Handle {
+        self.add_target(id);
+        self.handle_of(id).expect("target registered above")
+    }
+
+    /// Id and engine behind a handle (`None` once the target is removed)
+    pub fn entry(&self, handle: Handle) -> Option<(&String, &TargetEngine)> {
+        self.targets.resolve(handle)
+    }
+
+    /// Tick the target behind `handle`, skipping the id lookup
+    ///
+    /// Returns `None` for stale handles rather than registering anything.
+    pub fn tick_handle(
+        &mut self,
+        handle: Handle,
+        now_ms: f64,
+        pressure: PressureVector,
+    ) -> Option<TargetState> {
+        self.clock_ms = self.clock_ms.max(now_ms);
+        let (id, engine) = self.targets.resolve_mut(handle)?;
+        let state = engine.tick(now_ms, pressure);
+        mark_dirty(&mut self.dirty, &mut self.removed, id);
+        Some(state)
+    }
+
+    /// Intern a target id for `tick_resolved` (does not register the target)
+    pub fn resolve(&mut self, id: &str) -> TargetHandle {
+        let handle = self.interner.resolve(id);
+        if self.interned.len() <= handle.index() {
+            self.interned.resize(handle.index() + 1, None);
+        }
+        handle
+    }
+
+    /// Id behind an interned handle
+    pub fn resolved_id(&self, handle: TargetHandle) -> Option<&str> {
+        self.interner.name(handle)
+    }
+
+    /// `tick` for an interned id: registers the target on first sight and
+    /// hashes nothing once its engine is cached
+    ///
+    /// Returns `None` for handles this pool did not issue.
+    pub fn tick_resolved(
+        &mut self,
+        handle: TargetHandle,
+        now_ms: f64,
+        pressure: PressureVector,
+    ) -> Option<TargetState> {
+        let cached = *self.interned.get(handle.index())?;
+        if let Some(state) = cached.and_then(|slot| self.tick_handle(slot, now_ms, pressure)) {
+            return Some(state);
+        }
+        let id = self.interner.name(handle)?.to_string();
+        let slot = self.register(&id);
+        self.interned[handle.index()] = Some(slot);
+        self.tick_handle(slot, now_ms, pressure)
+    }
+
+    /// Quarantine check that skips hashing while nothing is quarantined
+    fn is_quarantined(&self, id: &str) -> bool {
+        !self.quarantine.is_empty() && self.quarantined(id).is_some()
+    }
+
+    /// Tick a target with an explicit sample confidence in [0, 1]
+    pub fn tick_with_confidence(
+        &mut self,
+        id: &str,
+        now_ms: f64,
+        pressure: PressureVector,
+        confidence: f64,
+    ) -> TargetState {
+        self.clock_ms = self.clock_ms.max(now_ms);
+        if !self.targets.contains_key(id) {
+            self.add_target(id);
+        }
+        self.get_mut(id)
+            .expect("target registered above")
+            .tick_with_confidence(now_ms, pressure, confidence)
+    }
+
+    /// Copy every target's state into a snapshot
+    pub fn snapshot(&self, now_ms: f64) -> Snapshot {
+        let mut targets: Vec<TargetSnapshot> = self
+            .targets
+            .iter()
+            .map(|(id, engine)| TargetSnapshot {
+                id: id.clone(),
+                state: *engine.state(),
+            })
+            .collect();
+        targets.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut quarantine = self.quarantine.clone();
+        quarantine.expire(now_ms);
+        Snapshot {
+            version: SNAPSHOT_VERSION,
+            taken_at_ms: now_ms,
+            targets,
+            quarantine,
+        }
+    }
+
+    /// Per-axis SLO compliance of every target, sorted by id (see
+    /// `TargetEngine::compliance`)
+    pub fn compliance(
+        &self,
+        window_ms: f64,
+        thresholds: &ComplianceThresholds,
+    ) -> Vec<TargetCompliance> {
+        let mut reports: Vec<TargetCompliance> = self
+            .targets
+            .iter()
+            .map(|(id, engine)| TargetCompliance {
+                id: id.clone(),
+                report: engine.compliance(window_ms, thresholds),
+            })
+            .collect();
+        reports.sort_by(|a, b| a.id.cmp(&b.id));
+        reports
+    }
+
+    /// Restore target states from a snapshot, returning how many were loaded
+    ///
+    /// Targets missing from the pool are created; targets not present in the
+    /// snapshot are left untouched.
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<usize, AtrionError> {
+        snapshot.check_version()?;
+        for target in &snapshot.targets {
+            self.load_state(&target.id, target.state);
+        }
+        for (id, entry) in snapshot.quarantine.active(snapshot.taken_at_ms) {
+            self.quarantine.insert(
+                id,
+                &entry.reason,
+                entry.since_ms,
+                entry.until_ms.map(|until| until - entry.since_ms),
+            );
+        }
+        Ok(snapshot.targets.len())
+    }
+
+    /// Restore a snapshot, then decay its targets up to `now_ms`
+    ///
+    /// A snapshot taken minutes ago should not impose the penalties of
+    /// minutes ago: see `TargetEngine::fast_forward`.
+    pub fn restore_at(&mut self, snapshot: &Snapshot, now_ms: f64) -> Result<usize, AtrionError> {
+        let restored = self.restore(snapshot)?;
+        for target in &snapshot.targets {
+            if let Some(engine) = self.targets.get_mut(&target.id) {
+                engine.fast_forward(now_ms);
+            }
+        }
+        self.clock_ms = self.clock_ms.max(now_ms);
+        Ok(restored)
+    }
+
+    /// Start a chunked export of `chunk_size` targets per chunk
+    pub fn chunk_cursor(&self, now_ms: f64, chunk_size: usize) -> ChunkCursor {
+        let mut ids: Vec<String> = self.targets.keys().cloned().collect();
+        ids.sort_unstable();
+        ChunkCursor {
+            ids,
+            position: 0,
+            chunk_size: chunk_size.max(1),
+            index: 0,
+            taken_at_ms: now_ms,
+        }
+    }
+
+    /// Encode the next chunk, or `None` once the cursor is exhausted
+    ///
+    /// An empty pool still yields one chunk so quarantine entries travel.
+    pub fn next_chunk(&self, cursor: &mut ChunkCursor) -> Option<Vec<u8>> {
+        if cursor.position >= cursor.ids.len() && cursor.index > 0 {
+            return None;
+        }
+        let end = (cursor.position + cursor.chunk_size).min(cursor.ids.len());
+        let targets: Vec<(&str, &TargetState)> = cursor.ids[cursor.position..end]
+            .iter()
+            .filter_map(|id| self.targets.get(id).map(|e| (id.as_str(), e.state())))
+            .collect();
+        let quarantine: Vec<_> = if cursor.index == 0 {
+            self.quarantine.active(cursor.taken_at_ms).collect()
+        } else {
+            Vec::new()
+        };
+        let bytes = chunk::encode_chunk(cursor.index, cursor.taken_at_ms, &targets, quarantine);
+        cursor.position = end;
+        cursor.index += 1;
+        Some(bytes)
+    }
+
+    /// Iterate over encoded chunks of `chunk_size` targets
+    pub fn export_chunks(
+        &self,
+        now_ms: f64,
+        chunk_size: usize,
+    ) -> impl Iterator<Item = Vec<u8>> + '_ {
+        let mut cursor = self.chunk_cursor(now_ms, chunk_size);
+        std::iter::from_fn(move || self.next_chunk(&mut cursor))
+    }
+
+    /// Restore the targets (and quarantine entries) of one chunk
+    ///
+    /// Chunks may arrive in any order; like `restore`, targets not in the
+    /// chunk are left untouched. Returns how many targets were loaded.
+    pub fn import_chunk(&mut self, bytes: &[u8]) -> Result<usize, AtrionError> {
+        let chunk = chunk::decode_chunk(bytes)?;
+        for target in &chunk.targets {
+            self.load_state(&target.id, target.state);
+        }
+        for (id, entry) in &chunk.quarantine {
+            self.quarantine.insert(
+                id,
+                &entry.reason,
+                entry.since_ms,
+                entry.until_ms.map(|until| until - entry.since_ms),
+            );
+        }
+        Ok(chunk.targets.len())
+    }
+
+    /// Publish a target's state to `store` and absorb the merged fleet state
+    ///
+    /// Returns `Ok(false)` for unknown targets.
+    pub fn sync<S: StateStore>(&mut self, id: &str, store: &S) -> Result<bool, S::Error> {
+        let Some(engine) = self.targets.get_mut(id) else {
+            return Ok(false);
+        };
+        let before = engine.shared_state();
+        let merged = store.merge(id, &before)?;
+        engine.absorb_shared(&merged);
+        if engine.shared_state() != before {
+            self.touch(id);
+        }
+        Ok(true)
+    }
+
+    /// Sync every registered target, stopping at the first store error
+    pub fn sync_all<S: StateStore>(&mut self, store: &S) -> Result<(), S::Error> {
+        let mut changed = Vec::new();
+        let mut result = Ok(());
+        for (id, engine) in self.targets.iter_mut() {
+            let before = engine.shared_state();
+            match store.merge(id, &before) {
+                Ok(merged) => engine.absorb_shared(&merged),
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+            if engine.shared_state() != before {
+                changed.push(id.clone());
+            }
+        }
+        for id in changed {
+            self.touch(&id);
+        }
+        result
+    }
+
+    /// Delta of every target changed or removed since the last checkpoint
+    ///
+    /// The first checkpoint of a pool holds every target. Dirty tracking is
+    /// reset, so each change is shipped exactly once.
+    pub fn checkpoint(&mut self, now_ms: f64) -> DeltaSnapshot {
+        let mut targets: Vec<TargetSnapshot> = self
+            .dirty
+            .drain()
+            .filter_map(|id| {
+                let state = *self.targets.get(&id)?.state();
+                Some(TargetSnapshot { id, state })
+            })
+            .collect();
+        targets.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut removed: Vec<String> = self.removed.drain().collect();
+        removed.sort_unstable();
+
+        let mut quarantine = self.quarantine.clone();
+        quarantine.expire(now_ms);
+        let since_ms = self.last_checkpoint_ms.replace(now_ms);
+        DeltaSnapshot {
+            version: SNAPSHOT_VERSION,
+            since_ms,
+            taken_at_ms: now_ms,
+            targets,
+            removed,
+            quarantine,
+        }
+    }
+
+    /// Targets changed since the last checkpoint
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len() + self.removed.len()
+    }
+
+    /// Apply a delta on top of this pool, returning how many targets it set
+    ///
+    /// Deltas must be applied in the order they were taken.
+    pub fn apply_delta(&mut self, delta: &DeltaSnapshot) -> Result<usize, AtrionError> {
+        delta.check_version()?;
+        for target in &delta.targets {
+            self.load_state(&target.id, target.state);
+        }
+        for id in &delta.removed {
+            self.remove(id);
+        }
+        self.quarantine = delta.quarantine.clone();
+        Ok(delta.targets.len())
+    }
+
+    /// Voltage gate for a target (unknown targets are admitted unless quarantined)
+    pub fn try_admit(&self, id: &str, voltage: f64) -> AdmissionDecision {
+        let decision = if self.quarantined(id).is_some() {
+            AdmissionDecision::Reject(RejectReason::Quarantined)
+        } else {
+            match self.targets.get(id) {
+                Some(engine) => engine.try_admit(voltage),
+                None => AdmissionDecision::Admit,
+            }
+        };
+        self.audit(id, decision)
+    }
+
+    /// Deadline-aware voltage gate for a target (see
+    /// `TargetEngine::try_admit_deadline`)
+    pub fn try_admit_deadline(
+        &self,
+        id: &str,
+        voltage: f64,
+        remaining_ms: f64,
+    ) -> AdmissionDecision {
+        let decision = if self.quarantined(id).is_some() {
+            AdmissionDecision::Reject(RejectReason::Quarantined)
+        } else {
+            match self.targets.get(id) {
+                Some(engine) => engine.try_admit_deadline(voltage, remaining_ms),
+                None => AdmissionDecision::Admit,
+            }
+        };
+        self.audit(id, decision)
+    }
+
+    /// Typed admission check for a target (see
+    /// `TargetEngine::check_admission`); quarantined targets advise
+    /// waiting out the TTL
+    pub fn check_admission(
+        &self,
+        id: &str,
+        voltage: f64,
+        remaining_ms: Option<f64>,
+    ) -> Result<(), Rejection> {
+        let checked = if let Some(entry) = self.quarantined(id) {
+            let wait_ms = entry
+                .remaining_ms(self.clock_ms)
+                .unwrap_or(retry::MAX_RETRY_MS);
+            Err(Rejection {
+                denial: Denial::Quarantine,
+                reason: RejectReason::Quarantined,
+                retry_after_ms: wait_ms.clamp(retry::MIN_RETRY_MS, retry::MAX_RETRY_MS),
+            })
+        } else {
+            match self.targets.get(id) {
+                Some(engine) => engine.check_admission(voltage, remaining_ms),
+                None => Ok(()),
+            }
+        };
+        let decision = match &checked {
+            Ok(()) => AdmissionDecision::Admit,
+            Err(rejection) => AdmissionDecision::Reject(rejection.reason),
+        };
+        self.audit(id, decision);
+        checked
+    }
+
+    /// Keep a sampled audit stream of admission decisions
+    pub fn enable_admission_audit(&mut self, config: AuditConfig) {
+        self.audit = Some(Mutex::new(AdmissionAudit::new(config)));
+    }
+
+    pub fn disable_admission_audit(&mut self) {
+        self.audit = None;
+    }
+
+    /// Retained audit intervals, oldest first (empty when disabled)
+    pub fn admission_audit(&self) -> Vec<AuditInterval> {
+        self.audit
+            .as_ref()
+            .map_or_else(Vec::new, |audit| lock(audit).intervals().cloned().collect())
+    }
+
+    /// Take the retained audit intervals, leaving the audit empty
+    pub fn drain_admission_audit(&mut self) -> Vec<AuditInterval> {
+        self.audit.as_mut().map_or_else(Vec::new, |audit| {
+            audit
+                .get_mut()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .drain()
+        })
+    }
+
+    /// Record `decision` in the audit stream, if enabled
+    fn audit(&self, id: &str, decision: AdmissionDecision) -> AdmissionDecision {
+        if let Some(audit) = &self.audit {
+            let resistance = self.resistance(id).unwrap_or(0.0);
+            lock(audit).record(self.clock_ms, id, decision, resistance);
+        }
+        decision
+    }
+
+    /// Operator override: hold a target in `mode` until `until_ms`
+    ///
+    /// Returns false for unknown targets.
+    pub fn force_mode(&mut self, id: &str, mode: OperationalMode, until_ms: f64) -> bool {
+        self.get_mut(id)
+            .map(|engine| engine.force_mode_until(mode, until_ms))
+            .is_some()
+    }
+
+    /// Operator override: record no trauma for a target until `until_ms`
+    pub fn pause_trauma(&mut self, id: &str, until_ms: f64) -> bool {
+        self.get_mut(id)
+            .map(|engine| engine.pause_trauma(until_ms))
+            .is_some()
+    }
+
+    pub fn clear_overrides(&mut self, id: &str) -> bool {
+        self.get_mut(id)
+            .map(TargetEngine::clear_overrides)
+            .is_some()
+    }
+
+    /// Put a target back into bootstrap (see `TargetEngine::reset`)
+    pub fn reset(&mut self, id: &str) -> bool {
+        self.get_mut(id).map(TargetEngine::reset).is_some()
+    }
+
+    pub fn reset_scar(&mut self, id: &str) -> bool {
+        self.get_mut(id).map(TargetEngine::reset_scar).is_some()
+    }
+
+    pub fn reset_momentum(&mut self, id: &str) -> bool {
+        self.get_mut(id).map(TargetEngine::reset_momentum).is_some()
+    }
+
+    pub fn clear_history(&mut self, id: &str) -> bool {
+        self.get_mut(id).map(TargetEngine::clear_history).is_some()
+    }
+
+    /// Record an operational event on one target (see
+    /// `TargetEngine::mark_event`)
+    pub fn mark_event(&mut self, id: &str, kind: MarkerKind, timestamp_ms: f64) -> bool {
+        self.get_mut(id)
+            .map(|engine| engine.mark_event(kind, timestamp_ms))
+            .is_some()
+    }
+
+    /// A new pod took over an existing target id: restart its warm-up
+    /// (see `TargetEngine::restart_warmup`)
+    pub fn reregister(&mut self, id: &str, now_ms: f64) -> bool {
+        self.get_mut(id)
+            .map(|engine| engine.restart_warmup(now_ms))
+            .is_some()
+    }
+
+    /// Report admitted-request outcomes for one target (see
+    /// `TargetEngine::record_outcomes`)
+    pub fn record_outcomes(&mut self, id: &str, now_ms: f64, good: u64, bad: u64) -> bool {
+        self.get_mut(id)
+            .map(|engine| engine.record_outcomes(now_ms, good, bad))
+            .is_some()
+    }
+
+    /// Enable staleness probing on every target, including those
+    /// registered or restored later
+    pub fn enable_probing(&mut self, config: ProbingConfig) {
+        self.probing = Some(config);
+        for (_, engine) in self.targets.iter_mut() {
+            engine.enable_probing(config);
+        }
+    }
+
+    /// Run `TargetEngine::check_staleness` on every target; returns the
+    /// ids that raised `ProbeRequested`
+    pub fn check_staleness(&mut self, now_ms: f64) -> Vec<String> {
+        self.targets
+            .iter_mut()
+            .filter_map(|(id, engine)| engine.check_staleness(now_ms).then(|| id.clone()))
+            .collect()
+    }
+
+    /// Retry advice for a target (unknown targets are never shed)
+    ///
+    /// Quarantined targets advise waiting out the TTL (capped at
+    /// `retry::MAX_RETRY_MS`).
+    pub fn retry_advice(&self, id: &str, shed_threshold: f64) -> Option<RetryAdvice> {
+        if let Some(entry) = self.quarantined(id) {
+            let wait_ms = entry
+                .remaining_ms(self.clock_ms)
+                .unwrap_or(retry::MAX_RETRY_MS);
+            return Some(RetryAdvice {
+                retry_after_ms: wait_ms.clamp(retry::MIN_RETRY_MS, retry::MAX_RETRY_MS),
+                reason: RejectReason::Quarantined,
+            });
+        }
+        self.targets
+            .get(id)
+            .and_then(|engine| engine.retry_advice(shed_threshold))
+    }
+
+    /// Hedge advice for a target (`None` for unknown targets)
+    ///
+    /// Quarantined targets are never hedged to.
+    pub fn hedge_advice(&self, id: &str, policy: &HedgePolicy) -> Option<HedgeAdvice> {
+        let advice = self.targets.get(id)?.hedge_advice(policy);
+        if self.quarantined(id).is_some() {
+            return Some(HedgeAdvice {
+                hedge: false,
+                reason: HedgeReason::Quarantined,
+                ..advice
+            });
+        }
+        Some(advice)
+    }
+
+    /// Quarantine a target (infinite resistance) for `ttl_ms`, or until lifted
+    ///
+    /// The target need not be registered: this doubles as a denylist.
+    pub fn quarantine(&mut self, id: &str, reason: &str, now_ms: f64, ttl_ms: Option<f64>) {
+        self.clock_ms = self.clock_ms.max(now_ms);
+        self.quarantine.insert(id, reason, now_ms, ttl_ms);
+    }
+
+    pub fn lift_quarantine(&mut self, id: &str) -> Option<Quarantine> {
+        self.quarantine.lift(id)
+    }
+
+    /// Quarantine entry in force for `id`, if any
+    pub fn quarantined(&self, id: &str) -> Option<&Quarantine> {
+        self.quarantine.get(id, self.clock_ms)
+    }
+
+    pub fn quarantine_list(&self) -> &QuarantineList {
+        &self.quarantine
+    }
+
+    /// Put a target in `group`, returning its previous group
+    ///
+    /// The target need not be registered yet; a target has one group.
+    pub fn assign_group(&mut self, id: &str, group: &str) -> Option<String> {
+        self.groups.insert(id.to_string(), group.to_string())
+    }
+
+    pub fn unassign_group(&mut self, id: &str) -> Option<String> {
+        self.groups.remove(id)
+    }
+
+    pub fn group_of(&self, id: &str) -> Option<&str> {
+        self.groups.get(id).map(String::as_str)
+    }
+
+    /// Ids assigned to `group`, sorted
+    pub fn group_members(&self, group: &str) -> Vec<&str> {
+        let mut members: Vec<&str> = self
+            .groups
+            .iter()
+            .filter(|(_, g)| g.as_str() == group)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        members.sort_unstable();
+        members
+    }
+
+    fn owned_members(&self, group: &str) -> Vec<String> {
+        self.group_members(group)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Tick every registered member of `group` with the same sample,
+    /// returning how many were ticked
+    ///
+    /// Members' resistances are evaluated together with the fixed-size
+    /// batch kernel; members with a reorder window tick one by one.
+    pub fn tick_group(&mut self, group: &str, now_ms: f64, pressure: PressureVector) -> usize {
+        let members = self.owned_members(group);
+        self.clock_ms = self.clock_ms.max(now_ms);
+        let sample = SequencedSample {
+            seq: None,
+            timestamp_ms: now_ms,
+            pressure,
+            confidence: None,
+        };
+        let mut pending = Vec::with_capacity(members.len());
+        let mut ticked = 0;
+        for id in &members {
+            let Some(engine) = self.get_mut(id) else {
+                continue;
+            };
+            ticked += 1;
+            if !engine.applies_directly() {
+                engine.tick(now_ms, pressure);
+            } else if let Some(tick) = engine.begin_tick(sample) {
+                pending.push((id, tick));
+            }
+        }
+
+        let lanes: Vec<ResistanceLane> = pending.iter().filter_map(|(_, tick)| tick.lane).collect();
+        let mut raw = vec![Ohms(0.0); lanes.len()];
+        resistance::calculate_resistance_batch(&lanes, &mut raw);
+        let mut raw = raw.into_iter();
+        for (id, tick) in pending {
+            let r = tick.lane.and_then(|_| raw.next());
+            let engine = self.targets.get_mut(id).expect("target registered above");
+            engine.finish_tick(tick, r);
+        }
+        ticked
+    }
+
+    /// Clear scar on every registered member of `group`, returning the count
+    pub fn reset_group(&mut self, group: &str) -> usize {
+        let mut reset = 0;
+        for id in self.owned_members(group) {
+            if let Some(engine) = self.get_mut(&id) {
+                engine.reset_scar();
+                reset += 1;
+            }
+        }
+        reset
+    }
+
+    /// The `top_n` worst targets by `metric` (ties broken by id)
+    pub fn rank_targets(&self, metric: RankMetric, top_n: usize) -> Vec<RankedTarget> {
+        let mut ranked: Vec<RankedTarget> = self
+            .targets
+            .iter()
+            .map(|(id, engine)| {
+                let state = engine.state();
+                let value = match metric {
+                    RankMetric::Resistance => state.resistance.0,
+                    RankMetric::Scar => state.scar.0,
+                    RankMetric::TraumaRate => {
+                        engine.rolling_stats(WINDOW_1M_MS).trauma_events as f64
+                    }
+                };
+                RankedTarget {
+                    id: id.clone(),
+                    value,
+                    mode: state.mode,
+                    quarantined: self.quarantined(id).is_some(),
+                    breakdown: engine.explain(),
+                }
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.value.total_cmp(&a.value).then_with(|| a.id.cmp(&b.id)));
+        ranked.truncate(top_n);
+        ranked
+    }
+
+    /// Mode counts and resistance/scar aggregates for `group`
+    ///
+    /// `None` when no target is assigned to it.
+    pub fn group_summary(&self, group: &str) -> Option<GroupSummary> {
+        let members = self.group_members(group);
+        if members.is_empty() {
+            return None;
+        }
+        let mut summary = GroupSummary {
+            targets: members.len(),
+            ..GroupSummary::default()
+        };
+        let mut registered = 0;
+        for id in members {
+            if self.quarantined(id).is_some() {
+                summary.quarantined += 1;
+            }
+            let Some(engine) = self.targets.get(id) else {
+                continue;
+            };
+            let state = engine.state();
+            match state.mode {
+                OperationalMode::Bootstrap => summary.bootstrap += 1,
+                OperationalMode::Operational => summary.operational += 1,
+                OperationalMode::CircuitBreaker => summary.circuit_breaker += 1,
+            }
+            registered += 1;
+            summary.mean_resistance += state.resistance.0;
+            summary.max_resistance = summary.max_resistance.max(state.resistance.0);
+            summary.total_scar += state.scar.0 + state.slow_scar.0;
+        }
+        if registered > 0 {
+            summary.mean_resistance /= registered as f64;
+        }
+        Some(summary)
+    }
+}
+
+#[wasm_bindgen]
+impl EnginePool {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: PhysicsConfig, weights: SensitivityWeights) -> Self {
+        Self {
+            config,
+            weights,
+            priors: WarmStartPriors::default(),
+            targets: Slab::new(),
+            bounds: HashMap::new(),
+            groups: HashMap::new(),
+            quarantine: QuarantineList::default(),
+            clock_ms: 0.0,
+            dirty: HashSet::new(),
+            removed: HashSet::new(),
+            last_checkpoint_ms: None,
+            interner: Interner::new(),
+            interned: Vec::new(),
+            audit: None,
+            probing: None,
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Set warm-start priors from a plain `WarmStartPriors` object
+    #[wasm_bindgen(js_name = setPriors)]
+    pub fn set_priors_js(&mut self, priors: JsValue) -> Result<(), JsValue> {
+        self.set_priors(serde_wasm_bindgen::from_value(priors)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addTarget)]
+    pub fn add_target_js(&mut self, id: &str) {
+        self.add_target(id);
+    }
+
+    /// Register a target seeded from the listed peers
+    #[wasm_bindgen(js_name = addTargetFrom)]
+    pub fn add_target_from_js(&mut self, id: &str, peers: Vec<String>) {
+        self.add_target_from(id, &peers);
+    }
+
+    #[wasm_bindgen(js_name = removeTarget)]
+    pub fn remove_target_js(&mut self, id: &str) -> bool {
+        self.remove(id).is_some()
+    }
+
+    /// Tick a target, returning its new resistance
+    #[wasm_bindgen(js_name = tick)]
+    pub fn tick_js(&mut self, id: &str, now_ms: f64, pressure: &PressureVector) -> f64 {
+        self.tick(id, now_ms, *pressure).resistance.0
+    }
+
+    /// Tick with a possibly late sample, returning the new resistance
+    #[wasm_bindgen(js_name = tickAt)]
+    pub fn tick_at_js(&mut self, id: &str, timestamp_ms: f64, pressure: &PressureVector) -> f64 {
+        self.tick_at(id, timestamp_ms, *pressure).resistance.0
+    }
+
+    /// Current resistance (infinite while quarantined)
+    pub fn resistance(&self, id: &str) -> Option<f64> {
+        if self.quarantined(id).is_some() {
+            return Some(f64::INFINITY);
+        }
+        self.targets.get(id).map(|e| e.state().resistance.0)
+    }
+
+    pub fn mode(&self, id: &str) -> Option<OperationalMode> {
+        self.targets.get(id).map(|e| e.state().mode)
+    }
+
+    /// Handle of a registered target as a u64, undefined if unknown
+    #[wasm_bindgen(js_name = handle)]
+    pub fn handle_js(&self, id: &str) -> Option<u64> {
+        self.handle_of(id).map(Handle::to_bits)
+    }
+
+    /// Register a target and return its handle as a u64
+    #[wasm_bindgen(js_name = register)]
+    pub fn register_js(&mut self, id: &str) -> u64 {
+        self.register(id).to_bits()
+    }
+
+    /// Intern a target id, returning a u32 handle for `tickResolved`
+    #[wasm_bindgen(js_name = resolve)]
+    pub fn resolve_js(&mut self, id: &str) -> u32 {
+        self.resolve(id).to_bits()
+    }
+
+    /// Tick by interned handle, returning the new resistance (undefined
+    /// for handles this pool did not issue)
+    #[wasm_bindgen(js_name = tickResolved)]
+    pub fn tick_resolved_js(
+        &mut self,
+        handle: u32,
+        now_ms: f64,
+        pressure: &PressureVector,
+    ) -> Option<f64> {
+        self.tick_resolved(TargetHandle::from_bits(handle), now_ms, *pressure)
+            .map(|state| state.resistance.0)
+    }
+
+    /// Tick by handle, returning the new resistance (undefined if stale)
+    #[wasm_bindgen(js_name = tickHandle)]
+    pub fn tick_handle_js(
+        &mut self,
+        handle: u64,
+        now_ms: f64,
+        pressure: &PressureVector,
+    ) -> Option<f64> {
+        self.tick_handle(Handle::from_bits(handle), now_ms, *pressure)
+            .map(|state| state.resistance.0)
+    }
+
+    /// Current resistance by handle (infinite while quarantined)
+    #[wasm_bindgen(js_name = resistanceOf)]
+    pub fn resistance_of_js(&self, handle: u64) -> Option<f64> {
+        let (id, engine) = self.entry(Handle::from_bits(handle))?;
+        if self.is_quarantined(id) {
+            return Some(f64::INFINITY);
+        }
+        Some(engine.state().resistance.0)
+    }
+
+    #[wasm_bindgen(js_name = modeOf)]
+    pub fn mode_of_js(&self, handle: u64) -> Option<OperationalMode> {
+        self.entry(Handle::from_bits(handle))
+            .map(|(_, engine)| engine.state().mode)
+    }
+
+    /// Snapshot as a plain object
+    #[wasm_bindgen(js_name = snapshot)]
+    pub fn snapshot_js(&self, now_ms: f64) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.snapshot(now_ms))
+    }
+
+    /// `[{ id, report }]` per-axis SLO compliance (`thresholds` undefined
+    /// = defaults)
+    #[wasm_bindgen(js_name = compliance)]
+    pub fn compliance_js(&self, window_ms: f64, thresholds: JsValue) -> Result<JsValue, JsValue> {
+        let thresholds = if thresholds.is_undefined() {
+            ComplianceThresholds::default()
+        } else {
+            serde_wasm_bindgen::from_value(thresholds)?
+        };
+        crate::to_js(&self.compliance(window_ms, &thresholds))
+    }
+
+    /// Restore from a plain snapshot object, returning the target count
+    #[wasm_bindgen(js_name = restore)]
+    pub fn restore_js(&mut self, snapshot: JsValue) -> Result<usize, JsValue> {
+        let snapshot: Snapshot = serde_wasm_bindgen::from_value(snapshot)?;
+        Ok(self.restore(&snapshot)?)
+    }
+
+    /// Restore and fast-forward decay to `nowMs`
+    #[wasm_bindgen(js_name = restoreAt)]
+    pub fn restore_at_js(&mut self, snapshot: JsValue, now_ms: f64) -> Result<usize, JsValue> {
+        let snapshot: Snapshot = serde_wasm_bindgen::from_value(snapshot)?;
+        Ok(self.restore_at(&snapshot, now_ms)?)
+    }
+
+    /// Cursor for `nextChunk`
+    #[wasm_bindgen(js_name = exportCursor)]
+    pub fn chunk_cursor_js(&self, now_ms: f64, chunk_size: usize) -> ChunkCursor {
+        self.chunk_cursor(now_ms, chunk_size)
+    }
+
+    /// Next encoded chunk as a `Uint8Array`, or undefined when done
+    #[wasm_bindgen(js_name = nextChunk)]
+    pub fn next_chunk_js(&self, cursor: &mut ChunkCursor) -> Option<Vec<u8>> {
+        self.next_chunk(cursor)
+    }
+
+    /// Restore one chunk, returning its target count
+    #[wasm_bindgen(js_name = importChunk)]
+    pub fn import_chunk_js(&mut self, bytes: &[u8]) -> Result<usize, JsValue> {
+        Ok(self.import_chunk(bytes)?)
+    }
+
+    /// Delta snapshot (plain object) of targets changed since the last one
+    #[wasm_bindgen(js_name = checkpoint)]
+    pub fn checkpoint_js(&mut self, now_ms: f64) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.checkpoint(now_ms))
+    }
+
+    #[wasm_bindgen(js_name = applyDelta)]
+    pub fn apply_delta_js(&mut self, delta: JsValue) -> Result<usize, JsValue> {
+        let delta: DeltaSnapshot = serde_wasm_bindgen::from_value(delta)?;
+        Ok(self.apply_delta(&delta)?)
+    }
+
+    #[wasm_bindgen(getter, js_name = dirtyCount)]
+    pub fn dirty_count_js(&self) -> usize {
+        self.dirty_count()
+    }
+
+    #[wasm_bindgen(js_name = tryAdmit)]
+    pub fn try_admit_js(&self, id: &str, voltage: f64) -> bool {
+        self.try_admit(id, voltage).is_admitted()
+    }
+
+    /// Deadline-aware voltage gate; the rejection reason label, or
+    /// undefined when admitted
+    #[wasm_bindgen(js_name = tryAdmitDeadline)]
+    pub fn try_admit_deadline_js(
+        &self,
+        id: &str,
+        voltage: f64,
+        remaining_ms: f64,
+    ) -> Option<String> {
+        match self.try_admit_deadline(id, voltage, remaining_ms) {
+            AdmissionDecision::Admit => None,
+            AdmissionDecision::Reject(reason) => Some(reason.as_str().to_string()),
+        }
+    }
+
+    /// Typed rejection as a plain object, or undefined when admitted
+    #[wasm_bindgen(js_name = checkAdmission)]
+    pub fn check_admission_js(
+        &self,
+        id: &str,
+        voltage: f64,
+        remaining_ms: Option<f64>,
+    ) -> Result<JsValue, JsValue> {
+        match self.check_admission(id, voltage, remaining_ms) {
+            Ok(()) => Ok(JsValue::UNDEFINED),
+            Err(rejection) => crate::to_js(&rejection),
+        }
+    }
+
+    /// Sample admission decisions (`config` undefined = defaults)
+    #[wasm_bindgen(js_name = enableAdmissionAudit)]
+    pub fn enable_admission_audit_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config = if config.is_undefined() {
+            AuditConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        self.enable_admission_audit(config);
+        Ok(())
+    }
+
+    /// Retained audit intervals as plain objects
+    #[wasm_bindgen(js_name = admissionAudit)]
+    pub fn admission_audit_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.admission_audit())
+    }
+
+    #[wasm_bindgen(js_name = drainAdmissionAudit)]
+    pub fn drain_admission_audit_js(&mut self) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.drain_admission_audit())
+    }
+
+    /// Voltage gate by handle (stale handles are admitted, like unknown ids)
+    #[wasm_bindgen(js_name = tryAdmitHandle)]
+    pub fn try_admit_handle_js(&self, handle: u64, voltage: f64) -> bool {
+        let (id, decision) = match self.entry(Handle::from_bits(handle)) {
+            Some((id, _)) if self.is_quarantined(id) => {
+                (id, AdmissionDecision::Reject(RejectReason::Quarantined))
+            }
+            Some((id, engine)) => (id, engine.try_admit(voltage)),
+            None => return true,
+        };
+        self.audit(id, decision).is_admitted()
+    }
+
+    /// Set per-target bounds from a plain `{ base_resistance?, ceiling? }` object
+    #[wasm_bindgen(js_name = setBounds)]
+    pub fn set_bounds_js(&mut self, id: &str, bounds: JsValue) -> Result<(), JsValue> {
+        self.set_bounds(id, serde_wasm_bindgen::from_value(bounds)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = clearBounds)]
+    pub fn clear_bounds_js(&mut self, id: &str) -> bool {
+        self.clear_bounds(id).is_some()
+    }
+
+    /// Quarantine a target; omit `ttlMs` to quarantine until lifted
+    #[wasm_bindgen(js_name = quarantine)]
+    pub fn quarantine_js(&mut self, id: &str, reason: &str, now_ms: f64, ttl_ms: Option<f64>) {
+        self.quarantine(id, reason, now_ms, ttl_ms);
+    }
+
+    #[wasm_bindgen(js_name = liftQuarantine)]
+    pub fn lift_quarantine_js(&mut self, id: &str) -> bool {
+        self.lift_quarantine(id).is_some()
+    }
+
+    /// `{ reason, sinceMs, untilMs }` for a quarantined target, else undefined
+    #[wasm_bindgen(js_name = quarantined)]
+    pub fn quarantined_js(&self, id: &str) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.quarantined(id))
+    }
+
+    /// Staleness probing for all targets (`config` undefined = defaults)
+    #[wasm_bindgen(js_name = enableProbing)]
+    pub fn enable_probing_js(&mut self, config: JsValue) -> Result<(), JsValue> {
+        let config = if config.is_undefined() {
+            ProbingConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        self.enable_probing(config);
+        Ok(())
+    }
+
+    /// Ids of targets that need a probe now
+    #[wasm_bindgen(js_name = checkStaleness)]
+    pub fn check_staleness_js(&mut self, now_ms: f64) -> Vec<String> {
+        self.check_staleness(now_ms)
+    }
+
+    #[wasm_bindgen(js_name = forceMode)]
+    pub fn force_mode_js(&mut self, id: &str, mode: OperationalMode, until_ms: f64) -> bool {
+        self.force_mode(id, mode, until_ms)
+    }
+
+    #[wasm_bindgen(js_name = pauseTrauma)]
+    pub fn pause_trauma_js(&mut self, id: &str, until_ms: f64) -> bool {
+        self.pause_trauma(id, until_ms)
+    }
+
+    #[wasm_bindgen(js_name = clearOverrides)]
+    pub fn clear_overrides_js(&mut self, id: &str) -> bool {
+        self.clear_overrides(id)
+    }
+
+    #[wasm_bindgen(js_name = reset)]
+    pub fn reset_js(&mut self, id: &str) -> bool {
+        self.reset(id)
+    }
+
+    #[wasm_bindgen(js_name = resetScar)]
+    pub fn reset_scar_js(&mut self, id: &str) -> bool {
+        self.reset_scar(id)
+    }
+
+    #[wasm_bindgen(js_name = markEvent)]
+    pub fn mark_event_js(&mut self, id: &str, kind: MarkerKind, timestamp_ms: f64) -> bool {
+        self.mark_event(id, kind, timestamp_ms)
+    }
+
+    #[wasm_bindgen(js_name = reregister)]
+    pub fn reregister_js(&mut self, id: &str, now_ms: f64) -> bool {
+        self.reregister(id, now_ms)
+    }
+
+    #[wasm_bindgen(js_name = recordOutcomes)]
+    pub fn record_outcomes_js(&mut self, id: &str, now_ms: f64, good: u32, bad: u32) -> bool {
+        self.record_outcomes(id, now_ms, good as u64, bad as u64)
+    }
+
+    #[wasm_bindgen(js_name = resetMomentum)]
+    pub fn reset_momentum_js(&mut self, id: &str) -> bool {
+        self.reset_momentum(id)
+    }
+
+    #[wasm_bindgen(js_name = clearHistory)]
+    pub fn clear_history_js(&mut self, id: &str) -> bool {
+        self.clear_history(id)
+    }
+
+    #[wasm_bindgen(js_name = assignGroup)]
+    pub fn assign_group_js(&mut self, id: &str, group: &str) {
+        self.assign_group(id, group);
+    }
+
+    #[wasm_bindgen(js_name = unassignGroup)]
+    pub fn unassign_group_js(&mut self, id: &str) -> bool {
+        self.unassign_group(id).is_some()
+    }
+
+    #[wasm_bindgen(js_name = groupMembers)]
+    pub fn group_members_js(&self, group: &str) -> Vec<String> {
+        self.owned_members(group)
+    }
+
+    #[wasm_bindgen(js_name = tickGroup)]
+    pub fn tick_group_js(&mut self, group: &str, now_ms: f64, pressure: &PressureVector) -> usize {
+        self.tick_group(group, now_ms, *pressure)
+    }
+
+    #[wasm_bindgen(js_name = resetGroup)]
+    pub fn reset_group_js(&mut self, group: &str) -> usize {
+        self.reset_group(group)
+    }
+
+    /// Plain `GroupSummary` object, or undefined for an empty group
+    #[wasm_bindgen(js_name = groupSummary)]
+    pub fn group_summary_js(&self, group: &str) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.group_summary(group))
+    }
+
+    /// Hedge advice as a plain object, or undefined for unknown targets
+    #[wasm_bindgen(js_name = hedgeAdvice)]
+    pub fn hedge_advice_js(&self, id: &str, policy: JsValue) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.hedge_advice(id, &hedge::policy_from_js(policy)?))
+    }
+
+    /// Array of plain `RankedTarget` objects, worst first
+    #[wasm_bindgen(js_name = rankTargets)]
+    pub fn rank_targets_js(&self, metric: RankMetric, top_n: usize) -> Result<JsValue, JsValue> {
+        crate::to_js(&self.rank_targets(metric, top_n))
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::warmstart::SeedStrategy;
+
+    fn pool() -> EnginePool {
+        EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default())
+    }
+
+    #[test]
+    fn test_tick_registers_unknown_targets() {
+        let mut pool = pool();
+        pool.tick("a", 0.0, PressureVector::new(0.1, 0.1, 0.1));
+        pool.tick("b", 0.0, PressureVector::new(0.1, 0.1, 0.1));
+
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.mode("a"), Some(OperationalMode::Bootstrap));
+        assert!(pool.remove("a").is_some());
+        assert!(!pool.contains("a"));
+    }
+
+    #[test]
+    fn test_probing_covers_targets_added_later() {
+        let mut pool = pool();
+        let pressure = PressureVector::new(0.1, 0.1, 0.1);
+        pool.tick("early", 0.0, pressure);
+        pool.enable_probing(ProbingConfig::default());
+        pool.tick("late", 0.0, pressure);
+        let mut source = self::pool();
+        source.tick("restored", 0.0, pressure);
+        pool.restore(&source.snapshot(0.0)).unwrap();
+
+        let mut stale = pool.check_staleness(11_000.0);
+        stale.sort();
+        assert_eq!(stale, ["early", "late", "restored"]);
+    }
+
+    #[test]
+    fn test_auto_registering_many_targets() {
+        let mut pool = pool();
+        let pressure = PressureVector::new(0.1, 0.1, 0.1);
+        for i in 0..20_000 {
+            pool.tick(&format!("target-{i}"), 0.0, pressure);
+        }
+        assert_eq!(pool.len(), 20_000);
+        let state = pool.get("target-19999").unwrap().state();
+        assert_eq!(state.scar, Scar(0.0));
+        assert_eq!(state.tick_count, 1);
+    }
+
+    #[test]
+    fn test_new_target_seeded_from_pool_average() {
+        let mut pool = pool();
+        pool.set_priors(WarmStartPriors {
+            seed: SeedStrategy::PeerAverage,
+            ..WarmStartPriors::default()
+        });
+
+        let storm = PressureVector::new(1.0, 1.0, 1.0);
+        for i in 0..20 {
+            pool.tick("veteran", i as f64 * 100.0, storm);
+        }
+        let veteran_scar = pool.get("veteran").unwrap().state().scar;
+
+        let rookie = pool.add_target("rookie");
+        assert_eq!(rookie.state().scar, veteran_scar);
+        assert!(rookie.state().scar.0 > 0.0);
+    }
+
+    #[test]
+    fn test_set_config_propagates() {
+        let mut pool = pool();
+        pool.add_target("a");
+        let config = PhysicsConfig {
+            base_resistance: 25.0,
+            ..PhysicsConfig::default()
+        };
+        pool.set_config(config);
+
+        assert_eq!(pool.get("a").unwrap().config().base_resistance, 25.0);
+        assert_eq!(pool.add_target("b").config().base_resistance, 25.0);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut pool = pool();
+        for i in 0..15 {
+            pool.tick("a", i as f64 * 100.0, PressureVector::new(1.0, 1.0, 1.0));
+            pool.tick("b", i as f64 * 100.0, PressureVector::new(0.1, 0.0, 0.0));
+        }
+        let snapshot = pool.snapshot(1500.0);
+        assert_eq!(snapshot.targets[0].id, "a");
+
+        let mut restored = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        assert_eq!(restored.restore(&snapshot), Ok(2));
+        assert_eq!(
+            restored.get("a").unwrap().state().scar,
+            pool.get("a").unwrap().state().scar
+        );
+
+        let mut stale = snapshot.clone();
+        stale.version = 0;
+        assert!(restored.restore(&stale).is_err());
+    }
+
+    #[test]
+    fn test_restore_at_decays_stale_penalties() {
+        let mut pool = pool();
+        let mut now = 0.0;
+        while pool.get("a").map(|e| e.state().mode) != Some(OperationalMode::CircuitBreaker) {
+            pool.tick("a", now, PressureVector::new(1.0, 1.0, 1.0));
+            now += 100.0;
+        }
+        pool.tick("a", now, PressureVector::new(0.3, 0.0, 0.0));
+        let snapshot = pool.snapshot(now);
+        let tripped = snapshot.targets[0].state;
+        assert_eq!(tripped.mode, OperationalMode::CircuitBreaker);
+
+        let mut restored = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        assert_eq!(restored.restore_at(&snapshot, now + 600_000.0), Ok(1));
+        let state = restored.get("a").unwrap().state();
+        assert!(state.scar.0 < tripped.scar.0 * 1e-6);
+        assert!(state.resistance.0 < tripped.resistance.0);
+        assert_eq!(state.mode, OperationalMode::Operational);
+        assert_eq!(state.last_updated_ms, now + 600_000.0);
+    }
+
+    #[test]
+    fn test_sync_shares_trauma_across_pools() {
+        let store = crate::store::MemoryStore::new();
+        let mut wounded = pool();
+        let mut fresh = pool();
+        for i in 0..20 {
+            let now = i as f64 * 100.0;
+            wounded.tick("api", now, PressureVector::new(1.0, 1.0, 1.0));
+            fresh.tick("api", now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        let before = fresh.get("api").unwrap().state().resistance;
+
+        wounded.sync_all(&store).unwrap();
+        assert_eq!(fresh.sync("api", &store), Ok(true));
+        let api = fresh.get("api").unwrap().state();
+        assert_eq!(api.scar, wounded.get("api").unwrap().state().scar);
+        assert!(api.resistance.0 > before.0);
+        assert_eq!(fresh.sync("unknown", &store), Ok(false));
+    }
+
+    #[test]
+    fn test_seed_from_explicit_parent() {
+        let mut pool = pool();
+        pool.set_priors(WarmStartPriors {
+            seed: SeedStrategy::PeerAverage,
+            ..WarmStartPriors::default()
+        });
+        for i in 0..20 {
+            pool.tick(
+                "parent",
+                i as f64 * 100.0,
+                PressureVector::new(1.0, 1.0, 1.0),
+            );
+            pool.tick("calm", i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+
+        let parent_scar = pool.get("parent").unwrap().state().scar;
+        let child = pool.add_target_from("child", &["parent"]);
+        assert_eq!(child.state().scar, parent_scar);
+    }
+
+    #[test]
+    fn test_per_target_floor_and_ceiling() {
+        let mut pool = pool();
+        pool.set_bounds(
+            "critical",
+            TargetBounds {
+                ceiling: Some(40.0),
+                ..TargetBounds::default()
+            },
+        );
+        pool.set_bounds(
+            "experimental",
+            TargetBounds {
+                base_resistance: Some(30.0),
+                ..TargetBounds::default()
+            },
+        );
+        let storm = PressureVector::new(1.0, 1.0, 1.0);
+        for i in 0..60 {
+            pool.tick("critical", i as f64 * 100.0, storm);
+            pool.tick(
+                "experimental",
+                i as f64 * 100.0,
+                PressureVector::new(0.0, 0.0, 0.0),
+            );
+        }
+
+        // Never trips, and a strong enough request always gets through
+        assert_eq!(pool.resistance("critical"), Some(40.0));
+        assert_eq!(pool.mode("critical"), Some(OperationalMode::Operational));
+        assert!(pool.try_admit("critical", 41.0).is_admitted());
+        assert_eq!(pool.resistance("experimental"), Some(30.0));
+
+        // Bounds survive a pool-wide config change
+        pool.set_config(PhysicsConfig::default());
+        assert_eq!(
+            pool.get("critical").unwrap().config().resistance_ceiling,
+            Some(40.0)
+        );
+        assert!(pool.clear_bounds("critical").is_some());
+        assert_eq!(pool.config_for("critical").resistance_ceiling, None);
+    }
+
+    #[test]
+    fn test_quarantine_blocks_admission_until_ttl() {
+        let mut pool = pool();
+        for i in 0..10 {
+            pool.tick("api", i as f64 * 100.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        pool.quarantine("api", "data corruption", 1000.0, Some(10_000.0));
+        pool.quarantine("unknown", "denylisted", 1000.0, None);
+
+        assert_eq!(
+            pool.try_admit("api", 1e9),
+            AdmissionDecision::Reject(RejectReason::Quarantined)
+        );
+        assert!(!pool.try_admit("unknown", 1e9).is_admitted());
+        assert_eq!(pool.resistance("api"), Some(f64::INFINITY));
+        assert_eq!(
+            pool.retry_advice("api", 0.0).unwrap().retry_after_ms,
+            10_000.0
+        );
+        let rejection = pool.check_admission("api", 1e9, None).unwrap_err();
+        assert_eq!(rejection.denial, Denial::Quarantine);
+        assert_eq!(rejection.status_code(), 503);
+
+        // Survives a snapshot round trip
+        let mut restored = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        restored.restore(&pool.snapshot(1000.0)).unwrap();
+        assert_eq!(restored.quarantine_list().len(), 2);
+
+        pool.tick("api", 11_000.0, PressureVector::new(0.0, 0.0, 0.0));
+        assert!(pool.try_admit("api", 1e9).is_admitted());
+        assert_eq!(pool.quarantined("unknown").unwrap().reason, "denylisted");
+    }
+
+    #[test]
+    fn test_group_reset_and_summary() {
+        let mut pool = pool();
+        for id in ["eu-api", "eu-db"] {
+            pool.add_target(id);
+            pool.assign_group(id, "eu-west-1");
+        }
+        pool.add_target("us-api");
+        pool.assign_group("us-api", "us-east-1");
+
+        for i in 0..30 {
+            let now = i as f64 * 100.0;
+            assert_eq!(
+                pool.tick_group("eu-west-1", now, PressureVector::new(1.0, 1.0, 1.0)),
+                2
+            );
+            pool.tick_group("us-east-1", now, PressureVector::new(1.0, 1.0, 1.0));
+        }
+        let before = pool.group_summary("eu-west-1").unwrap();
+        assert_eq!(before.targets, 2);
+        assert!(before.total_scar > 0.0);
+
+        assert_eq!(pool.reset_group("eu-west-1"), 2);
+        let after = pool.group_summary("eu-west-1").unwrap();
+        assert_eq!(after.total_scar, 0.0);
+        assert!(after.max_resistance < before.max_resistance);
+        assert!(pool.get("us-api").unwrap().state().scar.0 > 0.0);
+
+        assert_eq!(pool.group_members("eu-west-1"), ["eu-api", "eu-db"]);
+        assert!(pool.group_summary("ap-south-1").is_none());
+    }
+
+    #[test]
+    fn test_tick_group_counts_only_registered_members() {
+        let mut pool = pool();
+        for id in ["a", "b", "c"] {
+            pool.add_target(id);
+            pool.assign_group(id, "g");
+        }
+        pool.assign_group("never-added", "g");
+        pool.remove("b");
+
+        let pressure = PressureVector::new(0.2, 0.0, 0.1);
+        assert_eq!(pool.tick_group("g", 100.0, pressure), 2);
+        // Removed and unregistered members are skipped, not re-created
+        assert!(pool.get("b").is_none());
+        assert!(pool.get("never-added").is_none());
+        assert_eq!(pool.get("a").unwrap().state().tick_count, 1);
+    }
+
+    #[test]
+    fn test_tick_all_and_handles() {
+        let mut pool = pool();
+        for id in ["a", "b", "c"] {
+            pool.add_target(id);
+        }
+        pool.remove("b");
+        pool.checkpoint(0.0);
+
+        let hot = PressureVector::new(0.9, 0.9, 0.9);
+        let ticked = pool.tick_all(100.0, |id| (id != "c").then_some(hot));
+        assert_eq!(ticked, 1);
+        assert_eq!(pool.get("a").unwrap().state().tick_count, 1);
+        assert_eq!(pool.get("c").unwrap().state().tick_count, 0);
+        assert_eq!(pool.dirty_count(), 1);
+
+        let c = pool.handle_of("c").unwrap();
+        assert_eq!(pool.tick_handle(c, 200.0, hot).unwrap().tick_count, 1);
+        // "d" reuses the slot "b" freed, under a new generation
+        let d = pool.register("d");
+        assert_eq!((d.slot(), d.generation()), (1, 1));
+        assert!(pool.tick_handle(Handle::from_bits(1), 300.0, hot).is_none());
+
+        pool.remove("c");
+        assert!(pool.tick_handle(c, 300.0, hot).is_none());
+        assert!(pool.entry(c).is_none());
+        assert!(pool.try_admit_handle_js(c.to_bits(), 1.0));
+    }
+
+    #[test]
+    fn test_interned_ticks_survive_removal() {
+        let mut pool = pool();
+        let api = pool.resolve("api");
+        assert_eq!(pool.resolve("api"), api);
+        assert!(!pool.contains("api"));
+
+        let hot = PressureVector::new(0.9, 0.9, 0.9);
+        assert_eq!(pool.tick_resolved(api, 100.0, hot).unwrap().tick_count, 1);
+        assert_eq!(pool.tick_resolved(api, 200.0, hot).unwrap().tick_count, 2);
+        assert_eq!(pool.get("api").unwrap().state().tick_count, 2);
+
+        // Re-registered from scratch, same handle
+        pool.remove("api");
+        assert_eq!(pool.tick_resolved(api, 300.0, hot).unwrap().tick_count, 1);
+        assert_eq!(pool.resolved_id(api), Some("api"));
+        assert!(pool
+            .tick_resolved(TargetHandle::from_bits(5), 400.0, hot)
+            .is_none());
+    }
+
+    #[test]
+    fn test_batched_group_tick_matches_individual_ticks() {
+        let ids: Vec<String> = (0..11).map(|i| format!("t{i}")).collect();
+        let mut pools = [pool(), pool()];
+        for pool in &mut pools {
+            for (i, id) in ids.iter().enumerate() {
+                pool.assign_group(id, "g");
+                if i % 3 == 0 {
+                    let bounds = TargetBounds {
+                        base_resistance: Some(5.0 + i as f64),
+                        ceiling: Some(80.0),
+                    };
+                    pool.set_bounds(id, bounds);
+                }
+                // Diverge the states before the group ticks
+                for k in 0..=i {
+                    let p = (k % 4) as f64 * 0.3;
+                    pool.tick(id, k as f64 * 50.0, PressureVector::new(p, p * 0.5, 0.1));
+                }
+            }
+            pool.get_mut("t4")
+                .unwrap()
+                .enable_reorder(crate::reorder::ReorderConfig::default());
+        }
+
+        for step in 0..40 {
+            let now = 1_000.0 + step as f64 * 100.0;
+            let p = if (10..25).contains(&step) { 0.95 } else { 0.2 };
+            let pressure = PressureVector::new(p, p, p);
+            assert_eq!(pools[0].tick_group("g", now, pressure), 11);
+            for id in &ids {
+                pools[1].tick(id, now, pressure);
+            }
+        }
+        for id in &ids {
+            let batched = pools[0].get(id).unwrap();
+            let single = pools[1].get(id).unwrap();
+            // Debug output round-trips every f64, so equal text = equal bits
+            assert_eq!(
+                format!("{:?}", batched.state()),
+                format!("{:?}", single.state())
+            );
+            assert_eq!(batched.events().len(), single.events().len());
+        }
+    }
+
+    #[test]
+    fn test_chunked_export_import() {
+        let mut pool = pool();
+        for i in 0..25 {
+            let id = format!("t{i:02}");
+            pool.tick(
+                &id,
+                0.0,
+                PressureVector::new(0.1 * (i % 10) as f64, 0.0, 0.0),
+            );
+        }
+        pool.quarantine("t03", "bad data", 0.0, Some(5000.0));
+
+        let chunks: Vec<Vec<u8>> = pool.export_chunks(0.0, 10).collect();
+        assert_eq!(chunks.len(), 3);
+
+        // Restore in reverse order, piece by piece
+        let mut restored = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let loaded: usize = chunks
+            .iter()
+            .rev()
+            .map(|c| restored.import_chunk(c).unwrap())
+            .sum();
+        assert_eq!(loaded, 25);
+        for (id, engine) in pool.iter() {
+            let state = restored.get(id).unwrap().state();
+            assert_eq!(state.resistance, engine.state().resistance);
+            assert_eq!(state.pressure.latency, engine.state().pressure.latency);
+        }
+        assert_eq!(restored.quarantined("t03").unwrap().reason, "bad data");
+
+        assert!(restored.import_chunk(&chunks[0][..20]).is_err());
+        let mut cursor = restored.chunk_cursor(0.0, 10);
+        assert_eq!(cursor.remaining(), 3);
+        while restored.next_chunk(&mut cursor).is_some() {}
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn test_delta_checkpoints_ship_only_changes() {
+        let mut pool = pool();
+        for i in 0..100 {
+            pool.tick(&format!("t{i}"), 0.0, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        let mut replica = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let first = pool.checkpoint(0.0);
+        assert_eq!((first.since_ms, first.targets.len()), (None, 100));
+        replica.apply_delta(&first).unwrap();
+
+        // Mostly idle: two targets move, one is removed
+        pool.tick("t7", 100.0, PressureVector::new(0.9, 0.0, 0.0));
+        pool.pause_trauma("t8", 500.0);
+        pool.remove("t9");
+        assert_eq!(pool.dirty_count(), 3);
+
+        let delta = pool.checkpoint(100.0);
+        assert_eq!(delta.since_ms, Some(0.0));
+        let ids: Vec<&str> = delta.targets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["t7", "t8"]);
+        assert_eq!(delta.removed, ["t9"]);
+        assert!(pool.checkpoint(200.0).is_empty());
+
+        replica.apply_delta(&delta).unwrap();
+        assert_eq!(replica.len(), 99);
+        assert_eq!(
+            replica.get("t7").unwrap().state().tick_count,
+            pool.get("t7").unwrap().state().tick_count
+        );
+    }
+
+    #[test]
+    fn test_apply_delta_keeps_engine_layers() {
+        use crate::journal::{JournalEntry, MemoryJournal};
+
+        let mut source = pool();
+        source.tick("a", 0.0, PressureVector::new(0.9, 0.0, 0.0));
+        let delta = source.checkpoint(0.0);
+
+        let mut replica = pool();
+        replica.tick("a", 0.0, PressureVector::new(0.1, 0.0, 0.0));
+        let sink = MemoryJournal::default();
+        replica
+            .get_mut("a")
+            .unwrap()
+            .enable_journal(Box::new(sink.clone()), 0);
+        replica.apply_delta(&delta).unwrap();
+        replica.tick("a", 100.0, PressureVector::new(0.1, 0.0, 0.0));
+
+        let records = sink.lock().unwrap().clone();
+        assert_eq!(records.len(), 2);
+        assert!(matches!(records[0].entry, JournalEntry::State { .. }));
+        assert!(matches!(records[1].entry, JournalEntry::Sample { .. }));
+    }
+
+    #[test]
+    fn test_rank_targets_worst_first() {
+        let mut pool = pool();
+        for i in 0..30 {
+            let now = i as f64 * 1_000.0;
+            pool.tick("calm", now, PressureVector::new(0.1, 0.0, 0.0));
+            pool.tick("warm", now, PressureVector::new(0.5, 0.2, 0.0));
+            let spike = if i % 3 == 0 { 1.0 } else { 0.2 };
+            pool.tick("flaky", now, PressureVector::new(spike, 0.0, 0.0));
+        }
+
+        let by_trauma = pool.rank_targets(RankMetric::TraumaRate, 2);
+        assert_eq!(by_trauma.len(), 2);
+        assert_eq!(by_trauma[0].id, "flaky");
+        assert!(by_trauma[0].value > 0.0);
+        assert!(by_trauma[0].breakdown.scar > 0.0);
+
+        let by_resistance = pool.rank_targets(RankMetric::Resistance, 10);
+        assert_eq!(by_resistance.len(), 3);
+        assert_eq!(by_resistance.last().unwrap().id, "calm");
+        assert!(by_resistance
+            .windows(2)
+            .all(|pair| pair[0].value >= pair[1].value));
+    }
+}
@@ -0,0 +1,95 @@
+/**
+ * Priority-based shedding order.
+ *
+ * Requests carry a priority in 0..=7 (7 = most important). As resistance
+ * climbs through the shedding band (recovery_threshold → break_threshold),
+ * lower priorities are cut off first. Priority 7 is only shed when the
+ * breaker opens.
+ */
+use serde::{Deserialize, Serialize};
+
+/// Number of priority levels (0..=7)
+pub const PRIORITY_LEVELS: usize = 8;
+pub const MAX_PRIORITY: u8 = (PRIORITY_LEVELS - 1) as u8;
+
+/// Where each priority is cut off inside the shedding band
+///
+/// Band position is 0.0 at `recovery_threshold` and 1.0 at
+/// `break_threshold`; priority `p` is admitted while the position is below
+/// its cutoff.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum ShedCurve {
+    /// Evenly spaced cutoffs: p → (p + 1) / 8
+    #[default]
+    Linear,
+    /// Cutoffs ((p + 1) / 8)^exponent; exponent > 1 sheds low priorities earlier
+    Power(f64),
+    /// Explicit cutoff per priority (must be non-decreasing)
+    Custom([f64; PRIORITY_LEVELS]),
+}
+
+impl ShedCurve {
+    /// Band position at which `priority` starts being shed
+    #[inline]
+    pub fn cutoff(&self, priority: u8) -> f64 {
+        let p = priority.min(MAX_PRIORITY) as usize;
+        let fraction = (p + 1) as f64 / PRIORITY_LEVELS as f64;
+        match self {
+            ShedCurve::Linear => fraction,
+            ShedCurve::Power(exponent) => fraction.powf(*exponent),
+            ShedCurve::Custom(cutoffs) => cutoffs[p],
+        }
+    }
+
+    /// Lowest priority admitted at band position `position`
+    ///
+    /// Returns `None` when every priority is shed.
+    pub fn min_admitted_priority(&self, position: f64) -> Option<u8> {
+        (0..=MAX_PRIORITY).find(|&p| position < self.cutoff(p))
+    }
+}
+
+/// Position inside the shedding band given the admission ratio
+#[inline]
+pub fn band_position(admission_ratio: f64) -> f64 {
+    1.0 - admission_ratio.clamp(0.0, 1.0)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_everything_admitted_below_band() {
+        assert_eq!(ShedCurve::Linear.min_admitted_priority(0.0), Some(0));
+    }
+
+    #[test]
+    fn test_linear_sheds_low_priorities_first() {
+        let curve = ShedCurve::Linear;
+        assert_eq!(curve.min_admitted_priority(0.3), Some(2));
+        assert_eq!(curve.min_admitted_priority(0.9), Some(7));
+        assert_eq!(curve.min_admitted_priority(1.0), None);
+    }
+
+    #[test]
+    fn test_power_curve_sheds_earlier() {
+        let position = 0.3;
+        let linear = ShedCurve::Linear.min_admitted_priority(position).unwrap();
+        let convex = ShedCurve::Power(2.0)
+            .min_admitted_priority(position)
+            .unwrap();
+        assert!(convex > linear);
+    }
+
+    #[test]
+    fn test_custom_cutoffs() {
+        let curve = ShedCurve::Custom([0.0, 0.0, 0.0, 0.0, 0.5, 0.5, 0.9, 1.0]);
+        assert_eq!(curve.min_admitted_priority(0.0), Some(4));
+        assert_eq!(curve.min_admitted_priority(0.6), Some(6));
+    }
+}
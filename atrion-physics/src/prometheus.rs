@@ -0,0 +1,306 @@
+/**
+ * Prometheus instant-query pressure source (feature "prometheus", native only).
+ *
+ * Each axis is a PromQL expression with a `{target}` placeholder, scaled
+ * onto pressure with an `AxisMapping` baseline/limit (the mapping's
+ * `field` holds the query). `poll` runs one instant query per configured
+ * axis against `/api/v1/query`; when a query returns several series the
+ * highest value wins, so the worst instance drives admission.
+ *
+ * Plain HTTP/1.0 over std `TcpStream`, blocking with a timeout: put a
+ * TLS-terminating proxy in front of remote servers, and poll from a
+ * thread rather than an async task.
+ */
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::error::AtrionError;
+use crate::ingest::AxisMapping;
+use crate::sources::{PressureSource, TimestampedPressure};
+use crate::types::PressureVector;
+
+/// Source configuration
+#[derive(Debug, Clone)]
+pub struct PrometheusConfig {
+    /// Server address, `host:port`
+    pub address: String,
+    /// Prepended to `/api/v1/query` (e.g. "/prometheus" behind a proxy)
+    pub path_prefix: String,
+    /// Connect, read and write timeout per query
+    pub timeout_ms: u64,
+    pub latency: Option<AxisMapping>,
+    pub error: Option<AxisMapping>,
+    pub saturation: Option<AxisMapping>,
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            address: "127.0.0.1:9090".to_string(),
+            path_prefix: String::new(),
+            timeout_ms: 2_000,
+            latency: None,
+            error: None,
+            saturation: None,
+        }
+    }
+}
+
+/// Polls a Prometheus server with one instant query per axis
+#[derive(Debug, Clone)]
+pub struct PrometheusSource {
+    config: PrometheusConfig,
+    last_error: Option<AtrionError>,
+}
+
+impl PrometheusSource {
+    pub fn new(config: PrometheusConfig) -> Self {
+        Self {
+            config,
+            last_error: None,
+        }
+    }
+
+    pub fn config(&self) -> &PrometheusConfig {
+        &self.config
+    }
+
+    /// Why the most recent failed query failed (cleared by a good poll)
+    pub fn last_error(&self) -> Option<&AtrionError> {
+        self.last_error.as_ref()
+    }
+
+    /// Run one instant query; returns `(timestamp_ms, highest value)`, or
+    /// `None` for an empty result
+    pub fn query(&self, promql: &str) -> Result<Option<(f64, f64)>, AtrionError> {
+        let body = self.get(&format!(
+            "{}/api/v1/query?query={}",
+            self.config.path_prefix,
+            encode(promql)
+        ))?;
+        parse_instant(&body)
+    }
+
+    fn get(&self, path: &str) -> Result<String, AtrionError> {
+        let io =
+            |err: std::io::Error| AtrionError::Source(format!("{}: {err}", self.config.address));
+        let timeout = Duration::from_millis(self.config.timeout_ms.max(1));
+        let addr = self
+            .config
+            .address
+            .to_socket_addrs()
+            .map_err(io)?
+            .next()
+            .ok_or_else(|| AtrionError::Source(format!("{}: no address", self.config.address)))?;
+        let mut stream = TcpStream::connect_timeout(&addr, timeout).map_err(io)?;
+        stream.set_read_timeout(Some(timeout)).map_err(io)?;
+        stream.set_write_timeout(Some(timeout)).map_err(io)?;
+        // HTTP/1.0: no chunked encoding, the server closes after the body
+        write!(
+            stream,
+            "GET {path} HTTP/1.0\r\nHost: {}\r\nAccept: application/json\r\n\r\n",
+            self.config.address
+        )
+        .map_err(io)?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).map_err(io)?;
+
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| AtrionError::Parse("truncated HTTP response".to_string()))?;
+        let status = head.split_whitespace().nth(1).unwrap_or_default();
+        if status != "200" {
+            return Err(AtrionError::Source(format!(
+                "{}: HTTP {status}: {}",
+                self.config.address,
+                body.trim()
+            )));
+        }
+        Ok(body.to_string())
+    }
+}
+
+impl PressureSource for PrometheusSource {
+    /// Axes whose query fails or comes back empty read as 0; `None` when
+    /// none of them produced a value
+    fn poll(&mut self, target: &str) -> Option<TimestampedPressure> {
+        let mut newest = None::<f64>;
+        let mut failed = None;
+        let mut axis = |mapping: &Option<AxisMapping>| -> f64 {
+            let Some(mapping) = mapping else {
+                return 0.0;
+            };
+            match self.query(&mapping.field.replace("{target}", target)) {
+                Ok(Some((timestamp_ms, value))) => {
+                    newest = Some(newest.map_or(timestamp_ms, |t| t.max(timestamp_ms)));
+                    mapping.pressure(value)
+                }
+                Ok(None) => 0.0,
+                Err(err) => {
+                    failed = Some(err);
+                    0.0
+                }
+            }
+        };
+        let pressure = PressureVector::new(
+            axis(&self.config.latency),
+            axis(&self.config.error),
+            axis(&self.config.saturation),
+        );
+        self.last_error = failed;
+        Some(TimestampedPressure {
+            timestamp_ms: newest?,
+            pressure,
+        })
+    }
+}
+
+/// `(timestamp_ms, value)` of a `vector` or `scalar` instant-query result,
+/// taking the highest value across series
+fn parse_instant(body: &str) -> Result<Option<(f64, f64)>, AtrionError> {
+    let parse = |msg: &str| AtrionError::Parse(format!("Prometheus response: {msg}"));
+    let response: Value = serde_json::from_str(body).map_err(|err| parse(&err.to_string()))?;
+    if response["status"] != "success" {
+        return Err(AtrionError::Source(format!(
+            "Prometheus query failed: {}",
+            response["error"].as_str().unwrap_or("unknown error")
+        )));
+    }
+    let data = &response["data"];
+    let points: Vec<&Value> = match data["resultType"].as_str() {
+        Some("vector") => data["result"]
+            .as_array()
+            .ok_or_else(|| parse("vector result is not an array"))?
+            .iter()
+            .map(|series| &series["value"])
+            .collect(),
+        Some("scalar") => vec![&data["result"]],
+        other => return Err(parse(&format!("unsupported result type {other:?}"))),
+    };
+    let mut best: Option<(f64, f64)> = None;
+    for point in points {
+        let (Some(seconds), Some(value)) = (
+            point[0].as_f64(),
+            point[1].as_str().and_then(|v| v.parse::<f64>().ok()),
+        ) else {
+            return Err(parse("malformed sample"));
+        };
+        // NaN (e.g. 0/0 rates) carries no pressure
+        if value.is_nan() {
+            continue;
+        }
+        if best.is_none_or(|(_, highest)| value > highest) {
+            best = Some((seconds * 1000.0, value));
+        }
+    }
+    Ok(best)
+}
+
+/// Percent-encode a query-string value
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() * 3);
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_parse_instant_takes_worst_series() {
+        let body = r#"{"status":"success","data":{"resultType":"vector","result":[
+            {"metric":{"pod":"a"},"value":[1700000000.5,"120"]},
+            {"metric":{"pod":"b"},"value":[1700000000.5,"480"]},
+            {"metric":{"pod":"c"},"value":[1700000000.5,"NaN"]}]}}"#;
+        assert_eq!(
+            parse_instant(body).unwrap(),
+            Some((1_700_000_000_500.0, 480.0))
+        );
+        let empty = r#"{"status":"success","data":{"resultType":"vector","result":[]}}"#;
+        assert_eq!(parse_instant(empty).unwrap(), None);
+        let failed = r#"{"status":"error","errorType":"bad_data","error":"parse error"}"#;
+        assert!(matches!(parse_instant(failed), Err(AtrionError::Source(_))));
+        assert_eq!(
+            encode("rate(x{job=\"a b\"}[1m])"),
+            "rate%28x%7Bjob%3D%22a%20b%22%7D%5B1m%5D%29"
+        );
+    }
+
+    #[test]
+    fn test_poll_queries_each_axis() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let mut paths = Vec::new();
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                // Drain the headers so closing does not reset the connection
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+                let value = if request_line.contains("latency") {
+                    "250"
+                } else {
+                    "0.05"
+                };
+                let body = format!(
+                    r#"{{"status":"success","data":{{"resultType":"vector","result":[{{"metric":{{}},"value":[1700000000,"{value}"]}}]}}}}"#
+                );
+                write!(
+                    stream,
+                    "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n{body}"
+                )
+                .unwrap();
+                paths.push(request_line);
+            }
+            paths
+        });
+
+        let mut source = PrometheusSource::new(PrometheusConfig {
+            address,
+            latency: Some(AxisMapping::new(
+                "p99_latency_ms{service=\"{target}\"}",
+                0.0,
+                500.0,
+            )),
+            error: Some(AxisMapping::new(
+                "error_ratio{service=\"{target}\"}",
+                0.0,
+                0.1,
+            )),
+            ..PrometheusConfig::default()
+        });
+        let sample = source.poll("api").unwrap();
+        assert_eq!(sample.timestamp_ms, 1_700_000_000_000.0);
+        assert_eq!(sample.pressure.latency, 0.5);
+        assert!((sample.pressure.error - 0.5).abs() < 1e-12);
+        assert_eq!(sample.pressure.saturation, 0.0);
+        assert!(source.last_error().is_none());
+
+        let paths = server.join().unwrap();
+        assert!(paths[0]
+            .starts_with("GET /api/v1/query?query=p99_latency_ms%7Bservice%3D%22api%22%7D "));
+    }
+}
@@ -0,0 +1,112 @@
+/**
+ * Target quarantine (ban list).
+ *
+ * A quarantined target has infinite resistance: the pool rejects every
+ * request for it, known or not, until the quarantine is lifted or its
+ * TTL runs out. Entries carry the reason so rejections can be explained.
+ */
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Why and until when a target is quarantined
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Quarantine {
+    pub reason: String,
+    #[serde(alias = "since_ms")]
+    pub since_ms: f64,
+    /// `None` = until lifted
+    #[serde(alias = "until_ms")]
+    pub until_ms: Option<f64>,
+}
+
+impl Quarantine {
+    pub fn active_at(&self, now_ms: f64) -> bool {
+        self.until_ms.is_none_or(|until| now_ms < until)
+    }
+
+    /// Time left at `now_ms` (`None` when permanent)
+    pub fn remaining_ms(&self, now_ms: f64) -> Option<f64> {
+        self.until_ms.map(|until| (until - now_ms).max(0.0))
+    }
+}
+
+/// Quarantined targets by id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct QuarantineList {
+    entries: BTreeMap<String, Quarantine>,
+}
+
+impl QuarantineList {
+    /// Quarantine `id` from `now_ms`, for `ttl_ms` or until lifted
+    pub fn insert(&mut self, id: &str, reason: &str, now_ms: f64, ttl_ms: Option<f64>) {
+        self.entries.insert(
+            id.to_string(),
+            Quarantine {
+                reason: reason.to_string(),
+                since_ms: now_ms,
+                until_ms: ttl_ms.map(|ttl| now_ms + ttl.max(0.0)),
+            },
+        );
+    }
+
+    pub fn lift(&mut self, id: &str) -> Option<Quarantine> {
+        self.entries.remove(id)
+    }
+
+    /// Entry for `id` if it is in force at `now_ms`
+    pub fn get(&self, id: &str, now_ms: f64) -> Option<&Quarantine> {
+        self.entries.get(id).filter(|q| q.active_at(now_ms))
+    }
+
+    /// Entries in force at `now_ms`, sorted by id
+    pub fn active(&self, now_ms: f64) -> impl Iterator<Item = (&str, &Quarantine)> {
+        self.entries
+            .iter()
+            .filter(move |(_, q)| q.active_at(now_ms))
+            .map(|(id, q)| (id.as_str(), q))
+    }
+
+    /// Forget entries whose TTL ran out before `now_ms`
+    pub fn expire(&mut self, now_ms: f64) {
+        self.entries.retain(|_, q| q.active_at(now_ms));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_and_permanent_entries() {
+        let mut list = QuarantineList::default();
+        list.insert("flaky", "error storm", 0.0, Some(1000.0));
+        list.insert("evil", "abuse", 0.0, None);
+
+        assert_eq!(list.get("flaky", 999.0).unwrap().reason, "error storm");
+        assert!(list.get("flaky", 1000.0).is_none());
+        assert_eq!(list.get("evil", 1e12).unwrap().remaining_ms(1e12), None);
+
+        list.expire(5000.0);
+        assert_eq!(list.len(), 1);
+        assert!(list.lift("evil").is_some());
+        assert!(list.is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+/**
+ * Redis-backed StateStore (feature "distributed", native only).
+ *
+ * Each target is a hash `{prefix}:{id}` with fields scar, momentum and
+ * updated_ms. Merges run as a Lua script so concurrent gateways cannot
+ * lose each other's trauma: the stored scar is decayed to the incoming
+ * timestamp before the max, exactly like `SharedState::merge`.
+ */
+use std::sync::Mutex;
+
+use redis::{Client, Connection, RedisError, Script};
+
+use crate::scar::SCAR_DECAY_RATE;
+use crate::store::{SharedState, StateStore};
+
+/// KEYS[1] = hash; ARGV = scar, momentum, updated_ms, decay_rate, ttl_ms
+const MERGE_SCRIPT: &str = r#"
+local stored = redis.call('HMGET', KEYS[1], 'scar', 'momentum', 'updated_ms')
+local now = tonumber(ARGV[3])
+local scar = tonumber(ARGV[1])
+local momentum = ARGV[2]
+local updated = now
+if stored[1] then
+  local at = tonumber(stored[3])
+  local dt = math.max(now - at, 0) / 1000
+  scar = math.max(scar, tonumber(stored[1]) * math.exp(-tonumber(ARGV[4]) * dt))
+  if at > now then
+    momentum = stored[2]
+    updated = at
+  end
+end
+redis.call('HSET', KEYS[1], 'scar', tostring(scar), 'momentum', momentum, 'updated_ms', tostring(updated))
+if tonumber(ARGV[5]) > 0 then
+  redis.call('PEXPIRE', KEYS[1], ARGV[5])
+end
+return {tostring(scar), momentum, tostring(updated)}
+"#;
+
+/// Shared state in Redis
+pub struct RedisStore {
+    connection: Mutex<Connection>,
+    prefix: String,
+    ttl_ms: u64,
+    script: Script,
+}
+
+impl RedisStore {
+    /// Connect to `url` (e.g. `redis://127.0.0.1/`) using the `atrion` key prefix
+    pub fn connect(url: &str) -> Result<Self, RedisError> {
+        let connection = Client::open(url)?.get_connection()?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+            prefix: "atrion".to_string(),
+            ttl_ms: 0,
+            script: Script::new(MERGE_SCRIPT),
+        })
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Expire targets nobody has synced for `ttl_ms` (0 = never)
+    pub fn with_ttl_ms(mut self, ttl_ms: u64) -> Self {
+        self.ttl_ms = ttl_ms;
+        self
+    }
+
+    pub fn key(&self, id: &str) -> String {
+        format!("{}:{}", self.prefix, id)
+    }
+
+    fn connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl StateStore for RedisStore {
+    type Error = RedisError;
+
+    fn load(&self, id: &str) -> Result<Option<SharedState>, RedisError> {
+        let fields: (Option<f64>, Option<f64>, Option<f64>) = redis::cmd("HMGET")
+            .arg(self.key(id))
+            .arg("scar")
+            .arg("momentum")
+            .arg("updated_ms")
+            .query(&mut *self.connection())?;
+        Ok(match fields {
+            (Some(scar), Some(momentum), Some(updated_ms)) => Some(SharedState {
+                scar,
+                momentum,
+                updated_ms,
+            }),
+            _ => None,
+        })
+    }
+
+    fn merge(&self, id: &str, state: &SharedState) -> Result<SharedState, RedisError> {
+        let (scar, momentum, updated_ms): (f64, f64, f64) = self
+            .script
+            .key(self.key(id))
+            .arg(state.scar)
+            .arg(state.momentum)
+            .arg(state.updated_ms)
+            .arg(SCAR_DECAY_RATE)
+            .arg(self.ttl_ms)
+            .invoke(&mut *self.connection())?;
+        Ok(SharedState {
+            scar,
+            momentum,
+            updated_ms,
+        })
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Needs a live server: `ATRION_REDIS_URL=redis://127.0.0.1/ cargo test -- --ignored`
+    #[test]
+    #[ignore]
+    fn test_redis_max_merge() {
+        let url = std::env::var("ATRION_REDIS_URL").unwrap_or("redis://127.0.0.1/".into());
+        let store = RedisStore::connect(&url)
+            .unwrap()
+            .with_prefix(format!("atrion-test-{}", std::process::id()))
+            .with_ttl_ms(60_000);
+
+        let high = SharedState {
+            scar: 10.0,
+            momentum: 0.1,
+            updated_ms: 1000.0,
+        };
+        let low = SharedState {
+            scar: 2.0,
+            momentum: 0.5,
+            updated_ms: 1000.0,
+        };
+        store.merge("api", &high).unwrap();
+        let merged = store.merge("api", &low).unwrap();
+
+        assert_eq!(merged.scar, 10.0);
+        assert_eq!(store.load("api").unwrap(), Some(merged));
+    }
+}
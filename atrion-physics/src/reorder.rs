@@ -0,0 +1,213 @@
+/**
+ * Reorder window and duplicate suppression for tick inputs.
+ *
+ * Buffered metric pipelines deliver samples late, twice or out of order.
+ * Samples are held for `window_ms` (measured against the newest timestamp
+ * seen) and released in timestamp order; replays of an already buffered
+ * or released sample are dropped so trauma is never applied twice.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::types::PressureVector;
+
+/// Reorder buffer configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderConfig {
+    /// How long a sample waits for stragglers (0 = release immediately)
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Hard cap on buffered samples; the oldest are released early past it
+    #[serde(alias = "max_buffered")]
+    pub max_buffered: usize,
+}
+
+impl Default for ReorderConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 1000.0,
+            max_buffered: 256,
+        }
+    }
+}
+
+/// One tick input, optionally carrying a producer sequence number
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencedSample {
+    /// Dedup key when present; otherwise the timestamp is the key
+    pub seq: Option<u64>,
+    #[serde(alias = "timestamp_ms")]
+    pub timestamp_ms: f64,
+    pub pressure: PressureVector,
+    /// Weight in [0, 1] for trauma/momentum (`None` = automatic)
+    #[serde(default)]
+    pub confidence: Option<f64>,
+}
+
+/// What happened to offered samples
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ReorderStats {
+    pub released: u64,
+    /// Released in a different order than they arrived
+    pub reordered: u64,
+    pub duplicates: u64,
+    /// Arrived after a newer sample had already been released
+    pub late: u64,
+}
+
+/// Timestamp-ordered holding buffer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderBuffer {
+    config: ReorderConfig,
+    /// Sorted by timestamp; `arrival` tracks offer order
+    pending: Vec<(u64, SequencedSample)>,
+    arrivals: u64,
+    #[serde(alias = "newest_ms")]
+    newest_ms: f64,
+    #[serde(alias = "released_ms")]
+    released_ms: Option<f64>,
+    #[serde(alias = "released_seq")]
+    released_seq: Option<u64>,
+    stats: ReorderStats,
+}
+
+impl ReorderBuffer {
+    pub fn new(config: ReorderConfig) -> Self {
+        Self {
+            config,
+            pending: Vec::new(),
+            arrivals: 0,
+            newest_ms: f64::NEG_INFINITY,
+            released_ms: None,
+            released_seq: None,
+            stats: ReorderStats::default(),
+        }
+    }
+
+    pub fn config(&self) -> &ReorderConfig {
+        &self.config
+    }
+
+    pub fn stats(&self) -> ReorderStats {
+        self.stats
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Offer a sample; returns samples now ready, oldest first
+    pub fn push(&mut self, sample: SequencedSample) -> Vec<SequencedSample> {
+        if self.is_duplicate(&sample) {
+            self.stats.duplicates += 1;
+        } else if self.released_ms.is_some_and(|t| sample.timestamp_ms <= t) {
+            self.stats.late += 1;
+        } else {
+            let at = self
+                .pending
+                .partition_point(|(_, s)| s.timestamp_ms <= sample.timestamp_ms);
+            self.pending.insert(at, (self.arrivals, sample));
+            self.arrivals += 1;
+            self.newest_ms = self.newest_ms.max(sample.timestamp_ms);
+        }
+        let cutoff = self.newest_ms - self.config.window_ms;
+        let mut ready = self
+            .pending
+            .partition_point(|(_, s)| s.timestamp_ms <= cutoff);
+        ready = ready.max(self.pending.len().saturating_sub(self.config.max_buffered));
+        self.release(ready)
+    }
+
+    /// Release everything still buffered
+    pub fn flush(&mut self) -> Vec<SequencedSample> {
+        self.release(self.pending.len())
+    }
+
+    fn is_duplicate(&self, sample: &SequencedSample) -> bool {
+        match sample.seq {
+            Some(seq) => {
+                self.released_seq.is_some_and(|s| seq <= s)
+                    || self.pending.iter().any(|(_, p)| p.seq == Some(seq))
+            }
+            None => self
+                .pending
+                .iter()
+                .any(|(_, p)| p.seq.is_none() && p.timestamp_ms == sample.timestamp_ms),
+        }
+    }
+
+    fn release(&mut self, count: usize) -> Vec<SequencedSample> {
+        let released: Vec<_> = self.pending.drain(..count).collect();
+        for (i, (arrival, sample)) in released.iter().enumerate() {
+            // Overtook something that arrived earlier but sorts later
+            let later = released[i + 1..].iter().chain(&self.pending);
+            if later.clone().any(|(a, _)| a < arrival) {
+                self.stats.reordered += 1;
+            }
+            self.released_ms = Some(sample.timestamp_ms);
+            if let Some(seq) = sample.seq {
+                self.released_seq = Some(self.released_seq.map_or(seq, |s| s.max(seq)));
+            }
+        }
+        self.stats.released += released.len() as u64;
+        released.into_iter().map(|(_, sample)| sample).collect()
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(seq: Option<u64>, timestamp_ms: f64) -> SequencedSample {
+        SequencedSample {
+            seq,
+            timestamp_ms,
+            pressure: PressureVector::new(0.5, 0.0, 0.0),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_releases_in_timestamp_order_after_window() {
+        let mut buffer = ReorderBuffer::new(ReorderConfig {
+            window_ms: 250.0,
+            ..ReorderConfig::default()
+        });
+        assert!(buffer.push(sample(None, 100.0)).is_empty());
+        assert!(buffer.push(sample(None, 300.0)).is_empty());
+        assert!(buffer.push(sample(None, 200.0)).is_empty());
+        let ready: Vec<_> = buffer
+            .push(sample(None, 450.0))
+            .iter()
+            .map(|s| s.timestamp_ms)
+            .collect();
+        assert_eq!(ready, [100.0, 200.0]);
+        assert_eq!(buffer.stats().reordered, 1);
+
+        // Older than what was released: too late to apply
+        assert!(buffer.push(sample(None, 150.0)).is_empty());
+        assert_eq!(buffer.stats().late, 1);
+        assert_eq!(buffer.flush().len(), 2);
+    }
+
+    #[test]
+    fn test_duplicates_dropped_by_seq_and_timestamp() {
+        let mut buffer = ReorderBuffer::new(ReorderConfig::default());
+        buffer.push(sample(Some(1), 100.0));
+        buffer.push(sample(Some(1), 100.0));
+        buffer.push(sample(None, 200.0));
+        buffer.push(sample(None, 200.0));
+        assert_eq!(buffer.flush().len(), 2);
+
+        // Replayed seq after release, even with a fresh timestamp
+        buffer.push(sample(Some(1), 5000.0));
+        assert_eq!(buffer.stats().duplicates, 3);
+        assert_eq!(buffer.pending(), 0);
+    }
+}
@@ -0,0 +1,446 @@
+/**
+ * Headless run reports (feature "report", native only).
+ *
+ * Renders an engine history — a trace or journal replayed through a
+ * `TargetEngine`, or one target of a simulation — into a self-contained
+ * SVG chart or HTML page: resistance (with the breaker thresholds), scar
+ * and the three pressure axes over time, shaded bands while the engine was
+ * in bootstrap or had the breaker open, ticks where trauma was recorded
+ * and dashed lines at operational markers. Styles are inlined and nothing
+ * is fetched, so the file can be attached to a ticket or a tuning PR as-is.
+ */
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+use crate::engine::TargetEngine;
+use crate::history::{History, HistorySample};
+use crate::markers::Marker;
+use crate::types::OperationalMode;
+
+const WIDTH: f64 = 960.0;
+const PANEL_HEIGHT: f64 = 160.0;
+const PANEL_GAP: f64 = 36.0;
+const LEFT: f64 = 64.0;
+const RIGHT: f64 = 16.0;
+const TOP: f64 = 28.0;
+const X_TICKS: usize = 5;
+
+const STYLE: &str = "text{font:11px sans-serif;fill:#333}\
+.title{font-weight:bold}\
+.frame{fill:none;stroke:#999}\
+.series{fill:none;stroke-width:1.5}\
+.resistance{stroke:#1f77b4}.scar{stroke:#d62728}\
+.latency{stroke:#ff7f0e}.error{stroke:#9467bd}.saturation{stroke:#2ca02c}\
+.threshold{stroke:#555;stroke-dasharray:4 3}\
+.band-breaker{fill:#d62728;fill-opacity:0.12}\
+.band-bootstrap{fill:#888;fill-opacity:0.12}\
+.trauma{stroke:#d62728}\
+.marker{stroke:#333;stroke-dasharray:2 4}";
+
+/// Headline numbers shown above the chart
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct ReportSummary {
+    pub samples: usize,
+    pub duration_ms: f64,
+    pub peak_resistance: f64,
+    pub peak_scar: f64,
+    /// Operational → CircuitBreaker transitions
+    pub trips: usize,
+    /// Fraction of the run with the breaker open
+    pub breaker_fraction: f64,
+    /// Samples on which scar grew
+    pub trauma_ticks: usize,
+}
+
+/// A run ready to render
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub title: String,
+    samples: Vec<HistorySample>,
+    markers: Vec<Marker>,
+    /// Break and recovery thresholds drawn on the resistance panel
+    thresholds: Option<(f64, f64)>,
+}
+
+impl RunReport {
+    pub fn from_history(title: impl Into<String>, history: &History) -> Self {
+        Self {
+            title: title.into(),
+            samples: history.iter().copied().collect(),
+            markers: history.markers().copied().collect(),
+            thresholds: None,
+        }
+    }
+
+    /// The engine's history, with its breaker thresholds
+    pub fn from_engine(title: impl Into<String>, engine: &TargetEngine) -> Self {
+        let config = engine.config();
+        Self {
+            thresholds: Some((config.break_threshold, config.recovery_threshold)),
+            ..Self::from_history(title, engine.history())
+        }
+    }
+
+    pub fn summary(&self) -> ReportSummary {
+        let Some((first, last)) = self.samples.first().zip(self.samples.last()) else {
+            return ReportSummary::default();
+        };
+        let mut summary = ReportSummary {
+            samples: self.samples.len(),
+            duration_ms: last.timestamp_ms - first.timestamp_ms,
+            ..ReportSummary::default()
+        };
+        let mut open_ms = 0.0;
+        for sample in &self.samples {
+            summary.peak_resistance = summary.peak_resistance.max(sample.resistance.0);
+            summary.peak_scar = summary.peak_scar.max(sample.scar.0);
+        }
+        for pair in self.samples.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            if prev.mode == OperationalMode::CircuitBreaker {
+                open_ms += next.timestamp_ms - prev.timestamp_ms;
+            }
+            if prev.mode == OperationalMode::Operational
+                && next.mode == OperationalMode::CircuitBreaker
+            {
+                summary.trips += 1;
+            }
+        }
+        summary.trauma_ticks = self.trauma_at().count();
+        if summary.duration_ms > 0.0 {
+            summary.breaker_fraction = open_ms / summary.duration_ms;
+        }
+        summary
+    }
+
+    /// Standalone SVG document
+    pub fn to_svg(&self) -> String {
+        let panels = 3.0;
+        let height = TOP + panels * (PANEL_HEIGHT + PANEL_GAP);
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" \
+             viewBox=\"0 0 {WIDTH} {height}\"><style>{STYLE}</style>"
+        );
+        let _ = write!(
+            svg,
+            "<text class=\"title\" x=\"{LEFT}\" y=\"16\">{}</text>",
+            escape(&self.title)
+        );
+        if self.samples.is_empty() {
+            let _ = write!(
+                svg,
+                "<text x=\"{LEFT}\" y=\"{}\">no samples</text>",
+                TOP + 20.0
+            );
+            svg.push_str("</svg>");
+            return svg;
+        }
+
+        let summary = self.summary();
+        let resistance_max = self
+            .thresholds
+            .map_or(summary.peak_resistance, |(brk, _)| {
+                summary.peak_resistance.max(brk)
+            })
+            .max(1.0)
+            * 1.05;
+        let pressure_max = self
+            .samples
+            .iter()
+            .flat_map(|s| [s.pressure.latency, s.pressure.error, s.pressure.saturation])
+            .fold(1.0f64, f64::max);
+
+        let resistance = self.panel(&mut svg, 0, "Resistance (Ω)", resistance_max);
+        if let Some((brk, recovery)) = self.thresholds {
+            for threshold in [brk, recovery] {
+                let y = resistance.y(threshold);
+                let _ = write!(
+                    svg,
+                    "<line class=\"threshold\" x1=\"{LEFT}\" x2=\"{:.1}\" y1=\"{y:.1}\" y2=\"{y:.1}\"/>",
+                    WIDTH - RIGHT
+                );
+            }
+        }
+        self.series(&mut svg, &resistance, "resistance", |s| s.resistance.0);
+
+        let scar = self.panel(&mut svg, 1, "Scar", summary.peak_scar.max(1.0) * 1.05);
+        self.series(&mut svg, &scar, "scar", |s| s.scar.0);
+        for at_ms in self.trauma_at() {
+            let x = self.x(at_ms);
+            let _ = write!(
+                svg,
+                "<line class=\"trauma\" x1=\"{x:.1}\" x2=\"{x:.1}\" y1=\"{:.1}\" y2=\"{:.1}\"/>",
+                scar.top,
+                scar.top + 6.0
+            );
+        }
+
+        let pressure = self.panel(&mut svg, 2, "Pressure", pressure_max);
+        self.series(&mut svg, &pressure, "latency", |s| s.pressure.latency);
+        self.series(&mut svg, &pressure, "error", |s| s.pressure.error);
+        self.series(&mut svg, &pressure, "saturation", |s| s.pressure.saturation);
+
+        let bottom = pressure.top + PANEL_HEIGHT;
+        for marker in &self.markers {
+            let x = self.x(marker.timestamp_ms);
+            let _ = write!(
+                svg,
+                "<line class=\"marker\" x1=\"{x:.1}\" x2=\"{x:.1}\" y1=\"{TOP}\" y2=\"{bottom:.1}\">\
+                 <title>{:?} at {}</title></line>",
+                marker.kind,
+                self.format_time(marker.timestamp_ms)
+            );
+        }
+        for tick in 0..=X_TICKS {
+            let at_ms = self.start_ms() + self.span_ms() * tick as f64 / X_TICKS as f64;
+            let _ = write!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+                self.x(at_ms),
+                bottom + 14.0,
+                self.format_time(at_ms)
+            );
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Self-contained HTML page with the summary and the chart
+    pub fn to_html(&self) -> String {
+        let summary = self.summary();
+        let title = escape(&self.title);
+        format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title>\
+             <style>body{{font:14px sans-serif;margin:24px}}td{{padding:2px 12px 2px 0}}</style>\
+             </head><body><h1>{title}</h1><table>\
+             <tr><td>Samples</td><td>{}</td></tr>\
+             <tr><td>Duration</td><td>{:.1} s</td></tr>\
+             <tr><td>Peak resistance</td><td>{:.2} Ω</td></tr>\
+             <tr><td>Peak scar</td><td>{:.2}</td></tr>\
+             <tr><td>Breaker trips</td><td>{}</td></tr>\
+             <tr><td>Breaker open</td><td>{:.1}%</td></tr>\
+             <tr><td>Trauma ticks</td><td>{}</td></tr>\
+             </table>{}</body></html>\n",
+            summary.samples,
+            summary.duration_ms / 1000.0,
+            summary.peak_resistance,
+            summary.peak_scar,
+            summary.trips,
+            summary.breaker_fraction * 100.0,
+            summary.trauma_ticks,
+            self.to_svg()
+        )
+    }
+
+    pub fn write_html(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_html())
+    }
+
+    /// Draw a panel frame with its mode bands; returns its y scale
+    fn panel(&self, svg: &mut String, index: usize, label: &str, max: f64) -> Panel {
+        let panel = Panel {
+            top: TOP + index as f64 * (PANEL_HEIGHT + PANEL_GAP) + PANEL_GAP / 2.0,
+            max,
+        };
+        let _ = write!(svg, "<g class=\"panel\">");
+        for (mode, start_ms, end_ms) in self.mode_spans() {
+            let class = match mode {
+                OperationalMode::CircuitBreaker => "band-breaker",
+                OperationalMode::Bootstrap => "band-bootstrap",
+                OperationalMode::Operational => continue,
+            };
+            let (x0, x1) = (self.x(start_ms), self.x(end_ms));
+            let _ = write!(
+                svg,
+                "<rect class=\"{class}\" x=\"{x0:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{PANEL_HEIGHT}\"/>",
+                panel.top,
+                (x1 - x0).max(1.0)
+            );
+        }
+        let _ = write!(
+            svg,
+            "<rect class=\"frame\" x=\"{LEFT}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{PANEL_HEIGHT}\"/>\
+             <text class=\"title\" x=\"{LEFT}\" y=\"{:.1}\">{}</text>\
+             <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\
+             <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">0</text></g>",
+            panel.top,
+            WIDTH - LEFT - RIGHT,
+            panel.top - 4.0,
+            escape(label),
+            LEFT - 4.0,
+            panel.top + 10.0,
+            format_value(max),
+            LEFT - 4.0,
+            panel.top + PANEL_HEIGHT,
+        );
+        panel
+    }
+
+    fn series(
+        &self,
+        svg: &mut String,
+        panel: &Panel,
+        class: &str,
+        value: impl Fn(&HistorySample) -> f64,
+    ) {
+        let _ = write!(svg, "<polyline class=\"series {class}\" points=\"");
+        for sample in &self.samples {
+            let _ = write!(
+                svg,
+                "{:.1},{:.1} ",
+                self.x(sample.timestamp_ms),
+                panel.y(value(sample))
+            );
+        }
+        svg.push_str("\"/>");
+    }
+
+    /// `(mode, start, end)` for each run of samples in the same mode
+    fn mode_spans(&self) -> Vec<(OperationalMode, f64, f64)> {
+        let mut spans: Vec<(OperationalMode, f64, f64)> = Vec::new();
+        for sample in &self.samples {
+            match spans.last_mut() {
+                Some(span) if span.0 == sample.mode => span.2 = sample.timestamp_ms,
+                Some(span) => {
+                    span.2 = sample.timestamp_ms;
+                    spans.push((sample.mode, sample.timestamp_ms, sample.timestamp_ms));
+                }
+                None => spans.push((sample.mode, sample.timestamp_ms, sample.timestamp_ms)),
+            }
+        }
+        spans
+    }
+
+    /// Timestamps of samples on which scar grew
+    fn trauma_at(&self) -> impl Iterator<Item = f64> + '_ {
+        self.samples
+            .windows(2)
+            .filter(|pair| pair[1].scar.0 > pair[0].scar.0 + 1e-9)
+            .map(|pair| pair[1].timestamp_ms)
+    }
+
+    fn start_ms(&self) -> f64 {
+        self.samples.first().map_or(0.0, |s| s.timestamp_ms)
+    }
+
+    fn span_ms(&self) -> f64 {
+        let end_ms = self.samples.last().map_or(0.0, |s| s.timestamp_ms);
+        (end_ms - self.start_ms()).max(1.0)
+    }
+
+    fn x(&self, at_ms: f64) -> f64 {
+        let fraction = ((at_ms - self.start_ms()) / self.span_ms()).clamp(0.0, 1.0);
+        LEFT + fraction * (WIDTH - LEFT - RIGHT)
+    }
+
+    /// Time since the first sample
+    fn format_time(&self, at_ms: f64) -> String {
+        let seconds = (at_ms - self.start_ms()) / 1000.0;
+        if self.span_ms() < 10_000.0 {
+            format!("{seconds:.1}s")
+        } else {
+            format!("{seconds:.0}s")
+        }
+    }
+}
+
+/// Vertical placement of one panel
+struct Panel {
+    top: f64,
+    max: f64,
+}
+
+impl Panel {
+    fn y(&self, value: f64) -> f64 {
+        let fraction = (value / self.max).clamp(0.0, 1.0);
+        self.top + PANEL_HEIGHT * (1.0 - fraction)
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value >= 100.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markers::MarkerKind;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    fn breaker_run() -> TargetEngine {
+        let config = PhysicsConfig {
+            history_capacity: 1_000,
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        let mut now = 0.0;
+        for i in 0..600 {
+            now += 100.0;
+            if i == 100 {
+                engine.mark_event(MarkerKind::Deploy, now);
+            }
+            let p = if (100..150).contains(&i) { 1.0 } else { 0.0 };
+            engine.tick(now, PressureVector::new(p, p, p));
+        }
+        engine
+    }
+
+    #[test]
+    fn test_svg_shows_bands_trauma_and_markers() {
+        let report = RunReport::from_engine("canary", &breaker_run());
+        let summary = report.summary();
+        assert_eq!(summary.samples, 600);
+        assert_eq!(summary.trips, 1);
+        assert!(summary.trauma_ticks > 0);
+        assert!(summary.breaker_fraction > 0.0 && summary.breaker_fraction < 1.0);
+
+        let svg = report.to_svg();
+        assert_eq!(svg.matches("class=\"panel\"").count(), 3);
+        assert_eq!(svg.matches("<polyline").count(), 5);
+        assert!(svg.contains("band-breaker"));
+        assert!(svg.contains("band-bootstrap"));
+        assert!(svg.contains("class=\"trauma\""));
+        assert!(svg.contains("<title>Deploy at 10s</title>"));
+        assert_eq!(svg.matches("class=\"threshold\"").count(), 2);
+    }
+
+    #[test]
+    fn test_html_is_self_contained_and_escaped() {
+        let engine = breaker_run();
+        let report = RunReport::from_history("p99 <spike> & \"retry\"", engine.history());
+        let html = report.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>p99 &lt;spike&gt; &amp; &quot;retry&quot;</h1>"));
+        assert!(!html.contains("<script") && !html.contains("src=") && !html.contains("href="));
+        assert!(!html.contains("class=\"threshold\""));
+
+        let empty = RunReport::from_history("empty", &History::new(8));
+        assert!(empty.to_svg().contains("no samples"));
+        assert_eq!(empty.summary(), ReportSummary::default());
+    }
+}
@@ -2,10 +2,80 @@
  * Resistance calculation (Ohm's Law for traffic).
  *
  * R(t) = R_base + P·W + μ||M|| + S + U
+ *
+ * The pressure term depends on `PhysicsConfig::formula`: v1 is linear,
+ * v2 multiplies each component's excess over the knee by `KNEE_SLOPE`.
+ * `PhysicsConfig::term_caps` bounds individual terms so one runaway axis
+ * cannot dominate the total.
  */
-use crate::types::{Momentum, Ohms, PhysicsConfig, PressureVector, Scar, SensitivityWeights};
+use serde::{Deserialize, Serialize};
+
+use crate::fastmath;
+use crate::scar::CRITICAL_PRESSURE;
+use crate::types::{
+    FormulaRevision, Momentum, Ohms, PhysicsConfig, PressureVector, Scar, SensitivityWeights,
+};
 use crate::vector;
 
+/// Component pressure where the v2 curve bends (frozen)
+pub const KNEE: f64 = CRITICAL_PRESSURE;
+
+/// v2 slope above the knee, relative to the linear slope below it (frozen)
+pub const KNEE_SLOPE: f64 = 3.0;
+
+/// Shape one pressure component under the given formula revision
+#[inline]
+pub fn shape(revision: FormulaRevision, component: f64) -> f64 {
+    match revision {
+        FormulaRevision::V1 => component,
+        FormulaRevision::V2 if component > KNEE => KNEE + (component - KNEE) * KNEE_SLOPE,
+        FormulaRevision::V2 => component,
+    }
+}
+
+/// Weighted `[latency, error, saturation]` terms of P·W
+#[inline]
+pub fn weighted_terms(
+    pressure: &PressureVector,
+    weights: &SensitivityWeights,
+    revision: FormulaRevision,
+) -> [f64; 3] {
+    [
+        shape(revision, pressure.latency) * weights.w_latency,
+        shape(revision, pressure.error) * weights.w_error,
+        shape(revision, pressure.saturation) * weights.w_saturation,
+    ]
+}
+
+/// Upper bounds on single resistance terms, in Ohms (`None` = uncapped)
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct TermCaps {
+    /// Weighted latency term
+    pub latency: Option<f64>,
+    /// Weighted error term
+    pub error: Option<f64>,
+    /// Weighted saturation term
+    pub saturation: Option<f64>,
+    /// damping × momentum
+    pub momentum: Option<f64>,
+    /// Effective scar (fast and slow combined under the dual model)
+    pub scar: Option<f64>,
+    /// Staleness penalty for telemetry gaps (e.g. 20.0 = at most 20 Ω)
+    pub staleness: Option<f64>,
+}
+
+/// `value` limited to `cap`
+#[inline]
+pub fn cap(value: f64, cap: Option<f64>) -> f64 {
+    match cap {
+        Some(cap) if value > cap => cap,
+        _ => value,
+    }
+}
+
 /// Calculate instantaneous resistance
 ///
 /// Formula: R = R_base + (P · W) + damping×momentum + scar + staleness
@@ -16,7 +86,14 @@ use crate::vector;
 /// - damping: Momentum damping factor
 /// - S: Accumulated scar tissue
 /// - U: Staleness penalty
-#[inline]
+///
+/// With `term_caps` set, each term is limited before summing.
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", name = "atrion.calculate_resistance", skip_all)
+)]
 pub fn calculate_resistance(
     pressure: &PressureVector,
     momentum: Momentum,
@@ -25,14 +102,135 @@ pub fn calculate_resistance(
     config: &PhysicsConfig,
     staleness: f64,
 ) -> Ohms {
-    let weighted_pressure = vector::dot_product(pressure, weights);
+    if config.term_caps.is_some() {
+        return ResistanceLane::new(pressure, momentum, scar, weights, config, staleness)
+            .resistance();
+    }
+    let weighted_pressure = match config.formula {
+        FormulaRevision::V1 => vector::dot_product(pressure, weights),
+        revision => weighted_terms(pressure, weights, revision).iter().sum(),
+    };
     let momentum_contribution = config.damping_factor * momentum.0;
 
     let total =
         config.base_resistance + weighted_pressure + momentum_contribution + scar.0 + staleness;
 
     // Enforce minimum resistance
-    Ohms(total.max(config.base_resistance))
+    Ohms(fastmath::max(total, config.base_resistance))
+}
+
+// ============================================================================
+// BATCH KERNELS
+// ============================================================================
+
+/// One `calculate_resistance` call flattened for the batch kernels
+///
+/// Pressure is stored already shaped by the formula revision, so every
+/// lane runs the same straight-line arithmetic whatever its config.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct ResistanceLane {
+    pub shaped: [f64; 3],
+    pub weights: [f64; 3],
+    pub momentum: f64,
+    pub scar: f64,
+    pub staleness: f64,
+    pub base_resistance: f64,
+    pub damping_factor: f64,
+}
+
+impl ResistanceLane {
+    pub fn new(
+        pressure: &PressureVector,
+        momentum: Momentum,
+        scar: Scar,
+        weights: &SensitivityWeights,
+        config: &PhysicsConfig,
+        staleness: f64,
+    ) -> Self {
+        let lane = Self {
+            shaped: [
+                shape(config.formula, pressure.latency),
+                shape(config.formula, pressure.error),
+                shape(config.formula, pressure.saturation),
+            ],
+            weights: [weights.w_latency, weights.w_error, weights.w_saturation],
+            momentum: momentum.0,
+            scar: scar.0,
+            staleness,
+            base_resistance: config.base_resistance,
+            damping_factor: config.damping_factor,
+        };
+        match &config.term_caps {
+            Some(caps) => lane.capped(caps),
+            None => lane,
+        }
+    }
+
+    /// Fold the caps in: terms are capped up front and their multipliers
+    /// set to 1, so the kernels stay branch-free (`x * 1.0` is exact)
+    fn capped(self, caps: &TermCaps) -> Self {
+        let [l, e, s] = self.shaped;
+        let [wl, we, ws] = self.weights;
+        Self {
+            shaped: [
+                cap(l * wl, caps.latency),
+                cap(e * we, caps.error),
+                cap(s * ws, caps.saturation),
+            ],
+            weights: [1.0; 3],
+            momentum: cap(self.damping_factor * self.momentum, caps.momentum),
+            scar: cap(self.scar, caps.scar),
+            staleness: cap(self.staleness, caps.staleness),
+            damping_factor: 1.0,
+            ..self
+        }
+    }
+
+    /// Same result, bit for bit, as `calculate_resistance` on the inputs
+    #[inline(always)]
+    pub fn resistance(&self) -> Ohms {
+        let [l, e, s] = self.shaped;
+        let [wl, we, ws] = self.weights;
+        let weighted_pressure = l * wl + e * we + s * ws;
+        let total = self.base_resistance
+            + weighted_pressure
+            + self.damping_factor * self.momentum
+            + self.scar
+            + self.staleness;
+        Ohms(fastmath::max(total, self.base_resistance))
+    }
+}
+
+/// Resistance of every lane (dynamic batch size)
+pub fn calculate_resistance_batch(lanes: &[ResistanceLane], out: &mut [Ohms]) {
+    for (r, lane) in out.iter_mut().zip(lanes) {
+        *r = lane.resistance();
+    }
+}
+
+/// Resistance of a fixed-size batch
+///
+/// Not measurably faster than `calculate_resistance_batch` (see
+/// `resistance_batch` in the benches), which the pool and GPU fallback use.
+#[inline]
+pub fn calculate_resistance_n<const N: usize>(lanes: &[ResistanceLane; N]) -> [Ohms; N] {
+    std::array::from_fn(|i| lanes[i].resistance())
+}
+
+/// Lanes per fixed-size kernel call in `calculate_resistance_chunked`
+pub const BATCH_LANES: usize = 8;
+
+/// `calculate_resistance_n::<BATCH_LANES>` over whole chunks, the dynamic
+/// path for the remainder (`out` must be as long as `lanes`)
+pub fn calculate_resistance_chunked(lanes: &[ResistanceLane], out: &mut [Ohms]) {
+    debug_assert_eq!(lanes.len(), out.len());
+    let mut lane_chunks = lanes.chunks_exact(BATCH_LANES);
+    let mut out_chunks = out.chunks_exact_mut(BATCH_LANES);
+    for (lanes, out) in (&mut lane_chunks).zip(&mut out_chunks) {
+        let lanes = lanes.try_into().expect("exact chunk");
+        out.copy_from_slice(&calculate_resistance_n::<BATCH_LANES>(lanes));
+    }
+    calculate_resistance_batch(lane_chunks.remainder(), out_chunks.into_remainder());
 }
 
 #[cfg(test)]
@@ -71,4 +269,76 @@ mod tests {
 
         assert!((r.0 - (config.base_resistance + 10.0)).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_v2_knee() {
+        let weights = SensitivityWeights::new(1.0, 1.0, 1.0);
+        let v1 = PhysicsConfig::default();
+        let v2 = PhysicsConfig {
+            formula: FormulaRevision::V2,
+            ..PhysicsConfig::default()
+        };
+        let r = |pressure: PressureVector, config: &PhysicsConfig| {
+            calculate_resistance(&pressure, Momentum(0.0), Scar(0.0), &weights, config, 0.0).0
+        };
+
+        // Identical below the knee
+        let calm = PressureVector::new(0.5, 0.2, 0.0);
+        assert_eq!(r(calm, &v1), r(calm, &v2));
+
+        // Excess over the knee counts KNEE_SLOPE times
+        let hot = PressureVector::new(0.9, 0.0, 0.0);
+        assert!((r(hot, &v1) - 10.9).abs() < 1e-10);
+        assert!((r(hot, &v2) - (10.0 + KNEE + 0.2 * KNEE_SLOPE)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_batch_kernels_match_scalar_bitwise() {
+        let weights = SensitivityWeights::default();
+        let configs = [
+            PhysicsConfig::default(),
+            PhysicsConfig {
+                formula: FormulaRevision::V2,
+                damping_factor: 3.5,
+                ..PhysicsConfig::default()
+            },
+            PhysicsConfig {
+                term_caps: Some(TermCaps {
+                    latency: Some(4.0),
+                    momentum: Some(1.0),
+                    scar: Some(25.0),
+                    ..TermCaps::default()
+                }),
+                ..PhysicsConfig::default()
+            },
+        ];
+        let inputs: Vec<_> = (0..11)
+            .map(|i| {
+                let f = i as f64 / 10.0;
+                let pressure = PressureVector::new(f, 0.9 - f * 0.7, f * f - 0.3);
+                (pressure, Momentum(0.3 - f), Scar(f * 7.0), &configs[i % 3])
+            })
+            .collect();
+        let scalar: Vec<Ohms> = inputs
+            .iter()
+            .map(|(p, m, s, c)| calculate_resistance(p, *m, *s, &weights, c, 0.25))
+            .collect();
+        let lanes: Vec<ResistanceLane> = inputs
+            .iter()
+            .map(|(p, m, s, c)| ResistanceLane::new(p, *m, *s, &weights, c, 0.25))
+            .collect();
+
+        let mut dynamic = vec![Ohms(0.0); lanes.len()];
+        calculate_resistance_batch(&lanes, &mut dynamic);
+        let fixed = calculate_resistance_n::<8>(lanes[..8].try_into().unwrap());
+        let mut chunked = vec![Ohms(0.0); lanes.len()];
+        calculate_resistance_chunked(&lanes, &mut chunked);
+        for i in 0..lanes.len() {
+            assert_eq!(dynamic[i].0.to_bits(), scalar[i].0.to_bits());
+            assert_eq!(chunked[i].0.to_bits(), scalar[i].0.to_bits());
+        }
+        for i in 0..8 {
+            assert_eq!(fixed[i].0.to_bits(), scalar[i].0.to_bits());
+        }
+    }
 }
@@ -0,0 +1,273 @@
+/**
+ * Retry advice for shed requests.
+ *
+ * Estimates how long a rejected caller should wait before retrying, so
+ * HTTP adapters can send a meaningful `Retry-After` instead of a fixed
+ * constant. The estimate assumes pressure subsides and only scar decay
+ * (S · e^(-λt)) brings resistance back down; it is a lower bound on a
+ * quiet upstream, clamped to a sane range.
+ *
+ * `Rejection` packages a denied admission for HTTP layers: a typed
+ * `Denial` (breaker, shed with its probability, deadline, quarantine),
+ * the matching status code and the forecast retry-after.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::admission::RejectReason;
+use crate::engine::TargetState;
+use crate::scar::SCAR_DECAY_RATE;
+use crate::types::{OperationalMode, PhysicsConfig};
+
+/// Shortest advised wait (Retry-After has one-second granularity)
+pub const MIN_RETRY_MS: f64 = 1_000.0;
+
+/// Longest advised wait
+pub const MAX_RETRY_MS: f64 = 300_000.0;
+
+/// When a shed caller should come back, and why it was shed
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryAdvice {
+    #[serde(alias = "retry_after_ms")]
+    pub retry_after_ms: f64,
+    pub reason: RejectReason,
+}
+
+impl RetryAdvice {
+    /// Whole seconds for an HTTP `Retry-After` header (rounded up)
+    pub fn retry_after_secs(&self) -> u64 {
+        (self.retry_after_ms / 1000.0).ceil() as u64
+    }
+}
+
+/// Advice for a target, or `None` if it is below `shed_threshold` and closed
+///
+/// With the breaker open, the wait is the earlier of the two TS recovery
+/// conditions (scar below `scar_factor`, resistance below
+/// `recovery_threshold`). Otherwise it is the time for resistance to fall
+/// back under `shed_threshold`.
+pub fn retry_advice(
+    state: &TargetState,
+    config: &PhysicsConfig,
+    shed_threshold: f64,
+) -> Option<RetryAdvice> {
+    let scar = state.scar.0;
+    let scar_free = state.resistance.0 - scar;
+
+    let (wait_ms, reason) = if state.mode == OperationalMode::CircuitBreaker {
+        let via_scar = scar_decay_ms(scar, config.scar_factor);
+        let via_resistance = scar_decay_ms(scar, config.recovery_threshold - scar_free);
+        (via_scar.min(via_resistance), RejectReason::CircuitOpen)
+    } else if state.resistance.0 >= shed_threshold {
+        (
+            scar_decay_ms(scar, shed_threshold - scar_free),
+            RejectReason::InsufficientVoltage,
+        )
+    } else {
+        return None;
+    };
+
+    Some(RetryAdvice {
+        retry_after_ms: wait_ms.clamp(MIN_RETRY_MS, MAX_RETRY_MS),
+        reason,
+    })
+}
+
+/// What kind of denial an admission check produced
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all_fields = "camelCase")]
+pub enum Denial {
+    /// Breaker open
+    Breaker,
+    /// Shed by the resistance gate or one of the budget layers;
+    /// `probability` is the share of offered load currently shed
+    Shed { probability: f64 },
+    /// Predicted queueing plus service overruns the request deadline
+    Deadline {
+        #[serde(alias = "predicted_ms")]
+        predicted_ms: f64,
+        #[serde(alias = "remaining_ms")]
+        remaining_ms: f64,
+    },
+    /// Target quarantined by the pool
+    Quarantine,
+}
+
+/// A denied admission with everything an HTTP response needs
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rejection {
+    pub denial: Denial,
+    /// The underlying gate's reason (metric tags, logs)
+    pub reason: RejectReason,
+    #[serde(alias = "retry_after_ms")]
+    pub retry_after_ms: f64,
+}
+
+impl Rejection {
+    /// Whole seconds for an HTTP `Retry-After` header (rounded up)
+    pub fn retry_after_secs(&self) -> u64 {
+        (self.retry_after_ms / 1000.0).ceil() as u64
+    }
+
+    /// 503 when the target itself is unavailable, 429 when load was shed
+    pub fn status_code(&self) -> u16 {
+        match self.denial {
+            Denial::Breaker | Denial::Quarantine => 503,
+            Denial::Shed { .. } | Denial::Deadline { .. } => 429,
+        }
+    }
+}
+
+/// Rejection for `reason` against a target in `state`
+///
+/// `voltage` is the denied request's voltage: a shed caller may retry once
+/// resistance is forecast to fall below it. `shed_probability` and
+/// `deadline` (`(predicted_ms, remaining_ms)`) fill in the `Shed` and
+/// `Deadline` denials.
+pub fn rejection(
+    reason: RejectReason,
+    state: &TargetState,
+    config: &PhysicsConfig,
+    voltage: f64,
+    shed_probability: f64,
+    deadline: Option<(f64, f64)>,
+) -> Rejection {
+    let forecast = |threshold: f64| {
+        let scar = state.scar.0;
+        scar_decay_ms(scar, threshold - (state.resistance.0 - scar))
+    };
+    let (denial, wait_ms) = match reason {
+        RejectReason::CircuitOpen => {
+            let advice = retry_advice(state, config, f64::INFINITY);
+            (
+                Denial::Breaker,
+                advice.map_or(MIN_RETRY_MS, |a| a.retry_after_ms),
+            )
+        }
+        RejectReason::Quarantined => (Denial::Quarantine, MAX_RETRY_MS),
+        RejectReason::DeadlineUnmeetable => {
+            let (predicted_ms, remaining_ms) = deadline.unwrap_or((0.0, 0.0));
+            let denial = Denial::Deadline {
+                predicted_ms,
+                remaining_ms,
+            };
+            // Let the queue ahead drain before coming back
+            (denial, predicted_ms)
+        }
+        RejectReason::InsufficientVoltage => (
+            Denial::Shed {
+                probability: shed_probability,
+            },
+            forecast(voltage),
+        ),
+        RejectReason::FairShareExceeded
+        | RejectReason::PriorityShed
+        | RejectReason::BudgetExhausted => (
+            Denial::Shed {
+                probability: shed_probability,
+            },
+            forecast(config.recovery_threshold),
+        ),
+    };
+    Rejection {
+        denial,
+        reason,
+        retry_after_ms: wait_ms.clamp(MIN_RETRY_MS, MAX_RETRY_MS),
+    }
+}
+
+/// Time for scar to decay from `from` to strictly below `to`
+fn scar_decay_ms(from: f64, to: f64) -> f64 {
+    if from < to {
+        0.0
+    } else if to <= 0.0 {
+        f64::INFINITY
+    } else {
+        (from / to).ln() / SCAR_DECAY_RATE * 1000.0
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Ohms, Scar};
+
+    fn state(mode: OperationalMode, resistance: f64, scar: f64) -> TargetState {
+        let mut state = TargetState::bootstrap(&PhysicsConfig::default());
+        state.mode = mode;
+        state.resistance = Ohms(resistance);
+        state.scar = Scar(scar);
+        state
+    }
+
+    #[test]
+    fn test_no_advice_below_threshold() {
+        let config = PhysicsConfig::default();
+        let healthy = state(OperationalMode::Operational, 40.0, 0.0);
+        assert_eq!(retry_advice(&healthy, &config, 75.0), None);
+    }
+
+    #[test]
+    fn test_advice_follows_scar_decay() {
+        let config = PhysicsConfig::default();
+        // 80Ω with 40 scar: scar must decay to below 35 → ln(40/35)/0.1 ≈ 1.34s
+        let shedding = state(OperationalMode::Operational, 80.0, 40.0);
+        let advice = retry_advice(&shedding, &config, 75.0).unwrap();
+        assert!((advice.retry_after_ms - (40.0f64 / 35.0).ln() * 10_000.0).abs() < 1e-6);
+        assert_eq!(advice.retry_after_secs(), 2);
+        assert_eq!(advice.reason, RejectReason::InsufficientVoltage);
+
+        // Scar-free resistance alone exceeds threshold: clamp to max
+        let stuck = state(OperationalMode::Operational, 90.0, 0.0);
+        assert_eq!(
+            retry_advice(&stuck, &config, 75.0).unwrap().retry_after_ms,
+            MAX_RETRY_MS
+        );
+    }
+
+    #[test]
+    fn test_rejection_types_and_status() {
+        let config = PhysicsConfig::default();
+        let shedding = state(OperationalMode::Operational, 80.0, 40.0);
+        let shed = rejection(
+            RejectReason::InsufficientVoltage,
+            &shedding,
+            &config,
+            75.0,
+            0.6,
+            None,
+        );
+        assert_eq!(shed.denial, Denial::Shed { probability: 0.6 });
+        assert_eq!(shed.status_code(), 429);
+        assert!((shed.retry_after_ms - (40.0f64 / 35.0).ln() * 10_000.0).abs() < 1e-6);
+
+        let late = rejection(
+            RejectReason::DeadlineUnmeetable,
+            &shedding,
+            &config,
+            75.0,
+            0.6,
+            Some((4_000.0, 500.0)),
+        );
+        assert_eq!(late.retry_after_secs(), 4);
+        let open = state(OperationalMode::CircuitBreaker, 120.0, 50.0);
+        let breaker = rejection(RejectReason::CircuitOpen, &open, &config, 75.0, 1.0, None);
+        assert_eq!(breaker.status_code(), 503);
+        assert!((breaker.retry_after_ms - 10f64.ln() * 10_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_open_circuit_uses_earliest_recovery() {
+        let config = PhysicsConfig::default();
+        // Scar 50 → below scar_factor (5) takes ln(10)/0.1 ≈ 23s
+        let open = state(OperationalMode::CircuitBreaker, 120.0, 50.0);
+        let advice = retry_advice(&open, &config, 75.0).unwrap();
+        assert_eq!(advice.reason, RejectReason::CircuitOpen);
+        assert!((advice.retry_after_ms - 10f64.ln() * 10_000.0).abs() < 1e-6);
+    }
+}
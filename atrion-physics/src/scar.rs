@@ -6,10 +6,33 @@
  *
  * MUST match src/core/physics.ts updateScar() exactly:
  * S(t) = S(t-1) · e^(-λΔt) + σ · I(||P+|| > P_crit)
+ *
+ * The threshold test is `>` by default; `PhysicsConfig::critical_comparison`
+ * selects `>=`. Scar outputs are never -0.0 (see `vector::canonical_zero`).
  */
-use crate::types::{PhysicsConfig, PressureVector, Scar, SensitivityWeights};
+use serde::{Deserialize, Serialize};
+
+use crate::fastmath;
+use crate::types::{
+    PhysicsConfig, PressureVector, Scar, ScarPrecision, SensitivityWeights, ThresholdComparison,
+};
 use crate::vector;
 
+/// Positive stress magnitude above which trauma is recorded (TS: criticalPressure)
+pub const CRITICAL_PRESSURE: f64 = 0.7;
+
+/// Exponential scar decay rate per second (TS: decayRate)
+pub const SCAR_DECAY_RATE: f64 = 0.1;
+
+/// Whether a positive stress magnitude counts as trauma
+#[inline]
+pub fn is_critical(positive_stress: f64, comparison: ThresholdComparison) -> bool {
+    match comparison {
+        ThresholdComparison::Exclusive => positive_stress > CRITICAL_PRESSURE,
+        ThresholdComparison::Inclusive => positive_stress >= CRITICAL_PRESSURE,
+    }
+}
+
 /// Update scar tissue based on current pressure
 ///
 /// Formula (matches TypeScript):
@@ -32,8 +55,7 @@ pub fn update_scar(
 
     // Only add trauma if positive stress exceeds critical threshold
     // This matches TS: trauma = positiveStressMagnitude > criticalPressure ? scarFactor : 0
-    let trauma = if positive_stress > 0.7 {
-        // criticalPressure = 0.7 in TS
+    let trauma = if is_critical(positive_stress, config.critical_comparison) {
         config.scar_factor
     } else {
         0.0
@@ -41,7 +63,7 @@ pub fn update_scar(
 
     // Note: Decay is handled separately in the main physics loop
     // This matches TS which handles decay in updateScar function
-    Scar(current_scar.0 + trauma)
+    Scar(vector::canonical_zero(current_scar.0 + trauma))
 }
 
 /// Update scar with decay (full TS parity)
@@ -56,23 +78,251 @@ pub fn update_scar_with_decay(
     delta_t_ms: f64,
     config: &PhysicsConfig,
 ) -> Scar {
+    update_scar_weighted(current_scar, pressure, delta_t_ms, config, 1.0)
+}
+
+/// Update scar with trauma scaled by sample confidence in [0, 1]
+///
+/// Decay is unaffected: time passes regardless of how much was observed.
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", name = "atrion.scar", skip_all)
+)]
+pub fn update_scar_weighted(
+    current_scar: Scar,
+    pressure: &PressureVector,
+    delta_t_ms: f64,
+    config: &PhysicsConfig,
+    confidence: f64,
+) -> Scar {
+    if config.scar_precision == ScarPrecision::Fixed {
+        return update_scar_fixed(current_scar, pressure, delta_t_ms, config, confidence);
+    }
+    update_scar_decayed(
+        current_scar,
+        pressure,
+        scar_decay(delta_t_ms),
+        config,
+        confidence,
+    )
+}
+
+/// Scar decay factor e^(-decay_rate * dt) over `delta_t_ms`
+#[inline]
+pub fn scar_decay(delta_t_ms: f64) -> f64 {
     let dt_seconds = delta_t_ms / 1000.0;
+    fastmath::exp(-SCAR_DECAY_RATE * dt_seconds)
+}
 
+/// `update_scar_weighted` with a precomputed `scar_decay` factor
+/// (float precision only)
+#[inline]
+pub fn update_scar_decayed(
+    current_scar: Scar,
+    pressure: &PressureVector,
+    decay: f64,
+    config: &PhysicsConfig,
+    confidence: f64,
+) -> Scar {
     // Exponential decay: S * e^(-decay_rate * dt)
-    let decay_rate = 0.1; // Matches TS: decayRate: 0.1
-    let decayed = current_scar.0 * (-decay_rate * dt_seconds).exp();
+    let decayed = current_scar.0 * decay;
 
     // Check Valve: Only positive pressure causes trauma
     let positive_stress = vector::positive_stress_magnitude(pressure);
 
     // Trauma if stress > critical_pressure (0.7)
-    let trauma = if positive_stress > 0.7 {
+    let trauma = if is_critical(positive_stress, config.critical_comparison) {
         config.scar_factor
     } else {
         0.0
     };
 
-    Scar((decayed + trauma).max(0.0))
+    Scar(vector::canonical_zero(
+        (decayed + trauma * confidence.clamp(0.0, 1.0)).max(0.0),
+    ))
+}
+
+// ============================================================================
+// FIXED-POINT ACCOUNTING (Rust-only)
+// ============================================================================
+
+/// Scar units per 1.0 of scar (nano-scar resolution)
+pub const FIXED_SCAR_SCALE: f64 = 1e9;
+
+/// Scale of the decimal decay factors
+const DECAY_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// `DECAY_TABLE[k]` = e^(-SCAR_DECAY_RATE · 2^k µs) × 10^18, rounded half-up.
+/// Entries past the end round to zero.
+const DECAY_TABLE: [u128; 29] = [
+    999_999_900_000_005_000,
+    999_999_800_000_020_000,
+    999_999_600_000_080_000,
+    999_999_200_000_320_000,
+    999_998_400_001_279_999,
+    999_996_800_005_119_995,
+    999_993_600_020_479_956,
+    999_987_200_081_919_650,
+    999_974_400_327_677_204,
+    999_948_801_310_697_631,
+    999_897_605_242_701_048,
+    999_795_220_970_088_418,
+    999_590_483_874_627_927,
+    999_181_135_452_712_793,
+    998_362_941_444_572_390,
+    996_728_562_849_858_678,
+    993_467_828_000_744_681,
+    986_978_325_272_517_218,
+    974_126_214_557_742_800,
+    948_921_881_888_597_560,
+    900_452_737_926_997_497,
+    810_815_133_240_226_037,
+    657_421_180_291_365_502,
+    432_202_608_295_692_104,
+    186_799_094_617_599_461,
+    34_893_901_749_954_876,
+    1_217_584_379_335_504,
+    1_482_511_720_802,
+    2_197_841,
+];
+
+/// e^(-SCAR_DECAY_RATE · µs) × 10^18 as a product of table entries
+fn decay_factor(micros: u64) -> u128 {
+    if micros >> DECAY_TABLE.len() != 0 {
+        return 0;
+    }
+    DECAY_TABLE
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| micros & (1 << bit) != 0)
+        .fold(DECAY_ONE, |factor, (_, step)| {
+            // Decimal product, rounded half-up
+            (factor * step + DECAY_ONE / 2) / DECAY_ONE
+        })
+}
+
+/// Update scar in fixed-scale integer arithmetic
+///
+/// Scar is held as whole nano-units: Δt is rounded to microseconds, decay
+/// uses a decimal factor table and trauma is rounded to a unit once per
+/// tick. Two nodes fed the same samples agree exactly however long they
+/// run. The result is exposed as `units / 1e9`, which converts back to the
+/// same units losslessly below ~9·10^6 scar.
+pub fn update_scar_fixed(
+    current_scar: Scar,
+    pressure: &PressureVector,
+    delta_t_ms: f64,
+    config: &PhysicsConfig,
+    confidence: f64,
+) -> Scar {
+    let units = (current_scar.0.max(0.0) * FIXED_SCAR_SCALE).round() as u64;
+    let micros = (delta_t_ms.max(0.0) * 1000.0).round() as u64;
+    let decayed = (units as u128 * decay_factor(micros) + DECAY_ONE / 2) / DECAY_ONE;
+
+    let stress = vector::positive_stress_magnitude(pressure);
+    let trauma = if is_critical(stress, config.critical_comparison) {
+        (config.scar_factor * confidence.clamp(0.0, 1.0) * FIXED_SCAR_SCALE).round() as u64
+    } else {
+        0
+    };
+
+    let total = (decayed as u64).saturating_add(trauma);
+    Scar(total as f64 / FIXED_SCAR_SCALE)
+}
+
+// ============================================================================
+// DUAL TIMESCALE (Rust-only)
+// ============================================================================
+
+/// Fast + slow scar model
+///
+/// The TS scar is the fast component (decays in seconds, recent
+/// incidents). The slow component decays over hours so chronic flappers
+/// stay penalized after each blip has healed.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DualScarConfig {
+    /// Resistance per unit of fast scar
+    #[serde(alias = "fast_weight")]
+    pub fast_weight: f64,
+    /// Resistance per unit of slow scar
+    #[serde(alias = "slow_weight")]
+    pub slow_weight: f64,
+    /// Slow trauma added per critical tick
+    #[serde(alias = "slow_factor")]
+    pub slow_factor: f64,
+    /// Slow decay rate per second
+    #[serde(alias = "slow_decay_rate")]
+    pub slow_decay_rate: f64,
+}
+
+impl Default for DualScarConfig {
+    fn default() -> Self {
+        Self {
+            fast_weight: 1.0,
+            slow_weight: 1.0,
+            slow_factor: 0.5,
+            slow_decay_rate: std::f64::consts::LN_2 / 3600.0, // 1h half-life
+        }
+    }
+}
+
+impl DualScarConfig {
+    /// Combined scar contribution to resistance
+    #[inline]
+    pub fn effective(&self, fast: Scar, slow: Scar) -> f64 {
+        self.fast_weight * fast.0 + self.slow_weight * slow.0
+    }
+}
+
+/// Update the slow scar component (same trauma rule, slower decay)
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", name = "atrion.slow_scar", skip_all)
+)]
+pub fn update_slow_scar(
+    current_scar: Scar,
+    pressure: &PressureVector,
+    delta_t_ms: f64,
+    dual: &DualScarConfig,
+    comparison: ThresholdComparison,
+    confidence: f64,
+) -> Scar {
+    let decay = slow_scar_decay(dual, delta_t_ms);
+    update_slow_scar_decayed(current_scar, pressure, decay, dual, comparison, confidence)
+}
+
+/// Slow scar decay factor over `delta_t_ms`
+#[inline]
+pub fn slow_scar_decay(dual: &DualScarConfig, delta_t_ms: f64) -> f64 {
+    fastmath::exp(-dual.slow_decay_rate * delta_t_ms / 1000.0)
+}
+
+/// `update_slow_scar` with a precomputed `slow_scar_decay` factor
+#[inline]
+pub fn update_slow_scar_decayed(
+    current_scar: Scar,
+    pressure: &PressureVector,
+    decay: f64,
+    dual: &DualScarConfig,
+    comparison: ThresholdComparison,
+    confidence: f64,
+) -> Scar {
+    let decayed = current_scar.0 * decay;
+    let trauma = if is_critical(vector::positive_stress_magnitude(pressure), comparison) {
+        dual.slow_factor
+    } else {
+        0.0
+    };
+    Scar(vector::canonical_zero(
+        (decayed + trauma * confidence.clamp(0.0, 1.0)).max(0.0),
+    ))
 }
 
 #[cfg(test)]
@@ -122,6 +372,62 @@ mod tests {
         assert_eq!(scar.0, 5.0); // No change - under critical threshold
     }
 
+    #[test]
+    fn test_threshold_comparison_at_critical() {
+        let at = PressureVector::new(CRITICAL_PRESSURE, 0.0, 0.0);
+        let exclusive = PhysicsConfig::default();
+        let inclusive = PhysicsConfig {
+            critical_comparison: ThresholdComparison::Inclusive,
+            ..PhysicsConfig::default()
+        };
+
+        assert_eq!(
+            update_scar_with_decay(Scar(0.0), &at, 0.0, &exclusive).0,
+            0.0
+        );
+        assert_eq!(
+            update_scar_with_decay(Scar(0.0), &at, 0.0, &inclusive).0,
+            inclusive.scar_factor
+        );
+        // Next representable value down is below the threshold in both modes
+        let below = PressureVector::new(f64::from_bits(CRITICAL_PRESSURE.to_bits() - 1), 0.0, 0.0);
+        assert_eq!(
+            update_scar_with_decay(Scar(0.0), &below, 0.0, &inclusive).0,
+            0.0
+        );
+        // -0.0 scar comes back as +0.0
+        assert!(update_scar_with_decay(Scar(-0.0), &below, 0.0, &exclusive)
+            .0
+            .is_sign_positive());
+    }
+
+    #[test]
+    fn test_fixed_scar_is_exact_and_tracks_float() {
+        let fixed = PhysicsConfig {
+            scar_precision: ScarPrecision::Fixed,
+            ..PhysicsConfig::default()
+        };
+        let float = PhysicsConfig::default();
+        assert!(
+            (decay_factor(1_000_000) as f64 / DECAY_ONE as f64 - (-SCAR_DECAY_RATE).exp()).abs()
+                < 1e-15
+        );
+
+        let hot = PressureVector::new(0.9, 0.0, 0.0);
+        let calm = PressureVector::new(0.1, 0.0, 0.0);
+        let (mut a, mut b) = (Scar(0.0), Scar(0.0));
+        for i in 0..10_000 {
+            let pressure = if i % 7 == 0 { hot } else { calm };
+            let dt = 17.0 + (i % 13) as f64 * 0.25;
+            a = update_scar_with_decay(a, &pressure, dt, &fixed);
+            b = update_scar_with_decay(b, &pressure, dt, &float);
+            // Every fixed value round-trips through whole units
+            let units = (a.0 * FIXED_SCAR_SCALE).round();
+            assert_eq!(units / FIXED_SCAR_SCALE, a.0);
+        }
+        assert!((a.0 - b.0).abs() < 1e-6 * b.0.max(1.0));
+    }
+
     #[test]
     fn test_decay_with_time() {
         let pressure = PressureVector::new(0.0, 0.0, 0.0);
@@ -132,4 +438,33 @@ mod tests {
         assert!(scar.0 < 10.0);
         assert!(scar.0 > 9.0); // ~9.05 expected
     }
+
+    #[test]
+    fn test_slow_scar_outlives_fast_scar() {
+        let calm = PressureVector::new(0.0, 0.0, 0.0);
+        let dual = DualScarConfig::default();
+        let config = PhysicsConfig::default();
+
+        // One minute after an incident
+        let fast = update_scar_with_decay(Scar(10.0), &calm, 60_000.0, &config);
+        let slow = update_slow_scar(
+            Scar(10.0),
+            &calm,
+            60_000.0,
+            &dual,
+            ThresholdComparison::Exclusive,
+            1.0,
+        );
+        assert!(fast.0 < 0.1);
+        assert!(slow.0 > 9.8);
+    }
+
+    #[test]
+    fn test_low_confidence_scales_trauma() {
+        let pressure = PressureVector::new(0.8, 0.6, 0.5);
+        let config = PhysicsConfig::default();
+
+        let scar = update_scar_weighted(Scar(0.0), &pressure, 0.0, &config, 0.2);
+        assert!((scar.0 - 0.2 * config.scar_factor).abs() < 1e-12);
+    }
 }
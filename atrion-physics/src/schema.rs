@@ -0,0 +1,50 @@
+/**
+ * JSON Schema for the serialized config and snapshot formats.
+ *
+ * Lets non-Rust tooling (the TS package, config linters, CI checks on
+ * committed snapshots) validate documents against the same field names
+ * and types the engine deserializes.
+ */
+use schemars::{schema_for, Schema};
+
+use crate::snapshot::Snapshot;
+use crate::types::{PhysicsConfig, SensitivityWeights};
+
+/// Schema for `PhysicsConfig`
+pub fn config_schema() -> Schema {
+    schema_for!(PhysicsConfig)
+}
+
+/// Schema for `SensitivityWeights`
+pub fn weights_schema() -> Schema {
+    schema_for!(SensitivityWeights)
+}
+
+/// Schema for pool `Snapshot` documents
+pub fn snapshot_schema() -> Schema {
+    schema_for!(Snapshot)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schemas_use_camel_case() {
+        let config = serde_json::to_value(config_schema()).unwrap();
+        let props = &config["properties"];
+        assert!(props.get("baseResistance").is_some());
+        assert!(props.get("resistanceCeiling").is_some());
+        assert!(props.get("base_resistance").is_none());
+
+        let snapshot = serde_json::to_value(snapshot_schema()).unwrap();
+        assert!(snapshot["properties"].get("takenAtMs").is_some());
+        assert!(serde_json::to_string(&snapshot)
+            .unwrap()
+            .contains("lastUpdatedMs"));
+    }
+}
@@ -0,0 +1,273 @@
+/**
+ * Seasonal pressure baselines.
+ *
+ * A nightly batch window saturates its database at the same hour every
+ * day. Fed as-is, that load is trauma every single night and the scar
+ * never heals. With `PhysicsConfig::seasonal` set the engine learns what
+ * each axis normally looks like per slot of a daily or weekly period
+ * (hour of day, or hour of the week) and feeds the physics pressure
+ * relative to that expectation:
+ *
+ *   adjusted = max(0, p − expected) / (1 − expected)
+ *
+ * so expected load reads as zero while full pressure still reads as 1.0.
+ * Each slot is an EWMA over past occurrences: the mean pressure of a slot
+ * is folded in with weight `alpha` once the slot ends, so an incident
+ * moves the baseline only a little, and only slots seen on
+ * `min_seasons` earlier days (or weeks) adjust anything.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::types::PressureVector;
+
+const HOUR_MS: f64 = 3_600_000.0;
+
+/// Length of the repeating pattern
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SeasonalPeriod {
+    /// 24 hour-of-day slots
+    #[default]
+    Daily,
+    /// 168 hour-of-week slots
+    Weekly,
+}
+
+impl SeasonalPeriod {
+    pub fn slots(&self) -> usize {
+        match self {
+            SeasonalPeriod::Daily => 24,
+            SeasonalPeriod::Weekly => 24 * 7,
+        }
+    }
+}
+
+/// Seasonal baseline configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SeasonalConfig {
+    pub period: SeasonalPeriod,
+    /// Weight of the newest occurrence in a slot's EWMA (0..=1)
+    pub alpha: f64,
+    /// Occurrences a slot needs before it adjusts pressure
+    #[serde(alias = "min_seasons")]
+    pub min_seasons: u32,
+    /// Added to timestamps before slotting (local time vs UTC)
+    #[serde(alias = "utc_offset_ms")]
+    pub utc_offset_ms: f64,
+}
+
+impl Default for SeasonalConfig {
+    fn default() -> Self {
+        Self {
+            period: SeasonalPeriod::Daily,
+            alpha: 0.2,
+            min_seasons: 3,
+            utc_offset_ms: 0.0,
+        }
+    }
+}
+
+/// Learned expectation for one slot
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct SeasonalSlot {
+    pub expected: PressureVector,
+    /// Occurrences folded in so far
+    pub seasons: u32,
+}
+
+/// Per-slot EWMAs plus the running mean of the current occurrence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonalBaseline {
+    period: SeasonalPeriod,
+    slots: Vec<SeasonalSlot>,
+    /// Absolute slot index (hours since the epoch) being accumulated
+    current: Option<i64>,
+    sum: [f64; 3],
+    count: u32,
+}
+
+impl SeasonalBaseline {
+    pub fn new(period: SeasonalPeriod) -> Self {
+        let zero = PressureVector::new(0.0, 0.0, 0.0);
+        Self {
+            period,
+            slots: vec![
+                SeasonalSlot {
+                    expected: zero,
+                    seasons: 0,
+                };
+                period.slots()
+            ],
+            current: None,
+            sum: [0.0; 3],
+            count: 0,
+        }
+    }
+
+    pub fn period(&self) -> SeasonalPeriod {
+        self.period
+    }
+
+    /// Learned slots, slot 0 starting at midnight (Thursday for weekly:
+    /// the epoch's weekday)
+    pub fn slots(&self) -> &[SeasonalSlot] {
+        &self.slots
+    }
+
+    /// Expected pressure at `now_ms`, once its slot has enough seasons
+    pub fn expected(&self, config: &SeasonalConfig, now_ms: f64) -> Option<PressureVector> {
+        let slot = &self.slots[self.slot_of(hour(config, now_ms))];
+        (slot.seasons >= config.min_seasons.max(1)).then_some(slot.expected)
+    }
+
+    /// Pressure relative to the baseline at `now_ms`; learns from `raw`
+    pub fn adjust(
+        &mut self,
+        config: &SeasonalConfig,
+        now_ms: f64,
+        raw: PressureVector,
+    ) -> PressureVector {
+        let adjusted = match self.expected(config, now_ms) {
+            Some(expected) => PressureVector::new(
+                relative(raw.latency, expected.latency),
+                relative(raw.error, expected.error),
+                relative(raw.saturation, expected.saturation),
+            ),
+            None => raw,
+        };
+        self.learn(config, now_ms, raw);
+        adjusted
+    }
+
+    fn learn(&mut self, config: &SeasonalConfig, now_ms: f64, raw: PressureVector) {
+        let hour = hour(config, now_ms);
+        match self.current {
+            // Late samples still count toward the open occurrence
+            Some(current) if hour <= current => {}
+            _ => {
+                self.close(config);
+                self.current = Some(hour);
+            }
+        }
+        for (sum, value) in self
+            .sum
+            .iter_mut()
+            .zip([raw.latency, raw.error, raw.saturation])
+        {
+            *sum += value;
+        }
+        self.count += 1;
+    }
+
+    /// Fold the finished occurrence into its slot
+    fn close(&mut self, config: &SeasonalConfig) {
+        let Some(current) = self.current else {
+            return;
+        };
+        if self.count > 0 {
+            let n = self.count as f64;
+            let mean = PressureVector::new(self.sum[0] / n, self.sum[1] / n, self.sum[2] / n);
+            let index = self.slot_of(current);
+            let slot = &mut self.slots[index];
+            let alpha = if slot.seasons == 0 {
+                1.0
+            } else {
+                config.alpha.clamp(0.0, 1.0)
+            };
+            let blend = |old: f64, new: f64| old + alpha * (new - old);
+            slot.expected = PressureVector::new(
+                blend(slot.expected.latency, mean.latency),
+                blend(slot.expected.error, mean.error),
+                blend(slot.expected.saturation, mean.saturation),
+            );
+            slot.seasons += 1;
+        }
+        self.sum = [0.0; 3];
+        self.count = 0;
+    }
+
+    fn slot_of(&self, hour: i64) -> usize {
+        hour.rem_euclid(self.slots.len() as i64) as usize
+    }
+}
+
+/// Hours since the epoch in the configured time zone
+fn hour(config: &SeasonalConfig, now_ms: f64) -> i64 {
+    ((now_ms + config.utc_offset_ms) / HOUR_MS).floor() as i64
+}
+
+fn relative(value: f64, expected: f64) -> f64 {
+    if expected >= 1.0 {
+        return 0.0;
+    }
+    ((value - expected) / (1.0 - expected)).max(0.0)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    const DAY_MS: f64 = 24.0 * HOUR_MS;
+
+    #[test]
+    fn test_learns_slot_after_min_seasons() {
+        let config = SeasonalConfig::default();
+        let mut baseline = SeasonalBaseline::new(config.period);
+        let batch = PressureVector::new(0.1, 0.0, 0.8);
+        // One sample per minute over the 02:00 hour for three nights
+        for day in 0..3 {
+            for minute in 0..60 {
+                let at = day as f64 * DAY_MS + 2.0 * HOUR_MS + minute as f64 * 60_000.0;
+                assert_eq!(baseline.adjust(&config, at, batch).saturation, 0.8);
+            }
+        }
+        // The third occurrence closes once the next slot starts
+        baseline.adjust(&config, 2.0 * DAY_MS + 3.0 * HOUR_MS, batch);
+        let night = 3.0 * DAY_MS + 2.0 * HOUR_MS;
+        assert!((baseline.expected(&config, night).unwrap().saturation - 0.8).abs() < 1e-12);
+        assert!(baseline.adjust(&config, night, batch).saturation < 1e-9);
+        // Beyond the baseline pressure still climbs to 1.0
+        let spike = baseline.adjust(&config, night, PressureVector::new(0.1, 0.0, 1.0));
+        assert!((spike.saturation - 1.0).abs() < 1e-12);
+        // Other hours are untouched
+        assert!(baseline
+            .expected(&config, 3.0 * DAY_MS + 14.0 * HOUR_MS)
+            .is_none());
+    }
+
+    #[test]
+    fn test_nightly_batch_stops_scarring() {
+        let config = PhysicsConfig {
+            seasonal: Some(SeasonalConfig::default()),
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        let mut batch_scar = vec![0.0f64; 7];
+        // A week of minute ticks: 02:00-03:00 saturates, the rest is idle
+        for minute in 0..7 * 24 * 60 {
+            let now = minute as f64 * 60_000.0;
+            let in_batch = (now % DAY_MS) >= 2.0 * HOUR_MS && (now % DAY_MS) < 3.0 * HOUR_MS;
+            let pressure = if in_batch {
+                PressureVector::new(0.9, 0.0, 0.95)
+            } else {
+                PressureVector::new(0.1, 0.0, 0.1)
+            };
+            let state = engine.tick(now, pressure);
+            let day = (now / DAY_MS) as usize;
+            batch_scar[day] = batch_scar[day].max(state.scar.0);
+        }
+        // The first nights scar; once learned the batch adds nothing
+        assert!(batch_scar[0] > 1.0, "{batch_scar:?}");
+        assert!(batch_scar[6] < 0.01, "{batch_scar:?}");
+    }
+}
@@ -0,0 +1,212 @@
+/**
+ * Parameter sensitivity ("which knob matters for this workload?").
+ *
+ * Replays a trace under the base config and under each core parameter
+ * nudged down and up by a fixed fraction, then reports how trip count,
+ * mean resistance and recovery time move. Parameters are ranked by the
+ * largest relative change they cause, so the head of the list is where
+ * tuning effort pays off.
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::anneal::SearchParameter;
+use crate::counterfactual::{analyze, CandidateReport};
+use crate::trace::Trace;
+use crate::types::{PhysicsConfig, SensitivityWeights};
+
+/// Key outcomes of one replay
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Outcomes {
+    pub trips: u32,
+    #[serde(alias = "mean_resistance")]
+    pub mean_resistance: f64,
+    /// Mean trip-to-recovery time (`None` when nothing recovered)
+    #[serde(alias = "mean_recovery_ms")]
+    pub mean_recovery_ms: Option<f64>,
+}
+
+impl From<&CandidateReport> for Outcomes {
+    fn from(report: &CandidateReport) -> Self {
+        Self {
+            trips: report.trips,
+            mean_resistance: report.mean_resistance,
+            mean_recovery_ms: report.mean_recovery_ms(),
+        }
+    }
+}
+
+/// Effect of moving one parameter
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParameterImpact {
+    pub parameter: SearchParameter,
+    pub value: f64,
+    #[serde(alias = "lower_value")]
+    pub lower_value: f64,
+    #[serde(alias = "upper_value")]
+    pub upper_value: f64,
+    pub lower: Outcomes,
+    pub upper: Outcomes,
+    /// Largest relative change of any outcome versus the baseline
+    pub impact: f64,
+}
+
+/// Baseline plus per-parameter impacts, most influential first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensitivityReport {
+    pub perturbation: f64,
+    pub baseline: Outcomes,
+    pub parameters: Vec<ParameterImpact>,
+}
+
+/// Perturb every core parameter by ±`perturbation` (e.g. 0.1 for 10%)
+pub fn sensitivity_analysis(
+    trace: &Trace,
+    weights: &SensitivityWeights,
+    config: &PhysicsConfig,
+    perturbation: f64,
+) -> SensitivityReport {
+    let perturbation = perturbation.abs();
+    let mut candidates = vec![config.clone()];
+    for parameter in SearchParameter::ALL {
+        let value = parameter.get(config);
+        for factor in [1.0 - perturbation, 1.0 + perturbation] {
+            let mut candidate = config.clone();
+            parameter.set(&mut candidate, value * factor);
+            candidates.push(candidate);
+        }
+    }
+    let reports = analyze(trace, weights, &candidates);
+    let baseline = Outcomes::from(&reports[0]);
+
+    let mut parameters: Vec<ParameterImpact> = SearchParameter::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, &parameter)| {
+            let (lower_index, upper_index) = (1 + 2 * i, 2 + 2 * i);
+            let lower = Outcomes::from(&reports[lower_index]);
+            let upper = Outcomes::from(&reports[upper_index]);
+            ParameterImpact {
+                parameter,
+                value: parameter.get(config),
+                lower_value: parameter.get(&candidates[lower_index]),
+                upper_value: parameter.get(&candidates[upper_index]),
+                lower,
+                upper,
+                impact: change(&baseline, &lower).max(change(&baseline, &upper)),
+            }
+        })
+        .collect();
+    parameters.sort_by(|a, b| b.impact.total_cmp(&a.impact));
+
+    SensitivityReport {
+        perturbation,
+        baseline,
+        parameters,
+    }
+}
+
+/// Largest relative change between two outcomes
+fn change(base: &Outcomes, other: &Outcomes) -> f64 {
+    let trips = relative(base.trips as f64, other.trips as f64);
+    let resistance = relative(base.mean_resistance, other.mean_resistance);
+    let recovery = match (base.mean_recovery_ms, other.mean_recovery_ms) {
+        (Some(a), Some(b)) => relative(a, b),
+        (None, None) => 0.0,
+        // Gaining or losing recovery altogether is a full change
+        _ => 1.0,
+    };
+    trips.max(resistance).max(recovery)
+}
+
+fn relative(base: f64, other: f64) -> f64 {
+    let delta = (other - base).abs();
+    if base.abs() > f64::EPSILON {
+        delta / base.abs()
+    } else {
+        delta
+    }
+}
+
+/// Sensitivity report for a plain trace object and config
+#[wasm_bindgen(js_name = sensitivityAnalysis)]
+pub fn sensitivity_analysis_js(
+    trace: JsValue,
+    weights: &SensitivityWeights,
+    config: JsValue,
+    perturbation: f64,
+) -> Result<JsValue, JsValue> {
+    let trace: Trace = serde_wasm_bindgen::from_value(trace)?;
+    let config: PhysicsConfig = serde_wasm_bindgen::from_value(config)?;
+    crate::to_js(&sensitivity_analysis(
+        &trace,
+        weights,
+        &config,
+        perturbation,
+    ))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::TraceSample;
+    use crate::types::PressureVector;
+
+    fn incident_trace() -> Trace {
+        let mut trace = Trace::new();
+        for i in 0..300 {
+            let p = if (20..50).contains(&i) { 0.9 } else { 0.1 };
+            trace.record(
+                "api",
+                TraceSample {
+                    timestamp_ms: i as f64 * 100.0,
+                    pressure: PressureVector::new(p, p, p),
+                    offered: 10.0,
+                },
+            );
+        }
+        trace
+    }
+
+    #[test]
+    fn test_reports_every_knob_ranked_by_impact() {
+        let config = PhysicsConfig::default();
+        let report = sensitivity_analysis(
+            &incident_trace(),
+            &SensitivityWeights::default(),
+            &config,
+            0.2,
+        );
+
+        assert_eq!(report.parameters.len(), 7);
+        assert!(report.baseline.trips >= 1);
+        assert!(report.baseline.mean_recovery_ms.is_some());
+        assert!(report
+            .parameters
+            .windows(2)
+            .all(|w| w[0].impact >= w[1].impact));
+
+        let base = report
+            .parameters
+            .iter()
+            .find(|p| p.parameter == SearchParameter::BaseResistance)
+            .unwrap();
+        assert_eq!(base.lower_value, config.base_resistance * 0.8);
+        assert!(base.lower.mean_resistance < report.baseline.mean_resistance);
+        assert!(base.upper.mean_resistance > report.baseline.mean_resistance);
+        assert!(base.impact > 0.0);
+
+        let ticks = report
+            .parameters
+            .iter()
+            .find(|p| p.parameter == SearchParameter::BootstrapTicks)
+            .unwrap();
+        assert_eq!((ticks.lower_value, ticks.upper_value), (8.0, 12.0));
+    }
+}
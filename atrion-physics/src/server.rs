@@ -0,0 +1,360 @@
+/**
+ * gRPC control plane (feature "server", native only).
+ *
+ * Runs an EnginePool as a sidecar: polyglot services stream pressure
+ * samples in and receive resistance/mode updates back, without embedding
+ * the engine. Wire format is defined in proto/atrion.proto; the messages
+ * below are hand-mirrored so the build does not need protoc.
+ */
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+use tonic::codegen::{http, BoxFuture, BoxStream, Context, Poll, Service};
+use tonic::server::{Grpc, NamedService, ServerStreamingService, StreamingService, UnaryService};
+use tonic::{Request, Response, Status, Streaming};
+use tonic_prost::ProstCodec;
+
+use crate::engine::TargetState;
+use crate::pool::EnginePool;
+use crate::snapshot::Snapshot;
+use crate::types::{OperationalMode, PressureVector};
+
+/// Fully-qualified gRPC service name
+pub const SERVICE_NAME: &str = "atrion.v1.ControlPlane";
+
+/// Buffered updates per watcher before slow watchers start skipping
+const WATCH_BUFFER: usize = 1024;
+
+// ============================================================================
+// MESSAGES (mirror proto/atrion.proto)
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Mode {
+    Bootstrap = 0,
+    Operational = 1,
+    CircuitBreaker = 2,
+}
+
+impl From<OperationalMode> for Mode {
+    fn from(mode: OperationalMode) -> Self {
+        match mode {
+            OperationalMode::Bootstrap => Mode::Bootstrap,
+            OperationalMode::Operational => Mode::Operational,
+            OperationalMode::CircuitBreaker => Mode::CircuitBreaker,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PressureSample {
+    #[prost(string, tag = "1")]
+    pub target: String,
+    #[prost(double, tag = "2")]
+    pub timestamp_ms: f64,
+    #[prost(double, tag = "3")]
+    pub latency: f64,
+    #[prost(double, tag = "4")]
+    pub error: f64,
+    #[prost(double, tag = "5")]
+    pub saturation: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TargetUpdate {
+    #[prost(string, tag = "1")]
+    pub target: String,
+    #[prost(double, tag = "2")]
+    pub timestamp_ms: f64,
+    #[prost(double, tag = "3")]
+    pub resistance: f64,
+    #[prost(double, tag = "4")]
+    pub scar: f64,
+    #[prost(double, tag = "5")]
+    pub momentum: f64,
+    #[prost(enumeration = "Mode", tag = "6")]
+    pub mode: i32,
+}
+
+impl TargetUpdate {
+    fn from_state(target: String, state: &TargetState) -> Self {
+        Self {
+            target,
+            timestamp_ms: state.last_updated_ms,
+            resistance: state.resistance.0,
+            scar: state.scar.0,
+            momentum: state.momentum.0,
+            mode: Mode::from(state.mode) as i32,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WatchRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub targets: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SnapshotRequest {
+    #[prost(double, tag = "1")]
+    pub now_ms: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SnapshotReply {
+    #[prost(bytes = "vec", tag = "1")]
+    pub snapshot: Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub targets: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RestoreRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub snapshot: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RestoreReply {
+    #[prost(uint32, tag = "1")]
+    pub targets: u32,
+}
+
+// ============================================================================
+// SERVICE
+// ============================================================================
+
+/// gRPC control plane over a shared EnginePool
+#[derive(Clone)]
+pub struct ControlPlaneServer {
+    pool: Arc<Mutex<EnginePool>>,
+    updates: broadcast::Sender<TargetUpdate>,
+}
+
+impl ControlPlaneServer {
+    pub fn new(pool: EnginePool) -> Self {
+        Self::shared(Arc::new(Mutex::new(pool)))
+    }
+
+    /// Serve a pool that is also used in-process
+    pub fn shared(pool: Arc<Mutex<EnginePool>>) -> Self {
+        let (updates, _) = broadcast::channel(WATCH_BUFFER);
+        Self { pool, updates }
+    }
+
+    pub fn pool(&self) -> Arc<Mutex<EnginePool>> {
+        Arc::clone(&self.pool)
+    }
+
+    /// Serve on `addr` until the transport fails
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+        tonic::transport::Server::builder()
+            .add_service(self)
+            .serve(addr)
+            .await
+    }
+
+    /// Tick one sample and publish the resulting update
+    pub fn ingest(&self, sample: PressureSample) -> TargetUpdate {
+        let pressure = PressureVector::new(sample.latency, sample.error, sample.saturation);
+        let state = self
+            .lock()
+            .tick(&sample.target, sample.timestamp_ms, pressure);
+        let update = TargetUpdate::from_state(sample.target, &state);
+        // No watchers is not an error
+        let _ = self.updates.send(update.clone());
+        update
+    }
+
+    /// JSON-encoded pool snapshot
+    pub fn snapshot(&self, now_ms: f64) -> Result<SnapshotReply, Status> {
+        let snapshot = self.lock().snapshot(now_ms);
+        let bytes = serde_json::to_vec(&snapshot).map_err(|e| Status::internal(e.to_string()))?;
+        Ok(SnapshotReply {
+            snapshot: bytes,
+            targets: snapshot.targets.len() as u32,
+        })
+    }
+
+    /// Restore from a JSON-encoded snapshot
+    pub fn restore(&self, bytes: &[u8]) -> Result<RestoreReply, Status> {
+        let snapshot: Snapshot =
+            serde_json::from_slice(bytes).map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let targets = self
+            .lock()
+            .restore(&snapshot)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+        Ok(RestoreReply {
+            targets: targets as u32,
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, EnginePool> {
+        // A panic while holding the lock leaves plain data behind; keep serving
+        self.pool
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl NamedService for ControlPlaneServer {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+struct IngestMethod(ControlPlaneServer);
+
+impl StreamingService<PressureSample> for IngestMethod {
+    type Response = TargetUpdate;
+    type ResponseStream = BoxStream<TargetUpdate>;
+    type Future = BoxFuture<Response<Self::ResponseStream>, Status>;
+
+    fn call(&mut self, request: Request<Streaming<PressureSample>>) -> Self::Future {
+        let server = self.0.clone();
+        Box::pin(async move {
+            let updates = request
+                .into_inner()
+                .map(move |sample| sample.map(|s| server.ingest(s)));
+            Ok(Response::new(Box::pin(updates) as Self::ResponseStream))
+        })
+    }
+}
+
+struct WatchMethod(ControlPlaneServer);
+
+impl ServerStreamingService<WatchRequest> for WatchMethod {
+    type Response = TargetUpdate;
+    type ResponseStream = BoxStream<TargetUpdate>;
+    type Future = BoxFuture<Response<Self::ResponseStream>, Status>;
+
+    fn call(&mut self, request: Request<WatchRequest>) -> Self::Future {
+        let filter = request.into_inner().targets;
+        let updates = BroadcastStream::new(self.0.updates.subscribe());
+        Box::pin(async move {
+            // Lagged watchers skip missed updates instead of failing
+            let stream = updates.filter_map(move |update| match update {
+                Ok(u) if filter.is_empty() || filter.contains(&u.target) => Some(Ok(u)),
+                _ => None,
+            });
+            Ok(Response::new(Box::pin(stream) as Self::ResponseStream))
+        })
+    }
+}
+
+struct SnapshotMethod(ControlPlaneServer);
+
+impl UnaryService<SnapshotRequest> for SnapshotMethod {
+    type Response = SnapshotReply;
+    type Future = BoxFuture<Response<SnapshotReply>, Status>;
+
+    fn call(&mut self, request: Request<SnapshotRequest>) -> Self::Future {
+        let reply = self.0.snapshot(request.into_inner().now_ms);
+        Box::pin(async move { reply.map(Response::new) })
+    }
+}
+
+struct RestoreMethod(ControlPlaneServer);
+
+impl UnaryService<RestoreRequest> for RestoreMethod {
+    type Response = RestoreReply;
+    type Future = BoxFuture<Response<RestoreReply>, Status>;
+
+    fn call(&mut self, request: Request<RestoreRequest>) -> Self::Future {
+        let reply = self.0.restore(&request.into_inner().snapshot);
+        Box::pin(async move { reply.map(Response::new) })
+    }
+}
+
+impl<B> Service<http::Request<B>> for ControlPlaneServer
+where
+    B: tonic::codegen::Body + Send + 'static,
+    B::Error: Into<tonic::codegen::StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let server = self.clone();
+        let path = req.uri().path().strip_prefix("/atrion.v1.ControlPlane/");
+        match path {
+            Some("Ingest") => Box::pin(async move {
+                let mut grpc = Grpc::new(ProstCodec::default());
+                Ok(grpc.streaming(IngestMethod(server), req).await)
+            }),
+            Some("Watch") => Box::pin(async move {
+                let mut grpc = Grpc::new(ProstCodec::default());
+                Ok(grpc.server_streaming(WatchMethod(server), req).await)
+            }),
+            Some("Snapshot") => Box::pin(async move {
+                let mut grpc = Grpc::new(ProstCodec::default());
+                Ok(grpc.unary(SnapshotMethod(server), req).await)
+            }),
+            Some("Restore") => Box::pin(async move {
+                let mut grpc = Grpc::new(ProstCodec::default());
+                Ok(grpc.unary(RestoreMethod(server), req).await)
+            }),
+            _ => Box::pin(async move { Ok(Status::unimplemented("").into_http()) }),
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    fn server() -> ControlPlaneServer {
+        ControlPlaneServer::new(EnginePool::new(
+            PhysicsConfig::default(),
+            SensitivityWeights::default(),
+        ))
+    }
+
+    fn sample(target: &str, timestamp_ms: f64) -> PressureSample {
+        PressureSample {
+            target: target.to_string(),
+            timestamp_ms,
+            latency: 0.2,
+            error: 0.1,
+            saturation: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_ingest_ticks_pool_and_broadcasts() {
+        let server = server();
+        let mut watcher = server.updates.subscribe();
+
+        let update = server.ingest(sample("api", 0.0));
+        assert_eq!(update.mode, Mode::Bootstrap as i32);
+        assert_eq!(watcher.try_recv().unwrap(), update);
+        assert!(server.pool().lock().unwrap().contains("api"));
+    }
+
+    #[test]
+    fn test_snapshot_restore_round_trip() {
+        let source = server();
+        for i in 0..12 {
+            source.ingest(sample("api", i as f64 * 100.0));
+        }
+        let reply = source.snapshot(1200.0).unwrap();
+        assert_eq!(reply.targets, 1);
+
+        let target = server();
+        assert_eq!(target.restore(&reply.snapshot).unwrap().targets, 1);
+        assert!(target.restore(b"not json").is_err());
+    }
+}
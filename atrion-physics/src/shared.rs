@@ -0,0 +1,304 @@
+/**
+ * Thread-safe sharded pool (feature "shared", native only).
+ *
+ * Targets are spread over shards by id hash. Each shard pairs the engines
+ * (behind a mutex, taken by ticks and other mutations) with a published
+ * copy of every target's latest state (behind a read-write lock, taken by
+ * readers). A tick runs the physics under the shard mutex only, then
+ * publishes the new state before releasing it.
+ *
+ * Consistency guarantees:
+ * - Ticks of one target are serialized and published in tick order.
+ * - Readers never wait for physics, only for the publish itself, and see
+ *   the state of the target's last completed tick: at most one tick stale.
+ * - There is no cross-target snapshot: during `tick_all` some targets may
+ *   read at tick n while others still read at tick n − 1.
+ *
+ * The protocol is model-checked with loom:
+ * `RUSTFLAGS="--cfg loom" cargo test --release --features shared shared::`
+ */
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+#[cfg(loom)]
+use loom::sync::{Mutex, MutexGuard, RwLock};
+#[cfg(not(loom))]
+use std::sync::{Mutex, MutexGuard, RwLock};
+
+use crate::admission::AdmissionDecision;
+use crate::engine::TargetState;
+use crate::pool::EnginePool;
+use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+struct Shard {
+    /// Engines; ticks and other mutations hold this
+    engines: Mutex<EnginePool>,
+    /// State as of each target's last completed tick; readers hold this
+    published: RwLock<HashMap<String, TargetState>>,
+}
+
+impl Shard {
+    fn lock(&self) -> MutexGuard<'_, EnginePool> {
+        self.engines.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Copy `id`'s state (or its absence) out of `pool`
+    ///
+    /// Callers hold the engines lock, so publications cannot reorder.
+    fn publish(&self, pool: &EnginePool, id: &str) {
+        let mut published = self.published.write().unwrap_or_else(|e| e.into_inner());
+        match pool.get(id) {
+            Some(engine) => match published.get_mut(id) {
+                Some(state) => *state = *engine.state(),
+                None => {
+                    published.insert(id.to_string(), *engine.state());
+                }
+            },
+            None => {
+                published.remove(id);
+            }
+        }
+    }
+
+    fn tick_all(
+        &self,
+        now_ms: f64,
+        pressure_of: &(impl Fn(&str) -> Option<PressureVector> + Sync),
+    ) -> usize {
+        let mut pool = self.lock();
+        let mut ticked = Vec::new();
+        pool.tick_all(now_ms, |id| {
+            let pressure = pressure_of(id)?;
+            ticked.push(id.to_string());
+            Some(pressure)
+        });
+        for id in &ticked {
+            self.publish(&pool, id);
+        }
+        ticked.len()
+    }
+}
+
+/// Engine pool shared across threads, with lock-light state reads
+pub struct SharedPool {
+    shards: Box<[Shard]>,
+}
+
+impl SharedPool {
+    /// Pool with `shards` shards (at least one), each an `EnginePool`
+    pub fn new(config: PhysicsConfig, weights: SensitivityWeights, shards: usize) -> Self {
+        let shards = (0..shards.max(1))
+            .map(|_| Shard {
+                engines: Mutex::new(EnginePool::new(config.clone(), weights.clone())),
+                published: RwLock::new(HashMap::new()),
+            })
+            .collect();
+        Self { shards }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Shard a target lives in (stable for the life of the pool)
+    pub fn shard_of(&self, id: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        (hasher.finish() % self.shards.len() as u64) as usize
+    }
+
+    fn shard(&self, id: &str) -> &Shard {
+        &self.shards[self.shard_of(id)]
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tick a target, registering it on first sight
+    pub fn tick(&self, id: &str, now_ms: f64, pressure: PressureVector) -> TargetState {
+        let shard = self.shard(id);
+        let mut pool = shard.lock();
+        let state = pool.tick(id, now_ms, pressure);
+        shard.publish(&pool, id);
+        state
+    }
+
+    /// Tick every registered target, one thread per shard; returns how
+    /// many were ticked (see `EnginePool::tick_all`)
+    ///
+    /// Each shard publishes once its whole pass is done.
+    pub fn tick_all(
+        &self,
+        now_ms: f64,
+        pressure_of: impl Fn(&str) -> Option<PressureVector> + Sync,
+    ) -> usize {
+        #[cfg(not(loom))]
+        if self.shards.len() > 1 {
+            return std::thread::scope(|scope| {
+                let passes: Vec<_> = self
+                    .shards
+                    .iter()
+                    .map(|shard| scope.spawn(|| shard.tick_all(now_ms, &pressure_of)))
+                    .collect();
+                passes
+                    .into_iter()
+                    .map(|pass| pass.join().expect("shard tick panicked"))
+                    .sum()
+            });
+        }
+        self.shards
+            .iter()
+            .map(|shard| shard.tick_all(now_ms, &pressure_of))
+            .sum()
+    }
+
+    /// Published state of a target (at most one tick stale)
+    pub fn state(&self, id: &str) -> Option<TargetState> {
+        let published = self.shard(id).published.read();
+        let published = published.unwrap_or_else(|e| e.into_inner());
+        published.get(id).copied()
+    }
+
+    /// Published resistance of a target (at most one tick stale)
+    pub fn resistance(&self, id: &str) -> Option<f64> {
+        let published = self.shard(id).published.read();
+        let published = published.unwrap_or_else(|e| e.into_inner());
+        published.get(id).map(|state| state.resistance.0)
+    }
+
+    /// Exact admission check against the engines (takes the shard mutex)
+    pub fn try_admit(&self, id: &str, voltage: f64) -> AdmissionDecision {
+        self.shard(id).lock().try_admit(id, voltage)
+    }
+
+    /// Run `f` on the shard pool holding `id`, then republish `id`
+    ///
+    /// For mutations without a dedicated method (overrides, resets,
+    /// bounds). Touching other targets of the shard from `f` leaves their
+    /// published state behind until their next tick.
+    pub fn with_target<R>(&self, id: &str, f: impl FnOnce(&mut EnginePool) -> R) -> R {
+        let shard = self.shard(id);
+        let mut pool = shard.lock();
+        let result = f(&mut pool);
+        shard.publish(&pool, id);
+        result
+    }
+
+    pub fn remove(&self, id: &str) -> bool {
+        self.with_target(id, |pool| pool.remove(id).is_some())
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parallel_tick_all_publishes_every_shard() {
+        let pool = Arc::new(SharedPool::new(
+            PhysicsConfig::default(),
+            SensitivityWeights::default(),
+            4,
+        ));
+        let ids: Vec<String> = (0..64).map(|i| format!("t{i}")).collect();
+        for id in &ids {
+            pool.tick(id, 0.0, PressureVector::new(0.1, 0.0, 0.1));
+        }
+        let reader = {
+            let (pool, ids) = (pool.clone(), ids.clone());
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    for id in &ids {
+                        let ticks = pool.state(id).unwrap().tick_count;
+                        assert!((1..=2).contains(&ticks));
+                    }
+                }
+            })
+        };
+        let hot = PressureVector::new(0.9, 0.9, 0.9);
+        assert_eq!(pool.tick_all(1_000.0, |_| Some(hot)), 64);
+        reader.join().unwrap();
+
+        assert_eq!(pool.len(), 64);
+        for id in &ids {
+            let engine = pool.with_target(id, |p| *p.get(id).unwrap().state());
+            let published = pool.state(id).unwrap();
+            assert_eq!(published.tick_count, 2);
+            assert_eq!(published.resistance, engine.resistance);
+        }
+        assert!(pool.remove("t0"));
+        assert!(pool.state("t0").is_none());
+    }
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    fn pool() -> Arc<SharedPool> {
+        Arc::new(SharedPool::new(
+            PhysicsConfig::default(),
+            SensitivityWeights::default(),
+            2,
+        ))
+    }
+
+    #[test]
+    fn test_concurrent_ticks_publish_in_order() {
+        loom::model(|| {
+            let pool = pool();
+            let writers: Vec<_> = [100.0, 200.0]
+                .into_iter()
+                .map(|now_ms| {
+                    let pool = pool.clone();
+                    thread::spawn(move || {
+                        pool.tick("a", now_ms, PressureVector::new(0.5, 0.1, 0.5));
+                    })
+                })
+                .collect();
+            // Readers see nothing or a completed tick, never a regression
+            let first = pool.state("a").map_or(0, |s| s.tick_count);
+            let second = pool.state("a").map_or(0, |s| s.tick_count);
+            assert!(first <= second && second <= 2);
+            for writer in writers {
+                writer.join().unwrap();
+            }
+            // The last publication is the last tick, whichever thread ran it
+            let engine = pool.with_target("a", |p| *p.get("a").unwrap().state());
+            let published = pool.state("a").unwrap();
+            assert_eq!(published.tick_count, engine.tick_count);
+            assert_eq!(published.last_updated_ms, engine.last_updated_ms);
+        });
+    }
+
+    #[test]
+    fn test_reads_are_at_most_one_tick_stale() {
+        loom::model(|| {
+            let pool = pool();
+            pool.tick("a", 0.0, PressureVector::new(0.2, 0.0, 0.2));
+            let writer = {
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    pool.tick("a", 100.0, PressureVector::new(0.9, 0.9, 0.9));
+                })
+            };
+            let seen = pool.state("a").unwrap().tick_count;
+            assert!(seen == 1 || seen == 2);
+            writer.join().unwrap();
+            assert_eq!(pool.state("a").unwrap().tick_count, 2);
+        });
+    }
+}
@@ -0,0 +1,177 @@
+/**
+ * axum load-shedding middleware (feature "shed", native only).
+ *
+ * Maps each incoming route to a target and answers 503 with a
+ * `Retry-After` derived from `retry_advice` while that target is above
+ * the shed threshold or its breaker is open.
+ *
+ * Install with `Router::route_layer(from_fn_with_state(state, shed))` so the
+ * matched route pattern is available for target mapping.
+ */
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::pool::EnginePool;
+
+/// Route → target mapping and shed threshold
+#[derive(Debug, Clone, Default)]
+pub struct ShedConfig {
+    /// Route pattern (as registered with axum) or literal path → target id
+    pub routes: HashMap<String, String>,
+    /// Target for unmapped routes (`None` = never shed them)
+    pub default_target: Option<String>,
+    /// Resistance at which requests are shed (`None` = pool break_threshold)
+    pub shed_threshold: Option<f64>,
+}
+
+impl ShedConfig {
+    /// Map a route pattern to a target
+    pub fn route(mut self, pattern: impl Into<String>, target: impl Into<String>) -> Self {
+        self.routes.insert(pattern.into(), target.into());
+        self
+    }
+
+    /// Target for a request: matched pattern, then literal path, then default
+    pub fn target_for(&self, matched: Option<&str>, path: &str) -> Option<&str> {
+        matched
+            .and_then(|pattern| self.routes.get(pattern))
+            .or_else(|| self.routes.get(path))
+            .or(self.default_target.as_ref())
+            .map(String::as_str)
+    }
+}
+
+/// Middleware state: the shared pool plus shed config
+#[derive(Clone)]
+pub struct ShedLayerState {
+    pool: Arc<Mutex<EnginePool>>,
+    config: Arc<ShedConfig>,
+}
+
+impl ShedLayerState {
+    pub fn new(pool: Arc<Mutex<EnginePool>>, config: ShedConfig) -> Self {
+        Self {
+            pool,
+            config: Arc::new(config),
+        }
+    }
+}
+
+/// Middleware function for `axum::middleware::from_fn_with_state`
+pub async fn shed(State(state): State<ShedLayerState>, request: Request, next: Next) -> Response {
+    let matched = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str);
+    let advice = state
+        .config
+        .target_for(matched, request.uri().path())
+        .and_then(|target| {
+            let pool = state
+                .pool
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let threshold = state
+                .config
+                .shed_threshold
+                .unwrap_or(pool.config().break_threshold);
+            pool.retry_advice(target, threshold)
+        });
+
+    match advice {
+        None => next.run(request).await,
+        Some(advice) => {
+            let mut response =
+                (StatusCode::SERVICE_UNAVAILABLE, advice.reason.as_str()).into_response();
+            let headers = response.headers_mut();
+            headers.insert(
+                header::RETRY_AFTER,
+                HeaderValue::from(advice.retry_after_secs()),
+            );
+            headers.insert(
+                "x-atrion-reject",
+                HeaderValue::from_static(advice.reason.as_str()),
+            );
+            response
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+    use axum::routing::get;
+    use axum::Router;
+    use std::io::{Read, Write};
+    use std::net::SocketAddr;
+
+    fn get_path(addr: SocketAddr, path: &str) -> String {
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: shed\r\nConnection: close\r\n\r\n"
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_target_mapping() {
+        let config = ShedConfig::default().route("/orders/{id}", "orders");
+        assert_eq!(
+            config.target_for(Some("/orders/{id}"), "/orders/7"),
+            Some("orders")
+        );
+        assert_eq!(config.target_for(None, "/health"), None);
+    }
+
+    #[test]
+    fn test_sheds_open_circuit_with_retry_after() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        for i in 0..40 {
+            pool.tick(
+                "orders",
+                i as f64 * 100.0,
+                PressureVector::new(1.0, 1.0, 1.0),
+            );
+        }
+        let state = ShedLayerState::new(
+            Arc::new(Mutex::new(pool)),
+            ShedConfig::default().route("/orders/{id}", "orders"),
+        );
+        let app = Router::new()
+            .route("/orders/{id}", get(|| async { "order" }))
+            .route("/health", get(|| async { "ok" }))
+            .route_layer(axum::middleware::from_fn_with_state(state, shed));
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            runtime.block_on(async { axum::serve(listener, app).await.unwrap() });
+        });
+
+        let shed = get_path(addr, "/orders/7");
+        assert!(shed.starts_with("HTTP/1.1 503"));
+        assert!(shed.to_ascii_lowercase().contains("retry-after: "));
+        assert!(shed.contains("circuit_open"));
+        assert!(get_path(addr, "/health").starts_with("HTTP/1.1 200"));
+    }
+}
@@ -0,0 +1,424 @@
+/**
+ * Virtual cluster simulation (feature "simlab"; always built for tests).
+ *
+ * Replays and workloads feed the engine open-loop: what it admits has no
+ * effect on the pressure that comes next. `VirtualCluster` closes the
+ * loop so end-to-end behavior can be tested at the crate level: N clients,
+ * each gating its traffic through its own `EnginePool`, send to M servers
+ * with finite capacity and a FIFO queue. Admitted load drives queueing
+ * delay, delay past the client timeout turns into failures, and a share
+ * of failures comes back as retries, which is the feedback loop behind
+ * metastable failures. Failure scripts take servers down, cut their
+ * capacity or slow them for a time window.
+ *
+ * The model is a fluid one, stepped every `interval_ms`:
+ *
+ * - a server serves up to capacity · Δt from its queue, oldest first;
+ *   the served requests waited `backlog / capacity`, and count as failed
+ *   when that exceeds the timeout (the work is done, nobody reads it)
+ * - queue overflow past `queue_limit` and requests to a down server fail
+ *   at once
+ * - every client sees each server's latency, error rate and utilization
+ *   as pressure, as passive metrics would report them
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::anneal::Rng;
+use crate::metastable::MetastableConfig;
+use crate::pool::EnginePool;
+use crate::types::{OperationalMode, PhysicsConfig, PressureVector, SensitivityWeights};
+
+/// One simulated server
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerSpec {
+    /// Requests served per second
+    pub capacity: f64,
+    /// Service latency on an empty queue
+    #[serde(alias = "base_latency_ms")]
+    pub base_latency_ms: f64,
+    /// Queued requests beyond this are dropped
+    #[serde(alias = "queue_limit")]
+    pub queue_limit: f64,
+}
+
+impl Default for ServerSpec {
+    fn default() -> Self {
+        Self {
+            capacity: 100.0,
+            base_latency_ms: 20.0,
+            queue_limit: 500.0,
+        }
+    }
+}
+
+/// What a failure script does to its server
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+pub enum Fault {
+    /// Every request fails immediately; the queue is lost
+    Down,
+    /// Capacity multiplied by `factor`
+    Degraded { factor: f64 },
+    /// Fixed latency added to every request
+    Slow {
+        #[serde(alias = "extra_ms")]
+        extra_ms: f64,
+    },
+}
+
+/// A fault applied to `server` during `[from_ms, until_ms)`
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureScript {
+    pub server: usize,
+    #[serde(alias = "from_ms")]
+    pub from_ms: f64,
+    #[serde(alias = "until_ms")]
+    pub until_ms: f64,
+    pub fault: Fault,
+}
+
+/// Clients, timing and retry behavior
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SimConfig {
+    pub clients: usize,
+    /// New requests per second per client, spread evenly over servers
+    #[serde(alias = "offered_rps")]
+    pub offered_rps: f64,
+    #[serde(alias = "timeout_ms")]
+    pub timeout_ms: f64,
+    /// Share of failed requests retried on the next step (shed requests
+    /// are not retried)
+    #[serde(alias = "retry_ratio")]
+    pub retry_ratio: f64,
+    #[serde(alias = "interval_ms")]
+    pub interval_ms: f64,
+    #[serde(alias = "duration_ms")]
+    pub duration_ms: f64,
+    /// Gate client traffic through the engines; `false` runs the same
+    /// cluster unprotected for comparison
+    pub gate: bool,
+    /// Uniform ± noise on offered load
+    pub noise: f64,
+    pub seed: u64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            clients: 4,
+            offered_rps: 20.0,
+            timeout_ms: 1_000.0,
+            retry_ratio: 0.9,
+            interval_ms: 100.0,
+            duration_ms: 60_000.0,
+            gate: true,
+            noise: 0.05,
+            seed: 0x5EED,
+        }
+    }
+}
+
+/// Cluster-wide totals for one step
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimTick {
+    #[serde(alias = "at_ms")]
+    pub at_ms: f64,
+    /// New requests (retries excluded)
+    pub offered: f64,
+    /// Attempts let through by the clients (retries included)
+    pub admitted: f64,
+    pub shed: f64,
+    /// Served within the timeout
+    pub goodput: f64,
+    pub failed: f64,
+    /// Client/server pairs with the breaker open
+    #[serde(alias = "open_breakers")]
+    pub open_breakers: usize,
+    /// Client/server pairs suspecting a metastable failure
+    pub metastable: usize,
+    /// Worst queueing plus service latency across servers
+    #[serde(alias = "max_latency_ms")]
+    pub max_latency_ms: f64,
+}
+
+/// Per-step results of a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimReport {
+    pub ticks: Vec<SimTick>,
+}
+
+impl SimReport {
+    /// Goodput over new offered load for steps in `[from_ms, until_ms)`
+    pub fn goodput_ratio(&self, from_ms: f64, until_ms: f64) -> f64 {
+        let (goodput, offered) = self
+            .ticks
+            .iter()
+            .filter(|tick| tick.at_ms >= from_ms && tick.at_ms < until_ms)
+            .fold((0.0, 0.0), |(g, o), tick| {
+                (g + tick.goodput, o + tick.offered)
+            });
+        if offered > 0.0 {
+            goodput / offered
+        } else {
+            1.0
+        }
+    }
+
+    /// Whether any breaker was open in `[from_ms, until_ms)`
+    pub fn tripped(&self, from_ms: f64, until_ms: f64) -> bool {
+        self.ticks
+            .iter()
+            .any(|tick| tick.at_ms >= from_ms && tick.at_ms < until_ms && tick.open_breakers > 0)
+    }
+}
+
+/// Servers, failure scripts and the physics every client runs
+#[derive(Debug, Clone)]
+pub struct VirtualCluster {
+    pub config: SimConfig,
+    pub servers: Vec<ServerSpec>,
+    pub failures: Vec<FailureScript>,
+    pub physics: PhysicsConfig,
+    pub weights: SensitivityWeights,
+    /// Metastable detection on every client engine
+    pub metastable: Option<MetastableConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ServerState {
+    backlog: f64,
+}
+
+impl VirtualCluster {
+    pub fn new(config: SimConfig, servers: Vec<ServerSpec>) -> Self {
+        Self {
+            config,
+            servers,
+            failures: Vec::new(),
+            physics: PhysicsConfig::default(),
+            weights: SensitivityWeights::default(),
+            metastable: None,
+        }
+    }
+
+    /// Add a failure script
+    pub fn fail(mut self, script: FailureScript) -> Self {
+        self.failures.push(script);
+        self
+    }
+
+    /// Physics config and weights for every client's pool
+    pub fn with_physics(mut self, physics: PhysicsConfig, weights: SensitivityWeights) -> Self {
+        self.physics = physics;
+        self.weights = weights;
+        self
+    }
+
+    /// Run metastable detection in every client's engines
+    pub fn detect_metastable(mut self, config: MetastableConfig) -> Self {
+        self.metastable = Some(config);
+        self
+    }
+
+    fn target(server: usize) -> String {
+        format!("server-{server}")
+    }
+
+    /// Active fault on `server` at `now_ms`, the latest script winning
+    fn fault(&self, server: usize, now_ms: f64) -> Option<Fault> {
+        self.failures
+            .iter()
+            .rev()
+            .find(|f| f.server == server && now_ms >= f.from_ms && now_ms < f.until_ms)
+            .map(|f| f.fault)
+    }
+
+    /// Run the simulation to `duration_ms`
+    pub fn run(&self) -> SimReport {
+        let config = &self.config;
+        let dt_ms = config.interval_ms.max(1.0);
+        let servers = self.servers.len();
+        let mut rng = Rng(config.seed);
+        let mut pools: Vec<EnginePool> = (0..config.clients)
+            .map(|_| {
+                let mut pool = EnginePool::new(self.physics.clone(), self.weights.clone());
+                for server in 0..servers {
+                    let engine = pool.add_target(&Self::target(server));
+                    if let Some(config) = self.metastable {
+                        engine.enable_metastable_detection(config);
+                    }
+                }
+                pool
+            })
+            .collect();
+        let mut state = vec![ServerState::default(); servers];
+        // Retries owed by client c to server s
+        let mut retries = vec![vec![0.0; servers]; config.clients];
+        let mut ticks = Vec::new();
+
+        let mut now_ms = 0.0;
+        while now_ms < config.duration_ms {
+            let mut tick = SimTick {
+                at_ms: now_ms,
+                offered: 0.0,
+                admitted: 0.0,
+                shed: 0.0,
+                goodput: 0.0,
+                failed: 0.0,
+                open_breakers: 0,
+                metastable: 0,
+                max_latency_ms: 0.0,
+            };
+            // Client side: offer, gate, send
+            let mut sent = vec![vec![0.0; servers]; config.clients];
+            for (client, pool) in pools.iter().enumerate() {
+                for (server, sent) in sent[client].iter_mut().enumerate() {
+                    let noise = 1.0 + config.noise * (2.0 * rng.unit() - 1.0);
+                    let fresh = config.offered_rps * dt_ms / 1000.0 / servers as f64 * noise;
+                    let attempts = fresh + std::mem::take(&mut retries[client][server]);
+                    let ratio = match (config.gate, pool.get(&Self::target(server))) {
+                        (true, Some(engine)) => engine.admission_ratio(),
+                        _ => 1.0,
+                    };
+                    *sent = attempts * ratio;
+                    tick.offered += fresh;
+                    tick.admitted += *sent;
+                    tick.shed += attempts - *sent;
+                }
+            }
+
+            // Server side: queue, serve, time out
+            let mut pressures = Vec::with_capacity(servers);
+            for (server, spec) in self.servers.iter().enumerate() {
+                let arrivals: f64 = sent.iter().map(|row| row[server]).sum();
+                let fault = self.fault(server, now_ms);
+                let factor = match fault {
+                    Some(Fault::Degraded { factor }) => factor.max(0.0),
+                    _ => 1.0,
+                };
+                let extra_ms = match fault {
+                    Some(Fault::Slow { extra_ms }) => extra_ms,
+                    _ => 0.0,
+                };
+                let capacity = spec.capacity * factor * dt_ms / 1000.0;
+                let backlog = &mut state[server].backlog;
+
+                let (good, failed, latency_ms, utilization) = if fault == Some(Fault::Down) {
+                    let lost = *backlog + arrivals;
+                    *backlog = 0.0;
+                    (0.0, lost, config.timeout_ms, 1.0)
+                } else {
+                    let latency_ms = if capacity > 0.0 {
+                        *backlog / capacity * dt_ms + spec.base_latency_ms + extra_ms
+                    } else {
+                        f64::INFINITY
+                    };
+                    let queued = *backlog + arrivals;
+                    let dropped = (queued - spec.queue_limit).max(0.0);
+                    let served = (queued - dropped).min(capacity);
+                    *backlog = queued - dropped - served;
+                    let utilization = if capacity > 0.0 {
+                        (arrivals / capacity).min(1.0)
+                    } else {
+                        1.0
+                    };
+                    if latency_ms > config.timeout_ms {
+                        (0.0, dropped + served, latency_ms, utilization)
+                    } else {
+                        (served, dropped, latency_ms, utilization)
+                    }
+                };
+                tick.goodput += good;
+                tick.failed += failed;
+                tick.max_latency_ms = tick.max_latency_ms.max(latency_ms.min(1e9));
+
+                // Failures go back to clients in proportion to what they sent
+                if arrivals > 0.0 {
+                    for client in 0..config.clients {
+                        let share = sent[client][server] / arrivals;
+                        retries[client][server] += failed * share * config.retry_ratio;
+                    }
+                }
+                let span = (config.timeout_ms - spec.base_latency_ms).max(1.0);
+                let attempts = good + failed;
+                pressures.push(PressureVector::new(
+                    ((latency_ms - spec.base_latency_ms) / span).clamp(0.0, 1.0),
+                    if attempts > 0.0 {
+                        failed / attempts
+                    } else {
+                        0.0
+                    },
+                    utilization,
+                ));
+            }
+
+            now_ms += dt_ms;
+            for pool in &mut pools {
+                for (server, pressure) in pressures.iter().enumerate() {
+                    let target = Self::target(server);
+                    let state = pool.tick(&target, now_ms, *pressure);
+                    if state.mode == OperationalMode::CircuitBreaker {
+                        tick.open_breakers += 1;
+                    }
+                    if pool.get(&target).is_some_and(|e| e.metastable_suspected()) {
+                        tick.metastable += 1;
+                    }
+                }
+            }
+            ticks.push(tick);
+        }
+        SimReport { ticks }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brownout(gate: bool) -> VirtualCluster {
+        let config = SimConfig {
+            gate,
+            ..SimConfig::default()
+        };
+        VirtualCluster::new(config, vec![ServerSpec::default(); 2]).fail(FailureScript {
+            server: 0,
+            from_ms: 10_000.0,
+            until_ms: 20_000.0,
+            fault: Fault::Degraded { factor: 0.3 },
+        })
+    }
+
+    #[test]
+    fn test_healthy_cluster_serves_everything() {
+        let report =
+            VirtualCluster::new(SimConfig::default(), vec![ServerSpec::default(); 2]).run();
+        assert!(report.goodput_ratio(0.0, 60_000.0) > 0.99);
+        assert!(!report.tripped(0.0, 60_000.0));
+    }
+
+    #[test]
+    fn test_gating_avoids_metastable_failure() {
+        let unprotected = brownout(false).run();
+        let gated = brownout(true).run();
+        // Before the fault both are healthy
+        assert!(unprotected.goodput_ratio(0.0, 10_000.0) > 0.99);
+        assert!(gated.goodput_ratio(0.0, 10_000.0) > 0.99);
+
+        // Retries keep the unprotected server overloaded long after the
+        // fault ends; the gated cluster sheds, drains and recovers
+        assert!(unprotected.goodput_ratio(40_000.0, 60_000.0) < 0.7);
+        assert!(gated
+            .ticks
+            .iter()
+            .any(|tick| (10_000.0..20_000.0).contains(&tick.at_ms) && tick.shed > 0.0));
+        assert!(gated.goodput_ratio(40_000.0, 60_000.0) > 0.95);
+    }
+}
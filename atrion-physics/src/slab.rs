@@ -0,0 +1,248 @@
+/**
+ * Generational slab storage for pool engines.
+ *
+ * Values live in one contiguous vector and keep their slot (a stable
+ * index) until removed; freed slots are reused by later inserts. Ids map
+ * to slots through a separate hash map, so whole-pool passes walk memory
+ * front to back instead of chasing hash buckets.
+ *
+ * Each slot carries a generation that is bumped when its value is
+ * removed. A `Handle` pairs slot and generation, so callers (including JS,
+ * as a u64) can address a value without hashing its id, and a handle to a
+ * removed value never resolves to whatever reuses the slot.
+ */
+use std::collections::HashMap;
+
+/// Small copyable reference to a slab value
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: u32,
+    generation: u32,
+}
+
+impl Handle {
+    pub fn slot(self) -> usize {
+        self.slot as usize
+    }
+
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+
+    /// Packed form: generation in the high 32 bits, slot in the low
+    pub fn to_bits(self) -> u64 {
+        (self.generation as u64) << 32 | self.slot as u64
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        Self {
+            slot: bits as u32,
+            generation: (bits >> 32) as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u32,
+    entry: Option<(String, T)>,
+}
+
+/// Id-keyed values in stable, generation-checked slots
+#[derive(Debug, Clone)]
+pub struct Slab<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    index: HashMap<String, usize>,
+}
+
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Slab<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn contains_key(&self, id: &str) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// Slot of `id` (stable until it is removed)
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// Handle to `id`'s current value
+    pub fn handle_of(&self, id: &str) -> Option<Handle> {
+        let slot = self.index_of(id)?;
+        Some(Handle {
+            slot: slot as u32,
+            generation: self.slots[slot].generation,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&T> {
+        self.get_at(self.index_of(id)?).map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut T> {
+        let slot = self.index_of(id)?;
+        self.get_at_mut(slot).map(|(_, value)| value)
+    }
+
+    pub fn get_at(&self, slot: usize) -> Option<(&String, &T)> {
+        self.slots
+            .get(slot)?
+            .entry
+            .as_ref()
+            .map(|(id, value)| (id, value))
+    }
+
+    pub fn get_at_mut(&mut self, slot: usize) -> Option<(&String, &mut T)> {
+        self.slots
+            .get_mut(slot)?
+            .entry
+            .as_mut()
+            .map(|(id, value)| (&*id, value))
+    }
+
+    /// Value behind `handle`, or `None` once it has been removed
+    pub fn resolve(&self, handle: Handle) -> Option<(&String, &T)> {
+        match self.slots.get(handle.slot())? {
+            slot if slot.generation == handle.generation => self.get_at(handle.slot()),
+            _ => None,
+        }
+    }
+
+    pub fn resolve_mut(&mut self, handle: Handle) -> Option<(&String, &mut T)> {
+        match self.slots.get(handle.slot())? {
+            slot if slot.generation == handle.generation => self.get_at_mut(handle.slot()),
+            _ => None,
+        }
+    }
+
+    /// Insert or replace, returning the previous value
+    ///
+    /// A replaced value keeps its slot and handle; a new id takes a freed
+    /// slot first.
+    pub fn insert(&mut self, id: String, value: T) -> Option<T> {
+        if let Some(slot) = self.index_of(&id) {
+            let entry = self.slots[slot]
+                .entry
+                .as_mut()
+                .expect("indexed slot is occupied");
+            return Some(std::mem::replace(&mut entry.1, value));
+        }
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: None,
+                });
+                self.slots.len() - 1
+            }
+        };
+        self.index.insert(id.clone(), slot);
+        self.slots[slot].entry = Some((id, value));
+        None
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<T> {
+        let slot = self.index.remove(id)?;
+        self.free.push(slot);
+        let slot = &mut self.slots[slot];
+        slot.generation = slot.generation.wrapping_add(1);
+        slot.entry.take().map(|(_, value)| value)
+    }
+
+    /// Occupied slots in memory order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &T)> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.entry.as_ref().map(|(id, value)| (id, value)))
+    }
+
+    /// Occupied slots in memory order, values mutable
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut T)> {
+        self.slots
+            .iter_mut()
+            .filter_map(|slot| slot.entry.as_mut().map(|(id, value)| (&*id, value)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.iter().map(|(id, _)| id)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slots_are_stable_and_reused() {
+        let mut slab = Slab::new();
+        assert_eq!(slab.insert("a".to_string(), 1), None);
+        assert_eq!(slab.insert("b".to_string(), 2), None);
+        assert_eq!(slab.insert("c".to_string(), 3), None);
+        assert_eq!(slab.index_of("b"), Some(1));
+
+        assert_eq!(slab.insert("b".to_string(), 20), Some(2));
+        assert_eq!(slab.index_of("b"), Some(1));
+
+        assert_eq!(slab.remove("a"), Some(1));
+        assert_eq!(slab.remove("a"), None);
+        assert_eq!(slab.index_of("c"), Some(2));
+        slab.insert("d".to_string(), 4);
+        assert_eq!(slab.index_of("d"), Some(0));
+
+        let order: Vec<&str> = slab.keys().map(String::as_str).collect();
+        assert_eq!(order, ["d", "b", "c"]);
+        assert_eq!(slab.len(), 3);
+        assert_eq!(slab.get("c"), Some(&3));
+        *slab.get_mut("c").unwrap() += 1;
+        assert_eq!(slab.get_at(2), Some((&"c".to_string(), &4)));
+    }
+
+    #[test]
+    fn test_stale_handles_do_not_resolve() {
+        let mut slab = Slab::new();
+        slab.insert("a".to_string(), 1);
+        let a = slab.handle_of("a").unwrap();
+        assert_eq!(Handle::from_bits(a.to_bits()), a);
+        assert_eq!(slab.resolve(a), Some((&"a".to_string(), &1)));
+
+        slab.remove("a");
+        slab.insert("b".to_string(), 2);
+        let b = slab.handle_of("b").unwrap();
+        assert_eq!((b.slot(), b.generation()), (a.slot(), 1));
+        assert!(slab.resolve(a).is_none());
+        *slab.resolve_mut(b).unwrap().1 += 1;
+        assert_eq!(slab.get("b"), Some(&3));
+        assert!(slab.resolve(Handle::from_bits(5)).is_none());
+    }
+}
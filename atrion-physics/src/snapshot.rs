@@ -0,0 +1,98 @@
+/**
+ * Pool snapshots (persist / restore / transfer state).
+ *
+ * Snapshots carry per-target physics state only; history and admission
+ * accounting are intentionally excluded to keep them compact.
+ *
+ * `DeltaSnapshot`s carry only targets changed since the previous
+ * checkpoint; applying a pool's deltas in order rebuilds it.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::diff::{self, SnapshotDiff};
+use crate::engine::TargetState;
+use crate::error::AtrionError;
+use crate::quarantine::QuarantineList;
+
+/// Current snapshot format version
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// State of a single target inside a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct TargetSnapshot {
+    pub id: String,
+    pub state: TargetState,
+}
+
+/// Point-in-time copy of a pool's target states
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Snapshot {
+    pub version: u32,
+    #[serde(alias = "taken_at_ms")]
+    pub taken_at_ms: f64,
+    /// Sorted by id for deterministic output
+    pub targets: Vec<TargetSnapshot>,
+    /// Quarantine entries in force when the snapshot was taken
+    #[serde(default)]
+    pub quarantine: QuarantineList,
+}
+
+fn check_version(version: u32) -> Result<(), AtrionError> {
+    if version == SNAPSHOT_VERSION {
+        Ok(())
+    } else {
+        Err(AtrionError::SnapshotVersion {
+            found: version,
+            expected: SNAPSHOT_VERSION,
+        })
+    }
+}
+
+impl Snapshot {
+    /// Reject snapshots written by an incompatible format version
+    pub fn check_version(&self) -> Result<(), AtrionError> {
+        check_version(self.version)
+    }
+
+    /// Per-target changes from `self` (before) to `other` (after)
+    pub fn diff(&self, other: &Snapshot) -> SnapshotDiff {
+        diff::diff(self, other)
+    }
+}
+
+/// Targets changed since the previous checkpoint of the same pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DeltaSnapshot {
+    pub version: u32,
+    /// Previous checkpoint (`None` for the first, which holds every target)
+    pub since_ms: Option<f64>,
+    pub taken_at_ms: f64,
+    /// Changed or added targets, sorted by id
+    pub targets: Vec<TargetSnapshot>,
+    /// Targets removed since the previous checkpoint, sorted
+    #[serde(default)]
+    pub removed: Vec<String>,
+    /// Full quarantine list (small, so never diffed)
+    #[serde(default)]
+    pub quarantine: QuarantineList,
+}
+
+impl DeltaSnapshot {
+    pub fn check_version(&self) -> Result<(), AtrionError> {
+        check_version(self.version)
+    }
+
+    /// True when nothing changed since the previous checkpoint
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty() && self.removed.is_empty()
+    }
+}
@@ -0,0 +1,181 @@
+/**
+ * Soft start after circuit-breaker recovery.
+ *
+ * When the breaker closes, steady-state resistance is usually far below
+ * the breaker's, and admitting at the steady level at once lets a burst
+ * of queued retries hit a target that has only just recovered. With
+ * `PhysicsConfig::soft_start` set the engine keeps the excess and lets it
+ * fall away over `window_ms`, linearly or exponentially, on top of the
+ * steady-state resistance.
+ *
+ * The ramp is applied after the mode decision, so it cannot re-trip the
+ * breaker by itself; a real re-trip (or a forced mode) ends it early.
+ */
+use serde::{Deserialize, Serialize};
+
+/// How the excess resistance decays over the window
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RampShape {
+    /// Straight line to zero at the end of the window
+    #[default]
+    Linear,
+    /// e^(-5t/window): steep at first, under 1% left at the end
+    Exponential,
+}
+
+/// Soft-start configuration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct SoftStartConfig {
+    /// Time for the excess resistance to fall away
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    #[serde(default)]
+    pub shape: RampShape,
+}
+
+impl Default for SoftStartConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 10_000.0,
+            shape: RampShape::Linear,
+        }
+    }
+}
+
+/// Exponential decay rate per window
+const EXP_RATE: f64 = 5.0;
+
+/// A running recovery ramp
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ramp {
+    config: SoftStartConfig,
+    started_ms: f64,
+    /// Breaker resistance minus steady resistance when the breaker closed
+    excess: f64,
+}
+
+impl Ramp {
+    /// Ramp from `breaker` down to `steady` resistance; `None` when there
+    /// is nothing to ramp
+    pub fn start(config: SoftStartConfig, now_ms: f64, breaker: f64, steady: f64) -> Option<Self> {
+        let excess = breaker - steady;
+        (excess > 0.0 && config.window_ms > 0.0).then_some(Self {
+            config,
+            started_ms: now_ms,
+            excess,
+        })
+    }
+
+    pub fn excess(&self) -> f64 {
+        self.excess
+    }
+
+    pub fn started_ms(&self) -> f64 {
+        self.started_ms
+    }
+
+    /// Resistance added at `now_ms`, or `None` once the window has passed
+    pub fn surcharge(&self, now_ms: f64) -> Option<f64> {
+        let progress = ((now_ms - self.started_ms) / self.config.window_ms).max(0.0);
+        if progress >= 1.0 {
+            return None;
+        }
+        let left = match self.config.shape {
+            RampShape::Linear => 1.0 - progress,
+            RampShape::Exponential => (-EXP_RATE * progress).exp(),
+        };
+        Some(self.excess * left)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::events::EngineEvent;
+    use crate::types::{OperationalMode, PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_ramp_shapes() {
+        let linear = Ramp::start(SoftStartConfig::default(), 1_000.0, 90.0, 20.0).unwrap();
+        assert_eq!(linear.surcharge(1_000.0), Some(70.0));
+        assert_eq!(linear.surcharge(6_000.0), Some(35.0));
+        assert_eq!(linear.surcharge(11_000.0), None);
+
+        let config = SoftStartConfig {
+            shape: RampShape::Exponential,
+            ..SoftStartConfig::default()
+        };
+        let exp = Ramp::start(config, 0.0, 90.0, 20.0).unwrap();
+        assert!(exp.surcharge(2_000.0).unwrap() < linear.surcharge(3_000.0).unwrap());
+        assert!(exp.surcharge(9_999.0).unwrap() < 0.01 * 70.0);
+        assert!(Ramp::start(config, 0.0, 20.0, 30.0).is_none());
+    }
+
+    #[test]
+    fn test_breaker_recovery_ramps_down() {
+        let config = PhysicsConfig {
+            soft_start: Some(SoftStartConfig::default()),
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        let calm = PressureVector::new(0.0, 0.0, 0.0);
+        let storm = PressureVector::new(1.0, 1.0, 1.0);
+        let mut now = 0.0;
+        let mut step = |engine: &mut TargetEngine, pressure, dt| {
+            now += dt;
+            engine.tick(now, pressure)
+        };
+        for _ in 0..10 {
+            step(&mut engine, calm, 100.0);
+        }
+        while engine.state().mode != OperationalMode::CircuitBreaker {
+            step(&mut engine, storm, 100.0);
+        }
+        let mut breaker = *engine.state();
+        let mut closing = breaker;
+        while closing.mode == OperationalMode::CircuitBreaker {
+            breaker = closing;
+            closing = step(&mut engine, calm, 1_000.0);
+        }
+        // The closing tick still resists like the breaker did
+        assert!((closing.resistance.0 - breaker.resistance.0).abs() < 1e-9);
+
+        let mut previous = closing.resistance.0;
+        for _ in 0..10 {
+            let state = step(&mut engine, calm, 1_000.0);
+            assert_eq!(state.mode, OperationalMode::Operational);
+            assert!(state.resistance.0 < previous);
+            previous = state.resistance.0;
+        }
+        let events: Vec<_> = engine
+            .drain_events()
+            .into_iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    EngineEvent::SoftStartBegan { .. } | EngineEvent::SoftStartEnded { .. }
+                )
+            })
+            .collect();
+        assert!(matches!(
+            events[..],
+            [
+                EngineEvent::SoftStartBegan { .. },
+                EngineEvent::SoftStartEnded {
+                    completed: true,
+                    ..
+                }
+            ]
+        ));
+    }
+}
@@ -0,0 +1,207 @@
+/**
+ * Pressure sources: the ingestion side of admission control.
+ *
+ * A `PressureSource` answers "what is the pressure on this target right
+ * now?" with a timestamped vector, or `None` when it has nothing new.
+ * `tick_pool` polls one for every registered target and ticks the pool
+ * with whatever came back, so consumers wire a source to a pool instead
+ * of writing per-backend glue.
+ *
+ * Built-in adapters:
+ * - closures `FnMut(&str) -> Option<TimestampedPressure>`
+ * - `LatestPressure`: push-fed, hands each sample out once
+ * - `healthcheck::HealthCheckSource`: active probes blended with passive
+ * - `prometheus::PrometheusSource` (feature "prometheus"): instant queries
+ * - `OtlpSource` (feature "ingest"): OTLP/JSON metric export payloads
+ */
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::pool::EnginePool;
+use crate::types::PressureVector;
+
+/// Pressure observed at a point in time
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimestampedPressure {
+    pub timestamp_ms: f64,
+    pub pressure: PressureVector,
+}
+
+/// Anything that can report a target's current pressure
+pub trait PressureSource {
+    /// Newest pressure for `target`, or `None` when there is nothing new
+    fn poll(&mut self, target: &str) -> Option<TimestampedPressure>;
+}
+
+impl<F: FnMut(&str) -> Option<TimestampedPressure>> PressureSource for F {
+    fn poll(&mut self, target: &str) -> Option<TimestampedPressure> {
+        self(target)
+    }
+}
+
+/// Poll `source` for every registered target and tick the ones it
+/// answered for at their sample timestamps; returns how many were ticked
+pub fn tick_pool(pool: &mut EnginePool, source: &mut impl PressureSource) -> usize {
+    let ids: Vec<String> = pool.iter().map(|(id, _)| id.clone()).collect();
+    let mut ticked = 0;
+    for id in ids {
+        if let Some(sample) = source.poll(&id) {
+            pool.tick(&id, sample.timestamp_ms, sample.pressure);
+            ticked += 1;
+        }
+    }
+    ticked
+}
+
+/// Newest pushed sample per target, each handed out by `poll` once
+#[derive(Debug, Clone, Default)]
+pub struct LatestPressure {
+    latest: HashMap<String, TimestampedPressure>,
+}
+
+impl LatestPressure {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep `sample` unless a newer one is already waiting
+    pub fn push(&mut self, target: &str, sample: TimestampedPressure) {
+        match self.latest.get_mut(target) {
+            Some(waiting) if waiting.timestamp_ms > sample.timestamp_ms => {}
+            Some(waiting) => *waiting = sample,
+            None => {
+                self.latest.insert(target.to_string(), sample);
+            }
+        }
+    }
+
+    /// Targets with a sample waiting
+    pub fn len(&self) -> usize {
+        self.latest.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.latest.is_empty()
+    }
+}
+
+impl PressureSource for LatestPressure {
+    fn poll(&mut self, target: &str) -> Option<TimestampedPressure> {
+        self.latest.remove(target)
+    }
+}
+
+// ============================================================================
+// OTLP (feature "ingest")
+// ============================================================================
+
+#[cfg(all(feature = "ingest", not(target_arch = "wasm32")))]
+pub use otlp::OtlpSource;
+
+#[cfg(all(feature = "ingest", not(target_arch = "wasm32")))]
+mod otlp {
+    use super::{LatestPressure, PressureSource, TimestampedPressure};
+    use crate::error::AtrionError;
+    use crate::ingest::{self, TraceMapping};
+
+    /// Source fed with OTLP/JSON `ExportMetricsServiceRequest` payloads
+    ///
+    /// Metric names map onto axes through a `TraceMapping`, exactly as in
+    /// `ingest::read_otlp`; the newest point per target is kept.
+    #[derive(Debug, Clone)]
+    pub struct OtlpSource {
+        mapping: TraceMapping,
+        latest: LatestPressure,
+    }
+
+    impl OtlpSource {
+        pub fn new(mapping: TraceMapping) -> Self {
+            Self {
+                mapping,
+                latest: LatestPressure::new(),
+            }
+        }
+
+        /// Take one or more newline-separated payloads; returns the number
+        /// of samples they carried
+        pub fn push(&mut self, payload: &str) -> Result<u64, AtrionError> {
+            let latest = &mut self.latest;
+            ingest::read_otlp(payload.as_bytes(), &self.mapping, |target, sample| {
+                latest.push(
+                    target,
+                    TimestampedPressure {
+                        timestamp_ms: sample.timestamp_ms,
+                        pressure: sample.pressure,
+                    },
+                )
+            })
+        }
+    }
+
+    impl PressureSource for OtlpSource {
+        fn poll(&mut self, target: &str) -> Option<TimestampedPressure> {
+            self.latest.poll(target)
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, SensitivityWeights};
+
+    #[test]
+    fn test_latest_pressure_feeds_pool_once() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        pool.add_target("api");
+        pool.add_target("db");
+        let mut source = LatestPressure::new();
+        let at = |timestamp_ms| TimestampedPressure {
+            timestamp_ms,
+            pressure: PressureVector::new(0.3, 0.0, 0.3),
+        };
+        source.push("api", at(2_000.0));
+        source.push("api", at(1_000.0));
+        source.push("unknown", at(1_000.0));
+
+        assert_eq!(tick_pool(&mut pool, &mut source), 1);
+        assert_eq!(pool.get("api").unwrap().state().last_updated_ms, 2_000.0);
+        assert_eq!(tick_pool(&mut pool, &mut source), 0);
+        assert_eq!(source.len(), 1);
+
+        let mut constant = |_: &str| Some(at(3_000.0));
+        assert_eq!(tick_pool(&mut pool, &mut constant), 2);
+    }
+
+    #[cfg(all(feature = "ingest", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_otlp_source_keeps_newest_point() {
+        use crate::ingest::{AxisMapping, TraceMapping};
+
+        let mapping = TraceMapping {
+            target: "service.name".to_string(),
+            latency: Some(AxisMapping::new("p99_ms", 0.0, 1000.0)),
+            error: None,
+            saturation: None,
+            ..TraceMapping::default()
+        };
+        let mut source = OtlpSource::new(mapping);
+        let payload = |nanos: &str, value: f64| {
+            format!(
+                r#"{{"resourceMetrics":[{{"resource":{{"attributes":[{{"key":"service.name","value":{{"stringValue":"api"}}}}]}},"scopeMetrics":[{{"metrics":[{{"name":"p99_ms","gauge":{{"dataPoints":[{{"timeUnixNano":"{nanos}","asDouble":{value}}}]}}}}]}}]}}]}}"#
+            )
+        };
+        let batch = payload("2000000000", 500.0) + "\n" + &payload("1000000000", 900.0);
+        assert_eq!(source.push(&batch).unwrap(), 2);
+        let sample = source.poll("api").unwrap();
+        assert_eq!(sample.timestamp_ms, 2_000.0);
+        assert_eq!(sample.pressure.latency, 0.5);
+        assert!(source.poll("api").is_none());
+    }
+}
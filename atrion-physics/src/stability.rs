@@ -0,0 +1,282 @@
+/**
+ * Static stability analysis of a PhysicsConfig.
+ *
+ * Checks a config for oscillation and dead-zone risks before it reaches
+ * production, using closed forms of the update laws at a nominal tick
+ * interval Δt:
+ *
+ * - Momentum spike for a unit step: μ·√3·(1 − e^(−Δt/h))/Δt. When this
+ *   exceeds the steady pressure term, resistance overshoots and rings.
+ * - Scar equilibrium under sustained trauma: σ / (1 − e^(−λΔt)), and
+ *   the time to decay back below σ (breaker recovery).
+ * - Breaker hysteresis band vs a single trauma increment (chatter).
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::scar::SCAR_DECAY_RATE;
+use crate::types::{PhysicsConfig, SensitivityWeights};
+
+/// Largest pressure-delta magnitude for a [0,1]³ step
+const MAX_STEP: f64 = 1.732_050_807_568_877_2; // √3
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// One finding, with a suggested range for the offending parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StabilityIssue {
+    pub severity: Severity,
+    /// Stable identifier (e.g. "momentum_ringing")
+    pub code: String,
+    pub parameter: String,
+    pub message: String,
+    /// `(min, max)` for `parameter` that clears the finding
+    pub suggested: Option<(f64, f64)>,
+}
+
+/// Analysis result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StabilityReport {
+    #[serde(alias = "tick_interval_ms")]
+    pub tick_interval_ms: f64,
+    /// Peak momentum contribution / steady pressure term for a full unit step
+    #[serde(alias = "step_overshoot")]
+    pub step_overshoot: f64,
+    /// Scar level under sustained trauma
+    #[serde(alias = "scar_equilibrium")]
+    pub scar_equilibrium: f64,
+    /// Time from scar equilibrium back below `scar_factor`
+    #[serde(alias = "scar_recovery_ms")]
+    pub scar_recovery_ms: f64,
+    pub issues: Vec<StabilityIssue>,
+}
+
+impl StabilityReport {
+    /// No warnings or errors (info findings are allowed)
+    pub fn is_stable(&self) -> bool {
+        self.issues.iter().all(|i| i.severity == Severity::Info)
+    }
+
+    pub fn worst(&self) -> Option<Severity> {
+        self.issues.iter().map(|i| i.severity).max()
+    }
+}
+
+/// Analyze `config` for a target ticked every `tick_interval_ms`
+pub fn analyze(
+    config: &PhysicsConfig,
+    weights: &SensitivityWeights,
+    tick_interval_ms: f64,
+) -> StabilityReport {
+    let dt = tick_interval_ms.max(1e-3);
+    let h = config.momentum_halflife.max(1e-9);
+    let mut issues = Vec::new();
+    let mut issue = |severity, code: &str, parameter: &str, message: String, suggested| {
+        issues.push(StabilityIssue {
+            severity,
+            code: code.to_string(),
+            parameter: parameter.to_string(),
+            message,
+            suggested,
+        })
+    };
+
+    // Momentum ringing under a full step
+    let gain = (1.0 - (-dt / h).exp()) / dt;
+    let spike = config.damping_factor * MAX_STEP * gain;
+    let steady = weights.w_latency + weights.w_error + weights.w_saturation;
+    let step_overshoot = if steady > 0.0 { spike / steady } else { 0.0 };
+    if step_overshoot > 1.0 {
+        issue(
+            Severity::Warning,
+            "momentum_ringing",
+            "damping_factor",
+            format!(
+                "a step input spikes resistance by {spike:.1}Ω from momentum, {step_overshoot:.1}× the steady pressure term; resistance will overshoot and ring"
+            ),
+            Some((0.0, steady / (MAX_STEP * gain))),
+        );
+    }
+    if spike >= config.break_threshold - config.base_resistance {
+        issue(
+            Severity::Error,
+            "momentum_trip",
+            "damping_factor",
+            format!("momentum alone ({spike:.1}Ω) can trip the breaker on a single step"),
+            Some((
+                0.0,
+                0.5 * (config.break_threshold - config.base_resistance) / (MAX_STEP * gain),
+            )),
+        );
+    }
+    if h < 5.0 * dt {
+        issue(
+            Severity::Warning,
+            "momentum_unsmoothed",
+            "momentum_halflife",
+            format!("halflife {h}ms is under 5 ticks; momentum follows per-tick noise"),
+            Some((5.0 * dt, 100.0 * dt)),
+        );
+    }
+
+    // Breaker hysteresis
+    let band = config.break_threshold - config.recovery_threshold;
+    if band <= 0.0 {
+        issue(
+            Severity::Error,
+            "no_hysteresis",
+            "recovery_threshold",
+            "recovery_threshold must be below break_threshold or the breaker flaps".to_string(),
+            Some((config.base_resistance, config.break_threshold * 0.75)),
+        );
+    } else if band < config.scar_factor {
+        issue(
+            Severity::Warning,
+            "narrow_hysteresis",
+            "recovery_threshold",
+            format!(
+                "hysteresis band {band:.1}Ω is narrower than one trauma increment ({:.1}); expect chatter",
+                config.scar_factor
+            ),
+            Some((
+                config.base_resistance,
+                config.break_threshold - 2.0 * config.scar_factor,
+            )),
+        );
+    }
+    if config.recovery_threshold <= config.base_resistance {
+        issue(
+            Severity::Info,
+            "resistance_recovery_unreachable",
+            "recovery_threshold",
+            "resistance never falls below base_resistance; breakers only recover via scar decay"
+                .to_string(),
+            Some((config.base_resistance * 2.0, config.break_threshold * 0.75)),
+        );
+    }
+
+    // Reachability: full pressure plus sustained scar must be able to trip
+    let decay = (-SCAR_DECAY_RATE * dt / 1000.0).exp();
+    let scar_equilibrium = config.scar_factor.max(0.0) / (1.0 - decay).max(1e-12);
+    if config.base_resistance + steady + scar_equilibrium < config.break_threshold {
+        issue(
+            Severity::Warning,
+            "unreachable_break",
+            "break_threshold",
+            "even sustained full pressure cannot reach break_threshold; the breaker never trips"
+                .to_string(),
+            Some((
+                config.base_resistance + steady,
+                config.base_resistance + steady + scar_equilibrium,
+            )),
+        );
+    }
+
+    let scar_recovery_ms = if config.scar_factor > 0.0 {
+        (scar_equilibrium / config.scar_factor).ln().max(0.0) / SCAR_DECAY_RATE * 1000.0
+    } else {
+        0.0
+    };
+    if scar_recovery_ms > 300_000.0 {
+        issue(
+            Severity::Info,
+            "slow_recovery",
+            "scar_factor",
+            format!(
+                "after a sustained storm the breaker needs ~{:.0}s of calm to recover",
+                scar_recovery_ms / 1000.0
+            ),
+            None,
+        );
+    }
+
+    StabilityReport {
+        tick_interval_ms: dt,
+        step_overshoot,
+        scar_equilibrium,
+        scar_recovery_ms,
+        issues,
+    }
+}
+
+/// Stability report as a plain object
+#[wasm_bindgen(js_name = checkStability)]
+pub fn analyze_js(
+    config: &PhysicsConfig,
+    weights: &SensitivityWeights,
+    tick_interval_ms: f64,
+) -> Result<JsValue, JsValue> {
+    crate::to_js(&analyze(config, weights, tick_interval_ms))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_stable() {
+        let report = analyze(
+            &PhysicsConfig::default(),
+            &SensitivityWeights::default(),
+            100.0,
+        );
+        assert!(report.is_stable(), "{:?}", report.issues);
+        assert!(report.step_overshoot < 1.0);
+        // ln(σ*/σ)/λ with σ* ≈ σ/0.00995 → ≈ 46s
+        assert!((report.scar_recovery_ms / 1000.0 - 46.1).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_high_damping_short_halflife_rings() {
+        let config = PhysicsConfig {
+            damping_factor: 20_000.0,
+            momentum_halflife: 200.0,
+            ..PhysicsConfig::default()
+        };
+        let report = analyze(&config, &SensitivityWeights::default(), 100.0);
+        let codes: Vec<&str> = report.issues.iter().map(|i| i.code.as_str()).collect();
+        assert!(codes.contains(&"momentum_ringing"));
+        assert!(codes.contains(&"momentum_unsmoothed"));
+        assert_eq!(report.worst(), Some(Severity::Error));
+
+        // Following the suggestion clears the ringing warning
+        let ringing = report
+            .issues
+            .iter()
+            .find(|i| i.code == "momentum_ringing")
+            .unwrap();
+        let fixed = PhysicsConfig {
+            damping_factor: ringing.suggested.unwrap().1 * 0.9,
+            momentum_halflife: 1000.0,
+            ..config
+        };
+        assert!(analyze(&fixed, &SensitivityWeights::default(), 100.0).is_stable());
+    }
+
+    #[test]
+    fn test_hysteresis_checks() {
+        let flapping = PhysicsConfig {
+            recovery_threshold: 100.0,
+            ..PhysicsConfig::default()
+        };
+        let report = analyze(&flapping, &SensitivityWeights::default(), 100.0);
+        assert!(report.issues.iter().any(|i| i.code == "no_hysteresis"));
+
+        let narrow = PhysicsConfig {
+            recovery_threshold: 97.0,
+            ..PhysicsConfig::default()
+        };
+        let report = analyze(&narrow, &SensitivityWeights::default(), 100.0);
+        assert!(report.issues.iter().any(|i| i.code == "narrow_hysteresis"));
+    }
+}
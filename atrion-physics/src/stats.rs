@@ -0,0 +1,197 @@
+/**
+ * Rolling statistics over the engine history.
+ *
+ * Summaries are computed on demand from the history ring buffer, so the
+ * longest usable window is bounded by `history_capacity` × tick interval.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::goodput::GoodputStats;
+use crate::history::History;
+
+/// Standard dashboard windows (1m / 5m / 15m)
+pub const WINDOW_1M_MS: f64 = 60_000.0;
+pub const WINDOW_5M_MS: f64 = 300_000.0;
+pub const WINDOW_15M_MS: f64 = 900_000.0;
+
+/// Resistance statistics over a single time window
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingStats {
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    pub samples: u32,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    /// Ticks where scar grew (trauma was recorded)
+    #[serde(alias = "trauma_events")]
+    pub trauma_events: u32,
+    /// Outcomes reported via `record_outcomes` (filled in by the engine)
+    #[serde(default)]
+    pub goodput: GoodputStats,
+}
+
+/// Statistics for all standard windows
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingSummary {
+    #[serde(alias = "one_minute")]
+    pub one_minute: RollingStats,
+    #[serde(alias = "five_minutes")]
+    pub five_minutes: RollingStats,
+    #[serde(alias = "fifteen_minutes")]
+    pub fifteen_minutes: RollingStats,
+}
+
+/// Compute statistics over samples in `(now_ms - window_ms, now_ms]`
+///
+/// Returns zeroed stats (with `samples == 0`) for an empty window.
+pub fn rolling_stats(history: &History, now_ms: f64, window_ms: f64) -> RollingStats {
+    let start_ms = now_ms - window_ms;
+    let mut resistances: Vec<f64> = Vec::new();
+    let mut trauma_events = 0;
+    let mut previous_scar: Option<f64> = None;
+
+    for sample in history.iter() {
+        if sample.timestamp_ms > start_ms && sample.timestamp_ms <= now_ms {
+            resistances.push(sample.resistance.0);
+            if previous_scar.is_some_and(|prev| sample.scar.0 > prev) {
+                trauma_events += 1;
+            }
+        }
+        previous_scar = Some(sample.scar.0);
+    }
+
+    if resistances.is_empty() {
+        return RollingStats {
+            window_ms,
+            ..RollingStats::default()
+        };
+    }
+
+    resistances.sort_by(f64::total_cmp);
+    let sum: f64 = resistances.iter().sum();
+
+    RollingStats {
+        window_ms,
+        samples: resistances.len() as u32,
+        min: resistances[0],
+        max: resistances[resistances.len() - 1],
+        mean: sum / resistances.len() as f64,
+        p50: percentile(&resistances, 0.50),
+        p90: percentile(&resistances, 0.90),
+        p99: percentile(&resistances, 0.99),
+        trauma_events,
+        goodput: GoodputStats::default(),
+    }
+}
+
+/// Statistics for the 1m / 5m / 15m windows ending at `now_ms`
+pub fn rolling_summary(history: &History, now_ms: f64) -> RollingSummary {
+    RollingSummary {
+        one_minute: rolling_stats(history, now_ms, WINDOW_1M_MS),
+        five_minutes: rolling_stats(history, now_ms, WINDOW_5M_MS),
+        fifteen_minutes: rolling_stats(history, now_ms, WINDOW_15M_MS),
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice
+#[inline]
+pub(crate) fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let rank = (q * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::HistorySample;
+    use crate::types::*;
+
+    fn history_with(points: &[(f64, f64, f64)]) -> History {
+        let mut history = History::new(points.len());
+        for &(timestamp_ms, resistance, scar) in points {
+            history.push(HistorySample {
+                timestamp_ms,
+                pressure: PressureVector::new(0.0, 0.0, 0.0),
+                resistance: Ohms(resistance),
+                scar: Scar(scar),
+                momentum: Momentum(0.0),
+                mode: OperationalMode::Operational,
+            });
+        }
+        history
+    }
+
+    #[test]
+    fn test_empty_window() {
+        let history = History::new(4);
+        let stats = rolling_stats(&history, 1000.0, WINDOW_1M_MS);
+        assert_eq!(stats.samples, 0);
+        assert_eq!(stats.window_ms, WINDOW_1M_MS);
+    }
+
+    #[test]
+    fn test_min_max_mean_percentiles() {
+        let points: Vec<_> = (1..=100).map(|i| (i as f64, i as f64, 0.0)).collect();
+        let stats = rolling_stats(&history_with(&points), 100.0, WINDOW_1M_MS);
+
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 100.0);
+        assert!((stats.mean - 50.5).abs() < 1e-10);
+        assert_eq!(stats.p50, 50.0);
+        assert_eq!(stats.p90, 90.0);
+        assert_eq!(stats.p99, 99.0);
+    }
+
+    #[test]
+    fn test_window_excludes_old_samples_and_counts_trauma() {
+        let history = history_with(&[
+            (0.0, 50.0, 5.0),
+            (70_000.0, 10.0, 4.9),
+            (80_000.0, 20.0, 9.9),
+            (90_000.0, 15.0, 9.8),
+        ]);
+
+        let stats = rolling_stats(&history, 90_000.0, WINDOW_1M_MS);
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.max, 20.0);
+        assert_eq!(stats.trauma_events, 1);
+
+        let summary = rolling_summary(&history, 90_000.0);
+        assert_eq!(summary.five_minutes.samples, 4);
+    }
+
+    #[test]
+    fn test_json_is_camel_case_and_accepts_snake_case() {
+        let stats = RollingStats {
+            window_ms: WINDOW_1M_MS,
+            trauma_events: 2,
+            ..RollingStats::default()
+        };
+        let json = serde_json::to_value(RollingSummary {
+            one_minute: stats,
+            ..RollingSummary::default()
+        })
+        .unwrap();
+        assert_eq!(json["oneMinute"]["windowMs"], WINDOW_1M_MS);
+        assert_eq!(json["oneMinute"]["traumaEvents"], 2);
+        assert!(json["oneMinute"].get("trauma_events").is_none());
+
+        let legacy: RollingStats = serde_json::from_str(
+            r#"{ "window_ms": 60000, "samples": 0, "min": 0, "max": 0,
+                "mean": 0, "p50": 0, "p90": 0, "p99": 0, "trauma_events": 2 }"#,
+        )
+        .unwrap();
+        assert_eq!(legacy, stats);
+    }
+}
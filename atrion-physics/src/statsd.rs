@@ -0,0 +1,222 @@
+/**
+ * StatsD / DogStatsD emitter (feature "statsd", native only).
+ *
+ * Pushes per-target gauges over UDP at a fixed interval, for teams on
+ * Datadog without an OTLP collector. Tags use the DogStatsD `|#k:v`
+ * extension; plain StatsD servers can disable them.
+ */
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::engine::TargetState;
+use crate::metrics::mode_value;
+use crate::pool::EnginePool;
+
+/// Max payload per datagram (fits a 1500-byte MTU after IP/UDP headers)
+const MAX_PACKET_BYTES: usize = 1432;
+
+/// Emitter configuration
+#[derive(Debug, Clone)]
+pub struct StatsdConfig {
+    /// Metric name prefix (e.g. "atrion")
+    pub prefix: String,
+    /// Push interval for `spawn`
+    pub interval_ms: u64,
+    /// Emit DogStatsD tags (target, mode, plus `global_tags`)
+    pub tags: bool,
+    /// Extra tags appended to every metric
+    pub global_tags: Vec<(String, String)>,
+}
+
+impl Default for StatsdConfig {
+    fn default() -> Self {
+        Self {
+            prefix: "atrion".to_string(),
+            interval_ms: 10_000,
+            tags: true,
+            global_tags: Vec::new(),
+        }
+    }
+}
+
+/// UDP gauge emitter
+pub struct StatsdEmitter {
+    socket: UdpSocket,
+    config: StatsdConfig,
+}
+
+impl StatsdEmitter {
+    /// Bind an ephemeral local port and connect to the StatsD server
+    pub fn connect(server: SocketAddr, config: StatsdConfig) -> io::Result<Self> {
+        let local: SocketAddr = if server.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(server)?;
+        Ok(Self { socket, config })
+    }
+
+    pub fn config(&self) -> &StatsdConfig {
+        &self.config
+    }
+
+    /// Format every target's gauges as StatsD lines (targets sorted by id)
+    pub fn lines(&self, pool: &EnginePool) -> Vec<String> {
+        let mut targets: Vec<_> = pool.iter().collect();
+        targets.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut lines = Vec::with_capacity(targets.len() * 4);
+        for (id, engine) in targets {
+            let state = engine.state();
+            let tags = self.tags(id, state);
+            let gauges = [
+                ("resistance", state.resistance.0),
+                ("scar", state.scar.0),
+                ("momentum", state.momentum.0),
+                ("mode", mode_value(state.mode) as f64),
+            ];
+            for (name, value) in gauges {
+                lines.push(format!("{}.{name}:{value}|g{tags}", self.config.prefix));
+            }
+        }
+        lines
+    }
+
+    /// Send one round of gauges, returning the number of datagrams sent
+    pub fn emit(&self, pool: &EnginePool) -> io::Result<usize> {
+        let mut packets = 0;
+        let mut packet = String::new();
+        for line in self.lines(pool) {
+            if !packet.is_empty() && packet.len() + 1 + line.len() > MAX_PACKET_BYTES {
+                self.socket.send(packet.as_bytes())?;
+                packets += 1;
+                packet.clear();
+            }
+            if !packet.is_empty() {
+                packet.push('\n');
+            }
+            packet.push_str(&line);
+        }
+        if !packet.is_empty() {
+            self.socket.send(packet.as_bytes())?;
+            packets += 1;
+        }
+        Ok(packets)
+    }
+
+    /// Emit from a background thread every `interval_ms` until stopped
+    ///
+    /// Send errors are dropped: StatsD is fire-and-forget and a missing
+    /// agent must not take the service down.
+    pub fn spawn(self, pool: Arc<Mutex<EnginePool>>) -> EmitterHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let interval = Duration::from_millis(self.config.interval_ms.max(1));
+        let thread = std::thread::spawn(move || {
+            while !flag.load(Ordering::Relaxed) {
+                {
+                    let pool = pool.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                    let _ = self.emit(&pool);
+                }
+                std::thread::park_timeout(interval);
+            }
+        });
+        EmitterHandle { stop, thread }
+    }
+
+    fn tags(&self, id: &str, state: &TargetState) -> String {
+        if !self.config.tags {
+            return String::new();
+        }
+        let mut tags = format!("|#target:{},mode:{}", sanitize(id), state.mode.as_str());
+        for (key, value) in &self.config.global_tags {
+            tags.push_str(&format!(",{}:{}", sanitize(key), sanitize(value)));
+        }
+        tags
+    }
+}
+
+/// Handle to a background emitter thread
+pub struct EmitterHandle {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl EmitterHandle {
+    /// Stop the emitter and wait for the thread to exit
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.thread.thread().unpark();
+        let _ = self.thread.join();
+    }
+}
+
+/// Replace characters that are reserved in the StatsD line format
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '|' | ',' | ':' | '#' | '\n' | ' ' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    fn pool() -> EnginePool {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        pool.tick("api:v1", 0.0, PressureVector::new(0.0, 0.0, 0.0));
+        pool
+    }
+
+    #[test]
+    fn test_lines_with_tags() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let config = StatsdConfig {
+            global_tags: vec![("env".into(), "prod".into())],
+            ..StatsdConfig::default()
+        };
+        let emitter = StatsdEmitter::connect(receiver.local_addr().unwrap(), config).unwrap();
+
+        let lines = emitter.lines(&pool());
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "atrion.resistance:12|g|#target:api_v1,mode:bootstrap,env:prod"
+        );
+    }
+
+    #[test]
+    fn test_emit_over_udp() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let config = StatsdConfig {
+            tags: false,
+            ..StatsdConfig::default()
+        };
+        let emitter = StatsdEmitter::connect(receiver.local_addr().unwrap(), config).unwrap();
+        assert_eq!(emitter.emit(&pool()).unwrap(), 1);
+
+        let mut buf = [0u8; MAX_PACKET_BYTES];
+        let n = receiver.recv(&mut buf).unwrap();
+        let packet = std::str::from_utf8(&buf[..n]).unwrap();
+        assert_eq!(packet.lines().count(), 4);
+        assert!(packet.contains("atrion.mode:0|g"));
+    }
+}
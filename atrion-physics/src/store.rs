@@ -0,0 +1,135 @@
+/**
+ * Shared state store (fleet-wide trauma memory).
+ *
+ * Gateways in front of the same upstream each learn about it on their
+ * own. A `StateStore` lets them publish and absorb per-target scar and
+ * momentum so one instance's trauma is felt by all.
+ *
+ * Merge is CRDT-style: scar is a max-register (after decaying the stored
+ * value to the merge time), momentum is last-writer-wins on
+ * `updated_ms`. Merging is commutative and idempotent, so instances can
+ * sync in any order.
+ */
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::scar::SCAR_DECAY_RATE;
+
+/// Per-target state shared through a store
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedState {
+    pub scar: f64,
+    pub momentum: f64,
+    /// Timestamp the scar/momentum values refer to
+    #[serde(alias = "updated_ms")]
+    pub updated_ms: f64,
+}
+
+impl SharedState {
+    /// Scar decayed from `updated_ms` to `now_ms` (TS decay law)
+    pub fn scar_at(&self, now_ms: f64) -> f64 {
+        let dt_seconds = (now_ms - self.updated_ms).max(0.0) / 1000.0;
+        self.scar * (-SCAR_DECAY_RATE * dt_seconds).exp()
+    }
+
+    /// Max-merge scar, last-writer-wins momentum, as of `now_ms`
+    pub fn merge(&self, other: &SharedState, now_ms: f64) -> SharedState {
+        let newer = if other.updated_ms > self.updated_ms {
+            other
+        } else {
+            self
+        };
+        SharedState {
+            scar: self.scar_at(now_ms).max(other.scar_at(now_ms)),
+            momentum: newer.momentum,
+            updated_ms: now_ms.max(newer.updated_ms),
+        }
+    }
+}
+
+/// Backend for sharing per-target state across instances
+pub trait StateStore {
+    type Error;
+
+    /// Current shared state for a target, if any instance published it
+    fn load(&self, id: &str) -> Result<Option<SharedState>, Self::Error>;
+
+    /// Atomically merge `state` into the stored value and return the result
+    fn merge(&self, id: &str, state: &SharedState) -> Result<SharedState, Self::Error>;
+}
+
+/// In-process store (tests, single-host multi-pool setups)
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    states: Mutex<HashMap<String, SharedState>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateStore for MemoryStore {
+    type Error = std::convert::Infallible;
+
+    fn load(&self, id: &str) -> Result<Option<SharedState>, Self::Error> {
+        let states = self.states.lock().unwrap_or_else(|p| p.into_inner());
+        Ok(states.get(id).copied())
+    }
+
+    fn merge(&self, id: &str, state: &SharedState) -> Result<SharedState, Self::Error> {
+        let mut states = self.states.lock().unwrap_or_else(|p| p.into_inner());
+        let merged = match states.get(id) {
+            Some(stored) => stored.merge(state, state.updated_ms),
+            None => *state,
+        };
+        states.insert(id.to_string(), merged);
+        Ok(merged)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared(scar: f64, momentum: f64, updated_ms: f64) -> SharedState {
+        SharedState {
+            scar,
+            momentum,
+            updated_ms,
+        }
+    }
+
+    #[test]
+    fn test_merge_is_commutative_max() {
+        let a = shared(20.0, 0.1, 1000.0);
+        let b = shared(5.0, 0.4, 2000.0);
+        let ab = a.merge(&b, 2000.0);
+        let ba = b.merge(&a, 2000.0);
+
+        assert_eq!(ab, ba);
+        // a's scar decayed for 1s still dominates
+        assert!((ab.scar - 20.0 * (-0.1f64).exp()).abs() < 1e-10);
+        assert_eq!(ab.momentum, 0.4);
+        assert_eq!(ab.merge(&ab, 2000.0), ab);
+    }
+
+    #[test]
+    fn test_memory_store_round_trip() {
+        let store = MemoryStore::new();
+        assert_eq!(store.load("api").unwrap(), None);
+
+        store.merge("api", &shared(10.0, 0.0, 0.0)).unwrap();
+        let merged = store.merge("api", &shared(2.0, 0.3, 0.0)).unwrap();
+        assert_eq!(merged.scar, 10.0);
+        assert_eq!(store.load("api").unwrap(), Some(merged));
+    }
+}
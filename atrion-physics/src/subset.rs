@@ -0,0 +1,226 @@
+/**
+ * Health bands for client-side subsetting.
+ *
+ * Partitions targets into healthy / degraded / quarantined subsets so a
+ * client can route to its healthy subset and keep degraded targets as
+ * spares. Bands have hysteresis: a target degrades when resistance or
+ * scar reaches the `degrade_*` level and only returns to healthy once
+ * both are below the lower `recover_*` level. Quarantined (pool
+ * quarantine list or an open breaker) always wins, and a target leaving
+ * quarantine re-enters as degraded.
+ */
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::pool::EnginePool;
+use crate::types::OperationalMode;
+
+/// Band of one target
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub enum HealthBand {
+    Healthy,
+    Degraded,
+    Quarantined,
+}
+
+/// Band thresholds (`recover_*` must sit below `degrade_*`)
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsetConfig {
+    #[serde(alias = "degrade_resistance")]
+    pub degrade_resistance: f64,
+    #[serde(alias = "recover_resistance")]
+    pub recover_resistance: f64,
+    #[serde(alias = "degrade_scar")]
+    pub degrade_scar: f64,
+    #[serde(alias = "recover_scar")]
+    pub recover_scar: f64,
+}
+
+impl Default for SubsetConfig {
+    fn default() -> Self {
+        // Between base (10) and recovery_threshold (50) of the default config
+        Self {
+            degrade_resistance: 40.0,
+            recover_resistance: 25.0,
+            degrade_scar: 10.0,
+            recover_scar: 5.0,
+        }
+    }
+}
+
+/// Target ids per band, each sorted
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Subsets {
+    pub healthy: Vec<String>,
+    pub degraded: Vec<String>,
+    pub quarantined: Vec<String>,
+}
+
+/// Remembers each target's band between partitions
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct SubsetRouter {
+    config: SubsetConfig,
+    bands: HashMap<String, HealthBand>,
+}
+
+impl SubsetRouter {
+    pub fn new(config: SubsetConfig) -> Self {
+        Self {
+            config,
+            bands: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &SubsetConfig {
+        &self.config
+    }
+
+    /// Band assigned at the last partition
+    pub fn band(&self, id: &str) -> Option<HealthBand> {
+        self.bands.get(id).copied()
+    }
+
+    /// Drop the remembered band of a target that left the set
+    pub fn forget(&mut self, id: &str) -> bool {
+        self.bands.remove(id).is_some()
+    }
+
+    /// Classify `ids` against the pool's current state
+    ///
+    /// Unregistered targets count as healthy (no evidence against them).
+    pub fn partition(&mut self, pool: &EnginePool, ids: &[&str]) -> Subsets {
+        let mut subsets = Subsets::default();
+        for &id in ids {
+            let band = self.classify(pool, id);
+            self.bands.insert(id.to_string(), band);
+            let subset = match band {
+                HealthBand::Healthy => &mut subsets.healthy,
+                HealthBand::Degraded => &mut subsets.degraded,
+                HealthBand::Quarantined => &mut subsets.quarantined,
+            };
+            subset.push(id.to_string());
+        }
+        subsets.healthy.sort_unstable();
+        subsets.degraded.sort_unstable();
+        subsets.quarantined.sort_unstable();
+        subsets
+    }
+
+    fn classify(&self, pool: &EnginePool, id: &str) -> HealthBand {
+        let Some(state) = pool.get(id).map(|engine| *engine.state()) else {
+            return if pool.quarantined(id).is_some() {
+                HealthBand::Quarantined
+            } else {
+                HealthBand::Healthy
+            };
+        };
+        if pool.quarantined(id).is_some() || state.mode == OperationalMode::CircuitBreaker {
+            return HealthBand::Quarantined;
+        }
+        let c = &self.config;
+        let previous = self.band(id).unwrap_or(HealthBand::Healthy);
+        let degrade = state.resistance.0 >= c.degrade_resistance || state.scar.0 >= c.degrade_scar;
+        let recovered = state.resistance.0 < c.recover_resistance && state.scar.0 < c.recover_scar;
+        match previous {
+            HealthBand::Healthy if degrade => HealthBand::Degraded,
+            HealthBand::Healthy => HealthBand::Healthy,
+            HealthBand::Degraded if recovered => HealthBand::Healthy,
+            HealthBand::Degraded | HealthBand::Quarantined => HealthBand::Degraded,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl SubsetRouter {
+    /// Router from a plain `SubsetConfig` object (undefined = defaults)
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(config: JsValue) -> Result<SubsetRouter, JsValue> {
+        let config = if config.is_undefined() {
+            SubsetConfig::default()
+        } else {
+            serde_wasm_bindgen::from_value(config)?
+        };
+        Ok(Self::new(config))
+    }
+
+    /// Plain `Subsets` object (`{ healthy, degraded, quarantined }`)
+    #[wasm_bindgen(js_name = partition)]
+    pub fn partition_js(
+        &mut self,
+        pool: &EnginePool,
+        ids: Vec<String>,
+    ) -> Result<JsValue, JsValue> {
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        crate::to_js(&self.partition(pool, &ids))
+    }
+
+    #[wasm_bindgen(js_name = band)]
+    pub fn band_js(&self, id: &str) -> Option<HealthBand> {
+        self.band(id)
+    }
+
+    #[wasm_bindgen(js_name = forget)]
+    pub fn forget_js(&mut self, id: &str) -> bool {
+        self.forget(id)
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_bands_with_hysteresis() {
+        let mut pool = EnginePool::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut router = SubsetRouter::new(SubsetConfig::default());
+        let ids = ["a", "b", "new"];
+        let mut now = 0.0;
+        fn tick(pool: &mut EnginePool, load: f64, now: &mut f64) {
+            pool.tick("a", *now, PressureVector::new(load, 0.0, 0.0));
+            pool.tick("b", *now, PressureVector::new(0.1, 0.0, 0.0));
+            *now += 1_000.0;
+        }
+        for _ in 0..15 {
+            tick(&mut pool, 0.1, &mut now);
+        }
+        let subsets = router.partition(&pool, &ids);
+        assert_eq!(subsets.healthy, ["a", "b", "new"]);
+
+        // Three spikes: scar 15 degrades "a"
+        for _ in 0..3 {
+            tick(&mut pool, 0.8, &mut now);
+        }
+        assert_eq!(router.partition(&pool, &ids).degraded, ["a"]);
+
+        // Scar decays below degrade (10) but not recover (5): still degraded
+        while pool.get("a").unwrap().state().scar.0 >= 8.0 {
+            tick(&mut pool, 0.1, &mut now);
+        }
+        assert_eq!(router.band("a"), Some(HealthBand::Degraded));
+        assert_eq!(router.partition(&pool, &ids).degraded, ["a"]);
+
+        while pool.get("a").unwrap().state().scar.0 >= 4.0 {
+            tick(&mut pool, 0.1, &mut now);
+        }
+        assert_eq!(router.partition(&pool, &ids).healthy, ["a", "b", "new"]);
+
+        pool.quarantine("b", "draining", now, Some(5_000.0));
+        assert_eq!(router.partition(&pool, &ids).quarantined, ["b"]);
+        for _ in 0..6 {
+            tick(&mut pool, 0.1, &mut now);
+        }
+        // Leaving quarantine passes through degraded, even when calm
+        assert_eq!(router.partition(&pool, &ids).degraded, ["b"]);
+        assert_eq!(router.partition(&pool, &ids).healthy, ["a", "b", "new"]);
+    }
+}
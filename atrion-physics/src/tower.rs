@@ -0,0 +1,375 @@
+/**
+ * Tower middleware (feature "tower", native only).
+ *
+ * `AtrionLayer` wraps any tower `Service` (HTTP client, tonic channel,
+ * ...) as a circuit breaker driven by the physics engine:
+ *
+ * - Observed latency, failures and in-flight count are aggregated and
+ *   ticked into the target's engine every `tick_interval_ms`.
+ * - Calls are rejected when the voltage gate says so, and delayed in
+ *   proportion to lost admission ratio while resistance is in the
+ *   recovery..break band.
+ */
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::admission::{AdmissionDecision, RejectReason};
+use crate::pool::EnginePool;
+use crate::types::PressureVector;
+
+/// Boxed error returned by wrapped services (tower convention)
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Decides whether a completed call counts as a failure
+///
+/// Implemented for any `Fn(&Result<T, E>) -> bool`.
+pub trait Classify<T, E> {
+    fn is_failure(&self, result: &Result<T, E>) -> bool;
+}
+
+impl<T, E, F: Fn(&Result<T, E>) -> bool> Classify<T, E> for F {
+    #[inline]
+    fn is_failure(&self, result: &Result<T, E>) -> bool {
+        self(result)
+    }
+}
+
+/// Transport errors and HTTP 5xx responses are failures
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ServerErrorsAsFailures;
+
+impl<B, E> Classify<http::Response<B>, E> for ServerErrorsAsFailures {
+    fn is_failure(&self, result: &Result<http::Response<B>, E>) -> bool {
+        match result {
+            Ok(response) => response.status().is_server_error(),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Middleware configuration
+#[derive(Debug, Clone)]
+pub struct TowerConfig {
+    /// Latency at which latency pressure saturates at 1.0
+    pub latency_budget_ms: f64,
+    /// In-flight calls at which saturation pressure reaches 1.0
+    pub max_in_flight: usize,
+    /// Aggregation window between engine ticks
+    pub tick_interval_ms: f64,
+    /// Voltage presented by each call to the gate
+    pub voltage: f64,
+    /// Delay applied at zero admission ratio (scaled down linearly)
+    pub max_delay_ms: u64,
+}
+
+impl Default for TowerConfig {
+    fn default() -> Self {
+        Self {
+            latency_budget_ms: 1000.0,
+            max_in_flight: 100,
+            tick_interval_ms: 1000.0,
+            voltage: 100.0,
+            max_delay_ms: 0,
+        }
+    }
+}
+
+/// Error returned when the engine rejects a call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejected {
+    pub target: String,
+    pub reason: RejectReason,
+}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "atrion rejected call to {}: {}",
+            self.target,
+            self.reason.as_str()
+        )
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+/// Signals accumulated between ticks
+#[derive(Debug, Default)]
+struct Window {
+    started_ms: Option<f64>,
+    calls: u32,
+    failures: u32,
+    latency_sum_ms: f64,
+    peak_in_flight: usize,
+}
+
+/// State shared by every clone of one wrapped service
+struct Shared {
+    pool: Arc<Mutex<EnginePool>>,
+    target: String,
+    config: TowerConfig,
+    in_flight: AtomicUsize,
+    window: Mutex<Window>,
+}
+
+impl Shared {
+    fn lock_pool(&self) -> MutexGuard<'_, EnginePool> {
+        self.pool
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Gate decision and pacing delay for the next call
+    fn admit(&self) -> Result<Duration, Rejected> {
+        let pool = self.lock_pool();
+        let Some(engine) = pool.get(&self.target) else {
+            return Ok(Duration::ZERO);
+        };
+        if let AdmissionDecision::Reject(reason) = engine.try_admit(self.config.voltage) {
+            return Err(Rejected {
+                target: self.target.clone(),
+                reason,
+            });
+        }
+        let shortfall = 1.0 - engine.admission_ratio();
+        Ok(Duration::from_millis(
+            (self.config.max_delay_ms as f64 * shortfall).round() as u64,
+        ))
+    }
+
+    /// Record a completed call, ticking the engine once the window elapses
+    fn record(&self, now_ms: f64, latency_ms: f64, failed: bool, in_flight: usize) {
+        let mut window = self
+            .window
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let started_ms = *window.started_ms.get_or_insert(now_ms);
+        window.calls += 1;
+        window.failures += failed as u32;
+        window.latency_sum_ms += latency_ms;
+        window.peak_in_flight = window.peak_in_flight.max(in_flight);
+
+        if now_ms - started_ms >= self.config.tick_interval_ms {
+            let pressure = self.pressure(&window);
+            *window = Window::default();
+            drop(window);
+            self.lock_pool().tick(&self.target, now_ms, pressure);
+        }
+    }
+
+    fn pressure(&self, window: &Window) -> PressureVector {
+        let calls = window.calls.max(1) as f64;
+        let latency = window.latency_sum_ms / calls / self.config.latency_budget_ms.max(1e-9);
+        let error = window.failures as f64 / calls;
+        let saturation = window.peak_in_flight as f64 / self.config.max_in_flight.max(1) as f64;
+        PressureVector::new(latency.min(1.0), error, saturation.min(1.0))
+    }
+}
+
+/// Layer wrapping services with an engine-driven breaker for one target
+#[derive(Clone)]
+pub struct AtrionLayer<C = ServerErrorsAsFailures> {
+    pool: Arc<Mutex<EnginePool>>,
+    target: String,
+    config: TowerConfig,
+    classify: C,
+}
+
+impl AtrionLayer {
+    pub fn new(pool: Arc<Mutex<EnginePool>>, target: impl Into<String>) -> Self {
+        Self {
+            pool,
+            target: target.into(),
+            config: TowerConfig::default(),
+            classify: ServerErrorsAsFailures,
+        }
+    }
+}
+
+impl<C> AtrionLayer<C> {
+    pub fn with_config(mut self, config: TowerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Replace the failure classifier (e.g. for gRPC status codes)
+    pub fn with_classifier<C2>(self, classify: C2) -> AtrionLayer<C2> {
+        AtrionLayer {
+            pool: self.pool,
+            target: self.target,
+            config: self.config,
+            classify,
+        }
+    }
+}
+
+impl<S, C: Clone> Layer<S> for AtrionLayer<C> {
+    type Service = AtrionService<S, C>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AtrionService {
+            inner,
+            classify: self.classify.clone(),
+            shared: Arc::new(Shared {
+                pool: Arc::clone(&self.pool),
+                target: self.target.clone(),
+                config: self.config.clone(),
+                in_flight: AtomicUsize::new(0),
+                window: Mutex::new(Window::default()),
+            }),
+        }
+    }
+}
+
+/// Service produced by `AtrionLayer`
+#[derive(Clone)]
+pub struct AtrionService<S, C = ServerErrorsAsFailures> {
+    inner: S,
+    classify: C,
+    shared: Arc<Shared>,
+}
+
+impl<S, C, Req> Service<Req> for AtrionService<S, C>
+where
+    S: Service<Req> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+    C: Classify<S::Response, S::Error> + Clone + Send + 'static,
+    Req: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Req) -> Self::Future {
+        // Take the service that was driven to readiness, leave a fresh clone
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let classify = self.classify.clone();
+        let shared = Arc::clone(&self.shared);
+
+        Box::pin(async move {
+            let delay = shared.admit()?;
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
+            let in_flight = shared.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+            let started = Instant::now();
+            let result = inner.call(request).await;
+            shared.in_flight.fetch_sub(1, Ordering::Relaxed);
+
+            let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+            let failed = classify.is_failure(&result);
+            shared.record(unix_ms(), latency_ms, failed, in_flight);
+            result.map_err(Into::into)
+        })
+    }
+}
+
+fn unix_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{OperationalMode, PhysicsConfig, SensitivityWeights};
+
+    /// Always returns the configured HTTP status
+    #[derive(Clone)]
+    struct Fixed(u16);
+
+    impl Service<()> for Fixed {
+        type Response = http::Response<()>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: ()) -> Self::Future {
+            let mut response = http::Response::new(());
+            *response.status_mut() = http::StatusCode::from_u16(self.0).unwrap();
+            std::future::ready(Ok(response))
+        }
+    }
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    fn pool() -> Arc<Mutex<EnginePool>> {
+        Arc::new(Mutex::new(EnginePool::new(
+            PhysicsConfig::default(),
+            SensitivityWeights::default(),
+        )))
+    }
+
+    #[test]
+    fn test_failures_feed_engine() {
+        let pool = pool();
+        let config = TowerConfig {
+            tick_interval_ms: 0.0,
+            ..TowerConfig::default()
+        };
+        let mut service = AtrionLayer::new(Arc::clone(&pool), "upstream")
+            .with_config(config)
+            .layer(Fixed(500));
+
+        let runtime = runtime();
+        for _ in 0..3 {
+            let response = runtime.block_on(service.call(())).unwrap();
+            assert_eq!(response.status(), 500);
+        }
+        let pool = pool.lock().unwrap();
+        let state = pool.get("upstream").unwrap().state();
+        assert_eq!(state.tick_count, 3);
+        assert!(state.pressure.error > 0.0);
+    }
+
+    #[test]
+    fn test_open_circuit_rejects() {
+        let pool = pool();
+        {
+            let mut pool = pool.lock().unwrap();
+            for i in 0..40 {
+                pool.tick(
+                    "upstream",
+                    i as f64 * 100.0,
+                    PressureVector::new(1.0, 1.0, 1.0),
+                );
+            }
+            assert_eq!(pool.mode("upstream"), Some(OperationalMode::CircuitBreaker));
+        }
+        let mut service = AtrionLayer::new(pool, "upstream").layer(Fixed(200));
+
+        let error = runtime().block_on(service.call(())).unwrap_err();
+        let rejected = error.downcast_ref::<Rejected>().unwrap();
+        assert_eq!(rejected.reason, RejectReason::CircuitOpen);
+    }
+}
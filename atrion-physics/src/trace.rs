@@ -0,0 +1,303 @@
+/**
+ * Recorded pressure traces (replay input).
+ *
+ * A trace is the raw input side of a pool: which target saw which
+ * pressure when, and how much traffic was offered at the time. Traces
+ * are what analysis tools replay under alternative configs.
+ *
+ * Before a trace leaves production it can be downsampled into time
+ * buckets and anonymized (salted target hashes, jittered timestamps).
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::anneal::Rng;
+use crate::history::History;
+use crate::types::PressureVector;
+
+/// One recorded tick input
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceSample {
+    #[serde(alias = "timestamp_ms")]
+    pub timestamp_ms: f64,
+    pub pressure: PressureVector,
+    /// Requests offered since the previous sample (for shed accounting)
+    #[serde(default = "default_offered")]
+    pub offered: f64,
+}
+
+fn default_offered() -> f64 {
+    1.0
+}
+
+/// Ordered samples for one target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetTrace {
+    pub target: String,
+    pub samples: Vec<TraceSample>,
+}
+
+/// Recorded input for a set of targets
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Trace {
+    pub targets: Vec<TargetTrace>,
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a sample for `target` (samples must arrive in time order)
+    pub fn record(&mut self, target: &str, sample: TraceSample) {
+        match self.targets.iter_mut().find(|t| t.target == target) {
+            Some(trace) => trace.samples.push(sample),
+            None => self.targets.push(TargetTrace {
+                target: target.to_string(),
+                samples: vec![sample],
+            }),
+        }
+    }
+
+    /// Rebuild a single-target trace from engine history (one request per tick)
+    pub fn from_history(target: &str, history: &History) -> Self {
+        Self {
+            targets: vec![TargetTrace {
+                target: target.to_string(),
+                samples: history
+                    .iter()
+                    .map(|s| TraceSample {
+                        timestamp_ms: s.timestamp_ms,
+                        pressure: s.pressure,
+                        offered: 1.0,
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    /// Total number of samples across targets
+    pub fn len(&self) -> usize {
+        self.targets.iter().map(|t| t.samples.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Aggregate each target into `bucket_ms` buckets
+    ///
+    /// A bucket sample is stamped at the bucket start, sums `offered` and
+    /// combines pressure per `aggregation`. Empty buckets are skipped.
+    pub fn downsample(&self, bucket_ms: f64, aggregation: Aggregation) -> Trace {
+        if bucket_ms <= 0.0 || !bucket_ms.is_finite() {
+            return self.clone();
+        }
+        let targets = self
+            .targets
+            .iter()
+            .map(|target| {
+                let mut samples: Vec<TraceSample> = Vec::new();
+                let mut count = 0;
+                for sample in &target.samples {
+                    let start = (sample.timestamp_ms / bucket_ms).floor() * bucket_ms;
+                    match samples.last_mut() {
+                        Some(bucket) if bucket.timestamp_ms == start => {
+                            bucket.pressure =
+                                aggregation.combine(bucket.pressure, sample.pressure, count);
+                            bucket.offered += sample.offered;
+                            count += 1;
+                        }
+                        _ => {
+                            samples.push(TraceSample {
+                                timestamp_ms: start,
+                                ..*sample
+                            });
+                            count = 1;
+                        }
+                    }
+                }
+                TargetTrace {
+                    target: target.target.clone(),
+                    samples,
+                }
+            })
+            .collect();
+        Trace { targets }
+    }
+
+    /// Hash target ids and jitter timestamps
+    ///
+    /// Per-target sample order is preserved (jittered timestamps never go
+    /// backwards). Ids are hashed with salted FNV-1a, which hides names
+    /// only while the salt stays secret.
+    pub fn anonymize(&self, config: &AnonymizeConfig) -> Trace {
+        let mut rng = Rng(config.seed);
+        let shift = config.max_shift_ms * rng.unit();
+        let targets = self
+            .targets
+            .iter()
+            .map(|target| {
+                let mut previous = f64::NEG_INFINITY;
+                let samples = target
+                    .samples
+                    .iter()
+                    .map(|sample| {
+                        let jitter = config.jitter_ms * (2.0 * rng.unit() - 1.0);
+                        previous = (sample.timestamp_ms + shift + jitter).max(previous);
+                        TraceSample {
+                            timestamp_ms: previous,
+                            ..*sample
+                        }
+                    })
+                    .collect();
+                TargetTrace {
+                    target: anonymize_id(&target.target, &config.salt),
+                    samples,
+                }
+            })
+            .collect();
+        Trace { targets }
+    }
+}
+
+/// How `Trace::downsample` combines pressure within a bucket
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[wasm_bindgen]
+pub enum Aggregation {
+    Mean,
+    /// Keeps spikes (what breakers react to)
+    Max,
+}
+
+impl Aggregation {
+    /// Fold `next` into an aggregate of `count` samples
+    fn combine(&self, acc: PressureVector, next: PressureVector, count: u32) -> PressureVector {
+        let f = |a: f64, b: f64| match self {
+            Aggregation::Mean => a + (b - a) / (count + 1) as f64,
+            Aggregation::Max => a.max(b),
+        };
+        PressureVector::new(
+            f(acc.latency, next.latency),
+            f(acc.error, next.error),
+            f(acc.saturation, next.saturation),
+        )
+    }
+}
+
+/// Settings for `Trace::anonymize`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AnonymizeConfig {
+    /// Secret mixed into target hashes
+    pub salt: String,
+    /// Per-sample timestamp jitter in [−jitter_ms, jitter_ms]
+    #[serde(alias = "jitter_ms")]
+    pub jitter_ms: f64,
+    /// Whole-trace offset drawn from [0, max_shift_ms)
+    #[serde(alias = "max_shift_ms")]
+    pub max_shift_ms: f64,
+    pub seed: u64,
+}
+
+impl Default for AnonymizeConfig {
+    fn default() -> Self {
+        Self {
+            salt: String::new(),
+            jitter_ms: 50.0,
+            max_shift_ms: 3_600_000.0,
+            seed: 0x5EED,
+        }
+    }
+}
+
+/// `target-` plus the salted 64-bit FNV-1a hash of `id`
+pub fn anonymize_id(id: &str, salt: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in salt.bytes().chain([0]).chain(id.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("target-{hash:016x}")
+}
+
+/// Downsampled copy of a plain trace object
+#[wasm_bindgen(js_name = downsampleTrace)]
+pub fn downsample_js(
+    trace: JsValue,
+    bucket_ms: f64,
+    aggregation: Aggregation,
+) -> Result<JsValue, JsValue> {
+    let trace: Trace = serde_wasm_bindgen::from_value(trace)?;
+    crate::to_js(&trace.downsample(bucket_ms, aggregation))
+}
+
+/// Anonymized copy of a plain trace object (`config` plain `AnonymizeConfig`)
+#[wasm_bindgen(js_name = anonymizeTrace)]
+pub fn anonymize_js(trace: JsValue, config: JsValue) -> Result<JsValue, JsValue> {
+    let trace: Trace = serde_wasm_bindgen::from_value(trace)?;
+    let config: AnonymizeConfig = serde_wasm_bindgen::from_value(config)?;
+    crate::to_js(&trace.anonymize(&config))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp_ms: f64, latency: f64) -> TraceSample {
+        TraceSample {
+            timestamp_ms,
+            pressure: PressureVector::new(latency, 0.0, 0.0),
+            offered: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_downsample_buckets() {
+        let mut trace = Trace::new();
+        for (t, p) in [(0.0, 0.2), (400.0, 0.6), (900.0, 0.4), (2_100.0, 0.1)] {
+            trace.record("api", sample(t, p));
+        }
+
+        let mean = trace.downsample(1_000.0, Aggregation::Mean);
+        let samples = &mean.targets[0].samples;
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].timestamp_ms, 0.0);
+        assert!((samples[0].pressure.latency - 0.4).abs() < 1e-12);
+        assert_eq!(samples[0].offered, 6.0);
+        assert_eq!(samples[1].timestamp_ms, 2_000.0);
+
+        let max = trace.downsample(1_000.0, Aggregation::Max);
+        assert_eq!(max.targets[0].samples[0].pressure.latency, 0.6);
+    }
+
+    #[test]
+    fn test_anonymize_hides_ids_and_keeps_order() {
+        let mut trace = Trace::new();
+        for i in 0..50 {
+            trace.record("checkout-db", sample(i as f64 * 10.0, 0.5));
+        }
+        let config = AnonymizeConfig {
+            salt: "s3cret".into(),
+            ..AnonymizeConfig::default()
+        };
+
+        let anon = trace.anonymize(&config);
+        let target = &anon.targets[0];
+        assert!(target.target.starts_with("target-"));
+        assert!(!target.target.contains("checkout"));
+        assert_eq!(target.target, anonymize_id("checkout-db", "s3cret"));
+        assert_ne!(target.target, anonymize_id("checkout-db", "other"));
+        assert!(target
+            .samples
+            .windows(2)
+            .all(|w| w[0].timestamp_ms <= w[1].timestamp_ms));
+        assert_ne!(target.samples[0].timestamp_ms, 0.0);
+        assert_eq!(anon.len(), 50);
+    }
+}
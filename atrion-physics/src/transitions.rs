@@ -0,0 +1,150 @@
+/**
+ * Mode-transition evidence.
+ *
+ * "The breaker opened at 14:02" is where an incident review starts, not
+ * where it ends: which threshold was crossed, at what resistance, and
+ * which term drove it there. Every `ModeChanged` event carries a
+ * `TransitionEvidence` snapshot taken right after the transition, and the
+ * engine keeps the newest `TRANSITION_CAPACITY` transitions (independent
+ * of the event log, which callers drain) for `TargetEngine::transitions`.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::explain::ResistanceBreakdown;
+use crate::types::OperationalMode;
+
+/// Transitions retained per target (oldest dropped first)
+pub const TRANSITION_CAPACITY: usize = 32;
+
+/// Why the mode controller moved
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransitionReason {
+    /// Enough ticks collected to leave bootstrap
+    BootstrapComplete,
+    /// Resistance reached `break_threshold`
+    BreakThreshold,
+    /// Resistance fell below the (possibly flap-widened) recovery threshold
+    ResistanceRecovered,
+    /// Scar below `scar_factor` and pressure below critical
+    ScarAndPressureRecovered,
+    /// An operator override pinned the mode
+    Override,
+    /// Anything else (e.g. a backfill recomputing history)
+    Other,
+}
+
+/// What the engine saw when it transitioned
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitionEvidence {
+    pub reason: TransitionReason,
+    /// Threshold the reason refers to (Ohms, or scar for
+    /// `ScarAndPressureRecovered`), if any
+    pub threshold: Option<f64>,
+    pub resistance: f64,
+    pub scar: f64,
+    /// Pressure magnitude
+    pub pressure: f64,
+    /// Resistance terms after the transition
+    pub breakdown: ResistanceBreakdown,
+}
+
+/// One recorded mode transition
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeTransition {
+    #[serde(alias = "at_ms")]
+    pub at_ms: f64,
+    pub from: OperationalMode,
+    pub to: OperationalMode,
+    pub evidence: TransitionEvidence,
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backfill::BackfillConfig;
+    use crate::engine::TargetEngine;
+    use crate::events::EngineEvent;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    #[test]
+    fn test_breaker_cycle_records_evidence() {
+        let mut engine = TargetEngine::new(PhysicsConfig::default(), SensitivityWeights::default());
+        let mut now = 0.0;
+        for _ in 0..10 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        for _ in 0..50 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+        }
+        for _ in 0..600 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+
+        let reasons: Vec<TransitionReason> = engine
+            .transitions()
+            .map(|transition| transition.evidence.reason)
+            .collect();
+        assert_eq!(reasons[0], TransitionReason::BootstrapComplete);
+        assert_eq!(reasons[1], TransitionReason::BreakThreshold);
+        assert!(matches!(
+            reasons[2],
+            TransitionReason::ResistanceRecovered | TransitionReason::ScarAndPressureRecovered
+        ));
+
+        let trip = *engine.transitions().nth(1).unwrap();
+        assert_eq!(trip.to, OperationalMode::CircuitBreaker);
+        assert_eq!(trip.evidence.threshold, Some(100.0));
+        assert!(trip.evidence.resistance >= 100.0);
+        assert_eq!(trip.evidence.breakdown.total, trip.evidence.resistance);
+
+        // The event carries the same evidence
+        let events = engine.drain_events();
+        let carried = events.iter().find_map(|event| match event {
+            EngineEvent::ModeChanged {
+                to: OperationalMode::CircuitBreaker,
+                evidence,
+                ..
+            } => evidence.as_deref().copied(),
+            _ => None,
+        });
+        assert_eq!(carried, Some(trip.evidence));
+    }
+
+    #[test]
+    fn test_backfill_does_not_repeat_transitions() {
+        let config = PhysicsConfig {
+            break_threshold: 50.0,
+            recovery_threshold: 40.0,
+            ..PhysicsConfig::default()
+        };
+        let mut engine = TargetEngine::new(config, SensitivityWeights::default());
+        engine.enable_backfill(BackfillConfig::default());
+        let mut now = 0.0;
+        for _ in 0..10 {
+            now += 1_000.0;
+            engine.tick(now, PressureVector::new(0.0, 0.0, 0.0));
+        }
+        for _ in 0..20 {
+            now += 1_000.0;
+            engine.tick(now, PressureVector::new(1.0, 1.0, 1.0));
+        }
+        let before: Vec<ModeTransition> = engine.transitions().copied().collect();
+        assert_eq!(before.len(), 2);
+        assert!(before[1].at_ms > 15_500.0);
+
+        // Rewinds across the trip, which the replay records once
+        engine.tick_at(15_500.0, PressureVector::new(1.0, 1.0, 1.0));
+        let after: Vec<ModeTransition> = engine.transitions().copied().collect();
+        assert_eq!(after.len(), 2);
+        assert_eq!(after[1].to, OperationalMode::CircuitBreaker);
+        assert!(after[1].at_ms <= before[1].at_ms);
+    }
+}
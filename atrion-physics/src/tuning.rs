@@ -0,0 +1,386 @@
+/**
+ * Online auto-tuning of scar_factor and damping_factor.
+ *
+ * Slowly adapts the two parameters operators tune most, within bounds
+ * they set, to minimize a per-epoch loss. The default objective is the
+ * weighted sum of the fraction of ticks violating the latency SLO and the
+ * fraction of traffic shed; `TuningObjective::Goodput` instead maximizes
+ * the share of traffic that is both admitted and succeeds (outcomes from
+ * `TargetEngine::record_outcomes`). The gradient is estimated by coordinate-wise finite
+ * differences across epochs (θ+δ, θ−δ for one parameter, then the
+ * other), so it needs no model of the upstream and is deterministic.
+ * Steps happen in bound-normalized space and are capped per update.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::types::PhysicsConfig;
+
+/// Tunable parameter
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TunedParameter {
+    ScarFactor,
+    DampingFactor,
+}
+
+impl TunedParameter {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TunedParameter::ScarFactor => "scar_factor",
+            TunedParameter::DampingFactor => "damping_factor",
+        }
+    }
+
+    fn get(&self, config: &PhysicsConfig) -> f64 {
+        match self {
+            TunedParameter::ScarFactor => config.scar_factor,
+            TunedParameter::DampingFactor => config.damping_factor,
+        }
+    }
+
+    fn set(&self, config: &mut PhysicsConfig, value: f64) {
+        match self {
+            TunedParameter::ScarFactor => config.scar_factor = value,
+            TunedParameter::DampingFactor => config.damping_factor = value,
+        }
+    }
+}
+
+const PARAMETERS: [TunedParameter; 2] = [TunedParameter::ScarFactor, TunedParameter::DampingFactor];
+
+/// What the tuner optimizes
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TuningObjective {
+    /// Weighted SLO violations plus shed traffic
+    #[default]
+    SloAndShed,
+    /// 1 − admitted share × success ratio of admitted requests
+    Goodput,
+}
+
+/// Online tuning configuration
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TuningConfig {
+    /// Operator bounds `(min, max)`; the tuner never leaves them
+    #[serde(alias = "scar_factor_bounds")]
+    pub scar_factor_bounds: (f64, f64),
+    #[serde(alias = "damping_factor_bounds")]
+    pub damping_factor_bounds: (f64, f64),
+    /// Length of one measurement epoch
+    #[serde(alias = "epoch_ms")]
+    pub epoch_ms: f64,
+    /// Normalized latency above which a tick violates the SLO
+    #[serde(alias = "slo_latency")]
+    pub slo_latency: f64,
+    #[serde(alias = "violation_weight")]
+    pub violation_weight: f64,
+    #[serde(alias = "shed_weight")]
+    pub shed_weight: f64,
+    /// Gradient step in bound-normalized units
+    #[serde(alias = "learning_rate")]
+    pub learning_rate: f64,
+    /// Probe offset δ in bound-normalized units
+    pub perturbation: f64,
+    /// Largest single move in bound-normalized units
+    #[serde(alias = "max_step")]
+    pub max_step: f64,
+    #[serde(default)]
+    pub objective: TuningObjective,
+}
+
+impl Default for TuningConfig {
+    fn default() -> Self {
+        Self {
+            scar_factor_bounds: (1.0, 20.0),
+            damping_factor_bounds: (5.0, 80.0),
+            epoch_ms: 60_000.0,
+            slo_latency: 0.5,
+            violation_weight: 1.0,
+            shed_weight: 1.0,
+            learning_rate: 0.1,
+            perturbation: 0.05,
+            max_step: 0.05,
+            objective: TuningObjective::SloAndShed,
+        }
+    }
+}
+
+impl TuningConfig {
+    fn bounds(&self, parameter: TunedParameter) -> (f64, f64) {
+        match parameter {
+            TunedParameter::ScarFactor => self.scar_factor_bounds,
+            TunedParameter::DampingFactor => self.damping_factor_bounds,
+        }
+    }
+}
+
+/// A committed parameter change
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Adjustment {
+    pub parameter: TunedParameter,
+    pub from: f64,
+    pub to: f64,
+    /// Mean loss of the two probe epochs
+    pub loss: f64,
+}
+
+/// Where the tuner is in its probe cycle
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all_fields = "camelCase")]
+enum Phase {
+    Plus,
+    Minus {
+        #[serde(alias = "plus_loss")]
+        plus_loss: f64,
+    },
+}
+
+/// Finite-difference online tuner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlineTuner {
+    config: TuningConfig,
+    /// Committed parameter values in normalized [0, 1] space
+    theta: [f64; 2],
+    /// Index into PARAMETERS currently being probed
+    probing: usize,
+    phase: Phase,
+    #[serde(alias = "epoch_start_ms")]
+    epoch_start_ms: Option<f64>,
+    ticks: u32,
+    violations: u32,
+    shed: f64,
+    /// Admitted-request outcomes this epoch
+    #[serde(default)]
+    succeeded: u64,
+    #[serde(default)]
+    failed: u64,
+}
+
+impl OnlineTuner {
+    /// Start from the parameters in `physics` (clamped into bounds)
+    pub fn new(config: TuningConfig, physics: &PhysicsConfig) -> Self {
+        let theta = PARAMETERS.map(|p| {
+            let (lo, hi) = config.bounds(p);
+            normalize(p.get(physics), lo, hi)
+        });
+        Self {
+            config,
+            theta,
+            probing: 0,
+            phase: Phase::Plus,
+            epoch_start_ms: None,
+            ticks: 0,
+            violations: 0,
+            shed: 0.0,
+            succeeded: 0,
+            failed: 0,
+        }
+    }
+
+    pub fn config(&self) -> &TuningConfig {
+        &self.config
+    }
+
+    /// Committed (unperturbed) value of a parameter
+    pub fn value(&self, parameter: TunedParameter) -> f64 {
+        let index = PARAMETERS.iter().position(|p| *p == parameter).unwrap_or(0);
+        let (lo, hi) = self.config.bounds(parameter);
+        denormalize(self.theta[index], lo, hi)
+    }
+
+    /// Write the values for the current probe epoch into `physics`
+    pub fn apply(&self, physics: &mut PhysicsConfig) {
+        for (index, parameter) in PARAMETERS.iter().enumerate() {
+            let mut u = self.theta[index];
+            if index == self.probing {
+                u += match self.phase {
+                    Phase::Plus => self.config.perturbation,
+                    Phase::Minus { .. } => -self.config.perturbation,
+                };
+            }
+            let (lo, hi) = self.config.bounds(*parameter);
+            parameter.set(physics, denormalize(u.clamp(0.0, 1.0), lo, hi));
+        }
+    }
+
+    /// Count admitted-request outcomes toward the current epoch
+    pub fn record_outcomes(&mut self, good: u64, bad: u64) {
+        self.succeeded += good;
+        self.failed += bad;
+    }
+
+    /// Record one tick; returns a committed adjustment at epoch ends
+    ///
+    /// `physics` is updated in place whenever the probe phase changes.
+    pub fn observe(
+        &mut self,
+        now_ms: f64,
+        latency: f64,
+        admission_ratio: f64,
+        physics: &mut PhysicsConfig,
+    ) -> Option<Adjustment> {
+        let start = *self.epoch_start_ms.get_or_insert(now_ms);
+        self.ticks += 1;
+        self.violations += (latency > self.config.slo_latency) as u32;
+        self.shed += 1.0 - admission_ratio.clamp(0.0, 1.0);
+
+        if now_ms - start < self.config.epoch_ms {
+            return None;
+        }
+        let loss = self.epoch_loss();
+        self.epoch_start_ms = Some(now_ms);
+        self.ticks = 0;
+        self.violations = 0;
+        self.shed = 0.0;
+        self.succeeded = 0;
+        self.failed = 0;
+
+        let adjustment = match self.phase {
+            Phase::Plus => {
+                self.phase = Phase::Minus { plus_loss: loss };
+                None
+            }
+            Phase::Minus { plus_loss } => {
+                let adjustment = self.step(plus_loss, loss);
+                self.phase = Phase::Plus;
+                self.probing = (self.probing + 1) % PARAMETERS.len();
+                adjustment
+            }
+        };
+        self.apply(physics);
+        adjustment
+    }
+
+    fn epoch_loss(&self) -> f64 {
+        let ticks = self.ticks.max(1) as f64;
+        match self.config.objective {
+            TuningObjective::SloAndShed => {
+                self.config.violation_weight * self.violations as f64 / ticks
+                    + self.config.shed_weight * self.shed / ticks
+            }
+            TuningObjective::Goodput => {
+                let outcomes = self.succeeded + self.failed;
+                // Without outcomes only shedding is known to lose goodput
+                let success = if outcomes > 0 {
+                    self.succeeded as f64 / outcomes as f64
+                } else {
+                    1.0
+                };
+                1.0 - (1.0 - self.shed / ticks) * success
+            }
+        }
+    }
+
+    /// Gradient step on the probed parameter from the ± probe losses
+    fn step(&mut self, plus_loss: f64, minus_loss: f64) -> Option<Adjustment> {
+        let parameter = PARAMETERS[self.probing];
+        let delta = self.config.perturbation.max(1e-9);
+        let gradient = (plus_loss - minus_loss) / (2.0 * delta);
+        let max_step = self.config.max_step.abs();
+        let step = (-self.config.learning_rate * gradient).clamp(-max_step, max_step);
+
+        let before = self.theta[self.probing];
+        let after = (before + step).clamp(0.0, 1.0);
+        if (after - before).abs() < 1e-12 {
+            return None;
+        }
+        self.theta[self.probing] = after;
+        let (lo, hi) = self.config.bounds(parameter);
+        Some(Adjustment {
+            parameter,
+            from: denormalize(before, lo, hi),
+            to: denormalize(after, lo, hi),
+            loss: 0.5 * (plus_loss + minus_loss),
+        })
+    }
+}
+
+fn normalize(value: f64, lo: f64, hi: f64) -> f64 {
+    if hi > lo {
+        ((value - lo) / (hi - lo)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+fn denormalize(u: f64, lo: f64, hi: f64) -> f64 {
+    lo + u * (hi - lo).max(0.0)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_cycle_and_bounds() {
+        let mut physics = PhysicsConfig::default();
+        let config = TuningConfig {
+            epoch_ms: 1000.0,
+            ..TuningConfig::default()
+        };
+        let mut tuner = OnlineTuner::new(config, &physics);
+        assert_eq!(tuner.value(TunedParameter::ScarFactor), 5.0);
+
+        // Plus epoch is bad (all violations), minus epoch is clean:
+        // gradient points up, so scar_factor must come down
+        let mut now = 0.0;
+        let mut run_epoch = |tuner: &mut OnlineTuner, physics: &mut PhysicsConfig, latency| {
+            let mut result = None;
+            for _ in 0..=10 {
+                result = result.or(tuner.observe(now, latency, 1.0, physics));
+                now += 100.0;
+            }
+            result
+        };
+        assert_eq!(run_epoch(&mut tuner, &mut physics, 0.9), None);
+        let adjustment = run_epoch(&mut tuner, &mut physics, 0.1).unwrap();
+
+        assert_eq!(adjustment.parameter, TunedParameter::ScarFactor);
+        assert!(adjustment.to < adjustment.from);
+        // Capped at max_step (5% of the 1..20 range)
+        assert!((adjustment.from - adjustment.to - 0.05 * 19.0).abs() < 1e-9);
+        // Next probe is damping_factor, at +δ
+        assert!(physics.damping_factor > tuner.value(TunedParameter::DampingFactor));
+    }
+
+    #[test]
+    fn test_goodput_objective_uses_outcomes() {
+        let mut physics = PhysicsConfig::default();
+        let config = TuningConfig {
+            epoch_ms: 1000.0,
+            objective: TuningObjective::Goodput,
+            ..TuningConfig::default()
+        };
+        let mut tuner = OnlineTuner::new(config, &physics);
+        // Same latency and shedding in both epochs; only outcomes differ
+        let mut now = 0.0;
+        let mut run_epoch = |tuner: &mut OnlineTuner, physics: &mut PhysicsConfig, bad| {
+            let mut result = None;
+            for _ in 0..=10 {
+                tuner.record_outcomes(10, bad);
+                result = result.or(tuner.observe(now, 0.9, 0.5, physics));
+                now += 100.0;
+            }
+            result
+        };
+        assert_eq!(run_epoch(&mut tuner, &mut physics, 0), None);
+        let adjustment = run_epoch(&mut tuner, &mut physics, 10).unwrap();
+        // +δ kept every success, −δ lost half: scar_factor goes up
+        assert!(adjustment.to > adjustment.from);
+    }
+
+    #[test]
+    fn test_out_of_bounds_start_is_clamped() {
+        let physics = PhysicsConfig {
+            scar_factor: 500.0,
+            ..PhysicsConfig::default()
+        };
+        let tuner = OnlineTuner::new(TuningConfig::default(), &physics);
+        assert_eq!(tuner.value(TunedParameter::ScarFactor), 20.0);
+    }
+}
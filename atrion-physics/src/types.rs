@@ -6,22 +6,37 @@
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::backoff::BreakerBackoffConfig;
+use crate::brownout::BrownoutConfig;
+use crate::critical::AdaptiveCriticalConfig;
+use crate::jitter::RecoveryJitterConfig;
+use crate::markers::DeployGraceConfig;
+use crate::pid::PidConfig;
+use crate::resistance::TermCaps;
+use crate::scar::DualScarConfig;
+use crate::seasonal::SeasonalConfig;
+use crate::softstart::SoftStartConfig;
+use crate::warmup::WarmupConfig;
+
 // ============================================================================
 // BRANDED TYPES
 // ============================================================================
 
 /// Electrical resistance (Ohms)
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[repr(transparent)]
 pub struct Ohms(pub f64);
 
 /// Accumulated trauma (Scar Tissue)
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[repr(transparent)]
 pub struct Scar(pub f64);
 
 /// Momentum magnitude
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[repr(transparent)]
 pub struct Momentum(pub f64);
 
@@ -31,6 +46,9 @@ pub struct Momentum(pub f64);
 
 /// Normalized pressure vector [0, 1]³
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[wasm_bindgen]
 pub struct PressureVector {
     pub latency: f64,
@@ -48,6 +66,18 @@ impl PressureVector {
             saturation,
         }
     }
+
+    /// Parse a plain `{ latency, error, saturation }` object
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json_js(value: JsValue) -> Result<PressureVector, JsValue> {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+
+    /// Plain-object form (also picked up by `JSON.stringify`)
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(self)
+    }
 }
 
 // ============================================================================
@@ -55,16 +85,100 @@ impl PressureVector {
 // ============================================================================
 
 /// Physics engine configuration
+///
+/// Serialized in camelCase (TS parity); snake_case names from older
+/// payloads are still accepted. Omitted fields keep their defaults, so JS
+/// can pass `{ baseResistance: 12 }`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(default)]
 #[wasm_bindgen]
 pub struct PhysicsConfig {
+    #[serde(alias = "base_resistance")]
     pub base_resistance: f64,
+    #[serde(alias = "damping_factor")]
     pub damping_factor: f64,
+    #[serde(alias = "scar_factor")]
     pub scar_factor: f64,
+    #[serde(alias = "momentum_halflife")]
     pub momentum_halflife: f64,
+    #[serde(alias = "bootstrap_ticks")]
     pub bootstrap_ticks: u32,
+    #[serde(alias = "break_threshold")]
     pub break_threshold: f64,
+    #[serde(alias = "recovery_threshold")]
     pub recovery_threshold: f64,
+    /// Ticks retained by the stateful engine's history buffer (0 = disabled)
+    #[serde(alias = "history_capacity")]
+    pub history_capacity: u32,
+    /// Largest Δt a single tick may apply; longer gaps are clamped (0 = no limit)
+    #[serde(alias = "max_delta_t_ms")]
+    pub max_delta_t_ms: f64,
+    /// Open-loop resistance (TS) or PID setpoint control
+    #[serde(alias = "control_mode")]
+    pub control_mode: ControlMode,
+    /// Frozen formula version; both implementations must agree on it
+    pub formula: FormulaRevision,
+    /// Whether stress exactly at the critical pressure counts as trauma
+    #[serde(alias = "critical_comparison")]
+    pub critical_comparison: ThresholdComparison,
+    /// Float (TS) or fixed-scale integer scar accounting
+    #[serde(alias = "scar_precision")]
+    pub scar_precision: ScarPrecision,
+    /// Compute and report everything but always admit (shadow rollout)
+    #[serde(alias = "observe_only")]
+    pub observe_only: bool,
+    /// Gains for `ControlMode::Pid`
+    #[wasm_bindgen(skip)]
+    pub pid: PidConfig,
+    /// Upper bound on resistance, so a critical dependency is never fully
+    /// blocked (`None` = unbounded). Takes precedence over the base floor.
+    #[serde(alias = "resistance_ceiling")]
+    #[wasm_bindgen(skip)]
+    pub resistance_ceiling: Option<f64>,
+    /// Slow (chronic) scar component alongside the TS scar (`None` = TS model)
+    #[serde(alias = "dual_scar")]
+    #[wasm_bindgen(skip)]
+    pub dual_scar: Option<DualScarConfig>,
+    /// Per-term limits on the resistance contributions (`None` = uncapped)
+    #[serde(alias = "term_caps")]
+    #[wasm_bindgen(skip)]
+    pub term_caps: Option<TermCaps>,
+    /// Ramp resistance down after the breaker closes (`None` = drop at once)
+    #[serde(alias = "soft_start")]
+    #[wasm_bindgen(skip)]
+    pub soft_start: Option<SoftStartConfig>,
+    /// Minimum open time, backed off on repeated trips (`None` = close on
+    /// the recovery threshold alone)
+    #[serde(alias = "breaker_backoff")]
+    #[wasm_bindgen(skip)]
+    pub breaker_backoff: Option<BreakerBackoffConfig>,
+    /// Seeded per-target spread of recovery time and soft-start slope
+    /// (`None` = everyone recovers on the same tick)
+    #[serde(alias = "recovery_jitter")]
+    #[wasm_bindgen(skip)]
+    pub recovery_jitter: Option<RecoveryJitterConfig>,
+    /// Degradation ladder reported alongside the breaker (`None` = off)
+    #[wasm_bindgen(skip)]
+    pub brownout: Option<BrownoutConfig>,
+    /// Learned hour-of-day / hour-of-week pressure baseline the physics
+    /// sees pressure relative to (`None` = raw pressure)
+    #[wasm_bindgen(skip)]
+    pub seasonal: Option<SeasonalConfig>,
+    /// Reduced trauma for a while after deploy markers (`None` = off)
+    #[serde(alias = "deploy_grace")]
+    #[wasm_bindgen(skip)]
+    pub deploy_grace: Option<DeployGraceConfig>,
+    /// Reduced trauma for a while after registration (`None` = off)
+    #[wasm_bindgen(skip)]
+    pub warmup: Option<WarmupConfig>,
+    /// Per-target trauma threshold learned from the stress distribution
+    /// (`None` = `scar::CRITICAL_PRESSURE`)
+    #[serde(alias = "adaptive_critical")]
+    #[wasm_bindgen(skip)]
+    pub adaptive_critical: Option<AdaptiveCriticalConfig>,
 }
 
 #[wasm_bindgen]
@@ -73,6 +187,18 @@ impl PhysicsConfig {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Parse a (partial) plain object; omitted fields keep their defaults
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json_js(value: JsValue) -> Result<PhysicsConfig, JsValue> {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+
+    /// Plain-object form (also picked up by `JSON.stringify`)
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(self)
+    }
 }
 
 impl Default for PhysicsConfig {
@@ -86,16 +212,42 @@ impl Default for PhysicsConfig {
             bootstrap_ticks: 10,       // TS: 10
             break_threshold: 100.0,    // TS: breakMultiplier * baseResistance = 10*10
             recovery_threshold: 50.0,
+            history_capacity: 256,    // Not in TS (Rust-only engine history)
+            max_delta_t_ms: 60_000.0, // Not in TS (clock guard)
+            control_mode: ControlMode::OpenLoop, // Not in TS
+            formula: FormulaRevision::V1, // TS: linear (v1)
+            critical_comparison: ThresholdComparison::Exclusive, // TS: `>`
+            scar_precision: ScarPrecision::Float, // Not in TS
+            observe_only: false,      // Not in TS
+            pid: PidConfig::default(),
+            resistance_ceiling: None, // Not in TS
+            dual_scar: None,          // Not in TS
+            term_caps: None,          // Not in TS
+            soft_start: None,         // Not in TS
+            breaker_backoff: None,    // Not in TS
+            recovery_jitter: None,    // Not in TS
+            brownout: None,           // Not in TS
+            seasonal: None,           // Not in TS
+            deploy_grace: None,       // Not in TS
+            warmup: None,             // Not in TS
+            adaptive_critical: None,  // Not in TS
         }
     }
 }
 
 /// Sensitivity weights for pressure components
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(default)]
 #[wasm_bindgen]
 pub struct SensitivityWeights {
+    #[serde(alias = "w_latency")]
     pub w_latency: f64,
+    #[serde(alias = "w_error")]
     pub w_error: f64,
+    #[serde(alias = "w_saturation")]
     pub w_saturation: f64,
 }
 
@@ -109,6 +261,18 @@ impl SensitivityWeights {
             w_saturation,
         }
     }
+
+    /// Parse a (partial) plain object; omitted weights keep their defaults
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json_js(value: JsValue) -> Result<SensitivityWeights, JsValue> {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+
+    /// Plain-object form (also picked up by `JSON.stringify`)
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json_js(&self) -> Result<JsValue, JsValue> {
+        crate::to_js(self)
+    }
 }
 
 impl Default for SensitivityWeights {
@@ -130,9 +294,93 @@ impl Default for SensitivityWeights {
 // ============================================================================
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[wasm_bindgen]
 pub enum OperationalMode {
     Bootstrap,
     Operational,
     CircuitBreaker,
 }
+
+/// How the engine turns pressure into resistance
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[wasm_bindgen]
+pub enum ControlMode {
+    /// R = base + P·W + μM + S (TS parity)
+    #[default]
+    OpenLoop,
+    /// PID loop on latency vs `PhysicsConfig::pid.setpoint`
+    Pid,
+}
+
+/// Frozen revisions of the pressure → resistance formula
+///
+/// A revision never changes once released; new behavior gets a new
+/// variant so the Rust and TS engines can upgrade in lockstep.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+#[wasm_bindgen]
+pub enum FormulaRevision {
+    /// Linear P·W (RFC-0001, TS parity)
+    #[default]
+    V1,
+    /// Each pressure component steepens past a knee at the critical pressure
+    V2,
+}
+
+impl FormulaRevision {
+    /// Every revision this build can evaluate
+    pub const ALL: [FormulaRevision; 2] = [FormulaRevision::V1, FormulaRevision::V2];
+
+    /// Wire label ("v1", "v2")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FormulaRevision::V1 => "v1",
+            FormulaRevision::V2 => "v2",
+        }
+    }
+}
+
+/// How stress is compared against `scar::CRITICAL_PRESSURE`
+///
+/// Recovery from the circuit breaker always needs magnitude strictly below
+/// the threshold, so under `Exclusive` a magnitude of exactly 0.7 neither
+/// scars nor counts toward recovery.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+#[wasm_bindgen]
+pub enum ThresholdComparison {
+    /// `stress > 0.7` (TS parity)
+    #[default]
+    Exclusive,
+    /// `stress >= 0.7`
+    Inclusive,
+}
+
+/// Arithmetic used to accumulate and decay scar
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+#[wasm_bindgen]
+pub enum ScarPrecision {
+    /// f64 `exp` decay (TS parity)
+    #[default]
+    Float,
+    /// Integer nano-units with a fixed decimal decay table, bit-identical
+    /// across platforms and independent of libm (see `scar::update_scar_fixed`)
+    Fixed,
+}
+
+impl OperationalMode {
+    /// Lowercase label for metric tags and attributes
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationalMode::Bootstrap => "bootstrap",
+            OperationalMode::Operational => "operational",
+            OperationalMode::CircuitBreaker => "circuit_breaker",
+        }
+    }
+}
@@ -1,9 +1,25 @@
 /**
  * Vector mathematics with SIMD optimization.
  *
- * - AVX2 for x86_64 (native builds)
+ * - `core::simd` on every target with the `portable-simd` feature (nightly)
+ * - Otherwise AVX2 for x86_64 (native builds)
  * - SIMD128 for wasm32 (WASM builds)
  * - Scalar fallback for other architectures
+ *
+ * `force-scalar` (and any Miri run) selects the scalar path everywhere, so
+ * the test suite can run without `unsafe` intrinsics: `cargo +nightly miri
+ * test --features force-scalar`. The unsafe kernels carry debug assertions
+ * on output alignment and NaN propagation for ASan/debug runs.
+ *
+ * All backends sum the squares in the same order, (l² + e²) + s², so
+ * results are identical whichever one a build picks.
+ *
+ * Float semantics (shared with the TS engine, which has no FTZ mode):
+ * - Subnormals are ordinary values: never flushed to zero on input, and
+ *   squaring one underflows to 0 exactly as in JS.
+ * - ±0.0 both mean "no pressure". Outputs are canonicalized to +0.0
+ *   because `f64::max` leaves the sign of zero unspecified while JS
+ *   `Math.max(0, -0)` is +0.
  */
 use crate::types::{PressureVector, SensitivityWeights};
 
@@ -11,18 +27,41 @@ use crate::types::{PressureVector, SensitivityWeights};
 // SIMD-OPTIMIZED MAGNITUDE
 // ============================================================================
 
+// Portable: core::simd (nightly)
+#[cfg(all(feature = "portable-simd", not(any(feature = "force-scalar", miri))))]
+use std::simd::f64x4;
+
 // x86_64 Native: AVX2
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(
+    target_arch = "x86_64",
+    not(any(feature = "portable-simd", feature = "force-scalar", miri))
+))]
 use std::arch::x86_64::*;
 
 // wasm32: SIMD128
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(
+    target_arch = "wasm32",
+    not(any(feature = "portable-simd", feature = "force-scalar", miri))
+))]
 use std::arch::wasm32::*;
 
+/// Calculate vector magnitude with `core::simd` (any target)
+#[cfg(all(feature = "portable-simd", not(any(feature = "force-scalar", miri))))]
+#[inline]
+pub fn magnitude(v: &PressureVector) -> f64 {
+    let values = f64x4::from_array([v.latency, v.error, v.saturation, 0.0]);
+    let [l, e, s, _] = (values * values).to_array();
+    // Explicit order: `reduce_sum` does not promise one
+    ((l + e) + s).sqrt()
+}
+
 /// Calculate vector magnitude using AVX2 SIMD (x86_64 only)
 ///
 /// ~4x faster than scalar version
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(
+    target_arch = "x86_64",
+    not(any(feature = "portable-simd", feature = "force-scalar", miri))
+))]
 #[target_feature(enable = "avx2")]
 unsafe fn magnitude_simd_avx2(v: &PressureVector) -> f64 {
     let values = _mm256_set_pd(0.0, v.saturation, v.error, v.latency);
@@ -32,14 +71,20 @@ unsafe fn magnitude_simd_avx2(v: &PressureVector) -> f64 {
     let sum128 = _mm_add_pd(_mm256_castpd256_pd128(sum), hi128);
 
     let mut result: f64 = 0.0;
-    _mm_store_sd(&mut result as *mut f64, sum128);
+    let out = &mut result as *mut f64;
+    debug_assert!(out.is_aligned());
+    _mm_store_sd(out, sum128);
+    debug_assert_lanes(v, result);
     result.sqrt()
 }
 
 /// Calculate vector magnitude using WASM SIMD128 (wasm32 only)
 ///
 /// ~2x faster than scalar version in WASM
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(
+    target_arch = "wasm32",
+    not(any(feature = "portable-simd", feature = "force-scalar", miri))
+))]
 #[target_feature(enable = "simd128")]
 unsafe fn magnitude_simd_wasm(v: &PressureVector) -> f64 {
     // Load [latency, error] into v128 (2x f64)
@@ -56,36 +101,88 @@ unsafe fn magnitude_simd_wasm(v: &PressureVector) -> f64 {
     // Add: saturation²
     let total = sum_low + f64x2_extract_lane::<0>(high_sq);
 
+    debug_assert_lanes(v, total);
     total.sqrt()
 }
 
+/// Debug check on a kernel's sum of squares: NaN exactly when an input is
+/// NaN, and never negative (a stray or unzeroed lane breaks one of these)
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "wasm32"),
+    not(any(feature = "portable-simd", feature = "force-scalar", miri))
+))]
+#[inline(always)]
+fn debug_assert_lanes(v: &PressureVector, sum_of_squares: f64) {
+    let nan_input = v.latency.is_nan() || v.error.is_nan() || v.saturation.is_nan();
+    debug_assert_eq!(sum_of_squares.is_nan(), nan_input, "{v:?}");
+    debug_assert!(nan_input || sum_of_squares >= 0.0, "{v:?}");
+}
+
 /// Safe wrapper for SIMD magnitude (x86_64)
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(
+    target_arch = "x86_64",
+    not(any(feature = "portable-simd", feature = "force-scalar", miri))
+))]
 #[inline]
 pub fn magnitude(v: &PressureVector) -> f64 {
     // TODO: Add runtime AVX2 detection with is_x86_feature_detected!
+    debug_assert!(
+        std::is_x86_feature_detected!("avx2"),
+        "AVX2 kernel on a CPU without AVX2 (build with force-scalar)"
+    );
     unsafe { magnitude_simd_avx2(v) }
 }
 
 /// Safe wrapper for SIMD magnitude (wasm32)
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(
+    target_arch = "wasm32",
+    not(any(feature = "portable-simd", feature = "force-scalar", miri))
+))]
 #[inline]
 pub fn magnitude(v: &PressureVector) -> f64 {
     // WASM SIMD128 always available when enabled
     unsafe { magnitude_simd_wasm(v) }
 }
 
-/// Scalar fallback for other architectures
-#[cfg(not(any(target_arch = "x86_64", target_arch = "wasm32")))]
+/// Scalar fallback for other architectures, `force-scalar` builds and Miri
+#[cfg(any(
+    feature = "force-scalar",
+    miri,
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "wasm32",
+        feature = "portable-simd"
+    ))
+))]
 #[inline]
 pub fn magnitude(v: &PressureVector) -> f64 {
     (v.latency * v.latency + v.error * v.error + v.saturation * v.saturation).sqrt()
 }
 
+/// Backend used by `magnitude` in this build
+pub const SIMD_BACKEND: &str = if cfg!(any(feature = "force-scalar", miri)) {
+    "scalar"
+} else if cfg!(feature = "portable-simd") {
+    "portable"
+} else if cfg!(target_arch = "x86_64") {
+    "avx2"
+} else if cfg!(target_arch = "wasm32") {
+    "simd128"
+} else {
+    "scalar"
+};
+
 // ============================================================================
 // DOT PRODUCT
 // ============================================================================
 
+/// Map -0.0 to +0.0, leaving every other value (including NaN) unchanged
+#[inline]
+pub fn canonical_zero(x: f64) -> f64 {
+    // -0.0 + 0.0 == +0.0 under round-to-nearest
+    x + 0.0
+}
+
 /// Weighted dot product: P · W
 #[inline]
 pub fn dot_product(v: &PressureVector, weights: &SensitivityWeights) -> f64 {
@@ -149,4 +246,30 @@ mod tests {
         let result = dot_product(&v, &w);
         assert!((result - (0.5 * 8.0 + 0.2 * 10.0 + 0.3 * 5.0)).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_backend_matches_scalar_order() {
+        for i in 0..1_000 {
+            let f = i as f64 * 0.013;
+            let v = PressureVector::new(f.sin(), (f * 1.7).cos(), f.fract() * 3.0);
+            let scalar =
+                ((v.latency * v.latency + v.error * v.error) + v.saturation * v.saturation).sqrt();
+            assert_eq!(magnitude(&v).to_bits(), scalar.to_bits(), "{SIMD_BACKEND}");
+        }
+    }
+
+    #[test]
+    fn test_signed_zero_and_subnormals() {
+        assert!(canonical_zero(-0.0).is_sign_positive());
+        assert_eq!(canonical_zero(-1.5), -1.5);
+        assert_eq!(
+            positive_stress_magnitude(&PressureVector::new(-0.0, -0.0, -0.0)),
+            0.0
+        );
+
+        // Subnormals survive linear terms and underflow when squared
+        let tiny = PressureVector::new(1e-310, 0.0, 0.0);
+        assert!(dot_product(&tiny, &SensitivityWeights::new(1.0, 1.0, 1.0)) > 0.0);
+        assert_eq!(magnitude(&tiny), 0.0);
+    }
 }
@@ -0,0 +1,128 @@
+/**
+ * Build metadata (version, formula revision, backend, features).
+ *
+ * Mixed-version fleets exchange scars, snapshots and gossip digests;
+ * comparing `VersionInfo` at startup catches peers whose physics would
+ * disagree before their state is mixed.
+ */
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::snapshot::SNAPSHOT_VERSION;
+use crate::types::FormulaRevision;
+use crate::vector::SIMD_BACKEND;
+
+/// Revision of the resistance / scar / momentum formulas implemented here.
+/// Bump whenever a change alters numeric output for the same inputs.
+pub const FORMULA_REVISION: &str = "RFC-0001";
+
+/// Cargo features and whether each was compiled in
+const FEATURES: [(&str, bool); 26] = [
+    ("server", cfg!(feature = "server")),
+    ("admin", cfg!(feature = "admin")),
+    ("otel", cfg!(feature = "otel")),
+    ("statsd", cfg!(feature = "statsd")),
+    ("gossip", cfg!(feature = "gossip")),
+    ("envoy", cfg!(feature = "envoy")),
+    ("shed", cfg!(feature = "shed")),
+    ("tower", cfg!(feature = "tower")),
+    ("distributed", cfg!(feature = "distributed")),
+    ("kafka", cfg!(feature = "kafka")),
+    ("chaos", cfg!(feature = "chaos")),
+    ("schema", cfg!(feature = "schema")),
+    ("strict", cfg!(feature = "strict")),
+    ("vectors", cfg!(feature = "vectors")),
+    ("journal", cfg!(feature = "journal")),
+    ("ingest", cfg!(feature = "ingest")),
+    ("profiling", cfg!(feature = "profiling")),
+    ("tracing", cfg!(feature = "tracing")),
+    ("fast-math", cfg!(feature = "fast-math")),
+    ("webgpu", cfg!(feature = "webgpu")),
+    ("portable-simd", cfg!(feature = "portable-simd")),
+    ("shared", cfg!(feature = "shared")),
+    ("force-scalar", cfg!(feature = "force-scalar")),
+    ("tokio", cfg!(feature = "tokio")),
+    ("prometheus", cfg!(feature = "prometheus")),
+    ("simlab", cfg!(feature = "simlab")),
+];
+
+/// What this build of the engine is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionInfo {
+    pub crate_version: String,
+    pub formula_revision: String,
+    pub snapshot_version: u32,
+    /// `FormulaRevision`s this build can run ("v1", "v2", ...)
+    pub formulas: Vec<String>,
+    /// "avx2", "simd128" or "scalar"
+    pub simd_backend: String,
+    /// Enabled Cargo features, in manifest order
+    pub features: Vec<String>,
+}
+
+impl VersionInfo {
+    /// True when both builds compute and persist state identically
+    /// (backend and features do not affect results)
+    pub fn is_compatible(&self, other: &VersionInfo) -> bool {
+        self.formula_revision == other.formula_revision
+            && self.snapshot_version == other.snapshot_version
+    }
+
+    /// Whether this build can run the given formula revision
+    pub fn supports(&self, revision: FormulaRevision) -> bool {
+        self.formulas.iter().any(|f| f == revision.as_str())
+    }
+}
+
+/// Metadata for the running build
+pub fn version_info() -> VersionInfo {
+    VersionInfo {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        formula_revision: FORMULA_REVISION.to_string(),
+        snapshot_version: SNAPSHOT_VERSION,
+        formulas: FormulaRevision::ALL
+            .iter()
+            .map(|f| f.as_str().to_string())
+            .collect(),
+        simd_backend: SIMD_BACKEND.to_string(),
+        features: FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect(),
+    }
+}
+
+/// Build metadata as a plain object
+#[wasm_bindgen(js_name = versionInfo)]
+pub fn version_info_js() -> Result<JsValue, JsValue> {
+    crate::to_js(&version_info())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_info() {
+        let info = version_info();
+        assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.formula_revision, FORMULA_REVISION);
+        assert_eq!(
+            info.features.contains(&"chaos".to_string()),
+            cfg!(feature = "chaos")
+        );
+
+        let mut peer = info.clone();
+        peer.simd_backend = "scalar".into();
+        assert!(info.is_compatible(&peer));
+        assert!(info.supports(FormulaRevision::V2));
+        peer.formula_revision = "RFC-0000".into();
+        assert!(!info.is_compatible(&peer));
+    }
+}
@@ -0,0 +1,130 @@
+/**
+ * Warm-start priors for new targets.
+ *
+ * A cold target starts with zero scar and zero momentum, so its first
+ * incident always lands at full blast. Priors let new targets inherit a
+ * conservative starting point, either fixed or derived from peers.
+ */
+use serde::{Deserialize, Serialize};
+
+use crate::engine::TargetState;
+use crate::types::{Momentum, Ohms, Scar};
+
+/// Where a new target's initial scar/momentum come from
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SeedStrategy {
+    /// Use the fixed priors only
+    #[default]
+    Fixed,
+    /// Average scar/momentum of the given peers (group, parent, or whole pool)
+    PeerAverage,
+}
+
+/// Initial state priors applied at target creation
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarmStartPriors {
+    /// Scar a new target starts with (decays normally)
+    #[serde(alias = "initial_scar")]
+    pub initial_scar: f64,
+    /// Added to the conservative bootstrap resistance
+    #[serde(alias = "resistance_bias")]
+    pub resistance_bias: f64,
+    pub seed: SeedStrategy,
+}
+
+impl Default for WarmStartPriors {
+    fn default() -> Self {
+        // Cold start: identical to TS createBootstrapState
+        Self {
+            initial_scar: 0.0,
+            resistance_bias: 0.0,
+            seed: SeedStrategy::Fixed,
+        }
+    }
+}
+
+impl WarmStartPriors {
+    /// Apply priors to a fresh bootstrap state
+    ///
+    /// With `PeerAverage`, peer scar replaces `initial_scar` when it is
+    /// larger (priors are a floor, never a discount).
+    pub fn apply<'a>(
+        &self,
+        mut state: TargetState,
+        peers: impl IntoIterator<Item = &'a TargetState>,
+    ) -> TargetState {
+        let mut scar = self.initial_scar.max(0.0);
+        let mut momentum = 0.0;
+
+        if self.seed == SeedStrategy::PeerAverage {
+            let (count, scar_sum, momentum_sum) = peers
+                .into_iter()
+                .fold((0usize, 0.0, 0.0), |(n, s, m), peer| {
+                    (n + 1, s + peer.scar.0, m + peer.momentum.0)
+                });
+            if count > 0 {
+                scar = scar.max(scar_sum / count as f64);
+                momentum = momentum_sum / count as f64;
+            }
+        }
+
+        state.scar = Scar(scar);
+        state.momentum = Momentum(momentum);
+        state.resistance = Ohms(state.resistance.0 + self.resistance_bias.max(0.0) + scar);
+        state
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PhysicsConfig;
+
+    #[test]
+    fn test_default_priors_are_cold_start() {
+        let config = PhysicsConfig::default();
+        let cold = TargetState::bootstrap(&config);
+        let state = WarmStartPriors::default().apply(cold, []);
+
+        assert_eq!(state.scar, Scar(0.0));
+        assert_eq!(state.resistance, cold.resistance);
+    }
+
+    #[test]
+    fn test_fixed_priors() {
+        let config = PhysicsConfig::default();
+        let priors = WarmStartPriors {
+            initial_scar: 3.0,
+            resistance_bias: 2.0,
+            seed: SeedStrategy::Fixed,
+        };
+        let state = priors.apply(TargetState::bootstrap(&config), []);
+
+        assert_eq!(state.scar, Scar(3.0));
+        assert!((state.resistance.0 - (12.0 + 2.0 + 3.0)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_peer_average_seed() {
+        let config = PhysicsConfig::default();
+        let mut a = TargetState::bootstrap(&config);
+        a.scar = Scar(10.0);
+        a.momentum = Momentum(0.2);
+        let mut b = TargetState::bootstrap(&config);
+        b.scar = Scar(20.0);
+
+        let priors = WarmStartPriors {
+            seed: SeedStrategy::PeerAverage,
+            ..WarmStartPriors::default()
+        };
+        let state = priors.apply(TargetState::bootstrap(&config), [&a, &b]);
+
+        assert_eq!(state.scar, Scar(15.0));
+        assert!((state.momentum.0 - 0.1).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,108 @@
+/**
+ * Warm-up grace after target registration.
+ *
+ * A freshly started pod answers its first requests with cold caches, an
+ * unwarmed JIT and an empty connection pool. Those latency spikes are real
+ * pressure and should raise resistance, but scarring a brand-new instance
+ * for them makes it start life penalized. With `PhysicsConfig::warmup` set
+ * the engine scales trauma by `trauma_scale` (zero by default: no trauma
+ * at all) for `window_ms` after its first tick. Pressure, momentum and
+ * resistance are computed as usual.
+ *
+ * The window restarts on `TargetEngine::reset` and on
+ * `TargetEngine::restart_warmup` (`EnginePool::reregister` when a new pod
+ * takes over an existing target id). Engines restored from a snapshot
+ * with ticks behind them do not warm up again.
+ */
+use serde::{Deserialize, Serialize};
+
+/// Reduced trauma after registration
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct WarmupConfig {
+    /// How long after registration the grace applies
+    #[serde(alias = "window_ms")]
+    pub window_ms: f64,
+    /// Multiplier on trauma during warm-up (0..=1)
+    #[serde(alias = "trauma_scale")]
+    pub trauma_scale: f64,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 60_000.0,
+            trauma_scale: 0.0,
+        }
+    }
+}
+
+impl WarmupConfig {
+    /// Trauma multiplier at `now_ms` for a warm-up started at `started_ms`
+    pub fn trauma_scale(&self, started_ms: f64, now_ms: f64) -> f64 {
+        if now_ms - started_ms < self.window_ms {
+            self.trauma_scale.clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::TargetEngine;
+    use crate::pool::EnginePool;
+    use crate::types::{PhysicsConfig, PressureVector, SensitivityWeights};
+
+    fn warm_config() -> PhysicsConfig {
+        PhysicsConfig {
+            warmup: Some(WarmupConfig {
+                window_ms: 5_000.0,
+                ..WarmupConfig::default()
+            }),
+            ..PhysicsConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_cold_start_spike_leaves_no_scar() {
+        let mut engine = TargetEngine::new(warm_config(), SensitivityWeights::default());
+        let mut now = 0.0;
+        for _ in 0..30 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.95, 0.2, 0.9));
+        }
+        // Pressure still drives resistance; only the scar is held back
+        assert_eq!(engine.state().scar.0, 0.0);
+        assert!(engine.state().resistance.0 > engine.config().base_resistance);
+        for _ in 0..30 {
+            now += 100.0;
+            engine.tick(now, PressureVector::new(0.95, 0.2, 0.9));
+        }
+        assert!(engine.state().scar.0 > 0.0);
+    }
+
+    #[test]
+    fn test_reregister_restarts_warmup() {
+        let mut pool = EnginePool::new(warm_config(), SensitivityWeights::default());
+        let mut now = 0.0;
+        for _ in 0..100 {
+            now += 100.0;
+            pool.tick("api", now, PressureVector::new(0.1, 0.0, 0.1));
+        }
+        assert!(pool.reregister("api", now));
+        assert!(!pool.reregister("missing", now));
+        for _ in 0..30 {
+            now += 100.0;
+            pool.tick("api", now, PressureVector::new(0.95, 0.2, 0.9));
+        }
+        assert_eq!(pool.get("api").unwrap().state().scar.0, 0.0);
+    }
+}
@@ -0,0 +1,300 @@
+/**
+ * Synthetic workloads for benches, simulations and fuzzing.
+ *
+ * Each `Workload` shapes a load level over time; `generate` turns it into
+ * a `Trace` sampled on a `Timeline`, with seeded noise so runs are
+ * reproducible. Load maps onto pressure as latency = saturation = level,
+ * with errors appearing only above 70% load.
+ */
+use std::f64::consts::TAU;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::anneal::Rng;
+use crate::trace::{Trace, TraceSample};
+use crate::types::PressureVector;
+
+/// Sampling grid and noise shared by every workload
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Timeline {
+    #[serde(alias = "start_ms")]
+    pub start_ms: f64,
+    #[serde(alias = "duration_ms")]
+    pub duration_ms: f64,
+    #[serde(alias = "interval_ms")]
+    pub interval_ms: f64,
+    /// Uniform noise in [−noise, noise] added to each pressure axis
+    pub noise: f64,
+    /// Requests offered per interval at load 1
+    pub offered: f64,
+    pub seed: u64,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            start_ms: 0.0,
+            duration_ms: 600_000.0,
+            interval_ms: 1_000.0,
+            noise: 0.02,
+            offered: 100.0,
+            seed: 0x5EED,
+        }
+    }
+}
+
+/// Load shape over time (times relative to `Timeline::start_ms`)
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum Workload {
+    /// Smooth daily cycle between `base` and `peak` (trough at t = 0)
+    Diurnal {
+        base: f64,
+        peak: f64,
+        #[serde(alias = "period_ms")]
+        period_ms: f64,
+    },
+    /// Sudden surge: linear ramp, plateau, exponential decay back to base
+    FlashCrowd {
+        base: f64,
+        peak: f64,
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        #[serde(alias = "ramp_ms")]
+        ramp_ms: f64,
+        #[serde(alias = "hold_ms")]
+        hold_ms: f64,
+        #[serde(alias = "decay_ms")]
+        decay_ms: f64,
+    },
+    /// Load creeping linearly from `start` to `end` over the whole timeline
+    SlowBurn { start: f64, end: f64 },
+    /// `targets` sharing a dependency: target i degrades to `peak` at
+    /// `at_ms + i × stagger_ms` for `duration_ms`
+    CorrelatedFailure {
+        targets: u32,
+        base: f64,
+        peak: f64,
+        #[serde(alias = "at_ms")]
+        at_ms: f64,
+        #[serde(alias = "stagger_ms")]
+        stagger_ms: f64,
+        #[serde(alias = "duration_ms")]
+        duration_ms: f64,
+    },
+}
+
+impl Workload {
+    pub fn diurnal() -> Self {
+        Workload::Diurnal {
+            base: 0.1,
+            peak: 0.6,
+            period_ms: 86_400_000.0,
+        }
+    }
+
+    pub fn flash_crowd() -> Self {
+        Workload::FlashCrowd {
+            base: 0.1,
+            peak: 0.95,
+            at_ms: 60_000.0,
+            ramp_ms: 5_000.0,
+            hold_ms: 60_000.0,
+            decay_ms: 30_000.0,
+        }
+    }
+
+    pub fn slow_burn() -> Self {
+        Workload::SlowBurn {
+            start: 0.1,
+            end: 0.9,
+        }
+    }
+
+    pub fn correlated_failure() -> Self {
+        Workload::CorrelatedFailure {
+            targets: 4,
+            base: 0.1,
+            peak: 0.95,
+            at_ms: 60_000.0,
+            stagger_ms: 2_000.0,
+            duration_ms: 120_000.0,
+        }
+    }
+
+    /// Number of targets the workload produces
+    pub fn targets(&self) -> u32 {
+        match self {
+            Workload::CorrelatedFailure { targets, .. } => (*targets).max(1),
+            _ => 1,
+        }
+    }
+
+    /// Load level of target `index` at `t_ms` into a timeline of `duration_ms`
+    pub fn level(&self, index: u32, t_ms: f64, duration_ms: f64) -> f64 {
+        match *self {
+            Workload::Diurnal {
+                base,
+                peak,
+                period_ms,
+            } => {
+                let phase = if period_ms > 0.0 {
+                    t_ms / period_ms
+                } else {
+                    0.0
+                };
+                base + (peak - base) * (1.0 - (TAU * phase).cos()) / 2.0
+            }
+            Workload::FlashCrowd {
+                base,
+                peak,
+                at_ms,
+                ramp_ms,
+                hold_ms,
+                decay_ms,
+            } => {
+                let t = t_ms - at_ms;
+                let surge = if t < 0.0 {
+                    0.0
+                } else if t < ramp_ms {
+                    t / ramp_ms
+                } else if t < ramp_ms + hold_ms {
+                    1.0
+                } else if decay_ms > 0.0 {
+                    (-(t - ramp_ms - hold_ms) / decay_ms).exp()
+                } else {
+                    0.0
+                };
+                base + (peak - base) * surge
+            }
+            Workload::SlowBurn { start, end } => {
+                let progress = if duration_ms > 0.0 {
+                    (t_ms / duration_ms).clamp(0.0, 1.0)
+                } else {
+                    1.0
+                };
+                start + (end - start) * progress
+            }
+            Workload::CorrelatedFailure {
+                base,
+                peak,
+                at_ms,
+                stagger_ms,
+                duration_ms: failure_ms,
+                ..
+            } => {
+                let onset = at_ms + index as f64 * stagger_ms;
+                if t_ms >= onset && t_ms < onset + failure_ms {
+                    peak
+                } else {
+                    base
+                }
+            }
+        }
+    }
+
+    /// Trace with targets `{prefix}` (or `{prefix}-{i}` for several)
+    pub fn generate(&self, prefix: &str, timeline: &Timeline) -> Trace {
+        let mut rng = Rng(timeline.seed);
+        let mut trace = Trace::new();
+        if timeline.interval_ms <= 0.0 {
+            return trace;
+        }
+        let steps = (timeline.duration_ms / timeline.interval_ms).ceil() as u64;
+        let targets = self.targets();
+        for step in 0..steps {
+            let t_ms = step as f64 * timeline.interval_ms;
+            for index in 0..targets {
+                let level = self.level(index, t_ms, timeline.duration_ms).max(0.0);
+                let mut noisy = |x: f64| (x + timeline.noise * (2.0 * rng.unit() - 1.0)).max(0.0);
+                let errors = ((level - 0.7) / 0.3).clamp(0.0, 1.0) * 0.5;
+                let sample = TraceSample {
+                    timestamp_ms: timeline.start_ms + t_ms,
+                    pressure: PressureVector::new(noisy(level), noisy(errors), noisy(level)),
+                    offered: timeline.offered * level.min(1.0),
+                };
+                if targets == 1 {
+                    trace.record(prefix, sample);
+                } else {
+                    trace.record(&format!("{prefix}-{index}"), sample);
+                }
+            }
+        }
+        trace
+    }
+}
+
+/// Plain trace object for a plain `Workload` (`{ kind: "flash_crowd", ... }`)
+/// and `Timeline` (undefined = defaults)
+#[wasm_bindgen(js_name = generateWorkload)]
+pub fn generate_js(workload: JsValue, prefix: &str, timeline: JsValue) -> Result<JsValue, JsValue> {
+    let workload: Workload = serde_wasm_bindgen::from_value(workload)?;
+    let timeline = if timeline.is_undefined() {
+        Timeline::default()
+    } else {
+        serde_wasm_bindgen::from_value(timeline)?
+    };
+    crate::to_js(&workload.generate(prefix, &timeline))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes() {
+        let diurnal = Workload::Diurnal {
+            base: 0.1,
+            peak: 0.5,
+            period_ms: 1_000.0,
+        };
+        assert!((diurnal.level(0, 0.0, 0.0) - 0.1).abs() < 1e-12);
+        assert!((diurnal.level(0, 500.0, 0.0) - 0.5).abs() < 1e-12);
+
+        let flash = Workload::flash_crowd();
+        assert_eq!(flash.level(0, 0.0, 0.0), 0.1);
+        assert_eq!(flash.level(0, 100_000.0, 0.0), 0.95);
+        assert!(flash.level(0, 200_000.0, 0.0) < 0.2);
+
+        let burn = Workload::slow_burn();
+        assert_eq!(burn.level(0, 500.0, 1_000.0), 0.5);
+
+        let failure = Workload::correlated_failure();
+        assert_eq!(failure.level(0, 61_000.0, 0.0), 0.95);
+        assert_eq!(failure.level(1, 61_000.0, 0.0), 0.1);
+        assert_eq!(failure.level(1, 62_000.0, 0.0), 0.95);
+    }
+
+    #[test]
+    fn test_generate_is_seeded_and_names_targets() {
+        let timeline = Timeline::default();
+        let trace = Workload::correlated_failure().generate("db", &timeline);
+        assert_eq!(trace.targets.len(), 4);
+        assert_eq!(trace.targets[3].target, "db-3");
+        assert_eq!(trace.len(), 4 * 600);
+
+        let a = Workload::flash_crowd().generate("api", &timeline);
+        let b = Workload::flash_crowd().generate("api", &timeline);
+        assert_eq!(a.targets[0].target, "api");
+        let pressures = |t: &Trace| -> Vec<f64> {
+            t.targets[0]
+                .samples
+                .iter()
+                .map(|s| s.pressure.latency)
+                .collect()
+        };
+        assert_eq!(pressures(&a), pressures(&b));
+        assert!(a.targets[0].samples[100].pressure.error > 0.0);
+        assert!(a.targets[0].samples[0].pressure.error <= timeline.noise);
+    }
+}
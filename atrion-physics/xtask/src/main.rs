@@ -0,0 +1,365 @@
+/**
+ * Repository tasks for atrion-physics, run as `cargo xtask <task>`.
+ *
+ * bench-check: runs the criterion suite and compares every benchmark's
+ * median against `benches/baselines.json`, failing when one is slower
+ * than its baseline by more than the tolerance. Raw timings only compare
+ * on the machine that recorded them, so both sides are normalized by the
+ * `calibration/spin` bench: a fixed CPU workload that no crate change
+ * touches. A machine twice as slow runs the calibration twice as long and
+ * has its baselines scaled to match.
+ *
+ * Usage: cargo xtask bench-check [--tolerance PCT] [--update] [-- ARGS]
+ * (ARGS go to criterion, e.g. a name filter or --measurement-time 2).
+ * --update rewrites the baselines from this run instead of checking.
+ */
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+const USAGE: &str =
+    "usage: cargo xtask bench-check [--tolerance PCT] [--update] [-- CRITERION_ARGS]";
+
+/// Bench every baseline is normalized by
+const CALIBRATION_ID: &str = "calibration/spin";
+
+const DEFAULT_TOLERANCE_PCT: f64 = 10.0;
+
+/// Committed reference timings (`benches/baselines.json`)
+#[derive(Debug, Serialize, Deserialize)]
+struct Baselines {
+    /// Allowed slowdown before a benchmark counts as regressed
+    tolerance_pct: f64,
+    /// Median of `calibration/spin` on the recording machine (ns)
+    calibration_ns: f64,
+    /// Benchmark id → median (ns)
+    benchmarks: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    tolerance_pct: Option<f64>,
+    update: bool,
+    criterion_args: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("bench-check") => parse_options(&args[1..]).and_then(|options| bench_check(&options)),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("xtask: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--update" => options.update = true,
+            "--tolerance" => {
+                let value = args.next().ok_or("--tolerance needs a percentage")?;
+                let pct = value
+                    .parse::<f64>()
+                    .map_err(|_| format!("bad tolerance {value:?}"))?;
+                options.tolerance_pct = Some(pct);
+            }
+            "--" => {
+                options.criterion_args = args.cloned().collect();
+                break;
+            }
+            other => return Err(format!("unknown argument {other:?}\n{USAGE}")),
+        }
+    }
+    Ok(options)
+}
+
+// ============================================================================
+// BENCH CHECK
+// ============================================================================
+
+/// Returns whether every benchmark is within tolerance
+fn bench_check(options: &Options) -> Result<bool, String> {
+    let crate_dir = crate_dir();
+    let baselines_path = crate_dir.join("benches").join("baselines.json");
+    let started = SystemTime::now();
+    run_benches(&crate_dir, &options.criterion_args)?;
+
+    let mut results = read_results(&target_dir(&crate_dir).join("criterion"), started)?;
+    let calibration_ns = results
+        .remove(CALIBRATION_ID)
+        .ok_or(format!("no {CALIBRATION_ID} result (filtered out?)"))?;
+
+    if options.update {
+        let previous = load_baselines(&baselines_path).ok();
+        let mut benchmarks = previous
+            .as_ref()
+            .map(|b| b.benchmarks.clone())
+            .unwrap_or_default();
+        // Rescale entries this run did not cover onto the new calibration
+        if let Some(previous) = &previous {
+            let scale = calibration_ns / previous.calibration_ns;
+            benchmarks.values_mut().for_each(|ns| *ns *= scale);
+        }
+        benchmarks.extend(results);
+        let baselines = Baselines {
+            tolerance_pct: options
+                .tolerance_pct
+                .or(previous.map(|b| b.tolerance_pct))
+                .unwrap_or(DEFAULT_TOLERANCE_PCT),
+            calibration_ns,
+            benchmarks,
+        };
+        let json = serde_json::to_string_pretty(&baselines).map_err(|e| e.to_string())?;
+        fs::write(&baselines_path, json + "\n")
+            .map_err(|e| format!("{}: {e}", baselines_path.display()))?;
+        println!(
+            "wrote {} baselines to {}",
+            baselines.benchmarks.len(),
+            baselines_path.display()
+        );
+        return Ok(true);
+    }
+
+    let baselines = load_baselines(&baselines_path)?;
+    let tolerance_pct = options.tolerance_pct.unwrap_or(baselines.tolerance_pct);
+    let report = compare(&baselines, calibration_ns, &results, tolerance_pct);
+    print!(
+        "{}",
+        report.render(calibration_ns / baselines.calibration_ns, tolerance_pct)
+    );
+    Ok(report.regressions() == 0)
+}
+
+fn crate_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives inside the crate")
+        .to_path_buf()
+}
+
+fn target_dir(crate_dir: &Path) -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| crate_dir.join("target"))
+}
+
+fn run_benches(crate_dir: &Path, criterion_args: &[String]) -> Result<(), String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .current_dir(crate_dir)
+        .args(["bench", "--bench", "physics_bench", "--", "--noplot"])
+        .args(criterion_args)
+        .status()
+        .map_err(|e| format!("failed to run cargo bench: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("cargo bench failed ({status})"))
+    }
+}
+
+fn load_baselines(path: &Path) -> Result<Baselines, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Median (ns) of every benchmark criterion wrote since `since`
+fn read_results(criterion_dir: &Path, since: SystemTime) -> Result<BTreeMap<String, f64>, String> {
+    let mut results = BTreeMap::new();
+    let mut pending = vec![criterion_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = fs::read_dir(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path.file_name().is_some_and(|name| name == "new") {
+                if let Some((id, ns)) = read_estimate(&path, since) {
+                    results.insert(id, ns);
+                }
+            } else {
+                pending.push(path);
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// `(full id, median ns)` from a criterion `new/` directory, if fresh
+fn read_estimate(dir: &Path, since: SystemTime) -> Option<(String, f64)> {
+    let estimates = dir.join("estimates.json");
+    let modified = fs::metadata(&estimates).ok()?.modified().ok()?;
+    if modified < since {
+        return None;
+    }
+    let read = |path: PathBuf| -> Option<serde_json::Value> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    };
+    let benchmark = read(dir.join("benchmark.json"))?;
+    let estimates = read(estimates)?;
+    Some((
+        benchmark["full_id"].as_str()?.to_string(),
+        estimates["median"]["point_estimate"].as_f64()?,
+    ))
+}
+
+// ============================================================================
+// COMPARISON
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Ok,
+    Improved,
+    Regressed,
+    /// Measured but not in the baselines
+    New,
+}
+
+#[derive(Debug)]
+struct Row {
+    id: String,
+    /// Baseline scaled to this machine
+    expected_ns: Option<f64>,
+    measured_ns: f64,
+    status: Status,
+}
+
+#[derive(Debug)]
+struct Report {
+    rows: Vec<Row>,
+    /// Baseline ids this run did not measure
+    skipped: usize,
+}
+
+fn compare(
+    baselines: &Baselines,
+    calibration_ns: f64,
+    results: &BTreeMap<String, f64>,
+    tolerance_pct: f64,
+) -> Report {
+    let scale = calibration_ns / baselines.calibration_ns;
+    let rows = results
+        .iter()
+        .map(|(id, &measured_ns)| {
+            let expected_ns = baselines.benchmarks.get(id).map(|ns| ns * scale);
+            let status = match expected_ns {
+                None => Status::New,
+                Some(expected) => {
+                    let change_pct = (measured_ns / expected - 1.0) * 100.0;
+                    if change_pct > tolerance_pct {
+                        Status::Regressed
+                    } else if change_pct < -tolerance_pct {
+                        Status::Improved
+                    } else {
+                        Status::Ok
+                    }
+                }
+            };
+            Row {
+                id: id.clone(),
+                expected_ns,
+                measured_ns,
+                status,
+            }
+        })
+        .collect();
+    let skipped = baselines
+        .benchmarks
+        .keys()
+        .filter(|id| !results.contains_key(*id))
+        .count();
+    Report { rows, skipped }
+}
+
+impl Report {
+    fn regressions(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| row.status == Status::Regressed)
+            .count()
+    }
+
+    fn render(&self, scale: f64, tolerance_pct: f64) -> String {
+        let width = self.rows.iter().map(|row| row.id.len()).max().unwrap_or(0);
+        let mut out = format!("machine scale {scale:.3}, tolerance {tolerance_pct}%\n");
+        for row in &self.rows {
+            let (expected, change) = match row.expected_ns {
+                Some(expected) => (
+                    format_ns(expected),
+                    format!("{:+.1}%", (row.measured_ns / expected - 1.0) * 100.0),
+                ),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            out += &format!(
+                "{:<width$}  {:>10}  {:>10}  {:>8}  {:?}\n",
+                row.id,
+                expected,
+                format_ns(row.measured_ns),
+                change,
+                row.status,
+            );
+        }
+        out += &format!(
+            "{} regressed, {} not measured\n",
+            self.regressions(),
+            self.skipped
+        );
+        out
+    }
+}
+
+fn format_ns(ns: f64) -> String {
+    match ns {
+        ns if ns >= 1e6 => format!("{:.2} ms", ns / 1e6),
+        ns if ns >= 1e3 => format!("{:.2} µs", ns / 1e3),
+        ns => format!("{ns:.1} ns"),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_normalizes_by_calibration() {
+        let baselines = Baselines {
+            tolerance_pct: 10.0,
+            calibration_ns: 100.0,
+            benchmarks: BTreeMap::from([
+                ("a".to_string(), 1_000.0),
+                ("b".to_string(), 1_000.0),
+                ("gone".to_string(), 5.0),
+            ]),
+        };
+        // This machine is twice as slow: 2150 ns is +7.5%, within tolerance
+        let results = BTreeMap::from([
+            ("a".to_string(), 2_150.0),
+            ("b".to_string(), 2_500.0),
+            ("c".to_string(), 1.0),
+        ]);
+        let report = compare(&baselines, 200.0, &results, 10.0);
+        let status: Vec<Status> = report.rows.iter().map(|row| row.status).collect();
+        assert_eq!(status, [Status::Ok, Status::Regressed, Status::New]);
+        assert_eq!(report.rows[0].expected_ns, Some(2_000.0));
+        assert_eq!((report.regressions(), report.skipped), (1, 1));
+    }
+}